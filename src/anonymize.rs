@@ -0,0 +1,226 @@
+//! Anonymizing translations so they can be shared in bug reports.
+//!
+//! See [`Anonymizer`].
+
+use std::collections::BTreeMap;
+
+use crate::{api::TermKey, formats::NESTING_SEPARATOR, sync::TranslationSnapshot};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Replaces term keys and translations with meaningless data of the same shape.
+///
+/// - Term keys are replaced by hashes. Each segment between two
+///   [`NESTING_SEPARATOR`]s is hashed separately, so the hierarchy of the keys
+///   is kept, e.g. `checkout.title` and `checkout.button` still share a prefix.
+/// - Letters and digits of translations are replaced by random letters and digits
+///   of the same case. Whitespace, punctuation and the number of characters are
+///   kept, as are placeholders like `{name}`, `{{name}}`, `%s` or `%1$d` and
+///   HTML tags like `<b>`.
+/// - Locale codes are kept.
+///
+/// The output only depends on the input and the salt, so anonymizing the same
+/// project twice with the same salt gives the same result. The hash is not
+/// cryptographically secure: keep the salt secret so short keys cannot be guessed.
+///
+/// # Examples
+/// ```
+/// use traduora::{anonymize::Anonymizer, sync::TranslationSnapshot};
+///
+/// let mut snapshot = TranslationSnapshot::new();
+/// snapshot.set("checkout.title", "de_DE".into(), "Hallo {name}!");
+///
+/// let anonymized = Anonymizer::new("my secret salt").snapshot(&snapshot);
+/// let (key, translations) = anonymized.terms.iter().next().unwrap();
+/// let value = &translations[&"de_DE".into()];
+///
+/// assert_ne!(key.value(), "checkout.title");
+/// assert_eq!(key.segments().count(), 2);
+/// assert_ne!(value, "Hallo {name}!");
+/// assert!(value.ends_with(" {name}!"));
+/// assert_eq!(value.chars().count(), "Hallo {name}!".chars().count());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anonymizer {
+    salt: String,
+    keep_placeholders: bool,
+}
+
+impl Anonymizer {
+    /// Creates an anonymizer whose output depends on the given salt.
+    pub fn new(salt: impl Into<String>) -> Self {
+        Self {
+            salt: salt.into(),
+            keep_placeholders: true,
+        }
+    }
+
+    /// Whether placeholders and HTML tags in translations are kept unchanged.
+    ///
+    /// Disable this if the names of placeholders are confidential.
+    /// Defaults to `true`.
+    #[must_use]
+    pub const fn keep_placeholders(mut self, keep_placeholders: bool) -> Self {
+        self.keep_placeholders = keep_placeholders;
+        self
+    }
+
+    /// Anonymizes all terms and translations of the snapshot.
+    #[must_use]
+    pub fn snapshot(&self, snapshot: &TranslationSnapshot) -> TranslationSnapshot {
+        TranslationSnapshot {
+            terms: snapshot
+                .terms
+                .iter()
+                .map(|(key, translations)| {
+                    let translations = translations
+                        .iter()
+                        .map(|(locale, value)| (locale.clone(), self.value(value)))
+                        .collect();
+                    (self.key(key.value()).into(), translations)
+                })
+                .collect(),
+        }
+    }
+
+    /// Anonymizes the translations of a single locale, e.g. parsed by the
+    /// [`formats`](crate::formats) module.
+    #[must_use]
+    pub fn translations(
+        &self,
+        translations: &BTreeMap<TermKey, String>,
+    ) -> BTreeMap<TermKey, String> {
+        translations
+            .iter()
+            .map(|(key, value)| (self.key(key.value()).into(), self.value(value)))
+            .collect()
+    }
+
+    /// Replaces each segment of the term key with its hash.
+    #[must_use]
+    pub fn key(&self, key: &str) -> String {
+        key.split(NESTING_SEPARATOR)
+            .map(|segment| format!("k{:08x}", self.hash(segment) as u32))
+            .collect::<Vec<_>>()
+            .join(&NESTING_SEPARATOR.to_string())
+    }
+
+    /// Scrambles the letters and digits of a translation.
+    #[must_use]
+    pub fn value(&self, value: &str) -> String {
+        let mut random = self.hash(value) | 1;
+        let mut next = move || {
+            // xorshift64
+            random ^= random << 13;
+            random ^= random >> 7;
+            random ^= random << 17;
+            random
+        };
+
+        let chars: Vec<_> = value.chars().collect();
+        let mut scrambled = String::with_capacity(value.len());
+        let mut i = 0;
+        while i < chars.len() {
+            if self.keep_placeholders {
+                if let Some(len) = placeholder_len(&chars[i..]) {
+                    scrambled.extend(&chars[i..i + len]);
+                    i += len;
+                    continue;
+                }
+            }
+            let c = chars[i];
+            let offset = (next() % 26) as u8;
+            scrambled.push(if c.is_ascii_digit() {
+                char::from(b'0' + offset % 10)
+            } else if c.is_uppercase() {
+                char::from(b'A' + offset)
+            } else if c.is_alphabetic() {
+                char::from(b'a' + offset)
+            } else {
+                c
+            });
+            i += 1;
+        }
+        scrambled
+    }
+
+    fn hash(&self, value: &str) -> u64 {
+        // FNV-1a is stable across platforms and releases, unlike `DefaultHasher`
+        self.salt
+            .bytes()
+            .chain(std::iter::once(0))
+            .chain(value.bytes())
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+}
+
+/// Length of the placeholder or HTML tag at the start of `chars`, if any.
+fn placeholder_len(chars: &[char]) -> Option<usize> {
+    // position of the end of `close` after `start`, optionally stopping at whitespace
+    let find_close = |start: usize, close: &[char], allow_whitespace: bool| {
+        (start..chars.len())
+            .take_while(|&i| allow_whitespace || !chars[i].is_whitespace())
+            .find(|&i| chars[i..].starts_with(close))
+            .map(|i| i + close.len())
+    };
+
+    match chars {
+        ['{', '{', ..] => find_close(2, &['}', '}'], true),
+        ['{', ..] => find_close(1, &['}'], false),
+        ['<', c, ..] if c.is_ascii_alphabetic() || *c == '/' => find_close(1, &['>'], true),
+        ['%', '%', ..] => Some(2),
+        ['%', rest @ ..] => {
+            let flags = rest
+                .iter()
+                .take_while(|c| c.is_ascii_digit() || "$.-+#lh".contains(**c))
+                .count();
+            rest.get(flags)
+                .filter(|c| c.is_ascii_alphabetic())
+                .map(|_| flags + 2)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Anonymizer;
+
+    #[test]
+    fn placeholders_are_kept() {
+        let anonymizer = Anonymizer::new("salt");
+        let value = "Hi {{user}}, <b>%1$s</b> von {n}: 100% off, 1 < 2 { x }";
+        let scrambled = anonymizer.value(value);
+
+        assert_eq!(scrambled, anonymizer.value(value));
+        assert_eq!(scrambled.chars().count(), value.chars().count());
+        for kept in &["{{user}}", "<b>", "%1$s", "</b>", "{n}"] {
+            assert!(
+                scrambled.contains(kept),
+                "{} missing in {}",
+                kept,
+                scrambled
+            );
+        }
+        assert!(!scrambled.contains("von"));
+        assert!(!scrambled.contains("100"));
+        assert!(!scrambled.contains("off"));
+        assert!(!scrambled.contains(" x "));
+
+        let without = Anonymizer::new("salt").keep_placeholders(false);
+        assert!(!without.value(value).contains("user"));
+    }
+
+    #[test]
+    fn keys_depend_on_salt_and_keep_hierarchy() {
+        let a = Anonymizer::new("a");
+        let b = Anonymizer::new("b");
+
+        assert_eq!(a.key("x.y"), a.key("x.y"));
+        assert_ne!(a.key("x.y"), b.key("x.y"));
+        assert!(a.key("x.y").starts_with(&a.key("x")));
+    }
+}
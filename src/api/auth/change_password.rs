@@ -1,5 +1,6 @@
+use bytes::Bytes;
 use http::Method;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{api, auth::Authenticated, query::DefaultModel, Endpoint};
 
@@ -51,10 +52,10 @@ impl Endpoint for ChangePassword {
         "auth/change-password".into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
 }
@@ -62,7 +63,7 @@ impl Endpoint for ChangePassword {
 impl DefaultModel for ChangePassword {
     type Model = ();
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
@@ -45,15 +45,15 @@ impl Endpoint for Providers {
 impl DefaultModel for Providers {
     type Model = Vec<AuthProvider>;
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
 
 /// Default model.
 ///
 /// **Endpoint** `GET /api/v1/auth/providers`
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthProvider {
     /// Name of the authentication provider
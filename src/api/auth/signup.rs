@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use http::Method;
 use serde::{Deserialize, Serialize};
 
@@ -60,10 +61,10 @@ impl Endpoint for Signup {
         "auth/signup".into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
 }
@@ -75,7 +76,7 @@ impl DefaultModel for Signup {
 /// Default model.
 ///
 /// **Endpoint** `GET /api/v1/auth/providers`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct NewUser {
     /// Unique id of the created user.
@@ -1,9 +1,16 @@
 //! See type [`Token`].
 
+use bytes::Bytes;
 use http::Method;
 use serde::{Deserialize, Serialize};
 
-use crate::{api, auth::Unauthenticated, query::DefaultModel, BodyError, Endpoint};
+use crate::{
+    api,
+    auth::Unauthenticated,
+    credentials::{self, CredentialError},
+    query::DefaultModel,
+    BodyError, Endpoint,
+};
 
 /// Request an authentication token for an existing user or project client.
 ///
@@ -86,6 +93,46 @@ impl Token {
     }
 }
 
+impl Token {
+    /// Reads the credentials from environment variables.
+    ///
+    /// The credentials of a project client in `TRADUORA_CLIENT_ID` and
+    /// `TRADUORA_CLIENT_SECRET` take precedence over the credentials of a user
+    /// in `TRADUORA_MAIL` and `TRADUORA_PASSWORD`. Returns `None` if neither is set.
+    /// The host can be read with [`credentials::host_from_env`].
+    ///
+    /// # Errors
+    /// This function returns an error if only one variable of a pair is set.
+    ///
+    /// # Examples
+    /// ```
+    /// use traduora::Login;
+    ///
+    /// std::env::set_var("TRADUORA_MAIL", "user@traduora.example");
+    /// std::env::set_var("TRADUORA_PASSWORD", "password");
+    /// assert_eq!(Login::from_env().unwrap(), Some(Login::password("user@traduora.example", "password")));
+    /// ```
+    pub fn from_env() -> Result<Option<Self>, CredentialError> {
+        Self::from_lookup(|name| std::env::var(name).ok().filter(|v| !v.is_empty()))
+    }
+
+    pub(crate) fn from_lookup<F>(lookup: F) -> Result<Option<Self>, CredentialError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let client = credentials::pair(
+            &lookup,
+            credentials::ENV_CLIENT_ID,
+            credentials::ENV_CLIENT_SECRET,
+        )?;
+        if let Some((id, secret)) = client {
+            return Ok(Some(Self::client_credentials(id, secret)));
+        }
+        let user = credentials::pair(&lookup, credentials::ENV_MAIL, credentials::ENV_PASSWORD)?;
+        Ok(user.map(|(mail, password)| Self::password(mail, password)))
+    }
+}
+
 impl std::fmt::Debug for Token {
     /// Formats the value using the given formatter. Sensitive data is expunged.
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -113,10 +160,10 @@ impl Endpoint for Token {
         "auth/token".into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
 
@@ -126,15 +173,15 @@ impl Endpoint for Token {
 impl DefaultModel for Token {
     type Model = AccessToken;
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
 
 /// Default model.
 ///
 /// **Endpoint** `POST /api/v1/auth/token`
-#[derive(Clone, Debug, Deserialize, Hash, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Deserialize, Serialize, Hash, Eq, Ord, PartialEq, PartialOrd)]
 pub struct AccessToken {
     /// Authorization information for the client. To be sent in the `Authorization` header;
     pub access_token: api::AccessToken,
@@ -145,3 +192,27 @@ pub struct AccessToken {
     /// Type of access token. Should usually be `Bearer`.
     pub token_type: String,
 }
+
+impl AccessToken {
+    /// Parses [`expires_in`](Self::expires_in) into a duration.
+    ///
+    /// Returns `None` if the server sent a format other than a number of seconds
+    /// with an optional `s` suffix.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use traduora::api::auth::AccessToken;
+    ///
+    /// let token: AccessToken = serde_json::from_str(
+    ///     r#"{"access_token": "eyJhbGc...", "expires_in": "86400s", "token_type": "bearer"}"#,
+    /// ).unwrap();
+    /// assert_eq!(token.lifetime(), Some(Duration::from_secs(86400)));
+    /// ```
+    #[must_use]
+    pub fn lifetime(&self) -> Option<std::time::Duration> {
+        let seconds = self.expires_in.trim();
+        let seconds = seconds.strip_suffix('s').unwrap_or(seconds);
+        seconds.parse().ok().map(std::time::Duration::from_secs)
+    }
+}
@@ -0,0 +1,199 @@
+use futures_util::{
+    future,
+    stream::{self, StreamExt},
+};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{
+    cancel::{Cancellable, CancellationToken},
+    progress::{ProgressSink, Tracker},
+    query::DefaultModel,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// The per-item outcome of an operation that sends one request per item.
+///
+/// In contrast to a single `Result`, a failing item does not abort the
+/// operation. All items are attempted and their results are kept in the
+/// order of the input.
+#[derive(Debug)]
+pub struct BulkResult<K, T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// The result for each input item.
+    pub results: Vec<(K, Result<T, ApiError<E>>)>,
+}
+
+impl<K, T, E> BulkResult<K, T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Whether all items succeeded.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.results.iter().all(|(_, r)| r.is_ok())
+    }
+
+    /// Number of items in total.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Whether there were no items at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    /// The items that succeeded.
+    pub fn succeeded(&self) -> impl Iterator<Item = (&K, &T)> {
+        self.results
+            .iter()
+            .filter_map(|(k, r)| r.as_ref().ok().map(|t| (k, t)))
+    }
+
+    /// The items that failed together with their errors.
+    pub fn failed(&self) -> impl Iterator<Item = (&K, &ApiError<E>)> {
+        self.results
+            .iter()
+            .filter_map(|(k, r)| r.as_ref().err().map(|e| (k, e)))
+    }
+
+    /// Returns all values if every item succeeded or the first error otherwise.
+    ///
+    /// # Errors
+    /// Returns the error of the first failed item.
+    pub fn into_result(self) -> Result<Vec<T>, ApiError<E>> {
+        self.results.into_iter().map(|(_, r)| r).collect()
+    }
+}
+
+impl<K, T, E> Serialize for BulkResult<K, T, E>
+where
+    K: Serialize,
+    T: Serialize,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Item<'a, K, T, E: std::error::Error + Send + Sync + 'static>(
+            &'a K,
+            &'a Result<T, ApiError<E>>,
+        );
+
+        impl<K, T, E> Serialize for Item<'_, K, T, E>
+        where
+            K: Serialize,
+            T: Serialize,
+            E: std::error::Error + Send + Sync + 'static,
+        {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                struct Error<'a, E: std::error::Error + Send + Sync + 'static>(&'a ApiError<E>);
+
+                impl<E> Serialize for Error<'_, E>
+                where
+                    E: std::error::Error + Send + Sync + 'static,
+                {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        crate::reports::serialize_error(self.0, serializer)
+                    }
+                }
+
+                let mut item = serializer.serialize_struct("Item", 2)?;
+                item.serialize_field("key", self.0)?;
+                match self.1 {
+                    Ok(value) => item.serialize_field("value", value)?,
+                    Err(error) => item.serialize_field("error", &Error(error))?,
+                }
+                item.end()
+            }
+        }
+
+        let results: Vec<_> = self.results.iter().map(|(k, r)| Item(k, r)).collect();
+        let mut state = serializer.serialize_struct("BulkResult", 1)?;
+        state.serialize_field("results", &results)?;
+        state.end()
+    }
+}
+
+impl<K, T, E> FromIterator<(K, Result<T, ApiError<E>>)> for BulkResult<K, T, E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn from_iter<I: IntoIterator<Item = (K, Result<T, ApiError<E>>)>>(iter: I) -> Self {
+        Self {
+            results: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Sends the request of each item one after another until the token is
+/// cancelled and reports the progress.
+///
+/// `request` returns the key of the item in the result and its endpoint.
+pub(crate) fn run<'a, T, K, E, C>(
+    items: &'a [T],
+    request: impl Fn(&'a T) -> (K, E),
+    client: &C,
+    cancel: &CancellationToken,
+    progress: &dyn ProgressSink,
+) -> Cancellable<BulkResult<K, E::Model, C::Error>>
+where
+    E: DefaultModel,
+    E::AccessControl: From<C::AccessLevel>,
+    C: Client,
+{
+    let client = Tracker::new(client, progress, items.len());
+    let result: BulkResult<_, _, _> = items
+        .iter()
+        .take_while(|_| !cancel.is_cancelled())
+        .map(|item| {
+            let (key, endpoint) = request(item);
+            let result = endpoint.query(&client);
+            client.item_done();
+            (key, result)
+        })
+        .collect();
+    Cancellable {
+        skipped: items.len() - result.len(),
+        result,
+    }
+}
+
+/// Sends the requests of the items with up to `concurrency` requests at
+/// the same time until the token is cancelled and reports the progress.
+///
+/// See [`run`]. The results are in the same order as the items.
+pub(crate) async fn run_async<'a, T, K, E, C>(
+    items: &'a [T],
+    concurrency: usize,
+    request: impl Fn(&'a T) -> (K, E),
+    client: &C,
+    cancel: &CancellationToken,
+    progress: &dyn ProgressSink,
+) -> Cancellable<BulkResult<K, E::Model, C::Error>>
+where
+    E: DefaultModel + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+    C: AsyncClient + Sync,
+{
+    let client = &Tracker::new(client, progress, items.len());
+    let results: Vec<_> = stream::iter(items)
+        .take_while(|_| future::ready(!cancel.is_cancelled()))
+        .map(|item| {
+            let (key, endpoint) = request(item);
+            async move {
+                let result = endpoint.query_async(client).await;
+                client.item_done();
+                (key, result)
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    Cancellable {
+        skipped: items.len() - results.len(),
+        result: BulkResult { results },
+    }
+}
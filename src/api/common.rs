@@ -1,10 +1,48 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 impl_wrapper!(AccessToken, "Type-safe access token wrapper");
-impl_wrapper!(UserId, "Type-safe user id wrapper");
-impl_wrapper!(ProjectId, "Type-safe project id wrapper");
-impl_wrapper!(TermId, "Type-safe term id wrapper");
+impl_wrapper!(UserId, "Type-safe user id wrapper", uuid);
+impl_wrapper!(ProjectId, "Type-safe project id wrapper", uuid);
+impl_wrapper!(TermId, "Type-safe term id wrapper", uuid);
+
+/// The error which is returned when parsing an id that is not a UUID.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid {}: {:?} is not a UUID", kind, id)]
+pub struct ParseIdError {
+    id: String,
+    kind: &'static str,
+}
+
+impl ParseIdError {
+    /// The rejected id.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Checks for the hyphenated form with 32 hexadecimal digits.
+    pub(crate) fn check(id: &str, kind: &'static str) -> Result<(), Self> {
+        let is_uuid = id.len() == 36
+            && id.char_indices().all(|(i, c)| match i {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            });
+        if is_uuid {
+            Ok(())
+        } else {
+            Err(Self {
+                id: id.to_owned(),
+                kind,
+            })
+        }
+    }
+}
 
 /// Data object that is returned by the Traduora API
 /// for multiple endpoints.
@@ -12,7 +50,7 @@ impl_wrapper!(TermId, "Type-safe term id wrapper");
 /// It contains timestamps that inform about the latest
 /// interactions with an object. What exactly an object
 /// is depends on the endpoint that return the [`AccessDates`].
-#[derive(Debug, Copy, Clone, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Copy, Clone, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct AccessDates {
     /// Time when the object was created.
     pub created: DateTime<Utc>,
@@ -20,6 +58,53 @@ pub struct AccessDates {
     pub modified: DateTime<Utc>,
 }
 
+/// Fields of a response that this crate does not know yet, e.g. because
+/// the Traduora server is newer than this crate.
+///
+/// The fields are kept so they are not lost when a model is serialized again.
+/// Two instances are ordered and hashed by their JSON representation.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Extra(pub serde_json::Map<String, serde_json::Value>);
+
+impl Extra {
+    fn to_json(&self) -> String {
+        serde_json::to_string(&self.0).unwrap_or_default()
+    }
+}
+
+impl Deref for Extra {
+    type Target = serde_json::Map<String, serde_json::Value>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Hash for Extra {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if !self.is_empty() {
+            self.to_json().hash(state);
+        }
+    }
+}
+
+impl PartialOrd for Extra {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Extra {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.is_empty() && other.is_empty() {
+            Ordering::Equal
+        } else {
+            self.to_json().cmp(&other.to_json())
+        }
+    }
+}
+
 /// Data object that holds a role. These roles are project-specific for each user.
 /// By default, the creator of a new project becomes its admin.
 /// All other users get the role that the project admin chose for them while inviting.
@@ -38,6 +123,18 @@ pub enum Role {
     Viewer,
 }
 
+impl Role {
+    /// Whether this role may do everything that `required` may do.
+    #[must_use]
+    pub const fn grants(self, required: Self) -> bool {
+        match self {
+            Self::Admin => true,
+            Self::Editor => !matches!(required, Self::Admin),
+            Self::Viewer => matches!(required, Self::Viewer),
+        }
+    }
+}
+
 // False positive in clippy (containing module is glob-exported
 // so pub(crate) is not redundant here):
 // https://github.com/rust-lang/rust-clippy/issues/7862
@@ -45,3 +142,62 @@ pub enum Role {
 pub(crate) mod mime_types {
     pub const JSON: &str = "application/json";
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ProjectId, TermId};
+    use crate::api::{labels::Label, terms::Term};
+
+    #[test]
+    fn ids_are_validated_on_parse() {
+        let id = ProjectId::parse("b1001dd9-e1c0-4fb0-a60d-eaaec304d332").unwrap();
+        assert_eq!(id.value(), "b1001dd9-e1c0-4fb0-a60d-eaaec304d332");
+        assert!("B1001DD9-E1C0-4FB0-A60D-EAAEC304D332"
+            .parse::<TermId>()
+            .is_ok());
+
+        let error = "b1001dd9e1c04fb0a60deaaec304d332"
+            .parse::<TermId>()
+            .unwrap_err();
+        assert_eq!(error.id(), "b1001dd9e1c04fb0a60deaaec304d332");
+        assert_eq!(
+            error.to_string(),
+            "invalid TermId: \"b1001dd9e1c04fb0a60deaaec304d332\" is not a UUID"
+        );
+        assert!(ProjectId::parse("b1001dd9-e1c0-4fb0-a60d-eaaec304d33g").is_err());
+
+        // the permissive constructor still accepts anything
+        assert_eq!(ProjectId::from("project").value(), "project");
+    }
+
+    #[test]
+    fn models_round_trip_through_json() {
+        let data: serde_json::Value =
+            serde_json::from_slice(include_bytes!("../../data/terms.json")).unwrap();
+        let terms: Vec<Term> = serde_json::from_value(data["data"].clone()).unwrap();
+        let json = serde_json::to_value(&terms).unwrap();
+        assert_eq!(json, data["data"]);
+        assert_eq!(serde_json::from_value::<Vec<Term>>(json).unwrap(), terms);
+    }
+
+    #[test]
+    fn unknown_fields_are_kept() {
+        let json = serde_json::json!({
+            "id": "c16d0fc3", "value": "ui", "color": "#D81159", "priority": 3
+        });
+        let label: Label = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(label.extra["priority"], 3);
+        assert_eq!(serde_json::to_value(&label).unwrap(), json);
+        assert_ne!(label, Label::new("c16d0fc3".into(), "ui", "#D81159"));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn ids_convert_to_uuid() {
+        let uuid = uuid::Uuid::parse_str("b1001dd9-e1c0-4fb0-a60d-eaaec304d332").unwrap();
+        let id = ProjectId::from(uuid);
+        assert_eq!(id.value(), "b1001dd9-e1c0-4fb0-a60d-eaaec304d332");
+        assert_eq!(id.to_uuid().unwrap(), uuid);
+        assert!(ProjectId::new("project").to_uuid().is_err());
+    }
+}
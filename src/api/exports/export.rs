@@ -0,0 +1,164 @@
+use std::fmt;
+
+use http::Method;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{locales::LocaleCode, ProjectId, Role},
+    auth::Authenticated,
+    Endpoint,
+};
+
+/// Export all translated terms of a project's locale as a file.
+///
+/// The response is the file itself, so query this endpoint with
+/// [`api::raw`](crate::api::raw) and use the body of the response.
+///
+/// **Endpoint** `GET /api/v1/projects/{projectId}/exports`
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{self, exports::{Export, ExportFormat}}, formats, RawQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let endpoint = Export::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     "de_DE".into(),
+///     ExportFormat::JsonFlat,
+/// );
+/// let response = api::raw(endpoint).query_raw(&client)?;
+///
+/// let translations = formats::parse_json_flat(response.body().as_ref()).unwrap();
+/// assert_eq!(translations["hello.world"], "Hallo Welt");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct Export {
+    /// The project to export.
+    pub project: ProjectId,
+    /// The locale whose translations are exported.
+    pub locale: LocaleCode,
+    /// The file format of the export.
+    pub format: ExportFormat,
+}
+
+impl Export {
+    /// Create a new instance of the export endpoint.
+    pub const fn new(project: ProjectId, locale: LocaleCode, format: ExportFormat) -> Self {
+        Self {
+            project,
+            locale,
+            format,
+        }
+    }
+}
+
+impl Endpoint for Export {
+    type AccessControl = Authenticated;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/exports?locale={}&format={}",
+            self.project, self.locale, self.format
+        )
+        .into()
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project, Role::Viewer))
+    }
+}
+
+/// A file format that Traduora can export to and import from.
+#[derive(Clone, Copy, Debug, Eq, Ord, Hash, PartialEq, PartialOrd, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ExportFormat {
+    /// Android string resources.
+    #[serde(rename = "androidxml")]
+    AndroidXml,
+    /// Comma-separated values.
+    Csv,
+    /// XLIFF version 1.2.
+    #[serde(rename = "xliff12")]
+    Xliff12,
+    /// A flat JSON object from term keys to translations.
+    #[serde(rename = "jsonflat")]
+    JsonFlat,
+    /// A JSON object nested at the dots of the term keys.
+    #[serde(rename = "jsonnested")]
+    JsonNested,
+    /// A flat YAML map from term keys to translations.
+    #[serde(rename = "yamlflat")]
+    YamlFlat,
+    /// A YAML map nested at the dots of the term keys.
+    #[serde(rename = "yamlnested")]
+    YamlNested,
+    /// Java properties.
+    Properties,
+    /// Gettext PO file.
+    #[serde(rename = "po")]
+    Gettext,
+    /// Apple strings file.
+    Strings,
+}
+
+impl ExportFormat {
+    /// All formats.
+    pub const ALL: [Self; 10] = [
+        Self::AndroidXml,
+        Self::Csv,
+        Self::Xliff12,
+        Self::JsonFlat,
+        Self::JsonNested,
+        Self::YamlFlat,
+        Self::YamlNested,
+        Self::Properties,
+        Self::Gettext,
+        Self::Strings,
+    ];
+
+    /// The name of the format in the Traduora API, e.g. `jsonflat`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AndroidXml => "androidxml",
+            Self::Csv => "csv",
+            Self::Xliff12 => "xliff12",
+            Self::JsonFlat => "jsonflat",
+            Self::JsonNested => "jsonnested",
+            Self::YamlFlat => "yamlflat",
+            Self::YamlNested => "yamlnested",
+            Self::Properties => "properties",
+            Self::Gettext => "po",
+            Self::Strings => "strings",
+        }
+    }
+
+    /// The usual file extension of the format without dot, e.g. `json`.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::AndroidXml => "xml",
+            Self::Csv => "csv",
+            Self::Xliff12 => "xliff",
+            Self::JsonFlat | Self::JsonNested => "json",
+            Self::YamlFlat | Self::YamlNested => "yaml",
+            Self::Properties => "properties",
+            Self::Gettext => "po",
+            Self::Strings => "strings",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,5 @@
+//! Contains all endpoints under path `/api/v1/projects/{projectId}/exports`
+
+mod export;
+
+pub use export::{Export, ExportFormat};
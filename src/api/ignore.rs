@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+
+use crate::{endpoint, ApiError, AsyncClient, AsyncCustomQuery, Client, CustomQuery, Endpoint};
+
+/// A query modifier that ignores the response body of an endpoint.
+///
+/// Construct it with [`ignore`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ignore<E> {
+    endpoint: E,
+}
+
+/// Ignore the response body of an endpoint.
+///
+/// Errors are still reported as usual, but the body of a successful response
+/// is discarded without parsing it.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{self, terms::CreateTerm}, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let endpoint = CreateTerm::new("this.is.a.new.term", "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+/// api::ignore(endpoint).query_custom(&client)?;
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub const fn ignore<E>(endpoint: E) -> Ignore<E> {
+    Ignore { endpoint }
+}
+
+impl<E, C> CustomQuery<(), C> for Ignore<E>
+where
+    E: Endpoint,
+    C: Client,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<(), ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(&self.endpoint, client)?;
+        let rsp = client.rest(req, data)?;
+        endpoint::check_status(&rsp)
+            .map_err(|e| e.with_request(endpoint::request_context(&self.endpoint, &rsp)))
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncCustomQuery<(), C> for Ignore<E>
+where
+    E: Endpoint + Sync,
+    C: AsyncClient + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<(), ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(&self.endpoint, client)?;
+        let rsp = client.rest_async(req, data).await?;
+        endpoint::check_status(&rsp)
+            .map_err(|e| e.with_request(endpoint::request_context(&self.endpoint, &rsp)))
+    }
+}
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+use crate::api;
+
+/// Default model.
+///
+/// **Endpoint** `POST /api/v1/projects/{projectId}/imports`
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct ImportSummary {
+    /// What happened to the terms of the file.
+    pub terms: ImportedTerms,
+    /// What happened to the translations of the file.
+    pub translations: ImportedTranslations,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+/// Terms of an imported file.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct ImportedTerms {
+    /// Number of terms that were new to the project.
+    pub added: u32,
+    /// Number of terms that the project already had.
+    pub skipped: u32,
+}
+
+/// Translations of an imported file.
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct ImportedTranslations {
+    /// Number of translations that were created or overwritten.
+    pub upserted: u32,
+}
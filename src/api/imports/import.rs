@@ -0,0 +1,181 @@
+use bytes::{Bytes, BytesMut};
+use http::Method;
+
+use super::ImportSummary;
+use crate::{
+    api::{exports::ExportFormat, locales::LocaleCode, ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    BodyError, Endpoint,
+};
+
+/// Separates the parts of the multipart body.
+const BOUNDARY: &str = "traduora-rs-import-3f1c0d5e9a7b4c21";
+/// Content type of the multipart body, including [`BOUNDARY`].
+const CONTENT_TYPE: &str = "multipart/form-data; boundary=traduora-rs-import-3f1c0d5e9a7b4c21";
+
+/// Import the translations of a locale from a file.
+///
+/// Terms that the project does not have yet are created,
+/// existing translations of the locale are overwritten.
+/// The locale must already exist in the project.
+///
+/// **Endpoint** `POST /api/v1/projects/{projectId}/imports`
+///
+/// **Default model** [`ImportSummary`]
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{exports::ExportFormat, imports::Import}, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let file = br#"{"hello.world": "Hallo Welt", "new.term": "Neuer Begriff"}"#;
+/// let endpoint = Import::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     "de_DE".into(),
+///     ExportFormat::JsonFlat,
+///     file.to_vec(),
+/// );
+/// let summary = endpoint.query(&client)?;
+///
+/// assert_eq!(endpoint.file(), &file[..]);
+/// assert_eq!(summary.terms.added, 1);
+/// assert_eq!(summary.translations.upserted, 2);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct Import {
+    /// The project to import into.
+    pub project: ProjectId,
+    locale: LocaleCode,
+    format: ExportFormat,
+    /// The multipart body, encoded once so that every request shares it.
+    body: Bytes,
+    /// The file within `body`.
+    file: Bytes,
+}
+
+impl Import {
+    /// Create a new instance of the import endpoint.
+    ///
+    /// `file` may be a `Vec<u8>` or [`Bytes`]. It is copied once into the
+    /// request body here; the requests, including retries, share that body
+    /// instead of copying the file again.
+    pub fn new(
+        project: ProjectId,
+        locale: LocaleCode,
+        format: ExportFormat,
+        file: impl Into<Bytes>,
+    ) -> Self {
+        let file = file.into();
+        let header = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}.{}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n",
+            BOUNDARY,
+            locale,
+            format.extension()
+        );
+        let footer = format!("\r\n--{}--\r\n", BOUNDARY);
+        let mut body = BytesMut::with_capacity(header.len() + file.len() + footer.len());
+        body.extend_from_slice(header.as_bytes());
+        body.extend_from_slice(&file);
+        body.extend_from_slice(footer.as_bytes());
+        let body = body.freeze();
+        let file = body.slice(header.len()..header.len() + file.len());
+        Self {
+            project,
+            locale,
+            format,
+            body,
+            file,
+        }
+    }
+
+    /// The locale of the translations in the file.
+    #[must_use]
+    pub const fn locale(&self) -> &LocaleCode {
+        &self.locale
+    }
+
+    /// The file format of the file.
+    #[must_use]
+    pub const fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    /// Content of the file, shared with the request body.
+    #[must_use]
+    pub const fn file(&self) -> &Bytes {
+        &self.file
+    }
+}
+
+impl Endpoint for Import {
+    type AccessControl = Authenticated;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/imports?locale={}&format={}",
+            self.project, self.locale, self.format
+        )
+        .into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, BodyError> {
+        Ok(Some((CONTENT_TYPE, self.body.clone())))
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project, Role::Editor))
+    }
+}
+
+impl DefaultModel for Import {
+    type Model = ImportSummary;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Import, BOUNDARY, CONTENT_TYPE};
+    use crate::{api::exports::ExportFormat, Endpoint};
+
+    #[test]
+    fn file_is_sent_as_multipart_form() {
+        let endpoint = Import::new(
+            "p".into(),
+            "de_DE".into(),
+            ExportFormat::Properties,
+            b"hello=Hallo".to_vec(),
+        );
+        let (content_type, body) = endpoint.body().unwrap().unwrap();
+
+        assert!(content_type.ends_with(BOUNDARY));
+        assert_eq!(content_type, CONTENT_TYPE);
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert!(body.starts_with(&format!("--{}\r\n", BOUNDARY)));
+        assert!(body.contains("filename=\"de_DE.properties\"\r\n"));
+        assert!(body.ends_with(&format!("\r\n\r\nhello=Hallo\r\n--{}--\r\n", BOUNDARY)));
+    }
+
+    #[test]
+    fn requests_share_the_encoded_body() {
+        let endpoint = Import::new(
+            "p".into(),
+            "de_DE".into(),
+            ExportFormat::Properties,
+            b"hello=Hallo".to_vec(),
+        );
+        let (_, first) = endpoint.body().unwrap().unwrap();
+        let (_, retry) = endpoint.clone().body().unwrap().unwrap();
+
+        assert_eq!(first.as_ptr(), retry.as_ptr());
+        let file = endpoint.file().as_ptr() as usize - first.as_ptr() as usize;
+        assert_eq!(&first[file..file + endpoint.file().len()], b"hello=Hallo");
+    }
+}
@@ -0,0 +1,7 @@
+//! Contains all endpoints under path `/api/v1/projects/{projectId}/imports`
+
+mod common;
+mod import;
+
+pub use common::*;
+pub use import::Import;
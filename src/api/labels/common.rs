@@ -1,6 +1,8 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-impl_wrapper!(LabelId, "Type-safe label id wrapper");
+use crate::api;
+
+impl_wrapper!(LabelId, "Type-safe label id wrapper", uuid);
 
 /// Default model.
 ///
@@ -8,8 +10,9 @@ impl_wrapper!(LabelId, "Type-safe label id wrapper");
 /// - `POST /api/v1/projects/{projectId}/labels`
 /// - `GET /api/v1/projects/{projectId}/labels`
 /// - `PATCH /api/v1/projects/{projectId}/labels/{labelId}`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Label {
     /// Unique id of the label.
     pub id: LabelId,
@@ -17,4 +20,19 @@ pub struct Label {
     pub value: String,
     /// The color for this label. Usually in the hex form, e.g. `#D81159`.
     pub color: String,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl Label {
+    /// Creates a label, e.g. for tests.
+    pub fn new(id: LabelId, value: impl Into<String>, color: impl Into<String>) -> Self {
+        Self {
+            id,
+            value: value.into(),
+            color: color.into(),
+            extra: api::Extra::default(),
+        }
+    }
 }
@@ -0,0 +1,81 @@
+use bytes::Bytes;
+use http::Method;
+use serde::Serialize;
+
+use super::Label;
+use crate::{
+    api::{self, ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
+
+/// Create a new label for a project.
+///
+/// **Endpoint** `POST /api/v1/projects/{projectId}/labels`
+///
+/// **Default model** [`Label`]
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::labels::CreateLabel, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project_id = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+/// let label = CreateLabel::new(project_id, "needs review", "#218380").query(&client)?;
+///
+/// assert_eq!(label.value, "needs review");
+/// assert_eq!(label.color, "#218380");
+/// assert_eq!(label.id.value(), "9a3b7b5e-4c1f-4b8e-a8f4-6a9d7e1c2b3f");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd, Serialize)]
+pub struct CreateLabel {
+    /// Project for which the label should be created.
+    #[serde(skip_serializing)]
+    pub project: ProjectId,
+    /// Display name of the label.
+    pub value: String,
+    /// Color of the label in the hex form, e.g. `#D81159`.
+    pub color: String,
+}
+
+impl CreateLabel {
+    /// Create a new instance of the create label endpoint.
+    pub fn new(project: ProjectId, value: impl Into<String>, color: impl Into<String>) -> Self {
+        Self {
+            project,
+            value: value.into(),
+            color: color.into(),
+        }
+    }
+}
+
+impl Endpoint for CreateLabel {
+    type AccessControl = Authenticated;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/labels", self.project).into()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
+        Ok(Some((
+            api::mime_types::JSON,
+            serde_json::to_vec(self)?.into(),
+        )))
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project, Role::Editor))
+    }
+}
+
+impl DefaultModel for CreateLabel {
+    type Model = Label;
+}
@@ -0,0 +1,90 @@
+use http::Method;
+use serde::Deserialize;
+
+use super::LabelId;
+use crate::{
+    api::{locales::LocaleCode, ProjectId, Role, TermId},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
+
+/// Add a label to the translation of a term.
+///
+/// **Endpoint** `POST /api/v1/projects/{projectId}/labels/{labelId}/terms/{termId}/translations/{localeCode}`
+///
+/// **Default model** `()`
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::labels::LabelTranslation, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let endpoint = LabelTranslation::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
+///     "38ba819e-8023-464b-aa1b-6177c149f888".into(),
+///     "de_DE".into(),
+/// );
+/// endpoint.query(&client)?;
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct LabelTranslation {
+    /// Unique id of the project the label and term belong to.
+    pub project_id: ProjectId,
+    /// Unique id of the label to add.
+    pub label_id: LabelId,
+    /// Unique id of the translated term.
+    pub term_id: TermId,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+}
+
+impl LabelTranslation {
+    /// Create a new instance of the label translation endpoint.
+    #[must_use]
+    pub const fn new(
+        project_id: ProjectId,
+        label_id: LabelId,
+        term_id: TermId,
+        locale: LocaleCode,
+    ) -> Self {
+        Self {
+            project_id,
+            label_id,
+            term_id,
+            locale,
+        }
+    }
+}
+
+impl Endpoint for LabelTranslation {
+    type AccessControl = Authenticated;
+
+    fn method(&self) -> Method {
+        Method::POST
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!(
+            "projects/{}/labels/{}/terms/{}/translations/{}",
+            self.project_id, self.label_id, self.term_id, self.locale
+        )
+        .into()
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Editor))
+    }
+}
+
+impl DefaultModel for LabelTranslation {
+    type Model = ();
+
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
+    }
+}
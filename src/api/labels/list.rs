@@ -0,0 +1,53 @@
+use http::Method;
+
+use super::Label;
+use crate::{
+    api::{ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
+
+/// List a project's labels.
+///
+/// **Endpoint** `GET /api/v1/projects/{projectId}/labels`
+///
+/// **Default model** [`Label`]
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::labels::Labels, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let labels = Labels("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into()).query(&client)?;
+///
+/// assert_eq!(labels.len(), 2);
+/// assert_eq!(labels[0].value, "Example label");
+/// assert_eq!(labels[0].color, "#D81159");
+/// assert_eq!(labels[0].id.value(), "c16d0fc3-73e6-4962-b8d5-f3054b8ff002");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct Labels(pub ProjectId);
+
+impl Endpoint for Labels {
+    type AccessControl = Authenticated;
+
+    fn method(&self) -> Method {
+        Method::GET
+    }
+
+    fn endpoint(&self) -> std::borrow::Cow<'static, str> {
+        format!("projects/{}/labels", self.0).into()
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.0, Role::Viewer))
+    }
+}
+
+impl DefaultModel for Labels {
+    type Model = Vec<Label>;
+}
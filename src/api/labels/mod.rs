@@ -1,5 +1,12 @@
-//! Contains all endpoints under path `/api/v1/labels`
-//! The endpoints are not implemented yet.
+//! Contains the endpoints under path `/api/v1/projects/{projectId}/labels`.
+//! Editing, deleting and labeling terms are not implemented yet.
 
 mod common;
+mod create;
+mod label_translation;
+mod list;
+
 pub use common::*;
+pub use create::CreateLabel;
+pub use label_translation::LabelTranslation;
+pub use list::Labels;
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use super::LocaleCode;
+use crate::api::{translations::Translation, TermId};
+
+/// The order in which locales are searched for the translation of a term.
+///
+/// The first locale is the one the chain is for; the others are tried in order
+/// if it has no translation, e.g. `de_AT → de_DE → de → en`.
+/// Empty translations count as missing because Traduora returns
+/// them for terms that were not translated yet.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+/// use chrono::Utc;
+/// use traduora::api::{locales::FallbackChain, translations::Translation, AccessDates};
+///
+/// let date = AccessDates { created: Utc::now(), modified: Utc::now() };
+/// let mut translations = BTreeMap::new();
+/// translations.insert("de_AT".into(), vec![Translation::new("t1".into(), "Servus", date)]);
+/// translations.insert("de_DE".into(), vec![
+///     Translation::new("t1".into(), "Hallo", date),
+///     Translation::new("t2".into(), "Tschüss", date),
+///     Translation::new("t3".into(), "", date),
+/// ]);
+/// translations.insert("en".into(), vec![Translation::new("t3".into(), "Thanks", date)]);
+///
+/// let chain = FallbackChain::new("de_AT".into()).then("de_DE".into()).then("en".into());
+/// let effective = chain.resolve(&translations);
+///
+/// assert_eq!(effective[&"t1".into()], "Servus");
+/// assert_eq!(effective[&"t2".into()], "Tschüss");
+/// assert_eq!(effective[&"t3".into()], "Thanks");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FallbackChain {
+    locales: Vec<LocaleCode>,
+}
+
+impl FallbackChain {
+    /// Creates a chain for the locale without fallbacks.
+    #[must_use]
+    pub fn new(locale: LocaleCode) -> Self {
+        Self {
+            locales: vec![locale],
+        }
+    }
+
+    /// Creates the chain `locale → language → default`, e.g. `de_AT → de → en`.
+    ///
+    /// The language is the part of the locale code before the first `_`.
+    /// Duplicate locales are left out, so `en_US` with default `en`
+    /// becomes `en_US → en`.
+    #[must_use]
+    pub fn with_language(locale: LocaleCode, default: LocaleCode) -> Self {
+        let language = match locale.value().split_once('_') {
+            Some((language, _)) => LocaleCode::new(language),
+            None => locale.clone(),
+        };
+        Self::new(locale).then(language).then(default)
+    }
+
+    /// Adds a locale to the end of the chain unless the chain already contains it.
+    #[must_use]
+    pub fn then(mut self, locale: LocaleCode) -> Self {
+        if !self.locales.contains(&locale) {
+            self.locales.push(locale);
+        }
+        self
+    }
+
+    /// The locale the chain is for.
+    #[must_use]
+    pub fn locale(&self) -> &LocaleCode {
+        &self.locales[0]
+    }
+
+    /// All locales of the chain in the order they are searched.
+    #[must_use]
+    pub fn locales(&self) -> &[LocaleCode] {
+        &self.locales
+    }
+
+    /// The effective translation of each term for the locale of the chain.
+    ///
+    /// Each term gets the first non-empty translation along the chain.
+    /// Terms without any translation in the chain are missing.
+    /// Locales that are not in `translations` are skipped.
+    #[must_use]
+    pub fn resolve(
+        &self,
+        translations: &BTreeMap<LocaleCode, Vec<Translation>>,
+    ) -> BTreeMap<TermId, String> {
+        let mut effective = BTreeMap::new();
+        for locale in self.locales.iter().rev() {
+            let found = translations.get(locale).into_iter().flatten();
+            for translation in found.filter(|t| !t.value.is_empty()) {
+                effective.insert(translation.term_id.clone(), translation.value.clone());
+            }
+        }
+        effective
+    }
+}
+
+/// The effective translations of each chain's locale, see [`FallbackChain::resolve`].
+#[must_use]
+pub fn resolve_fallbacks<'a>(
+    chains: impl IntoIterator<Item = &'a FallbackChain>,
+    translations: &BTreeMap<LocaleCode, Vec<Translation>>,
+) -> BTreeMap<LocaleCode, BTreeMap<TermId, String>> {
+    chains
+        .into_iter()
+        .map(|chain| (chain.locale().clone(), chain.resolve(translations)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::{resolve_fallbacks, FallbackChain};
+    use crate::api::{translations::Translation, AccessDates};
+
+    #[test]
+    fn chain_with_language_skips_duplicates() {
+        let chain = FallbackChain::with_language("de_AT".into(), "en".into());
+        let codes: Vec<_> = chain.locales().iter().map(|l| l.value()).collect();
+        assert_eq!(codes, ["de_AT", "de", "en"]);
+
+        let chain = FallbackChain::with_language("en_US".into(), "en".into());
+        let codes: Vec<_> = chain.locales().iter().map(|l| l.value()).collect();
+        assert_eq!(codes, ["en_US", "en"]);
+    }
+
+    #[test]
+    fn every_chain_is_resolved_on_its_own() {
+        let date = AccessDates {
+            created: Utc::now(),
+            modified: Utc::now(),
+        };
+        let mut translations = BTreeMap::new();
+        translations.insert(
+            "de".into(),
+            vec![Translation::new("t".into(), "Hallo", date)],
+        );
+        translations.insert(
+            "en".into(),
+            vec![Translation::new("t".into(), "Hello", date)],
+        );
+
+        let chains = [
+            FallbackChain::with_language("de_CH".into(), "en".into()),
+            FallbackChain::new("fr".into()),
+        ];
+        let effective = resolve_fallbacks(&chains, &translations);
+
+        assert_eq!(effective[&"de_CH".into()][&"t".into()], "Hallo");
+        assert!(effective[&"fr".into()].is_empty());
+    }
+}
@@ -1,7 +1,7 @@
 use http::Method;
 use serde::{Deserialize, Serialize};
 
-use crate::{auth::Authenticated, query::DefaultModel, Endpoint};
+use crate::{api, auth::Authenticated, query::DefaultModel, Endpoint};
 
 /// List all available locales.
 ///
@@ -61,6 +61,7 @@ impl_wrapper!(
 /// - `GET /api/v1/locales`
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Locale {
     /// Unique code that identifies this locale.
     pub code: LocaleCode,
@@ -68,4 +69,19 @@ pub struct Locale {
     pub language: String,
     /// Display string for the region where it is spoken.
     pub region: String,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl Locale {
+    /// Creates a locale, e.g. for tests.
+    pub fn new(code: LocaleCode, language: impl Into<String>, region: impl Into<String>) -> Self {
+        Self {
+            code,
+            language: language.into(),
+            region: region.into(),
+            extra: api::Extra::default(),
+        }
+    }
 }
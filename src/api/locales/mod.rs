@@ -1,5 +1,7 @@
 //! Contains all endpoints under path `/api/v1/locales`
 
+mod fallback;
 mod list;
 
+pub use fallback::{resolve_fallbacks, FallbackChain};
 pub use list::{AllLocales, Locale, LocaleCode};
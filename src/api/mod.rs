@@ -1,9 +1,18 @@
 //! Contains all endpoints accessible via the [Traduora API](https://docs.traduora.co/docs/api/v1/overview)
 //! (that were implemented yet).
 
+mod bulk;
 mod common;
+mod ignore;
+mod modified_since;
+mod raw;
+mod retry;
+mod term_key;
+mod with_headers;
 
 pub mod auth;
+pub mod exports;
+pub mod imports;
 pub mod labels;
 pub mod locales;
 pub mod projects;
@@ -11,4 +20,11 @@ pub mod terms;
 pub mod translations;
 pub mod users;
 
+pub use bulk::BulkResult;
 pub use common::*;
+pub use ignore::{ignore, Ignore};
+pub use modified_since::{Dated, ModifiedSince};
+pub use raw::{raw, Raw};
+pub use retry::{retry, retry_create, Dedupe, Retry, RetryCreate, RetryPolicy};
+pub use term_key::TermKey;
+pub use with_headers::{with_headers, WithHeaders};
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::{
+        projects::Project, terms::Term, translations::ProjectLocale, translations::Translation,
+        AccessDates,
+    },
+    query::DefaultModel,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// A model that records when it was created and last changed.
+pub trait Dated {
+    /// Creation and modification time.
+    fn date(&self) -> &AccessDates;
+}
+
+impl Dated for Term {
+    fn date(&self) -> &AccessDates {
+        &self.date
+    }
+}
+
+impl Dated for Translation {
+    fn date(&self) -> &AccessDates {
+        &self.date
+    }
+}
+
+impl Dated for Project {
+    fn date(&self) -> &AccessDates {
+        &self.date
+    }
+}
+
+impl Dated for ProjectLocale {
+    fn date(&self) -> &AccessDates {
+        &self.date
+    }
+}
+
+/// A query modifier that only keeps the entries of a list that were changed after a point in time.
+///
+/// Traduora has no query parameter for this, so the whole list is
+/// still downloaded and filtered by the client. This saves processing
+/// the unchanged entries, e.g. when polling a project for changes.
+///
+/// Construct it with e.g. [`Terms::modified_since`](crate::api::terms::Terms::modified_since)
+/// or [`Translations::modified_since`](crate::api::translations::Translations::modified_since).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ModifiedSince<E> {
+    endpoint: E,
+    since: DateTime<Utc>,
+}
+
+impl<E> ModifiedSince<E> {
+    /// Keeps the entries of the list endpoint whose `date.modified` is after `since`.
+    pub const fn new(endpoint: E, since: DateTime<Utc>) -> Self {
+        Self { endpoint, since }
+    }
+
+    /// The point in time after which entries are kept.
+    #[must_use]
+    pub const fn since(&self) -> DateTime<Utc> {
+        self.since
+    }
+}
+
+impl<E, M, C> CustomQuery<Vec<M>, C> for ModifiedSince<E>
+where
+    E: DefaultModel<Model = Vec<M>> + Query<C>,
+    M: Dated,
+    C: Client,
+{
+    fn query_custom(&self, client: &C) -> Result<Vec<M>, ApiError<C::Error>> {
+        let mut entries = self.endpoint.query(client)?;
+        entries.retain(|e| e.date().modified > self.since);
+        Ok(entries)
+    }
+}
+
+#[async_trait]
+impl<E, M, C> AsyncCustomQuery<Vec<M>, C> for ModifiedSince<E>
+where
+    E: DefaultModel<Model = Vec<M>> + AsyncQuery<C> + Sync,
+    M: Dated + Send,
+    C: AsyncClient + Sync,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<Vec<M>, ApiError<C::Error>> {
+        let mut entries = self.endpoint.query_async(client).await?;
+        entries.retain(|e| e.date().modified > self.since);
+        Ok(entries)
+    }
+}
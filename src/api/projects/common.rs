@@ -1,6 +1,6 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::api::{AccessDates, ProjectId, Role};
+use crate::api::{self, AccessDates, ProjectId, Role};
 
 /// A Traduora project.
 /// Each project contains a collection of terms and their translations
@@ -13,8 +13,9 @@ use crate::api::{AccessDates, ProjectId, Role};
 /// - `GET /api/v1/projects/{projectId}`
 /// - `PATCH /api/v1/projects/{projectId}`
 /// - `POST /api/v1/projects`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Project {
     /// Unique id of the project.
     pub id: ProjectId,
@@ -30,4 +31,23 @@ pub struct Project {
     pub role: Role,
     /// Time when the project was created and last modified.
     pub date: AccessDates,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl Project {
+    /// Creates an empty project without description, e.g. for tests.
+    pub fn new(id: ProjectId, name: impl Into<String>, role: Role, date: AccessDates) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            description: String::new(),
+            locales_count: 0,
+            terms_count: 0,
+            role,
+            date,
+            extra: api::Extra::default(),
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
@@ -60,10 +61,10 @@ impl Endpoint for CreateProject {
         "projects".into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
 }
@@ -1,7 +1,12 @@
 use http::Method;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{api::ProjectId, auth::Authenticated, query::DefaultModel, Endpoint};
+use crate::{
+    api::{ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
 
 /// Delete a project.
 ///
@@ -33,12 +38,16 @@ impl Endpoint for DeleteProject {
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
         format!("projects/{}", self.0).into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.0, Role::Admin))
+    }
 }
 
 impl DefaultModel for DeleteProject {
     type Model = ();
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
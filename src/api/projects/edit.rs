@@ -1,9 +1,10 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
 use super::Project;
 use crate::{
-    api::{self, ProjectId},
+    api::{self, ProjectId, Role},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -73,12 +74,16 @@ impl Endpoint for EditProject {
         format!("projects/{}", self.id).into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.id, Role::Admin))
+    }
 }
 
 impl DefaultModel for EditProject {
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+
+use super::{Project, Projects};
+use crate::{
+    auth::Authenticated, ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery,
+    Query,
+};
+
+/// Find a project the current user has access to by its name.
+///
+/// Traduora has no endpoint for this. All projects are listed with
+/// [`Projects`] and filtered on the client. If several projects have the
+/// same name, the first one is returned.
+///
+/// **Endpoint** `GET /api/v1/projects`
+///
+/// **Model** `Option<`[`Project`]`>`
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::projects::FindProject, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+///
+/// let project = FindProject::new("Test project").query_custom(&client)?;
+/// assert_eq!(project.unwrap().id.value(), "64f92751-ef8f-4d1e-83d1-ea10e6939db9");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct FindProject {
+    /// The name of the project.
+    pub name: String,
+}
+
+impl FindProject {
+    /// Creates a new instance of the [`FindProject`] query.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into() }
+    }
+}
+
+impl<C> CustomQuery<Option<Project>, C> for FindProject
+where
+    C: Client,
+    Authenticated: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<Option<Project>, ApiError<C::Error>> {
+        let projects = Projects.query(client)?;
+        Ok(projects.into_iter().find(|p| p.name == self.name))
+    }
+}
+
+#[async_trait]
+impl<C> AsyncCustomQuery<Option<Project>, C> for FindProject
+where
+    C: AsyncClient + Sync,
+    Authenticated: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<Option<Project>, ApiError<C::Error>> {
+        let projects = Projects.query_async(client).await?;
+        Ok(projects.into_iter().find(|p| p.name == self.name))
+    }
+}
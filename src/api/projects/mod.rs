@@ -4,6 +4,7 @@ mod common;
 mod create;
 mod delete;
 mod edit;
+mod find;
 mod list;
 mod show;
 
@@ -11,5 +12,6 @@ pub use common::*;
 pub use create::CreateProject;
 pub use delete::DeleteProject;
 pub use edit::EditProject;
+pub use find::FindProject;
 pub use list::Projects;
 pub use show::ShowProject;
@@ -1,7 +1,12 @@
 use http::Method;
 
 use super::Project;
-use crate::{api::ProjectId, auth::Authenticated, query::DefaultModel, Endpoint};
+use crate::{
+    api::{ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
 
 /// Get a project by id.
 ///
@@ -42,6 +47,10 @@ impl Endpoint for ShowProject {
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
         format!("projects/{}", self.0).into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.0, Role::Viewer))
+    }
 }
 
 impl DefaultModel for ShowProject {
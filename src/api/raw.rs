@@ -0,0 +1,59 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::Response;
+
+use crate::{endpoint, ApiError, AsyncClient, AsyncRawQuery, Client, Endpoint, RawQuery};
+
+/// A query modifier that returns the raw response of an endpoint.
+///
+/// Construct it with [`raw`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Raw<E> {
+    endpoint: E,
+}
+
+/// Return the raw response of an endpoint instead of deserializing it.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use http::StatusCode;
+/// use traduora::{api::{self, users::Me}, RawQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let response = api::raw(Me).query_raw(&client)?;
+///
+/// assert_eq!(response.status(), StatusCode::OK);
+/// let json: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+/// assert_eq!(json["data"]["name"], "Tester");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub const fn raw<E>(endpoint: E) -> Raw<E> {
+    Raw { endpoint }
+}
+
+impl<E, C> RawQuery<C> for Raw<E>
+where
+    E: Endpoint,
+    C: Client,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    fn query_raw(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(&self.endpoint, client)?;
+        client.rest(req, data)
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncRawQuery<C> for Raw<E>
+where
+    E: Endpoint + Sync,
+    C: AsyncClient + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_raw_async(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(&self.endpoint, client)?;
+        client.rest_async(req, data).await
+    }
+}
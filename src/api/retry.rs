@@ -0,0 +1,681 @@
+use bytes::Bytes;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use http::{header::HeaderName, HeaderMap, HeaderValue, Method};
+use serde::Deserialize;
+
+use crate::logging::debug;
+use crate::{
+    api::{ProjectId, Role},
+    query::DefaultModel,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, BodyError, Client, CustomQuery, Endpoint,
+    Query, QueryParams,
+};
+
+/// The header that carries the idempotency key unless configured otherwise.
+const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Decides how often and after which delay a failed request is sent again.
+///
+/// Only transient failures are retried, see [`ApiError::is_transient`], i.e. when
+/// the connection failed or timed out, the server answered with a `5xx` status
+/// code or the rate limit was hit.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use traduora::api::RetryPolicy;
+///
+/// let policy = RetryPolicy::new()
+///     .max_retries(5)
+///     .delay(Duration::from_millis(100));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[must_use]
+pub struct RetryPolicy {
+    max_retries: u32,
+    delay: Duration,
+    backoff_factor: u32,
+}
+
+impl RetryPolicy {
+    /// Construct a new retry policy.
+    ///
+    /// The policy is initialized with the following defaults:
+    /// - at most 3 retries
+    /// - 500ms delay before the first retry
+    /// - the delay doubles with every retry
+    pub const fn new() -> Self {
+        Self {
+            max_retries: 3,
+            delay: Duration::from_millis(500),
+            backoff_factor: 2,
+        }
+    }
+
+    /// Sets the maximum number of retries after the initial attempt.
+    pub const fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied with after each retry.
+    /// A factor of `1` results in a constant delay.
+    pub const fn backoff_factor(mut self, backoff_factor: u32) -> Self {
+        self.backoff_factor = backoff_factor;
+        self
+    }
+
+    /// Whether the error of the attempt after `retry` retries is retried.
+    fn retries<E>(&self, retry: u32, error: &ApiError<E>) -> bool
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        retry < self.max_retries && error.is_transient()
+    }
+
+    fn delay_for(&self, retry: u32) -> Duration {
+        self.delay * self.backoff_factor.saturating_pow(retry)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::api::terms::CreateTerm {}
+    impl Sealed for crate::api::translations::CreateLocale {}
+}
+
+/// An endpoint that creates an entity which can be found again afterwards.
+///
+/// Sending a `POST` request twice usually creates two entities. If the first
+/// attempt reached the server but the response got lost, blindly retrying
+/// produces a duplicate. Endpoints implementing this trait know how to look up
+/// the entity they create so that [`RetryCreate`] can check whether a previous
+/// attempt already succeeded before sending the request again.
+///
+/// This trait is sealed and implemented for the create endpoints whose
+/// entities have a unique key, e.g. [`CreateTerm`](crate::api::terms::CreateTerm).
+pub trait Dedupe: DefaultModel + sealed::Sealed {
+    /// The endpoint listing the entities that this endpoint creates.
+    type Lookup: DefaultModel;
+
+    /// Creates an instance of the lookup endpoint.
+    fn lookup(&self) -> Self::Lookup;
+
+    /// Searches the lookup result for the entity this endpoint would create.
+    fn find(&self, existing: <Self::Lookup as DefaultModel>::Model) -> Option<Self::Model>;
+}
+
+/// Whether sending a request with the method twice has the same effect as sending it once.
+///
+/// Traduora's `PATCH` endpoints set the given fields, so they are idempotent as well.
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+/// Retries an idempotent endpoint according to a [`RetryPolicy`].
+///
+/// `GET`, `PUT`, `PATCH` and `DELETE` requests are sent again on transient
+/// failures. Other requests, in particular `POST` requests that create
+/// entities, are sent only once because a retry could create a duplicate.
+/// Retry those with [`retry_create`] instead.
+///
+/// Construct it with [`retry`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Retry<E> {
+    endpoint: E,
+    policy: RetryPolicy,
+}
+
+/// Retries an idempotent endpoint on transient failures.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{self, terms::Terms, RetryPolicy}, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let endpoint = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+/// let terms = api::retry(endpoint, RetryPolicy::new()).query_custom(&client)?;
+///
+/// assert_eq!(terms.len(), 2);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub const fn retry<E>(endpoint: E, policy: RetryPolicy) -> Retry<E> {
+    Retry { endpoint, policy }
+}
+
+impl<E, C> CustomQuery<E::Model, C> for Retry<E>
+where
+    E: DefaultModel,
+    C: Client,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
+        let idempotent = is_idempotent(&self.endpoint.method());
+        let mut retry = 0;
+        loop {
+            match self.endpoint.query(client) {
+                Err(e) if idempotent && self.policy.retries(retry, &e) => {
+                    debug!(target: "traduora", "retrying {} after error: {}", self.endpoint.endpoint(), e);
+                    std::thread::sleep(self.policy.delay_for(retry));
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncCustomQuery<E::Model, C> for Retry<E>
+where
+    E: DefaultModel + Sync,
+    E::Model: Send,
+    C: AsyncClient + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
+        let idempotent = is_idempotent(&self.endpoint.method());
+        let mut retry = 0;
+        loop {
+            match self.endpoint.query_async(client).await {
+                Err(e) if idempotent && self.policy.retries(retry, &e) => {
+                    debug!(target: "traduora", "retrying {} after error: {}", self.endpoint.endpoint(), e);
+                    tokio::time::sleep(self.policy.delay_for(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Retries a create endpoint according to a [`RetryPolicy`] without creating duplicates.
+///
+/// Before each retry, the lookup endpoint of [`Dedupe`] is queried. If the
+/// entity already exists, it is returned instead of creating it again.
+/// A lookup that fails with a transient error uses up a retry as well and is
+/// repeated before the request is sent again. If the lookup fails otherwise,
+/// [`ApiError::LookupFailed`] keeps the error of the request and of the lookup.
+///
+/// `POST` requests carry an `Idempotency-Key` header with the same random key
+/// in every attempt, so servers and proxies that support it can recognize a
+/// retried request, too. If a retried request is rejected because the entity
+/// already exists, the lookup is queried once more. The conflict is only
+/// returned if the entity still can't be found, i.e. it was created by someone
+/// else; it is classified as [`ApiError::is_conflict`].
+///
+/// Construct it with [`retry_create`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RetryCreate<E> {
+    endpoint: E,
+    policy: RetryPolicy,
+    idempotency_header: Option<HeaderName>,
+}
+
+/// Retries a create endpoint on transient failures without creating duplicates.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{self, terms::CreateTerm, RetryPolicy}, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let endpoint = CreateTerm::new("this.is.a.new.term", "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+/// let term = api::retry_create(endpoint, RetryPolicy::new()).query_custom(&client)?;
+///
+/// assert_eq!(term.value, "this.is.a.new.term");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub const fn retry_create<E>(endpoint: E, policy: RetryPolicy) -> RetryCreate<E> {
+    RetryCreate {
+        endpoint,
+        policy,
+        idempotency_header: Some(IDEMPOTENCY_KEY),
+    }
+}
+
+impl<E> RetryCreate<E> {
+    /// Sets the header that carries the idempotency key of `POST` requests.
+    ///
+    /// `None` sends no idempotency key. Defaults to `Idempotency-Key`.
+    #[must_use]
+    pub fn idempotency_header(mut self, header: Option<HeaderName>) -> Self {
+        self.idempotency_header = header;
+        self
+    }
+
+    fn idempotent(&self) -> Idempotent<'_, E>
+    where
+        E: Endpoint,
+    {
+        let key = match &self.idempotency_header {
+            Some(name) if self.endpoint.method() == Method::POST => {
+                Some((name.clone(), idempotency_key()))
+            }
+            _ => None,
+        };
+        Idempotent {
+            endpoint: &self.endpoint,
+            key,
+        }
+    }
+}
+
+/// Generates a key that is unique across processes and calls.
+fn idempotency_key() -> HeaderValue {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+    let key = format!(
+        "{:x}-{:x}-{:x}",
+        nanos,
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    HeaderValue::from_str(&key).expect("hex digits are a valid header value")
+}
+
+/// An endpoint that sends the same idempotency key with every attempt.
+struct Idempotent<'a, E> {
+    endpoint: &'a E,
+    key: Option<(HeaderName, HeaderValue)>,
+}
+
+impl<E: Endpoint> Endpoint for Idempotent<'_, E> {
+    type AccessControl = E::AccessControl;
+
+    fn method(&self) -> Method {
+        self.endpoint.method()
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.endpoint.endpoint()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, BodyError> {
+        self.endpoint.body()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        self.endpoint.parameters()
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = self.endpoint.headers();
+        if let Some((name, value)) = &self.key {
+            headers.insert(name.clone(), value.clone());
+        }
+        headers
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        self.endpoint.required_role()
+    }
+}
+
+impl<E: DefaultModel> DefaultModel for Idempotent<'_, E> {
+    type Model = E::Model;
+
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        E::map(data)
+    }
+}
+
+impl<E, C> CustomQuery<E::Model, C> for RetryCreate<E>
+where
+    E: Dedupe,
+    C: Client,
+    E::AccessControl: From<C::AccessLevel>,
+    <E::Lookup as Endpoint>::AccessControl: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
+        let endpoint = self.idempotent();
+        let mut retry = 0;
+        loop {
+            let error = match endpoint.query(client) {
+                Err(e) if self.policy.retries(retry, &e) => e,
+                Err(e) if retry > 0 && e.is_conflict() => {
+                    return match self.endpoint.lookup().query(client) {
+                        Ok(existing) => self.endpoint.find(existing).ok_or(e),
+                        Err(lookup) => Err(ApiError::lookup_failed(e, lookup)),
+                    };
+                }
+                result => return result,
+            };
+            // a failed lookup counts as another attempt, the request is only
+            // sent again once the lookup shows that it did not go through
+            loop {
+                debug!(target: "traduora", "retrying {} after error: {}", self.endpoint.endpoint(), error);
+                std::thread::sleep(self.policy.delay_for(retry));
+                retry += 1;
+
+                match self.endpoint.lookup().query(client) {
+                    Ok(existing) => match self.endpoint.find(existing) {
+                        Some(model) => return Ok(model),
+                        None => break,
+                    },
+                    Err(lookup) if self.policy.retries(retry, &lookup) => {}
+                    Err(lookup) => return Err(ApiError::lookup_failed(error, lookup)),
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<E, C> AsyncCustomQuery<E::Model, C> for RetryCreate<E>
+where
+    E: Dedupe + Sync,
+    E::Model: Send,
+    E::Lookup: Send + Sync,
+    C: AsyncClient + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+    <E::Lookup as Endpoint>::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
+        let endpoint = self.idempotent();
+        let mut retry = 0;
+        loop {
+            let error = match endpoint.query_async(client).await {
+                Err(e) if self.policy.retries(retry, &e) => e,
+                Err(e) if retry > 0 && e.is_conflict() => {
+                    let lookup = self.endpoint.lookup();
+                    return match lookup.query_async(client).await {
+                        Ok(existing) => self.endpoint.find(existing).ok_or(e),
+                        Err(lookup) => Err(ApiError::lookup_failed(e, lookup)),
+                    };
+                }
+                result => return result,
+            };
+            // a failed lookup counts as another attempt, see `query_custom`
+            loop {
+                debug!(target: "traduora", "retrying {} after error: {}", self.endpoint.endpoint(), error);
+                tokio::time::sleep(self.policy.delay_for(retry)).await;
+                retry += 1;
+
+                let lookup = self.endpoint.lookup();
+                match lookup.query_async(client).await {
+                    Ok(existing) => match self.endpoint.find(existing) {
+                        Some(model) => return Ok(model),
+                        None => break,
+                    },
+                    Err(lookup) if self.policy.retries(retry, &lookup) => {}
+                    Err(lookup) => return Err(ApiError::lookup_failed(error, lookup)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::{Cell, RefCell};
+    use std::io;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use http::{request::Builder as RequestBuilder, HeaderValue, Method, Response, StatusCode};
+    use url::Url;
+
+    use crate::{
+        api::{
+            self,
+            terms::{CreateTerm, Terms},
+            RetryPolicy,
+        },
+        auth::Authenticated,
+        ApiError, Client, CustomQuery, RestClient,
+    };
+
+    fn connection_lost() -> ApiError<io::Error> {
+        ApiError::client(io::ErrorKind::ConnectionReset.into())
+    }
+
+    /// Pretends the first `POST` went through but the response got lost.
+    ///
+    /// With `conflicts`, the created term only shows up in the term list after
+    /// the first retry, which is rejected because the term already exists.
+    ///
+    /// The first `failing_lookups` lookups fail as well. With `invalid_lookups`,
+    /// all lookups fail with an error that is not caused by the network.
+    ///
+    /// With `invalid`, the `POST` fails with an error that is not caused by the network.
+    #[derive(Default)]
+    struct FlakyClient {
+        posts: Cell<u32>,
+        keys: RefCell<Vec<Option<HeaderValue>>>,
+        conflicts: bool,
+        failing_lookups: Cell<u32>,
+        invalid_lookups: bool,
+        invalid: bool,
+    }
+
+    impl RestClient for FlakyClient {
+        type Error = io::Error;
+        type AccessLevel = Authenticated;
+
+        fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+            Ok(format!("http://localhost/api/v1/{}", endpoint).parse()?)
+        }
+    }
+
+    impl Client for FlakyClient {
+        fn rest(
+            &self,
+            request: RequestBuilder,
+            _: Bytes,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            if request.method_ref() == Some(&Method::POST) {
+                let key = request.headers_ref().unwrap().get("idempotency-key");
+                self.keys.borrow_mut().push(key.cloned());
+                self.posts.set(self.posts.get() + 1);
+                if self.conflicts && self.posts.get() > 1 {
+                    return Ok(Response::builder()
+                        .status(StatusCode::CONFLICT)
+                        .body(Bytes::from_static(
+                            br#"{"error":{"code":"AlreadyExists","message":"Term already exists"}}"#,
+                        ))
+                        .unwrap());
+                }
+                if self.invalid {
+                    return Err(ApiError::client(io::ErrorKind::InvalidInput.into()));
+                }
+                return Err(connection_lost());
+            }
+            if self.invalid_lookups {
+                return Err(ApiError::client(io::ErrorKind::InvalidData.into()));
+            }
+            if self.failing_lookups.get() > 0 {
+                self.failing_lookups.set(self.failing_lookups.get() - 1);
+                return Err(connection_lost());
+            }
+            let body: &[u8] = if self.conflicts && self.posts.get() < 2 {
+                br#"{"data":[]}"#
+            } else {
+                include_bytes!("../../data/terms.json")
+            };
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .body(Bytes::from_static(body))
+                .unwrap())
+        }
+    }
+
+    #[test]
+    fn existing_term_is_returned_instead_of_recreated() {
+        let client = FlakyClient::default();
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let term = api::retry_create(endpoint, policy)
+            .query_custom(&client)
+            .unwrap();
+
+        assert_eq!(term.id.value(), "38ba819e-8023-464b-aa1b-6177c149f888");
+        assert_eq!(client.posts.get(), 1);
+    }
+
+    #[test]
+    fn gives_up_after_max_retries() {
+        let client = FlakyClient::default();
+        let endpoint = CreateTerm::new("not.yet.created", "project".into());
+        let policy = RetryPolicy::new().max_retries(2).delay(Duration::ZERO);
+
+        let result = api::retry_create(endpoint, policy).query_custom(&client);
+
+        assert!(matches!(result, Err(ApiError::Client { .. })));
+        assert_eq!(client.posts.get(), 3);
+    }
+
+    #[test]
+    fn attempts_share_an_idempotency_key_and_conflicts_are_looked_up() {
+        let client = FlakyClient {
+            conflicts: true,
+            ..FlakyClient::default()
+        };
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let term = api::retry_create(endpoint, policy)
+            .query_custom(&client)
+            .unwrap();
+
+        assert_eq!(term.id.value(), "38ba819e-8023-464b-aa1b-6177c149f888");
+        let keys = client.keys.into_inner();
+        assert_eq!(keys.len(), 2);
+        assert!(keys[0].is_some());
+        assert_eq!(keys[0], keys[1]);
+    }
+
+    #[test]
+    fn conflict_is_returned_if_the_term_is_not_found() {
+        let client = FlakyClient {
+            conflicts: true,
+            ..FlakyClient::default()
+        };
+        let endpoint = CreateTerm::new("not.yet.created", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let error = api::retry_create(endpoint, policy)
+            .idempotency_header(None)
+            .query_custom(&client)
+            .unwrap_err();
+
+        assert!(error.is_conflict());
+        assert_eq!(client.keys.into_inner(), [None, None]);
+    }
+
+    #[test]
+    fn failed_lookup_is_retried_instead_of_aborting() {
+        let client = FlakyClient {
+            failing_lookups: Cell::new(1),
+            ..FlakyClient::default()
+        };
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let term = api::retry_create(endpoint, policy)
+            .query_custom(&client)
+            .unwrap();
+
+        assert_eq!(term.id.value(), "38ba819e-8023-464b-aa1b-6177c149f888");
+        assert_eq!(client.posts.get(), 1);
+        assert_eq!(client.failing_lookups.get(), 0);
+    }
+
+    #[test]
+    fn non_network_client_error_is_not_retried() {
+        let client = FlakyClient {
+            invalid: true,
+            ..FlakyClient::default()
+        };
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let error = api::retry_create(endpoint, policy)
+            .query_custom(&client)
+            .unwrap_err();
+
+        assert!(!error.is_transient());
+        assert_eq!(client.posts.get(), 1);
+    }
+
+    #[test]
+    fn request_error_is_kept_if_the_lookup_fails() {
+        let client = FlakyClient {
+            invalid_lookups: true,
+            ..FlakyClient::default()
+        };
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let error = api::retry_create(endpoint, policy)
+            .query_custom(&client)
+            .unwrap_err();
+
+        let (error, lookup) = match error {
+            ApiError::LookupFailed { error, lookup } => (error, lookup),
+            e => panic!("unexpected error: {}", e),
+        };
+        assert!(
+            matches!(*error, ApiError::Client { ref source } if source.kind() == io::ErrorKind::ConnectionReset)
+        );
+        assert!(lookup.to_string().contains("invalid data"));
+        assert_eq!(client.posts.get(), 1);
+    }
+
+    #[test]
+    fn idempotent_requests_are_retried() {
+        let client = FlakyClient {
+            failing_lookups: Cell::new(2),
+            ..FlakyClient::default()
+        };
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let terms = api::retry(Terms::new("project".into()), policy)
+            .query_custom(&client)
+            .unwrap();
+
+        assert_eq!(terms.len(), 2);
+        assert_eq!(client.failing_lookups.get(), 0);
+    }
+
+    #[test]
+    fn posts_are_not_retried_without_dedupe() {
+        let client = FlakyClient::default();
+        let endpoint = CreateTerm::new("this.is.a.term", "project".into());
+        let policy = RetryPolicy::new().delay(Duration::ZERO);
+
+        let error = api::retry(endpoint, policy)
+            .query_custom(&client)
+            .unwrap_err();
+
+        assert!(error.is_transient());
+        assert_eq!(client.posts.get(), 1);
+        assert_eq!(client.keys.into_inner(), [None]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::borrow::Borrow;
+
+use crate::formats::NESTING_SEPARATOR;
+
+impl_wrapper!(
+    TermKey,
+    "Type-safe wrapper for the string of a term, e.g. `checkout.title`.\n\n\
+     In contrast to the [`TermId`](crate::api::TermId), which is chosen by Traduora, \
+     the key is what applications use to look up translations. Keys form a hierarchy \
+     whose levels are separated by [`NESTING_SEPARATOR`]."
+);
+
+impl TermKey {
+    /// The levels of the key, e.g. `checkout` and `title` for `checkout.title`.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split(NESTING_SEPARATOR)
+    }
+
+    /// The key one level up, e.g. `checkout` for `checkout.title`.
+    ///
+    /// `None` for top-level keys.
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        self.0
+            .rsplit_once(NESTING_SEPARATOR)
+            .map(|(parent, _)| Self::new(parent))
+    }
+
+    /// The key one level down, e.g. `checkout.title` for `checkout` and `title`.
+    #[must_use]
+    pub fn child(&self, segment: &str) -> Self {
+        Self(format!("{}{}{}", self.0, NESTING_SEPARATOR, segment))
+    }
+
+    /// Whether `parent` is the key one level up.
+    #[must_use]
+    pub fn is_child_of(&self, parent: &Self) -> bool {
+        self.parent().as_ref() == Some(parent)
+    }
+
+    /// Whether `ancestor` is a key any number of levels up.
+    ///
+    /// `checkout.form.name` is a descendant of `checkout` but not of `check`.
+    #[must_use]
+    pub fn is_descendant_of(&self, ancestor: &Self) -> bool {
+        self.0
+            .strip_prefix(ancestor.value())
+            .map_or(false, |rest| rest.starts_with(NESTING_SEPARATOR))
+    }
+
+    /// Whether the key matches a glob pattern.
+    ///
+    /// The pattern is split into levels like a key. `*` matches any part of
+    /// a single level and a level of only `**` matches any number of levels,
+    /// including none.
+    ///
+    /// # Examples
+    /// ```
+    /// use traduora::api::TermKey;
+    ///
+    /// let key = TermKey::new("checkout.form.name");
+    /// assert!(key.matches("checkout.**"));
+    /// assert!(key.matches("checkout.*.name"));
+    /// assert!(key.matches("**.n*"));
+    /// assert!(!key.matches("checkout.*"));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, pattern: &str) -> bool {
+        let key: Vec<_> = self.segments().collect();
+        let pattern: Vec<_> = pattern.split(NESTING_SEPARATOR).collect();
+        match_levels(&key, &pattern)
+    }
+
+    /// The key for parsers that build it piece by piece.
+    pub(crate) fn value_mut(&mut self) -> &mut String {
+        &mut self.0
+    }
+}
+
+// the struct is declared by `impl_wrapper!`, which cannot derive it
+#[allow(clippy::derivable_impls)]
+impl Default for TermKey {
+    fn default() -> Self {
+        Self(String::new())
+    }
+}
+
+impl Borrow<str> for TermKey {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for TermKey {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+fn match_levels(key: &[&str], pattern: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => key.is_empty(),
+        Some((&"**", rest)) => (0..=key.len()).any(|skip| match_levels(&key[skip..], rest)),
+        Some((level, rest)) => match key.split_first() {
+            Some((segment, key)) => match_level(segment, level) && match_levels(key, rest),
+            None => false,
+        },
+    }
+}
+
+fn match_level(segment: &str, pattern: &str) -> bool {
+    match pattern.split_once('*') {
+        None => segment == pattern,
+        Some((prefix, rest)) => segment.strip_prefix(prefix).map_or(false, |segment| {
+            segment
+                .char_indices()
+                .map(|(i, _)| i)
+                .chain(std::iter::once(segment.len()))
+                .any(|i| match_level(&segment[i..], rest))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TermKey;
+
+    #[test]
+    fn hierarchy_is_split_at_separator() {
+        let key = TermKey::new("checkout.form.name");
+        assert_eq!(key.parent(), Some("checkout.form".into()));
+        assert_eq!(TermKey::new("checkout").parent(), None);
+        assert_eq!(
+            TermKey::new("checkout").child("form"),
+            "checkout.form".into()
+        );
+        assert!(key.is_child_of(&"checkout.form".into()));
+        assert!(!key.is_child_of(&"checkout".into()));
+        assert!(key.is_descendant_of(&"checkout".into()));
+        assert!(!key.is_descendant_of(&"check".into()));
+        assert!(!key.is_descendant_of(&key));
+    }
+
+    #[test]
+    fn globs_match_levels() {
+        let key = TermKey::new("checkout.title");
+        assert!(key.matches("checkout.*"));
+        assert!(key.matches("*.title"));
+        assert!(key.matches("check*.t*e"));
+        assert!(key.matches("**"));
+        assert!(key.matches("checkout.**.title"));
+        assert!(!key.matches("checkout"));
+        assert!(!key.matches("*"));
+        assert!(!key.matches("checkout.*.title"));
+        assert!(!key.matches("check*.x*"));
+    }
+}
@@ -0,0 +1,334 @@
+use super::{CreateTerm, DeleteTerm, Term};
+use crate::{
+    api::{bulk, BulkResult, ProjectId, TermId},
+    auth::Authenticated,
+    cancel::{Cancellable, CancellationToken},
+    progress::{NoProgress, ProgressSink},
+    AsyncClient, Client,
+};
+
+/// Add many new terms to a project.
+///
+/// Sends one [`CreateTerm`] request per term. A failing term does not stop
+/// the creation of the remaining terms; the outcome of each term is
+/// returned in a [`BulkResult`].
+///
+/// **Endpoint** `POST /api/v1/projects/{projectId}/terms` (once per term)
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::api::{terms::CreateTerms, ProjectId};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = ProjectId::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332");
+/// let result = CreateTerms::new(project, ["this.is.a.new.term"]).create(&client);
+///
+/// assert!(result.is_ok());
+/// assert_eq!(result.succeeded().count(), 1);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CreateTerms {
+    /// Project for which the terms should be created.
+    pub project: ProjectId,
+    /// The strings that should become terms.
+    pub terms: Vec<String>,
+    concurrency: usize,
+}
+
+impl CreateTerms {
+    /// Creates the terms with up to 4 concurrent requests on asynchronous clients.
+    pub fn new<I>(project: ProjectId, terms: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self {
+            project,
+            terms: terms.into_iter().map(Into::into).collect(),
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many requests are sent at the same time by [`CreateTerms::create_async`].
+    ///
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Creates the terms one after another.
+    pub fn create<C>(&self, client: &C) -> BulkResult<String, Term, C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.create_with_progress(client, &NoProgress)
+    }
+
+    /// Creates the terms one after another and reports the progress.
+    pub fn create_with_progress<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<String, Term, C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.create_cancellable(client, &CancellationToken::new(), progress)
+            .into_inner()
+    }
+
+    /// Creates the terms one after another until the token is cancelled and reports the progress.
+    ///
+    /// The result only contains the terms that were sent before the cancellation.
+    pub fn create_cancellable<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<String, Term, C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        bulk::run(
+            &self.terms,
+            |term| {
+                (
+                    term.clone(),
+                    CreateTerm::new(term.as_str(), self.project.clone()),
+                )
+            },
+            client,
+            cancel,
+            progress,
+        )
+    }
+
+    /// Creates the terms asynchronously with bounded concurrency.
+    ///
+    /// The results are in the same order as the terms.
+    pub async fn create_async<C>(&self, client: &C) -> BulkResult<String, Term, C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.create_with_progress_async(client, &NoProgress).await
+    }
+
+    /// Creates the terms asynchronously with bounded concurrency and reports the progress.
+    ///
+    /// The results are in the same order as the terms.
+    pub async fn create_with_progress_async<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<String, Term, C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.create_cancellable_async(client, &CancellationToken::new(), progress)
+            .await
+            .into_inner()
+    }
+
+    /// Creates the terms asynchronously with bounded concurrency until the
+    /// token is cancelled and reports the progress.
+    ///
+    /// No new requests are sent after the cancellation, running requests are
+    /// finished. The result only contains the terms that were sent, in the
+    /// same order as the terms.
+    pub async fn create_cancellable_async<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<String, Term, C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        bulk::run_async(
+            &self.terms,
+            self.concurrency,
+            |term| {
+                (
+                    term.clone(),
+                    CreateTerm::new(term.as_str(), self.project.clone()),
+                )
+            },
+            client,
+            cancel,
+            progress,
+        )
+        .await
+    }
+}
+
+/// Remove many terms of a project.
+///
+/// Sends one [`DeleteTerm`] request per term. A failing term does not stop
+/// the deletion of the remaining terms; the outcome of each term is
+/// returned in a [`BulkResult`] in the order of the term ids.
+///
+/// With a [`DryRunClient`](crate::dry_run::DryRunClient), all deletions
+/// succeed without being sent and the recorded requests are the plan.
+///
+/// **Endpoint** `DELETE /api/v1/projects/{projectId}/terms/{termId}` (once per term)
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::terms::DeleteTerms, dry_run::DryRunClient};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = DryRunClient::new(Traduora::with_auth("localhost:8080", login)?);
+/// let result = DeleteTerms::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     ["38ba819e-8023-464b-aa1b-6177c149f888".into(), "7eafe83d-1448-49ea-8ae0-f8753cbd669c".into()],
+/// )
+/// .delete(&client);
+///
+/// assert!(result.is_ok());
+/// for request in client.recorded() {
+///     println!("would send {}", request);
+/// }
+/// assert_eq!(client.recorded().len(), 2);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct DeleteTerms {
+    /// Project the terms belong to.
+    pub project: ProjectId,
+    /// Unique ids of the terms to delete.
+    pub terms: Vec<TermId>,
+    concurrency: usize,
+}
+
+impl DeleteTerms {
+    /// Deletes the terms with up to 4 concurrent requests on asynchronous clients.
+    pub fn new<I>(project: ProjectId, terms: I) -> Self
+    where
+        I: IntoIterator<Item = TermId>,
+    {
+        Self {
+            project,
+            terms: terms.into_iter().collect(),
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many requests are sent at the same time by [`DeleteTerms::delete_async`].
+    ///
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Deletes the terms one after another.
+    pub fn delete<C>(&self, client: &C) -> BulkResult<TermId, (), C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.delete_with_progress(client, &NoProgress)
+    }
+
+    /// Deletes the terms one after another and reports the progress.
+    pub fn delete_with_progress<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<TermId, (), C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.delete_cancellable(client, &CancellationToken::new(), progress)
+            .into_inner()
+    }
+
+    /// Deletes the terms one after another until the token is cancelled and reports the progress.
+    ///
+    /// The result only contains the terms that were sent before the cancellation.
+    pub fn delete_cancellable<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<TermId, (), C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        bulk::run(
+            &self.terms,
+            |term| {
+                (
+                    term.clone(),
+                    DeleteTerm::new(self.project.clone(), term.clone()),
+                )
+            },
+            client,
+            cancel,
+            progress,
+        )
+    }
+
+    /// Deletes the terms asynchronously with bounded concurrency.
+    ///
+    /// The results are in the same order as the term ids.
+    pub async fn delete_async<C>(&self, client: &C) -> BulkResult<TermId, (), C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.delete_with_progress_async(client, &NoProgress).await
+    }
+
+    /// Deletes the terms asynchronously with bounded concurrency and reports the progress.
+    ///
+    /// The results are in the same order as the term ids.
+    pub async fn delete_with_progress_async<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<TermId, (), C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.delete_cancellable_async(client, &CancellationToken::new(), progress)
+            .await
+            .into_inner()
+    }
+
+    /// Deletes the terms asynchronously with bounded concurrency until the
+    /// token is cancelled and reports the progress.
+    ///
+    /// No new requests are sent after the cancellation, running requests are
+    /// finished. The result only contains the terms that were sent, in the
+    /// same order as the term ids.
+    pub async fn delete_cancellable_async<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<TermId, (), C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        bulk::run_async(
+            &self.terms,
+            self.concurrency,
+            |term| {
+                (
+                    term.clone(),
+                    DeleteTerm::new(self.project.clone(), term.clone()),
+                )
+            },
+            client,
+            cancel,
+            progress,
+        )
+        .await
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::api;
 
@@ -8,8 +8,9 @@ use crate::api;
 /// - `POST /api/v1/projects/{projectId}/terms`
 /// - `GET /api/v1/projects/{projectId}/terms`
 /// - `PATCH /api/v1/projects/{projectId}/terms/{termId}`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Term {
     /// Unique id of the created term.
     pub id: api::TermId,
@@ -20,4 +21,27 @@ pub struct Term {
     /// Timestamp about creation and last modification
     /// of this term.
     pub date: api::AccessDates,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl Term {
+    /// Creates a term without labels, e.g. for tests.
+    pub fn new(id: api::TermId, value: impl Into<String>, date: api::AccessDates) -> Self {
+        Self {
+            id,
+            value: value.into(),
+            labels: Vec::new(),
+            date,
+            extra: api::Extra::default(),
+        }
+    }
+
+    /// Replaces the labels of the term.
+    #[must_use]
+    pub fn with_labels(mut self, labels: Vec<api::labels::Label>) -> Self {
+        self.labels = labels;
+        self
+    }
 }
@@ -1,8 +1,14 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
-use super::Term;
-use crate::{api, auth::Authenticated, query::DefaultModel, Endpoint};
+use super::{Term, Terms};
+use crate::{
+    api::{self, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
 
 /// Add a new project term
 ///
@@ -30,14 +36,14 @@ use crate::{api, auth::Authenticated, query::DefaultModel, Endpoint};
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
 pub struct CreateTerm {
     /// The string that should become a term.
-    pub term: String,
+    pub term: api::TermKey,
     /// Project for which the term should be created.
     pub project: api::ProjectId,
 }
 
 impl CreateTerm {
     /// Creates a new instance of the [`CreateTerm`] endpoint.
-    pub fn new(term: impl Into<String>, project: api::ProjectId) -> Self {
+    pub fn new(term: impl Into<api::TermKey>, project: api::ProjectId) -> Self {
         Self {
             term: term.into(),
             project,
@@ -56,21 +62,39 @@ impl Endpoint for CreateTerm {
         format!("projects/{}/terms", self.project).into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         #[derive(Serialize)]
         struct Dto<'a> {
             pub value: &'a str,
         }
 
-        let dto = Dto { value: &self.term };
+        let dto = Dto {
+            value: self.term.value(),
+        };
 
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(&dto)?.into_bytes(),
+            serde_json::to_vec(&dto)?.into(),
         )))
     }
+
+    fn required_role(&self) -> Option<(&api::ProjectId, Role)> {
+        Some((&self.project, Role::Editor))
+    }
 }
 
 impl DefaultModel for CreateTerm {
     type Model = Term;
 }
+
+impl api::Dedupe for CreateTerm {
+    type Lookup = Terms;
+
+    fn lookup(&self) -> Self::Lookup {
+        Terms::new(self.project.clone())
+    }
+
+    fn find(&self, existing: Vec<Term>) -> Option<Self::Model> {
+        existing.into_iter().find(|t| t.value == self.term.value())
+    }
+}
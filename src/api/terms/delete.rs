@@ -1,7 +1,8 @@
 use http::Method;
+use serde::Deserialize;
 
 use crate::{
-    api::{ProjectId, TermId},
+    api::{ProjectId, Role, TermId},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -55,12 +56,16 @@ impl Endpoint for DeleteTerm {
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
         format!("projects/{}/terms/{}", self.project_id, self.term_id).into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Editor))
+    }
 }
 
 impl DefaultModel for DeleteTerm {
     type Model = ();
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
@@ -1,9 +1,10 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
 use super::Term;
 use crate::{
-    api::{self, ProjectId, TermId},
+    api::{self, ProjectId, Role, TermId},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -30,11 +31,7 @@ use crate::{
 /// assert_eq!(term.value,  "new.term.text");
 /// assert_eq!(
 ///     term.labels,
-///     vec![Label {
-///         id: "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
-///         value: "Example label".into(),
-///         color: "#D81159".into()
-///     }]
+///     vec![Label::new("c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(), "Example label", "#D81159")]
 /// );
 /// assert_eq!(term.id.value(), "0fa39756-65db-423c-a6d9-534b62fe9ead");
 /// assert_eq!(term.date.created, Utc.ymd(2021, 10, 23).and_hms_milli(16, 12, 55, 691));
@@ -75,12 +72,16 @@ impl Endpoint for EditTerm {
         format!("projects/{}/terms/{}", self.project_id, self.term_id).into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Editor))
+    }
 }
 
 impl DefaultModel for EditTerm {
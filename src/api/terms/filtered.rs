@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+
+use super::{Term, Terms};
+use crate::{
+    api::labels::LabelId, auth::Authenticated, ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery,
+    Client, CustomQuery, Query,
+};
+
+/// A query modifier that filters and pages the terms of a project.
+///
+/// Traduora has no query parameters for this, so the whole list is
+/// still downloaded and filtered by the client. It only saves processing
+/// the other terms; offset and limit page the filtered list, not the download.
+///
+/// Construct it with e.g. [`Terms::search`] or [`Terms::limit`]. The
+/// filters can also be applied to a list that was already fetched with
+/// [`FilteredTerms::apply`].
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct FilteredTerms {
+    endpoint: Terms,
+    search: Option<String>,
+    label: Option<LabelId>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl FilteredTerms {
+    /// Keeps all terms of the list endpoint until filters are added.
+    #[must_use]
+    pub const fn new(endpoint: Terms) -> Self {
+        Self {
+            endpoint,
+            search: None,
+            label: None,
+            offset: 0,
+            limit: None,
+        }
+    }
+
+    /// Only keeps terms whose value contains `search`, case-sensitively.
+    #[must_use]
+    pub fn search(mut self, search: impl Into<String>) -> Self {
+        self.search = Some(search.into());
+        self
+    }
+
+    /// Only keeps terms with the label.
+    #[must_use]
+    pub fn label(mut self, label: LabelId) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Skips the first `offset` terms that match the filters.
+    #[must_use]
+    pub const fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Keeps at most `limit` terms that match the filters.
+    #[must_use]
+    pub const fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether the term matches the search text and the label.
+    #[must_use]
+    pub fn matches(&self, term: &Term) -> bool {
+        self.search
+            .as_ref()
+            .map_or(true, |s| term.value.contains(s))
+            && self
+                .label
+                .as_ref()
+                .map_or(true, |label| term.labels.iter().any(|l| &l.id == label))
+    }
+
+    /// Filters and pages a list of terms.
+    #[must_use]
+    pub fn apply(&self, terms: Vec<Term>) -> Vec<Term> {
+        terms
+            .into_iter()
+            .filter(|t| self.matches(t))
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+impl<C> CustomQuery<Vec<Term>, C> for FilteredTerms
+where
+    C: Client,
+    Authenticated: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<Vec<Term>, ApiError<C::Error>> {
+        let terms = self.endpoint.query(client)?;
+        Ok(self.apply(terms))
+    }
+}
+
+#[async_trait]
+impl<C> AsyncCustomQuery<Vec<Term>, C> for FilteredTerms
+where
+    C: AsyncClient + Sync,
+    Authenticated: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<Vec<Term>, ApiError<C::Error>> {
+        let terms = self.endpoint.query_async(client).await?;
+        Ok(self.apply(terms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::Terms;
+    use crate::{
+        api::terms::Term,
+        fixtures,
+        mock::{Expectation, MockTraduora},
+        CustomQuery,
+    };
+
+    #[test]
+    fn filters_and_pagination_are_applied_by_the_client() {
+        let mock = MockTraduora::new();
+        let mut labelled = fixtures::term("3", "app.title");
+        labelled["labels"] = json!([fixtures::label("l", "Label", "#000000")]);
+        let terms = json!([
+            fixtures::term("1", "app.name"),
+            fixtures::term("2", "menu.open"),
+            labelled,
+            fixtures::term("4", "app.quit"),
+            fixtures::term("5", "app.help"),
+        ]);
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/terms")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(terms)),
+        );
+
+        let page: Vec<Term> = Terms::new("p".into())
+            .search("app.")
+            .offset(1)
+            .limit(2)
+            .query_custom(&mock)
+            .unwrap();
+        let labelled: Vec<Term> = Terms::new("p".into())
+            .label("l".into())
+            .query_custom(&mock)
+            .unwrap();
+
+        let ids: Vec<_> = page.iter().map(|t| t.id.value()).collect();
+        assert_eq!(ids, ["3", "4"]);
+        assert_eq!(labelled.len(), 1);
+        assert_eq!(labelled[0].value, "app.title");
+        let calls = mock.calls();
+        assert!(calls.iter().all(|call| call.query.is_none()));
+        mock.verify().unwrap();
+    }
+}
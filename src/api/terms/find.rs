@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+
+use super::{Term, Terms};
+use crate::{
+    api::{ProjectId, TermId},
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// Find a project's term by its term string or by its id.
+///
+/// Traduora has no endpoint for this, not even for a single term by id.
+/// All terms of the project are listed with [`Terms`] and searched on the
+/// client, so every query downloads the whole list. If no term matches,
+/// the model is `None`.
+///
+/// **Endpoint** `GET /api/v1/projects/{projectId}/terms`
+///
+/// **Model** `Option<`[`Term`]`>`
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::terms::FindTerm, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let term = FindTerm::new(project, "this.is.another.term").query_custom(&client)?;
+/// assert_eq!(term.unwrap().id.value(), "7eafe83d-1448-49ea-8ae0-f8753cbd669c");
+///
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+/// let term = FindTerm::new(project, "no.such.term").query_custom(&client)?;
+/// assert!(term.is_none());
+///
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+/// let id = "38ba819e-8023-464b-aa1b-6177c149f888".into();
+/// let term = FindTerm::by_id(project, id).query_custom(&client)?;
+/// assert_eq!(term.unwrap().value, "this.is.a.term");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct FindTerm {
+    /// Project to search.
+    pub project: ProjectId,
+    lookup: Lookup,
+}
+
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+enum Lookup {
+    Key(String),
+    Id(TermId),
+}
+
+impl FindTerm {
+    /// Creates a new instance of the [`FindTerm`] query that looks for the term string.
+    pub fn new(project: ProjectId, key: impl Into<String>) -> Self {
+        Self {
+            project,
+            lookup: Lookup::Key(key.into()),
+        }
+    }
+
+    /// Creates a new instance of the [`FindTerm`] query that looks for the
+    /// unique id of the term, e.g. one stored by a previous run.
+    #[must_use]
+    pub const fn by_id(project: ProjectId, id: TermId) -> Self {
+        Self {
+            project,
+            lookup: Lookup::Id(id),
+        }
+    }
+
+    fn find(&self, terms: Vec<Term>) -> Option<Term> {
+        terms.into_iter().find(|t| match &self.lookup {
+            Lookup::Key(key) => &t.value == key,
+            Lookup::Id(id) => &t.id == id,
+        })
+    }
+}
+
+impl<C> CustomQuery<Option<Term>, C> for FindTerm
+where
+    C: Client,
+    Authenticated: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<Option<Term>, ApiError<C::Error>> {
+        let terms = Terms::new(self.project.clone()).query(client)?;
+        Ok(self.find(terms))
+    }
+}
+
+#[async_trait]
+impl<C> AsyncCustomQuery<Option<Term>, C> for FindTerm
+where
+    C: AsyncClient + Sync,
+    Authenticated: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<Option<Term>, ApiError<C::Error>> {
+        let terms = Terms::new(self.project.clone()).query_async(client).await?;
+        Ok(self.find(terms))
+    }
+}
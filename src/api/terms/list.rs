@@ -1,7 +1,13 @@
+use chrono::{DateTime, Utc};
 use http::Method;
 
-use super::Term;
-use crate::{api::ProjectId, auth::Authenticated, query::DefaultModel, Endpoint};
+use super::{FilteredTerms, Term};
+use crate::{
+    api::{labels::LabelId, ModifiedSince, ProjectId, Role},
+    auth::Authenticated,
+    query::DefaultModel,
+    Endpoint,
+};
 
 /// List a project's terms.
 ///
@@ -17,17 +23,13 @@ use crate::{api::ProjectId, auth::Authenticated, query::DefaultModel, Endpoint};
 ///
 /// # let login = Login::password("tester@mail.example", "letmeinpls");
 /// let client = Traduora::with_auth("localhost:8080", login)?;
-/// let terms = Terms("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into()).query(&client)?;
+/// let terms = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into()).query(&client)?;
 ///
 /// assert_eq!(terms.len(), 2);
 /// assert_eq!(terms[0].value, "this.is.a.term");
 /// assert_eq!(
 ///     terms[0].labels,
-///     vec![Label {
-///         id: "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
-///         value: "Example label".into(),
-///         color: "#D81159".into()
-///     }]
+///     vec![Label::new("c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(), "Example label", "#D81159")]
 /// );
 /// assert_eq!(terms[0].id.value(), "38ba819e-8023-464b-aa1b-6177c149f888");
 /// assert_eq!(terms[0].date.created, Utc.ymd(2021, 10, 24).and_hms_milli(18, 43, 12, 131));
@@ -41,7 +43,93 @@ use crate::{api::ProjectId, auth::Authenticated, query::DefaultModel, Endpoint};
 /// # Ok::<(), TraduoraError>(())
 /// ```
 #[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
-pub struct Terms(pub ProjectId);
+pub struct Terms {
+    /// Project whose terms are listed.
+    pub project: ProjectId,
+}
+
+impl Terms {
+    /// Lists all terms of the project.
+    #[must_use]
+    pub const fn new(project: ProjectId) -> Self {
+        Self { project }
+    }
+
+    /// Only returns the terms whose value contains `search`, case-sensitively.
+    ///
+    /// The terms are filtered by the client, see [`FilteredTerms`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use traduora::{api::terms::{Term, Terms}, CustomQuery};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let terms: Vec<Term> = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+    ///     .search("another")
+    ///     .limit(10)
+    ///     .query_custom(&client)?;
+    ///
+    /// assert_eq!(terms.len(), 1);
+    /// assert_eq!(terms[0].value, "this.is.another.term");
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    #[must_use]
+    pub fn search(self, search: impl Into<String>) -> FilteredTerms {
+        FilteredTerms::new(self).search(search)
+    }
+
+    /// Only returns the terms with the label.
+    ///
+    /// The terms are filtered by the client, see [`FilteredTerms`].
+    #[must_use]
+    pub fn label(self, label: LabelId) -> FilteredTerms {
+        FilteredTerms::new(self).label(label)
+    }
+
+    /// Skips the first `offset` terms.
+    ///
+    /// The terms are paged by the client, see [`FilteredTerms`].
+    #[must_use]
+    pub fn offset(self, offset: usize) -> FilteredTerms {
+        FilteredTerms::new(self).offset(offset)
+    }
+
+    /// Returns at most `limit` terms.
+    ///
+    /// The terms are paged by the client, see [`FilteredTerms`].
+    #[must_use]
+    pub fn limit(self, limit: usize) -> FilteredTerms {
+        FilteredTerms::new(self).limit(limit)
+    }
+
+    /// Only returns the terms that were changed after `since`.
+    ///
+    /// The terms are filtered by the client, see [`ModifiedSince`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use chrono::{TimeZone, Utc};
+    /// use traduora::{api::terms::{Term, Terms}, CustomQuery};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let since = Utc.ymd(2021, 10, 24).and_hms(19, 0, 0);
+    /// let terms: Vec<Term> = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+    ///     .modified_since(since)
+    ///     .query_custom(&client)?;
+    ///
+    /// assert_eq!(terms.len(), 1);
+    /// assert_eq!(terms[0].value, "this.is.another.term");
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    #[must_use]
+    pub const fn modified_since(self, since: DateTime<Utc>) -> ModifiedSince<Self> {
+        ModifiedSince::new(self, since)
+    }
+}
 
 impl Endpoint for Terms {
     type AccessControl = Authenticated;
@@ -51,7 +139,11 @@ impl Endpoint for Terms {
     }
 
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
-        format!("projects/{}/terms", self.0).into()
+        format!("projects/{}/terms", self.project).into()
+    }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project, Role::Viewer))
     }
 }
 
@@ -1,13 +1,19 @@
 //! Contains all endpoints under path `/api/v1/projects/{projectId}/terms`.
 
+mod bulk;
 mod common;
 mod create;
 mod delete;
 mod edit;
+mod filtered;
+mod find;
 mod list;
 
+pub use bulk::{CreateTerms, DeleteTerms};
 pub use common::*;
 pub use create::CreateTerm;
 pub use delete::DeleteTerm;
 pub use edit::EditTerm;
+pub use filtered::FilteredTerms;
+pub use find::FindTerm;
 pub use list::Terms;
@@ -0,0 +1,176 @@
+use super::{EditTranslation, Translation};
+use crate::{
+    api::{bulk, locales::LocaleCode, BulkResult, ProjectId, TermId},
+    auth::Authenticated,
+    cancel::{Cancellable, CancellationToken},
+    progress::{NoProgress, ProgressSink},
+    AsyncClient, Client,
+};
+
+/// Update many translations of a locale.
+///
+/// Sends one [`EditTranslation`] request per translation. A failing
+/// translation does not stop the update of the remaining ones; the outcome
+/// of each translation is returned in a [`BulkResult`] keyed by the term id.
+///
+/// **Endpoint** `PATCH /api/v1/projects/{projectId}/translations/{localeCode}` (once per translation)
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::api::translations::EditTranslations;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let result = EditTranslations::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     "en_US".into(),
+///     [("7eafe83d-1448-49ea-8ae0-f8753cbd669c".into(), "New translation")],
+/// )
+/// .edit(&client);
+///
+/// assert!(result.is_ok());
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct EditTranslations {
+    /// Project the terms belong to.
+    pub project: ProjectId,
+    /// Locale of the translations.
+    pub locale: LocaleCode,
+    /// The new translation of each term.
+    pub translations: Vec<(TermId, String)>,
+    concurrency: usize,
+}
+
+impl EditTranslations {
+    /// Updates the translations with up to 4 concurrent requests on asynchronous clients.
+    pub fn new<I, V>(project: ProjectId, locale: LocaleCode, translations: I) -> Self
+    where
+        I: IntoIterator<Item = (TermId, V)>,
+        V: Into<String>,
+    {
+        Self {
+            project,
+            locale,
+            translations: translations
+                .into_iter()
+                .map(|(id, value)| (id, value.into()))
+                .collect(),
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many requests are sent at the same time by [`EditTranslations::edit_async`].
+    ///
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    fn endpoint(&self, term: &TermId, value: &str) -> EditTranslation {
+        EditTranslation::new(
+            self.project.clone(),
+            self.locale.clone(),
+            term.clone(),
+            value,
+        )
+    }
+
+    /// Updates the translations one after another.
+    pub fn edit<C>(&self, client: &C) -> BulkResult<TermId, Translation, C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.edit_with_progress(client, &NoProgress)
+    }
+
+    /// Updates the translations one after another and reports the progress.
+    pub fn edit_with_progress<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<TermId, Translation, C::Error>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.edit_cancellable(client, &CancellationToken::new(), progress)
+            .into_inner()
+    }
+
+    /// Updates the translations one after another until the token is
+    /// cancelled and reports the progress.
+    ///
+    /// The result only contains the translations that were sent before the cancellation.
+    pub fn edit_cancellable<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<TermId, Translation, C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        bulk::run(
+            &self.translations,
+            |(term, value)| (term.clone(), self.endpoint(term, value)),
+            client,
+            cancel,
+            progress,
+        )
+    }
+
+    /// Updates the translations asynchronously with bounded concurrency.
+    ///
+    /// The results are in the same order as the translations.
+    pub async fn edit_async<C>(&self, client: &C) -> BulkResult<TermId, Translation, C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.edit_with_progress_async(client, &NoProgress).await
+    }
+
+    /// Updates the translations asynchronously with bounded concurrency and reports the progress.
+    ///
+    /// The results are in the same order as the translations.
+    pub async fn edit_with_progress_async<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> BulkResult<TermId, Translation, C::Error>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.edit_cancellable_async(client, &CancellationToken::new(), progress)
+            .await
+            .into_inner()
+    }
+
+    /// Updates the translations asynchronously with bounded concurrency until
+    /// the token is cancelled and reports the progress.
+    ///
+    /// No new requests are sent after the cancellation, running requests are
+    /// finished. The result only contains the translations that were sent,
+    /// in the same order as the translations.
+    pub async fn edit_cancellable_async<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Cancellable<BulkResult<TermId, Translation, C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        bulk::run_async(
+            &self.translations,
+            self.concurrency,
+            |(term, value)| (term.clone(), self.endpoint(term, value)),
+            client,
+            cancel,
+            progress,
+        )
+        .await
+    }
+}
@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::api;
 
@@ -8,16 +8,40 @@ use crate::api;
 ///
 /// **Endpoint**
 /// - `GET /api/v1/projects/{projectId}/translations/{localeCode}`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct Translation {
     /// Unique id of the term.
     pub term_id: api::TermId,
     /// The translation of the term.
     pub value: String,
-    /// Labels the term is tagged with.
+    /// Labels the translation is tagged with.
     pub labels: Vec<api::labels::Label>,
     /// Timestamp about creation and last modification
     /// of this translation.
     pub date: api::AccessDates,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl Translation {
+    /// Creates a translation without labels, e.g. for tests.
+    pub fn new(term_id: api::TermId, value: impl Into<String>, date: api::AccessDates) -> Self {
+        Self {
+            term_id,
+            value: value.into(),
+            labels: Vec::new(),
+            date,
+            extra: api::Extra::default(),
+        }
+    }
+
+    /// Replaces the labels of the translation.
+    #[must_use]
+    pub fn with_labels(mut self, labels: Vec<api::labels::Label>) -> Self {
+        self.labels = labels;
+        self
+    }
 }
@@ -1,9 +1,10 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
-use super::ProjectLocale;
+use super::{Locales, ProjectLocale};
 use crate::{
-    api::{self, locales::LocaleCode, ProjectId},
+    api::{self, locales::LocaleCode, ProjectId, Role},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -59,14 +60,30 @@ impl Endpoint for CreateLocale {
         format!("projects/{}/translations", self.project).into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project, Role::Editor))
+    }
 }
 
 impl DefaultModel for CreateLocale {
     type Model = ProjectLocale;
 }
+
+impl api::Dedupe for CreateLocale {
+    type Lookup = Locales;
+
+    fn lookup(&self) -> Self::Lookup {
+        Locales(self.project.clone())
+    }
+
+    fn find(&self, existing: Vec<ProjectLocale>) -> Option<Self::Model> {
+        existing.into_iter().find(|l| l.locale.code == self.code)
+    }
+}
@@ -1,7 +1,8 @@
 use http::Method;
+use serde::Deserialize;
 
 use crate::{
-    api::{locales::LocaleCode, ProjectId},
+    api::{locales::LocaleCode, ProjectId, Role},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -52,12 +53,16 @@ impl Endpoint for DeleteLocale {
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
         format!("projects/{}/translations/{}", self.project_id, self.locale).into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Editor))
+    }
 }
 
 impl DefaultModel for DeleteLocale {
     type Model = ();
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
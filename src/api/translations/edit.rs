@@ -1,9 +1,10 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
 use super::Translation;
 use crate::{
-    api::{self, locales::LocaleCode, ProjectId, TermId},
+    api::{self, locales::LocaleCode, ProjectId, Role, TermId},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -32,11 +33,7 @@ use crate::{
 /// assert_eq!(translation.value, "New translation");
 /// assert_eq!(
 ///     translation.labels,
-///     vec![Label {
-///         id: "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
-///         value: "Example label".into(),
-///         color: "#D81159".into()
-///     }]
+///     vec![Label::new("c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(), "Example label", "#D81159")]
 /// );
 /// assert_eq!(translation.term_id.value(), "7eafe83d-1448-49ea-8ae0-f8753cbd669c");
 /// assert_eq!(translation.date.created, Utc.ymd(2021, 10, 25).and_hms_milli(18, 54, 16, 422));
@@ -86,12 +83,16 @@ impl Endpoint for EditTranslation {
         format!("projects/{}/translations/{}", self.project_id, self.locale).into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Editor))
+    }
 }
 
 impl DefaultModel for EditTranslation {
@@ -0,0 +1,155 @@
+use async_trait::async_trait;
+
+use super::{Translation, Translations};
+use crate::{
+    api::labels::LabelId, auth::Authenticated, ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery,
+    Client, CustomQuery, Query,
+};
+
+/// A query modifier that filters the translations of a locale.
+///
+/// Traduora has no query parameters for this, so the whole list is
+/// still downloaded and filtered by the client.
+///
+/// Construct it with [`Translations::untranslated_only`] or [`Translations::label`].
+/// The filters can also be applied to a list that was already fetched with
+/// [`FilteredTranslations::apply`].
+#[derive(Clone, Debug, Eq, Ord, Hash, PartialEq, PartialOrd)]
+pub struct FilteredTranslations {
+    endpoint: Translations,
+    untranslated: bool,
+    label: Option<LabelId>,
+}
+
+impl FilteredTranslations {
+    /// Keeps all translations of the list endpoint until filters are added.
+    #[must_use]
+    pub const fn new(endpoint: Translations) -> Self {
+        Self {
+            endpoint,
+            untranslated: false,
+            label: None,
+        }
+    }
+
+    /// Only keeps the terms that are not translated yet.
+    ///
+    /// Traduora lists terms without a translation with an empty value.
+    #[must_use]
+    pub const fn untranslated_only(mut self) -> Self {
+        self.untranslated = true;
+        self
+    }
+
+    /// Only keeps the translations that are tagged with the label.
+    ///
+    /// Only the labels of the translation itself are compared, the labels
+    /// of its term are not part of the list.
+    #[must_use]
+    pub fn label(mut self, label: LabelId) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Whether the translation matches the filters.
+    #[must_use]
+    pub fn matches(&self, translation: &Translation) -> bool {
+        (!self.untranslated || translation.value.is_empty())
+            && self.label.as_ref().map_or(true, |label| {
+                translation.labels.iter().any(|l| &l.id == label)
+            })
+    }
+
+    /// Filters a list of translations.
+    #[must_use]
+    pub fn apply(&self, mut translations: Vec<Translation>) -> Vec<Translation> {
+        translations.retain(|t| self.matches(t));
+        translations
+    }
+}
+
+impl<C> CustomQuery<Vec<Translation>, C> for FilteredTranslations
+where
+    C: Client,
+    Authenticated: From<C::AccessLevel>,
+{
+    fn query_custom(&self, client: &C) -> Result<Vec<Translation>, ApiError<C::Error>> {
+        let translations = self.endpoint.query(client)?;
+        Ok(self.apply(translations))
+    }
+}
+
+#[async_trait]
+impl<C> AsyncCustomQuery<Vec<Translation>, C> for FilteredTranslations
+where
+    C: AsyncClient + Sync,
+    Authenticated: From<C::AccessLevel>,
+{
+    async fn query_custom_async(&self, client: &C) -> Result<Vec<Translation>, ApiError<C::Error>> {
+        let translations = self.endpoint.query_async(client).await?;
+        Ok(self.apply(translations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::Translations;
+    use crate::{
+        api::translations::Translation,
+        fixtures,
+        mock::{Expectation, MockTraduora},
+        AsyncCustomQuery, CustomQuery,
+    };
+
+    fn mock(times: usize) -> MockTraduora {
+        let mock = MockTraduora::new();
+        let mut labelled = fixtures::translation("2", "");
+        labelled["labels"] = json!([fixtures::label("l", "Label", "#000000")]);
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/translations/de")
+                .times(times)
+                .respond_json(
+                    StatusCode::OK,
+                    fixtures::data(json!([
+                        fixtures::translation("1", "Hallo"),
+                        labelled,
+                        fixtures::translation("3", ""),
+                    ])),
+                ),
+        );
+        mock
+    }
+
+    #[test]
+    fn filters_are_applied_by_the_client() {
+        let mock = mock(1);
+
+        let translations: Vec<Translation> = Translations::new("p".into(), "de".into())
+            .untranslated_only()
+            .label("l".into())
+            .query_custom(&mock)
+            .unwrap();
+
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations[0].term_id.value(), "2");
+        assert_eq!(mock.calls()[0].query, None);
+    }
+
+    #[tokio::test]
+    async fn untranslated_terms_are_kept() {
+        let mock = mock(1);
+
+        let translations: Vec<Translation> = Translations::new("p".into(), "de".into())
+            .untranslated_only()
+            .query_custom_async(&mock)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = translations.iter().map(|t| t.term_id.value()).collect();
+        assert_eq!(ids, ["2", "3"]);
+        mock.verify().unwrap();
+    }
+}
@@ -1,8 +1,9 @@
+use chrono::{DateTime, Utc};
 use http::Method;
 
-use super::Translation;
+use super::{FilteredTranslations, Translation};
 use crate::{
-    api::{locales::LocaleCode, ProjectId},
+    api::{labels::LabelId, locales::LocaleCode, ModifiedSince, ProjectId, Role},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -30,11 +31,7 @@ use crate::{
 /// assert_eq!(translations[0].value, "My first translation");
 /// assert_eq!(
 ///     translations[0].labels,
-///     vec![Label {
-///         id: "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
-///         value: "Example label".into(),
-///         color: "#D81159".into()
-///     }]
+///     vec![Label::new("c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(), "Example label", "#D81159")]
 /// );
 /// assert_eq!(translations[0].term_id.value(), "38ba819e-8023-464b-aa1b-6177c149f888");
 /// assert_eq!(translations[0].date.created, Utc.ymd(2021, 10, 25).and_hms_milli(18, 54, 16, 426));
@@ -64,6 +61,69 @@ impl Translations {
             locale_code,
         }
     }
+
+    /// Only returns the terms that are not translated yet.
+    ///
+    /// The translations are filtered by the client, see [`FilteredTranslations`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use traduora::{api::translations::{Translation, Translations}, CustomQuery};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let missing: Vec<Translation> =
+    ///     Translations::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(), "en_US".into())
+    ///         .untranslated_only()
+    ///         .query_custom(&client)?;
+    ///
+    /// assert!(missing.is_empty());
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    #[must_use]
+    pub const fn untranslated_only(self) -> FilteredTranslations {
+        FilteredTranslations::new(self).untranslated_only()
+    }
+
+    /// Only returns the translations that are tagged with the label.
+    ///
+    /// The translations are filtered by the client, see [`FilteredTranslations`].
+    #[must_use]
+    pub fn label(self, label: LabelId) -> FilteredTranslations {
+        FilteredTranslations::new(self).label(label)
+    }
+
+    /// Only returns the translations that were changed after `since`.
+    ///
+    /// The translations are filtered by the client, see [`ModifiedSince`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use chrono::{TimeZone, Utc};
+    /// use traduora::{api::translations::{Translation, Translations}, AsyncCustomQuery};
+    ///
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let since = Utc.ymd(2021, 10, 25).and_hms(18, 54, 20);
+    /// let translations: Vec<Translation> =
+    ///     Translations::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(), "en_US".into())
+    ///         .modified_since(since)
+    ///         .query_custom_async(&client)
+    ///         .await?;
+    ///
+    /// assert_eq!(translations.len(), 1);
+    /// assert_eq!(translations[0].value, "My second translation");
+    /// # Ok::<(), TraduoraError>(())
+    /// # })?;
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    #[must_use]
+    pub const fn modified_since(self, since: DateTime<Utc>) -> ModifiedSince<Self> {
+        ModifiedSince::new(self, since)
+    }
 }
 
 impl Endpoint for Translations {
@@ -81,6 +141,10 @@ impl Endpoint for Translations {
         )
         .into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.project_id, Role::Viewer))
+    }
 }
 
 impl DefaultModel for Translations {
@@ -1,8 +1,8 @@
 use http::Method;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    api::{locales::Locale, AccessDates, ProjectId},
+    api::{self, locales::Locale, AccessDates, ProjectId, Role},
     auth::Authenticated,
     query::DefaultModel,
     Endpoint,
@@ -50,6 +50,10 @@ impl Endpoint for Locales {
     fn endpoint(&self) -> std::borrow::Cow<'static, str> {
         format!("projects/{}/translations", self.0).into()
     }
+
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        Some((&self.0, Role::Viewer))
+    }
 }
 
 impl DefaultModel for Locales {
@@ -58,7 +62,8 @@ impl DefaultModel for Locales {
 
 impl_wrapper!(
     ProjectLocaleId,
-    "Type-safe wrapper for a project locale id."
+    "Type-safe wrapper for a project locale id.",
+    uuid
 );
 
 /// A project's locale. Contains a generic [`Locale`] as well
@@ -68,8 +73,9 @@ impl_wrapper!(
 ///
 /// **Endpoint**
 /// - `GET /api/v1/projects/{projectId}/translations`
-#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct ProjectLocale {
     /// Unique id.
     pub id: ProjectLocaleId,
@@ -77,4 +83,19 @@ pub struct ProjectLocale {
     pub locale: Locale,
     /// Modification and creation times.
     pub date: AccessDates,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl ProjectLocale {
+    /// Creates a project locale, e.g. for tests.
+    pub fn new(id: ProjectLocaleId, locale: Locale, date: AccessDates) -> Self {
+        Self {
+            id,
+            locale,
+            date,
+            extra: api::Extra::default(),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use http::Method;
+use serde::Deserialize;
 
 use crate::{auth::Authenticated, query::DefaultModel, Endpoint};
 
@@ -36,7 +37,7 @@ impl Endpoint for DeleteMe {
 impl DefaultModel for DeleteMe {
     type Model = ();
 
-    fn map(data: serde_json::Value) -> Result<Self::Model, serde_json::Error> {
-        serde_json::from_value(data)
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(data)
     }
 }
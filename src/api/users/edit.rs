@@ -1,3 +1,4 @@
+use bytes::Bytes;
 use http::Method;
 use serde::Serialize;
 
@@ -75,10 +76,10 @@ impl Endpoint for EditMe {
         "users/me".into()
     }
 
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, crate::BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, crate::BodyError> {
         Ok(Some((
             api::mime_types::JSON,
-            serde_json::to_string(self)?.into_bytes(),
+            serde_json::to_vec(self)?.into(),
         )))
     }
 }
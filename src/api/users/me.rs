@@ -1,4 +1,4 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::{api, auth::Authenticated, query::DefaultModel, Endpoint};
 
@@ -46,8 +46,9 @@ impl DefaultModel for Me {
 /// **Endpoint**
 /// - `GET /api/v1/users/me`
 /// - `PATCH /api/v1/user/me`
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct UserInfo {
     /// Unique id for the user.
     pub id: api::UserId,
@@ -58,4 +59,20 @@ pub struct UserInfo {
     /// Number of projects the user created. If it's none,
     /// the API didn't provide this value.
     pub num_projects_created: Option<u64>,
+    /// Fields sent by the Traduora server that this crate does not know yet.
+    #[serde(flatten)]
+    pub extra: api::Extra,
+}
+
+impl UserInfo {
+    /// Creates the user information, e.g. for tests.
+    pub fn new(id: api::UserId, name: impl Into<String>, email: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+            email: email.into(),
+            num_projects_created: None,
+            extra: api::Extra::default(),
+        }
+    }
 }
@@ -0,0 +1,90 @@
+use bytes::Bytes;
+use std::borrow::Cow;
+
+use http::{header::HeaderName, HeaderMap, HeaderValue, Method};
+use serde::Deserialize;
+
+use crate::{
+    api::{ProjectId, Role},
+    query::DefaultModel,
+    BodyError, Endpoint, QueryParams,
+};
+
+/// An endpoint modifier that sends additional headers.
+///
+/// Construct it with [`with_headers`].
+#[derive(Debug, Clone)]
+pub struct WithHeaders<E> {
+    endpoint: E,
+    headers: HeaderMap,
+}
+
+/// Send additional headers with the request of an endpoint.
+///
+/// Headers with the same name as one set by the endpoint replace it.
+/// The result is an endpoint itself and can be queried like the wrapped one.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use http::{HeaderMap, HeaderValue};
+/// use traduora::{api::{self, users::Me}, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-request-id", HeaderValue::from_static("f1d1c3a0"));
+/// let user_info = api::with_headers(Me, headers).query(&client)?;
+///
+/// assert_eq!(user_info.name, "Tester");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub const fn with_headers<E>(endpoint: E, headers: HeaderMap) -> WithHeaders<E> {
+    WithHeaders { endpoint, headers }
+}
+
+impl<E> WithHeaders<E> {
+    /// Adds another header, replacing previous values with the same name.
+    #[must_use]
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+}
+
+impl<E: Endpoint> Endpoint for WithHeaders<E> {
+    type AccessControl = E::AccessControl;
+
+    fn method(&self) -> Method {
+        self.endpoint.method()
+    }
+
+    fn endpoint(&self) -> Cow<'static, str> {
+        self.endpoint.endpoint()
+    }
+
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, BodyError> {
+        self.endpoint.body()
+    }
+
+    fn parameters(&self) -> QueryParams<'_> {
+        self.endpoint.parameters()
+    }
+
+    fn headers(&self) -> HeaderMap {
+        let mut headers = self.endpoint.headers();
+        headers.extend(self.headers.clone());
+        headers
+    }
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        self.endpoint.required_role()
+    }
+}
+
+impl<E: DefaultModel> DefaultModel for WithHeaders<E> {
+    type Model = E::Model;
+
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
+        E::map(data)
+    }
+}
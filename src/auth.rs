@@ -1,17 +1,34 @@
 //! Permission levels.
 //!
-//! Types that implement the trait [Scope] represent a particular permission level.
-//! Currently there are only two distinct levels:
+//! Permissions are checked on two levels.
+//!
+//! Types that implement the trait [Scope] represent whether a client is logged in:
 //! * [Unauthenticated]
 //! * [Authenticated]
 //!
 //! For a client, a higher access level allows it to access more endpoints.
 //! For an endpoint, a higher access level prevents it from being accessed by
-//! more clients.
+//! more clients. Having distinct types for the different scopes allows
+//! compile-time permission checks.
+//!
+//! Within each project, the client additionally has a [`Role`](crate::api::Role),
+//! e.g. a user may administrate one project but only view another. Each endpoint
+//! of a project knows its project and the least privileged role that may access
+//! it. If the role of the client in that project is declared with
+//! [`TraduoraBuilder::project_role`](crate::TraduoraBuilder::project_role),
+//! requests for endpoints that need a more privileged role fail with
+//! [`ApiError::MissingRole`](crate::ApiError::MissingRole) before they are sent.
+//! This check happens at runtime because the roles are only known then; projects
+//! without a declared role are not checked and rely on the server instead.
 //!
-//! Having distinct types for the different scopes allows compile-time permission checks.
+//! How the authentication information of a scope ends up in a request is decided
+//! by an [`Authenticator`]. The default is [`BearerAuth`].
 
-use http::{HeaderMap, HeaderValue};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use http::{HeaderMap, HeaderValue, Request};
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
 use std::fmt::Debug;
 use thiserror::Error;
 
@@ -28,6 +45,26 @@ pub enum AuthError {
         #[from]
         source: http::header::InvalidHeaderValue,
     },
+    /// A custom [`Authenticator`] failed.
+    #[error("authenticator error: {}", source)]
+    Custom {
+        /// Inner error.
+        source: Box<dyn StdError + Send + Sync>,
+    },
+}
+
+impl AuthError {
+    /// Create an authentication error from an arbitrary error.
+    ///
+    /// Meant to be used by custom [`Authenticator`] implementations.
+    pub fn custom<E>(source: E) -> Self
+    where
+        E: Into<Box<dyn StdError + Send + Sync>>,
+    {
+        Self::Custom {
+            source: source.into(),
+        }
+    }
 }
 
 /// Determines the permissions of a client.
@@ -42,9 +79,225 @@ pub trait Scope {
     fn set_header<'a>(&self, headers: &'a mut HeaderMap) -> Result<&'a mut HeaderMap, AuthError>;
 }
 
+/// Attaches authentication information to an outgoing request.
+///
+/// By default, clients use [`BearerAuth`] which lets the client's [`Scope`]
+/// set the `Authorization` header. Implement this trait to support other schemes,
+/// e.g. to sign requests for an API gateway or to add session cookies. Configure
+/// the authenticator with [`TraduoraBuilder::authenticator`](crate::TraduoraBuilder::authenticator).
+///
+/// # Examples
+/// Adding a cookie that a reverse proxy in front of Traduora requires:
+/// ```
+/// use bytes::Bytes;
+/// use http::{header, HeaderValue, Request};
+/// use traduora::auth::{AuthError, Authenticator, BearerAuth, Scope};
+///
+/// #[derive(Debug)]
+/// struct ProxyCookie(String);
+///
+/// impl Authenticator for ProxyCookie {
+///     fn authenticate(&self, scope: &dyn Scope, request: &mut Request<Bytes>) -> Result<(), AuthError> {
+///         BearerAuth.authenticate(scope, request)?;
+///         let cookie = HeaderValue::from_str(&format!("proxy_session={}", self.0))?;
+///         request.headers_mut().insert(header::COOKIE, cookie);
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Authenticator: Debug + Send + Sync {
+    /// Adds authentication information to the request.
+    ///
+    /// The request is fully built, so the method, URI and body are available
+    /// e.g. to compute a signature.
+    ///
+    /// # Errors
+    /// Returns an error if the authentication information cannot be added.
+    fn authenticate(
+        &self,
+        scope: &dyn Scope,
+        request: &mut Request<Bytes>,
+    ) -> Result<(), AuthError>;
+}
+
+/// The default [`Authenticator`].
+///
+/// Sends the access token of the client's [`Scope`] as `Bearer` token in
+/// the `Authorization` header.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BearerAuth;
+
+impl Authenticator for BearerAuth {
+    fn authenticate(
+        &self,
+        scope: &dyn Scope,
+        request: &mut Request<Bytes>,
+    ) -> Result<(), AuthError> {
+        scope.set_header(request.headers_mut())?;
+        Ok(())
+    }
+}
+
+/// An access token together with the time it expires.
+///
+/// Store it, e.g. in a file, to reuse the token in the next run of the
+/// process instead of logging in again. Pass it to
+/// [`TraduoraBuilder::with_access_token`](crate::TraduoraBuilder::with_access_token)
+/// to build a client with it.
+///
+/// # Examples
+/// ```
+/// # use traduora::{TestClient, TraduoraError};
+/// use traduora::{api::auth::Token, auth::StoredToken, Query, TraduoraBuilder};
+///
+/// # let client = TestClient::new("localhost:8080")?;
+/// // the same as `client.access_token()` of an authenticated client
+/// let token: StoredToken = Token::password("tester@mail.example", "letmeinpls")
+///     .query(&client)?
+///     .into();
+/// let json = serde_json::to_string(&token).unwrap();
+///
+/// // in the next run
+/// let token: StoredToken = serde_json::from_str(&json).unwrap();
+/// if !token.is_expired() {
+///     let client = TraduoraBuilder::new("localhost:8080")
+///         .with_access_token(token)
+///         .build()?;
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StoredToken {
+    /// The token sent in the `Authorization` header.
+    pub access_token: api::AccessToken,
+    /// When the token expires. `None` if unknown.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl StoredToken {
+    /// Creates a token that expires at the given time.
+    #[must_use]
+    pub const fn new(access_token: api::AccessToken, expires_at: Option<DateTime<Utc>>) -> Self {
+        Self {
+            access_token,
+            expires_at,
+        }
+    }
+
+    /// When the token expires.
+    ///
+    /// Taken from the `exp` claim if the token is a JWT, which is the case for
+    /// tokens issued by Traduora, and from [`expires_at`](Self::expires_at) otherwise.
+    /// `None` if unknown.
+    #[must_use]
+    pub fn expiry(&self) -> Option<DateTime<Utc>> {
+        Claims::parse(self.access_token.value())
+            .and_then(|claims| claims.exp)
+            .and_then(|exp| Utc.timestamp_opt(exp, 0).single())
+            .or(self.expires_at)
+    }
+
+    /// Whether the token has expired. Tokens without known expiry never expire.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.expiry().map_or(false, |expiry| expiry <= Utc::now())
+    }
+
+    /// The user or project client the token was issued for, i.e. the `sub` claim of the JWT.
+    #[must_use]
+    pub fn subject(&self) -> Option<String> {
+        Claims::parse(self.access_token.value()).and_then(|claims| claims.sub)
+    }
+}
+
+/// The claims of a JWT that are of interest to the client.
+///
+/// The signature is not verified: the token is only inspected to find out
+/// when to log in again. The server still validates it on every request.
+#[derive(Debug, Deserialize)]
+struct Claims {
+    exp: Option<i64>,
+    sub: Option<String>,
+}
+
+impl Claims {
+    fn parse(token: &str) -> Option<Self> {
+        let payload = token.split('.').nth(1)?;
+        serde_json::from_slice(&decode_base64url(payload)?).ok()
+    }
+}
+
+/// Decodes unpadded base64url as used by JWTs.
+fn decode_base64url(input: &str) -> Option<Vec<u8>> {
+    let sextet = |c: u8| match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'-' => Some(62),
+        b'_' => Some(63),
+        _ => None,
+    };
+
+    let mut output = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0_u32;
+    let mut bits = 0;
+    for c in input.trim_end_matches('=').bytes() {
+        buffer = buffer << 6 | u32::from(sextet(c)?);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            output.push((buffer >> bits) as u8);
+        }
+    }
+    Some(output)
+}
+
+impl From<api::AccessToken> for StoredToken {
+    /// Wraps a token string whose expiry is unknown.
+    fn from(f: api::AccessToken) -> Self {
+        Self::new(f, None)
+    }
+}
+
+impl From<api::auth::AccessToken> for StoredToken {
+    /// Computes the expiry of a freshly issued token from its lifetime.
+    fn from(f: api::auth::AccessToken) -> Self {
+        let expires_at = f
+            .lifetime()
+            .and_then(|lifetime| chrono::Duration::from_std(lifetime).ok())
+            .and_then(|lifetime| Utc::now().checked_add_signed(lifetime));
+        Self::new(f.access_token, expires_at)
+    }
+}
+
 /// Client is authenticated and has an access token.
 /// This allows calling all endpoints, including those that need authorization.
-pub struct Authenticated(api::AccessToken);
+pub struct Authenticated(StoredToken);
+
+impl Authenticated {
+    /// The access token of the client.
+    #[must_use]
+    pub const fn token(&self) -> &StoredToken {
+        &self.0
+    }
+
+    /// When the access token expires. `None` if unknown.
+    ///
+    /// See [`StoredToken::expiry`].
+    #[must_use]
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.0.expiry()
+    }
+
+    /// Whether the access token has expired.
+    ///
+    /// Check this before making calls to log in again proactively instead of
+    /// reacting to `401 Unauthorized` responses.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.0.is_expired()
+    }
+}
 
 /// Client is not authenticated. This means only a small subset of endpoints are available.
 /// An endpoint with this scope can be queried without authentification.
@@ -52,7 +305,7 @@ pub struct Unauthenticated;
 
 impl Scope for Authenticated {
     fn set_header<'a>(&self, headers: &'a mut HeaderMap) -> Result<&'a mut HeaderMap, AuthError> {
-        let value = format!("Bearer {}", self.0);
+        let value = format!("Bearer {}", self.0.access_token);
         let mut token_header_value = HeaderValue::from_str(&value)?;
         token_header_value.set_sensitive(true);
         headers.insert(http::header::AUTHORIZATION, token_header_value);
@@ -80,13 +333,48 @@ impl From<Authenticated> for Unauthenticated {
 impl From<api::auth::AccessToken> for Authenticated {
     /// Constructs a new [Authenticated] scope from the given token.
     fn from(f: api::auth::AccessToken) -> Self {
-        Self(f.access_token)
+        Self(f.into())
     }
 }
 
 impl From<api::AccessToken> for Authenticated {
     /// Constructs a new [Authenticated] scope from the given token string.
     fn from(f: api::AccessToken) -> Self {
+        Self(f.into())
+    }
+}
+
+impl From<StoredToken> for Authenticated {
+    /// Constructs a new [Authenticated] scope from a stored token.
+    fn from(f: StoredToken) -> Self {
         Self(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{decode_base64url, StoredToken};
+
+    #[test]
+    fn expiry_and_subject_from_jwt() {
+        assert_eq!(decode_base64url("aGk_-w").unwrap(), b"hi?\xfb");
+
+        let jwt = include_str!("../data/access_token.json")
+            .split('"')
+            .nth(3)
+            .unwrap();
+        let token = StoredToken::new(jwt.into(), None);
+        assert_eq!(token.expiry(), Utc.timestamp_opt(1_635_103_249, 0).single());
+        assert!(token.is_expired());
+        assert_eq!(
+            token.subject().as_deref(),
+            Some("40379230-ced0-43b8-8b78-37c924f491a7")
+        );
+
+        let opaque = StoredToken::new("opaque".into(), None);
+        assert_eq!(opaque.expiry(), None);
+        assert!(!opaque.is_expired());
+    }
+}
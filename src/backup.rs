@@ -0,0 +1,400 @@
+//! Backups of whole projects independent of database dumps.
+//!
+//! [`export_project`] fetches a project into a [`ProjectBackup`], which can be
+//! stored with serde in any format, e.g. JSON. [`restore`] recreates the
+//! backup as a new project, on the same or on another Traduora instance.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::backup::{self, RestoreOptions};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let backup = backup::export_project(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+//! let json = serde_json::to_string(&backup).unwrap();
+//!
+//! let backup = serde_json::from_str(&json).unwrap();
+//! let options = RestoreOptions::new().name("Restored project");
+//! let project = backup::restore(&client, &backup, &options).unwrap();
+//! # assert!(!project.id.value().is_empty());
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    api::{
+        labels::Label,
+        locales::LocaleCode,
+        projects::{CreateProject, Project, ShowProject},
+        terms::CreateTerm,
+        translations::{CreateLocale, EditTranslation},
+        ProjectId,
+    },
+    auth::Authenticated,
+    ops::ProjectData,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// Version of the backup format written by [`export_project`].
+///
+/// It is increased whenever a field is removed or changes its meaning.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The error which is returned by [`restore`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum BackupError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A request failed.
+    #[error("failed to restore backup: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// The backup was written by a newer version of this crate.
+    #[error("unsupported backup format version {}", version)]
+    UnsupportedVersion {
+        /// Format version of the backup.
+        version: u32,
+    },
+}
+
+/// Everything that makes up a project.
+///
+/// Members and API clients of the project are not included.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectBackup {
+    /// Version of the backup format, see [`FORMAT_VERSION`].
+    pub format_version: u32,
+    /// When the backup was exported.
+    pub exported_at: DateTime<Utc>,
+    /// Name of the project.
+    pub name: String,
+    /// Description of the project.
+    pub description: String,
+    /// Locales of the project, sorted.
+    pub locales: Vec<LocaleCode>,
+    /// Labels used by any term or translation, sorted by name.
+    pub labels: Vec<LabelBackup>,
+    /// Terms of the project with their translations, sorted by term string.
+    pub terms: Vec<TermBackup>,
+}
+
+/// A label of a backed up project.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct LabelBackup {
+    /// Display name of the label.
+    pub value: String,
+    /// Color of the label, e.g. `#D81159`.
+    pub color: String,
+}
+
+/// A term of a backed up project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TermBackup {
+    /// The term string.
+    pub term: String,
+    /// Names of the labels of the term.
+    pub labels: Vec<String>,
+    /// Non-empty translations of the term.
+    pub translations: BTreeMap<LocaleCode, TranslationBackup>,
+}
+
+/// A translation of a backed up term.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationBackup {
+    /// The translated text.
+    pub value: String,
+    /// Names of the labels of the translation.
+    pub labels: Vec<String>,
+}
+
+/// Options for [`restore`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestoreOptions {
+    name: Option<String>,
+    locales: Option<Vec<LocaleCode>>,
+}
+
+impl RestoreOptions {
+    /// Restores everything under the name of the backed up project.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            name: None,
+            locales: None,
+        }
+    }
+
+    /// Name of the new project instead of the name of the backed up project.
+    #[must_use]
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Only restores the given locales and their translations.
+    #[must_use]
+    pub fn locales(mut self, locales: Vec<LocaleCode>) -> Self {
+        self.locales = Some(locales);
+        self
+    }
+
+    fn includes(&self, locale: &LocaleCode) -> bool {
+        self.locales.as_ref().map_or(true, |l| l.contains(locale))
+    }
+}
+
+impl ProjectBackup {
+    fn new(project: Project, data: ProjectData) -> Self {
+        let mut labels = BTreeMap::new();
+        let mut names = |l: Vec<Label>| -> Vec<String> {
+            l.into_iter()
+                .map(|label| {
+                    let name = label.value.clone();
+                    labels.entry(label.value.clone()).or_insert(LabelBackup {
+                        value: label.value,
+                        color: label.color,
+                    });
+                    name
+                })
+                .collect()
+        };
+
+        let mut terms: BTreeMap<_, _> = data
+            .terms
+            .into_iter()
+            .map(|term| {
+                let backup = TermBackup {
+                    term: term.value,
+                    labels: names(term.labels),
+                    translations: BTreeMap::new(),
+                };
+                (term.id, backup)
+            })
+            .collect();
+        let locales = data.translations.keys().cloned().collect();
+        for (locale, translations) in data.translations {
+            for translation in translations {
+                match terms.get_mut(&translation.term_id) {
+                    Some(term) if !translation.value.is_empty() => {
+                        let backup = TranslationBackup {
+                            value: translation.value,
+                            labels: names(translation.labels),
+                        };
+                        term.translations.insert(locale.clone(), backup);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut terms: Vec<_> = terms.into_values().collect();
+        terms.sort_by(|a, b| a.term.cmp(&b.term));
+        Self {
+            format_version: FORMAT_VERSION,
+            exported_at: Utc::now(),
+            name: project.name,
+            description: project.description,
+            locales,
+            labels: labels.into_values().collect(),
+            terms,
+        }
+    }
+
+    fn check_version<E>(&self) -> Result<(), BackupError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if self.format_version > FORMAT_VERSION {
+            Err(BackupError::UnsupportedVersion {
+                version: self.format_version,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn create_project(&self, options: &RestoreOptions) -> CreateProject {
+        CreateProject::new(
+            options.name.as_deref().unwrap_or(&self.name),
+            &*self.description,
+        )
+    }
+}
+
+/// Fetches the project with all its locales, terms and translations.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+pub fn export_project<C>(
+    client: &C,
+    project: &ProjectId,
+) -> Result<ProjectBackup, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let metadata = ShowProject(project.clone()).query(client)?;
+    let data = ProjectData::fetch(client, project, |_| true)?;
+    Ok(ProjectBackup::new(metadata, data))
+}
+
+/// Fetches the project with all its locales, terms and translations asynchronously.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+pub async fn export_project_async<C>(
+    client: &C,
+    project: &ProjectId,
+) -> Result<ProjectBackup, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let metadata = ShowProject(project.clone()).query_async(client).await?;
+    let data = ProjectData::fetch_async(client, project, |_| true).await?;
+    Ok(ProjectBackup::new(metadata, data))
+}
+
+/// Creates a new project with the contents of the backup.
+///
+/// Labels are not restored because the label endpoints are not implemented yet.
+///
+/// # Errors
+/// This function returns an error if the backup has an unsupported format version
+/// or a request fails. The project created up to the failing request is not removed.
+pub fn restore<C>(
+    client: &C,
+    backup: &ProjectBackup,
+    options: &RestoreOptions,
+) -> Result<Project, BackupError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    backup.check_version()?;
+    let project = backup.create_project(options).query(client)?;
+    for locale in backup.locales.iter().filter(|l| options.includes(l)) {
+        CreateLocale::new(project.id.clone(), locale.clone()).query(client)?;
+    }
+    for term in &backup.terms {
+        let id = CreateTerm::new(&*term.term, project.id.clone())
+            .query(client)?
+            .id;
+        for (locale, translation) in term
+            .translations
+            .iter()
+            .filter(|(l, _)| options.includes(l))
+        {
+            let endpoint = EditTranslation::new(
+                project.id.clone(),
+                locale.clone(),
+                id.clone(),
+                &*translation.value,
+            );
+            endpoint.query(client)?;
+        }
+    }
+    Ok(project)
+}
+
+/// Creates a new project with the contents of the backup asynchronously.
+///
+/// Labels are not restored because the label endpoints are not implemented yet.
+///
+/// # Errors
+/// This function returns an error if the backup has an unsupported format version
+/// or a request fails. The project created up to the failing request is not removed.
+pub async fn restore_async<C>(
+    client: &C,
+    backup: &ProjectBackup,
+    options: &RestoreOptions,
+) -> Result<Project, BackupError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    backup.check_version()?;
+    let project = backup.create_project(options).query_async(client).await?;
+    for locale in backup.locales.iter().filter(|l| options.includes(l)) {
+        CreateLocale::new(project.id.clone(), locale.clone())
+            .query_async(client)
+            .await?;
+    }
+    for term in &backup.terms {
+        let id = CreateTerm::new(&*term.term, project.id.clone())
+            .query_async(client)
+            .await?
+            .id;
+        for (locale, translation) in term
+            .translations
+            .iter()
+            .filter(|(l, _)| options.includes(l))
+        {
+            let endpoint = EditTranslation::new(
+                project.id.clone(),
+                locale.clone(),
+                id.clone(),
+                &*translation.value,
+            );
+            endpoint.query_async(client).await?;
+        }
+    }
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ProjectBackup, FORMAT_VERSION};
+    use crate::ops::ProjectData;
+
+    #[test]
+    fn backup_collects_labels_and_translations() {
+        let date = json!({ "created": "2022-01-01T00:00:00Z", "modified": "2022-01-01T00:00:00Z" });
+        let label = json!({ "id": "l1", "value": "ui", "color": "#D81159" });
+        let project = serde_json::from_value(json!({
+            "id": "p", "name": "App", "description": "", "localesCount": 1, "termsCount": 2,
+            "role": "admin", "date": date
+        }))
+        .unwrap();
+        let mut data = ProjectData {
+            terms: serde_json::from_value(json!([
+                { "id": "2", "value": "title", "labels": [label], "date": date },
+                { "id": "1", "value": "button", "labels": [], "date": date },
+            ]))
+            .unwrap(),
+            ..ProjectData::default()
+        };
+        data.translations.insert(
+            "de".into(),
+            serde_json::from_value(json!([
+                { "termId": "1", "value": "", "labels": [], "date": date },
+                { "termId": "2", "value": "Titel", "labels": [label], "date": date },
+            ]))
+            .unwrap(),
+        );
+
+        let backup = ProjectBackup::new(project, data);
+        assert_eq!(backup.format_version, FORMAT_VERSION);
+        assert_eq!(backup.locales, ["de".into()]);
+        assert_eq!(backup.labels.len(), 1);
+        assert_eq!(backup.terms[0].term, "button");
+        assert!(backup.terms[0].translations.is_empty());
+        assert_eq!(backup.terms[1].labels, ["ui"]);
+        assert_eq!(backup.terms[1].translations[&"de".into()].value, "Titel");
+
+        let json = serde_json::to_value(&backup).unwrap();
+        assert_eq!(
+            serde_json::from_value::<ProjectBackup>(json).unwrap(),
+            backup
+        );
+    }
+}
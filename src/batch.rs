@@ -0,0 +1,270 @@
+//! Running many unrelated queries concurrently.
+//!
+//! A [`Batch`] collects endpoints of any type and runs them with a limit on
+//! the number of concurrent requests. The results are returned in the order
+//! the endpoints were added, either as JSON or converted by a closure into a
+//! common type.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{
+//!     api::{projects::Projects, terms::Terms, users::Me},
+//!     batch::Batch,
+//! };
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+//!
+//! let mut batch = Batch::new().concurrency(2);
+//! batch.push(Me);
+//! batch.push(Projects);
+//! batch.push(Terms::new(project.into()));
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let results = batch.execute(&client).await;
+//!
+//! assert!(results.is_ok());
+//! let (_, me) = &results.results[0];
+//! assert_eq!(me.as_ref().unwrap()["name"], "Tester");
+//! # });
+//! # Ok::<(), TraduoraError>(())
+//! ```
+//!
+//! Results of a common type:
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{
+//!     api::{translations::Locales, terms::Terms},
+//!     batch::Batch,
+//! };
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+//!
+//! let mut batch = Batch::new();
+//! batch.push_map(Terms::new(project.into()), |terms| terms.len());
+//! batch.push_map(Locales(project.into()), |locales| locales.len());
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! let counts: Vec<_> = batch.execute(&client).await.succeeded().map(|(_, n)| *n).collect();
+//!
+//! assert_eq!(counts, [2, 2]);
+//! # });
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::fmt::{self, Debug};
+
+use async_trait::async_trait;
+use futures_util::{stream, StreamExt};
+use serde_json::Value;
+
+use crate::{api::BulkResult, ApiError, AsyncClient, AsyncQuery};
+
+/// A query of a [`Batch`] with its result converted to `T`.
+#[async_trait]
+trait Call<C, T>: Send + Sync
+where
+    C: AsyncClient + Sync,
+{
+    async fn call(&self, client: &C) -> Result<T, ApiError<C::Error>>;
+}
+
+/// An endpoint whose default model is converted by a closure.
+struct Mapped<E, F> {
+    endpoint: E,
+    map: F,
+}
+
+#[async_trait]
+impl<C, T, E, F> Call<C, T> for Mapped<E, F>
+where
+    C: AsyncClient + Sync,
+    E: AsyncQuery<C> + Send + Sync,
+    E::Model: Send,
+    F: Fn(E::Model) -> T + Send + Sync,
+{
+    async fn call(&self, client: &C) -> Result<T, ApiError<C::Error>> {
+        self.endpoint.query_async(client).await.map(&self.map)
+    }
+}
+
+/// An endpoint whose response is returned as JSON.
+struct Json<E>(E);
+
+#[async_trait]
+impl<C, E> Call<C, Value> for Json<E>
+where
+    C: AsyncClient + Sync,
+    E: AsyncQuery<C> + Send + Sync,
+{
+    async fn call(&self, client: &C) -> Result<Value, ApiError<C::Error>> {
+        self.0.query_as_async(client).await
+    }
+}
+
+/// Queries of different endpoints that are run together.
+///
+/// Endpoints are added with [`push`](Batch::push), which returns the
+/// response as JSON, or with [`push_map`](Batch::push_map), which converts
+/// the default model of the endpoint into `T`.
+/// [`execute`](Batch::execute) runs all queries, at most
+/// [`concurrency`](Batch::concurrency) at a time.
+///
+/// See the [module documentation](self) for examples.
+pub struct Batch<'a, C, T = Value>
+where
+    C: AsyncClient + Sync,
+{
+    calls: Vec<Box<dyn Call<C, T> + 'a>>,
+    concurrency: usize,
+}
+
+impl<'a, C, T> Batch<'a, C, T>
+where
+    C: AsyncClient + Sync,
+{
+    /// Creates an empty batch that runs up to 4 queries at a time.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            calls: Vec::new(),
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many queries run at the same time.
+    ///
+    /// Values below 1 are treated as 1.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Adds an endpoint whose default model is converted into `T` by `map`.
+    ///
+    /// Returns the position of its result.
+    pub fn push_map<E, F>(&mut self, endpoint: E, map: F) -> usize
+    where
+        E: AsyncQuery<C> + Send + Sync + 'a,
+        E::Model: Send,
+        F: Fn(E::Model) -> T + Send + Sync + 'a,
+    {
+        self.calls.push(Box::new(Mapped { endpoint, map }));
+        self.calls.len() - 1
+    }
+
+    /// Number of queries in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Whether the batch has no queries.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Runs all queries of the batch.
+    ///
+    /// A failing query does not stop the others. The results are keyed by
+    /// the position of their endpoint and in the order the endpoints were added.
+    pub async fn execute(&self, client: &C) -> BulkResult<usize, T, C::Error> {
+        let calls = self
+            .calls
+            .iter()
+            .enumerate()
+            .map(|(i, call)| async move { (i, call.call(client).await) });
+        BulkResult {
+            results: stream::iter(calls)
+                .buffered(self.concurrency)
+                .collect()
+                .await,
+        }
+    }
+}
+
+impl<'a, C> Batch<'a, C, Value>
+where
+    C: AsyncClient + Sync,
+{
+    /// Adds an endpoint whose response is returned as JSON.
+    ///
+    /// The JSON is the default model of the endpoint before deserialization,
+    /// i.e. without the `data` wrapper of most endpoints.
+    /// Returns the position of its result.
+    pub fn push<E>(&mut self, endpoint: E) -> usize
+    where
+        E: AsyncQuery<C> + Send + Sync + 'a,
+    {
+        self.calls.push(Box::new(Json(endpoint)));
+        self.calls.len() - 1
+    }
+}
+
+impl<C, T> Default for Batch<'_, C, T>
+where
+    C: AsyncClient + Sync,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, T> Debug for Batch<'_, C, T>
+where
+    C: AsyncClient + Sync,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Batch")
+            .field("calls", &self.calls.len())
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::Batch;
+    use crate::{
+        api::{terms::Terms, translations::Locales},
+        fixtures,
+        mock::{Expectation, MockTraduora},
+    };
+
+    #[tokio::test]
+    async fn results_keep_order_of_endpoints() {
+        let client = MockTraduora::new();
+        client
+            .expect(
+                Expectation::new(Method::GET, "projects/slow/terms")
+                    .delay(Duration::from_millis(50))
+                    .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+            )
+            .expect(
+                Expectation::new(Method::GET, "projects/fast/terms")
+                    .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+            );
+
+        let mut batch = Batch::new().concurrency(2);
+        batch.push_map(Terms::new("slow".into()), |_| "slow");
+        batch.push_map(Terms::new("fast".into()), |_| "fast");
+        batch.push_map(Locales("missing".into()), |_| "missing");
+        let results = batch.execute(&client).await;
+
+        let keys: Vec<_> = results.results.iter().map(|(i, _)| *i).collect();
+        assert_eq!(keys, [0, 1, 2]);
+        let values: Vec<_> = results.succeeded().map(|(_, v)| *v).collect();
+        assert_eq!(values, ["slow", "fast"]);
+        assert_eq!(results.failed().count(), 1);
+        client.verify().unwrap();
+    }
+}
@@ -0,0 +1,465 @@
+//! Measuring the throughput of a Traduora instance.
+//!
+//! A [`Benchmark`] sends the same requests many times and reports the latency
+//! of each kind of operation. It is meant to compare the performance of
+//! different client configurations or crate versions on real workloads.
+//!
+//! Because a benchmark sends many requests, it has safety guards:
+//! - Only reading requests are sent unless writes are enabled with
+//!   [`Benchmark::allow_writes`], which requires the name of the project.
+//!   The benchmark refuses to run if the project has a different name.
+//! - All terms created by the benchmark are prefixed with [`TERM_PREFIX`]
+//!   and deleted again afterwards.
+//! - The number of iterations is limited to [`MAX_ITERATIONS`].
+//!
+//! This module is only available with the `bench` feature.
+
+use std::time::{Duration, Instant};
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::{
+    api::{
+        self,
+        locales::LocaleCode,
+        projects::ShowProject,
+        terms::{CreateTerm, DeleteTerm, Terms},
+        translations::{EditTranslation, Locales},
+        ProjectId, TermId,
+    },
+    auth::Authenticated,
+    reports::Report,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// Prefix of the terms created by a benchmark.
+pub const TERM_PREFIX: &str = "traduora-bench.";
+/// Maximum number of iterations of a benchmark.
+pub const MAX_ITERATIONS: usize = 1000;
+
+/// The error which is returned when a benchmark cannot be run.
+///
+/// Failing requests during the benchmark are counted in the [`BenchReport`]
+/// instead of aborting it.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum BenchError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// The project could not be inspected before the benchmark.
+    #[error("failed to prepare benchmark: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// Writes are enabled for a project with a different name.
+    #[error("refusing to write to project `{}`, expected `{}`", actual, expected)]
+    ProjectMismatch {
+        /// The name passed to [`Benchmark::allow_writes`].
+        expected: String,
+        /// The actual name of the project.
+        actual: String,
+    },
+    /// More iterations than [`MAX_ITERATIONS`] were requested.
+    #[error(
+        "{} iterations requested but at most {} are allowed",
+        iterations,
+        MAX_ITERATIONS
+    )]
+    TooManyIterations {
+        /// The requested number of iterations.
+        iterations: usize,
+    },
+}
+
+/// Latencies of a single kind of operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationStats {
+    /// Name of the operation, e.g. `list terms`.
+    pub operation: &'static str,
+    /// Latency of each successful request in the order they were sent.
+    pub latencies: Vec<Duration>,
+    /// Number of failed requests.
+    pub failures: usize,
+    /// Wall-clock time of all requests including failed ones.
+    pub total: Duration,
+}
+
+impl OperationStats {
+    const fn new(operation: &'static str) -> Self {
+        Self {
+            operation,
+            latencies: Vec::new(),
+            failures: 0,
+            total: Duration::from_secs(0),
+        }
+    }
+
+    fn record<T, E>(&mut self, start: Instant, result: Result<T, E>) -> Option<T> {
+        let elapsed = start.elapsed();
+        self.total += elapsed;
+        match result {
+            Ok(value) => {
+                self.latencies.push(elapsed);
+                Some(value)
+            }
+            Err(_) => {
+                self.failures += 1;
+                None
+            }
+        }
+    }
+
+    /// Number of requests including failed ones.
+    #[must_use]
+    pub fn requests(&self) -> usize {
+        self.latencies.len() + self.failures
+    }
+
+    /// Average latency of successful requests.
+    #[must_use]
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.latencies.len())
+            .ok()
+            .filter(|&c| c > 0)?;
+        Some(self.latencies.iter().sum::<Duration>() / count)
+    }
+
+    /// Latency that `percentile` percent of the successful requests did not exceed.
+    #[must_use]
+    pub fn percentile(&self, percentile: u8) -> Option<Duration> {
+        let mut sorted = self.latencies.clone();
+        sorted.sort();
+        let index = (sorted.len() * usize::from(percentile.min(100)) + 99) / 100;
+        sorted.get(index.saturating_sub(1)).copied()
+    }
+
+    /// Number of requests sent per second.
+    #[must_use]
+    pub fn requests_per_second(&self) -> f64 {
+        let secs = self.total.as_secs_f64();
+        if secs > 0.0 {
+            self.requests() as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Serialize for OperationStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let millis = |d: Option<Duration>| d.map(|d| d.as_secs_f64() * 1000.0);
+        let mut state = serializer.serialize_struct("OperationStats", 9)?;
+        state.serialize_field("operation", self.operation)?;
+        state.serialize_field("requests", &self.requests())?;
+        state.serialize_field("failures", &self.failures)?;
+        state.serialize_field("total_ms", &millis(Some(self.total)))?;
+        state.serialize_field("mean_ms", &millis(self.mean()))?;
+        state.serialize_field("p50_ms", &millis(self.percentile(50)))?;
+        state.serialize_field("p95_ms", &millis(self.percentile(95)))?;
+        state.serialize_field("max_ms", &millis(self.percentile(100)))?;
+        state.serialize_field("requests_per_second", &self.requests_per_second())?;
+        state.end()
+    }
+}
+
+/// The result of a [`Benchmark`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BenchReport {
+    /// The project the benchmark ran against.
+    pub project: ProjectId,
+    /// Number of iterations per operation.
+    pub iterations: usize,
+    /// Statistics per operation in the order they were run.
+    pub operations: Vec<OperationStats>,
+}
+
+impl Report for BenchReport {
+    const KIND: &'static str = "bench";
+    const VERSION: u32 = 1;
+}
+
+/// Configuration of a benchmark run.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::bench::Benchmark;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let report = Benchmark::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+///     .iterations(5)
+///     .run(&client)
+///     .unwrap();
+///
+/// for stats in &report.operations {
+///     println!("{}: {:.1} req/s", stats.operation, stats.requests_per_second());
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Benchmark {
+    project: ProjectId,
+    iterations: usize,
+    writes: Option<String>,
+}
+
+/// What the benchmark runs after the project has been checked.
+struct Plan {
+    writes: bool,
+    locale: Option<LocaleCode>,
+}
+
+impl Benchmark {
+    /// Creates a read-only benchmark with 10 iterations.
+    #[must_use]
+    pub const fn new(project: ProjectId) -> Self {
+        Self {
+            project,
+            iterations: 10,
+            writes: None,
+        }
+    }
+
+    /// Sets how often each operation is run.
+    #[must_use]
+    pub const fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Enables creating, editing and deleting terms.
+    ///
+    /// `project_name` must be the name of the project, otherwise the benchmark
+    /// is not run. This prevents writing to a production project by accident.
+    #[must_use]
+    pub fn allow_writes(mut self, project_name: impl Into<String>) -> Self {
+        self.writes = Some(project_name.into());
+        self
+    }
+
+    /// Runs the benchmark.
+    ///
+    /// # Errors
+    /// This method returns an error if the safety guards prevent the benchmark
+    /// or the project cannot be inspected.
+    pub fn run<C>(&self, client: &C) -> Result<BenchReport, BenchError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let plan = match &self.writes {
+            Some(_) => {
+                let project = ShowProject(self.project.clone()).query(client)?;
+                let locales = Locales(self.project.clone()).query(client)?;
+                self.plan(&project.name, locales.into_iter().next())?
+            }
+            None => self.plan("", None)?,
+        };
+
+        let mut list = OperationStats::new("list terms");
+        for _ in 0..self.iterations {
+            list.record(
+                Instant::now(),
+                Terms::new(self.project.clone()).query(client),
+            );
+        }
+        let mut operations = vec![list];
+
+        if plan.writes {
+            let mut create = OperationStats::new("create term");
+            let mut edit = OperationStats::new("edit translation");
+            let mut delete = OperationStats::new("delete term");
+            let mut created = Vec::new();
+            for i in 0..self.iterations {
+                let endpoint = CreateTerm::new(term(i), self.project.clone());
+                if let Some(term) = create.record(Instant::now(), endpoint.query(client)) {
+                    created.push(term.id);
+                }
+            }
+            if let Some(locale) = &plan.locale {
+                for (i, term_id) in created.iter().enumerate() {
+                    let endpoint = self.edit(locale, term_id, i);
+                    edit.record(Instant::now(), api::ignore(endpoint).query_custom(client));
+                }
+            }
+            for term_id in created {
+                let endpoint = DeleteTerm::new(self.project.clone(), term_id);
+                delete.record(Instant::now(), api::ignore(endpoint).query_custom(client));
+            }
+            operations.extend(vec![create, edit, delete]);
+        }
+
+        Ok(self.report(operations))
+    }
+
+    /// Runs the benchmark asynchronously. Requests are sent one after another.
+    ///
+    /// # Errors
+    /// This method returns an error if the safety guards prevent the benchmark
+    /// or the project cannot be inspected.
+    pub async fn run_async<C>(&self, client: &C) -> Result<BenchReport, BenchError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let plan = match &self.writes {
+            Some(_) => {
+                let project = ShowProject(self.project.clone())
+                    .query_async(client)
+                    .await?;
+                let locales = Locales(self.project.clone()).query_async(client).await?;
+                self.plan(&project.name, locales.into_iter().next())?
+            }
+            None => self.plan("", None)?,
+        };
+
+        let mut list = OperationStats::new("list terms");
+        for _ in 0..self.iterations {
+            let start = Instant::now();
+            let result = Terms::new(self.project.clone()).query_async(client).await;
+            list.record(start, result);
+        }
+        let mut operations = vec![list];
+
+        if plan.writes {
+            let mut create = OperationStats::new("create term");
+            let mut edit = OperationStats::new("edit translation");
+            let mut delete = OperationStats::new("delete term");
+            let mut created = Vec::new();
+            for i in 0..self.iterations {
+                let start = Instant::now();
+                let result = CreateTerm::new(term(i), self.project.clone())
+                    .query_async(client)
+                    .await;
+                if let Some(term) = create.record(start, result) {
+                    created.push(term.id);
+                }
+            }
+            if let Some(locale) = &plan.locale {
+                for (i, term_id) in created.iter().enumerate() {
+                    let start = Instant::now();
+                    let result = api::ignore(self.edit(locale, term_id, i))
+                        .query_custom_async(client)
+                        .await;
+                    edit.record(start, result);
+                }
+            }
+            for term_id in created {
+                let start = Instant::now();
+                let result = api::ignore(DeleteTerm::new(self.project.clone(), term_id))
+                    .query_custom_async(client)
+                    .await;
+                delete.record(start, result);
+            }
+            operations.extend(vec![create, edit, delete]);
+        }
+
+        Ok(self.report(operations))
+    }
+
+    /// Checks the safety guards.
+    fn plan<E>(
+        &self,
+        project_name: &str,
+        locale: Option<api::translations::ProjectLocale>,
+    ) -> Result<Plan, BenchError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if self.iterations > MAX_ITERATIONS {
+            return Err(BenchError::TooManyIterations {
+                iterations: self.iterations,
+            });
+        }
+        match &self.writes {
+            Some(expected) if expected != project_name => Err(BenchError::ProjectMismatch {
+                expected: expected.clone(),
+                actual: project_name.to_owned(),
+            }),
+            writes => Ok(Plan {
+                writes: writes.is_some(),
+                locale: locale.map(|l| l.locale.code),
+            }),
+        }
+    }
+
+    fn edit(&self, locale: &LocaleCode, term_id: &TermId, i: usize) -> EditTranslation {
+        EditTranslation::new(
+            self.project.clone(),
+            locale.clone(),
+            term_id.clone(),
+            format!("benchmark translation {}", i),
+        )
+    }
+
+    fn report(&self, operations: Vec<OperationStats>) -> BenchReport {
+        BenchReport {
+            project: self.project.clone(),
+            iterations: self.iterations,
+            operations,
+        }
+    }
+}
+
+fn term(i: usize) -> String {
+    format!("{}{}", TERM_PREFIX, i)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{OperationStats, MAX_ITERATIONS};
+    use crate::{bench::Benchmark, TestClient};
+
+    #[test]
+    fn guards_prevent_writes_to_other_projects() {
+        let client = TestClient::with_auth("localhost", crate::Login::password("a", "b")).unwrap();
+        let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+
+        let err = Benchmark::new(project.into())
+            .allow_writes("production")
+            .run(&client)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("refusing to write"));
+
+        let report = Benchmark::new(project.into())
+            .iterations(3)
+            .allow_writes("Traduora API bindings")
+            .run(&client)
+            .unwrap();
+        let operations: Vec<_> = report.operations.iter().map(|o| o.operation).collect();
+        assert_eq!(
+            operations,
+            [
+                "list terms",
+                "create term",
+                "edit translation",
+                "delete term"
+            ]
+        );
+        assert!(report.operations.iter().all(|o| o.requests() == 3));
+
+        assert!(Benchmark::new(project.into())
+            .iterations(MAX_ITERATIONS + 1)
+            .run(&client)
+            .is_err());
+    }
+
+    #[test]
+    fn percentiles() {
+        let mut stats = OperationStats::new("test");
+        stats.latencies = (1..=100).map(Duration::from_millis).collect();
+        stats.failures = 1;
+
+        assert_eq!(stats.requests(), 101);
+        assert_eq!(stats.percentile(50), Some(Duration::from_millis(50)));
+        assert_eq!(stats.percentile(95), Some(Duration::from_millis(95)));
+        assert_eq!(stats.percentile(100), Some(Duration::from_millis(100)));
+        assert_eq!(stats.mean(), Some(Duration::from_micros(50_500)));
+        assert_eq!(OperationStats::new("empty").percentile(50), None);
+    }
+}
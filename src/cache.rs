@@ -0,0 +1,748 @@
+//! Caching of responses to avoid downloading unchanged data again.
+//!
+//! [`ConditionalCache`] revalidates cached responses with the server on every
+//! request, [`CachedClient`] answers from the cache until a time to live expires.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{
+    header::{self, HeaderName},
+    request::Builder as RequestBuilder,
+    HeaderMap, HeaderValue, Method, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::logging::{debug, warn};
+use crate::store::{KvStore, MemoryStore};
+use crate::{
+    api::{ProjectId, Role},
+    ApiError, AsyncClient, Client, Endpoint, RestClient,
+};
+
+/// Prefix of the keys in the store.
+const NAMESPACE: &str = "http-cache/";
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+/// Everything but the body of a [`CacheEntry`] as it is written to the store.
+#[derive(Serialize, Deserialize)]
+struct StoredHeader {
+    status: u16,
+    headers: Vec<(String, String)>,
+}
+
+impl CacheEntry {
+    fn from_response(response: &Response<Bytes>) -> Option<Self> {
+        let etag = response.headers().get(header::ETAG).cloned();
+        let last_modified = response.headers().get(header::LAST_MODIFIED).cloned();
+        if !response.status().is_success() || (etag.is_none() && last_modified.is_none()) {
+            return None;
+        }
+        Some(Self {
+            etag,
+            last_modified,
+            status: response.status(),
+            headers: response.headers().clone(),
+            body: response.body().clone(),
+        })
+    }
+
+    fn conditional_headers(&self) -> impl Iterator<Item = (HeaderName, HeaderValue)> + '_ {
+        let etag = self.etag.iter().map(|v| (header::IF_NONE_MATCH, v.clone()));
+        let modified = self
+            .last_modified
+            .iter()
+            .map(|v| (header::IF_MODIFIED_SINCE, v.clone()));
+        etag.chain(modified)
+    }
+
+    fn to_response(&self) -> Response<Bytes> {
+        let mut response = Response::new(self.body.clone());
+        *response.status_mut() = self.status;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, serde_json::Error> {
+        encode_response(&self.to_response())
+    }
+
+    fn decode(data: &[u8]) -> Option<Self> {
+        Self::from_response(&decode_response(data)?)
+    }
+}
+
+/// Encodes the response as a JSON header line followed by the raw body.
+fn encode_response(response: &Response<Bytes>) -> Result<Vec<u8>, serde_json::Error> {
+    let header = StoredHeader {
+        status: response.status().as_u16(),
+        headers: response
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_owned())))
+            .collect(),
+    };
+    let mut data = serde_json::to_vec(&header)?;
+    data.push(b'\n');
+    data.extend_from_slice(response.body());
+    Ok(data)
+}
+
+fn decode_response(data: &[u8]) -> Option<Response<Bytes>> {
+    let split = data.iter().position(|b| *b == b'\n')?;
+    let header: StoredHeader = serde_json::from_slice(&data[..split]).ok()?;
+    let headers = header
+        .headers
+        .iter()
+        .map(|(k, v)| {
+            Some((
+                HeaderName::try_from(k).ok()?,
+                HeaderValue::try_from(v).ok()?,
+            ))
+        })
+        .collect::<Option<HeaderMap>>()?;
+    let mut response = Response::new(Bytes::copy_from_slice(&data[split + 1..]));
+    *response.status_mut() = StatusCode::from_u16(header.status).ok()?;
+    *response.headers_mut() = headers;
+    Some(response)
+}
+
+/// A client wrapper that caches responses to `GET` requests and revalidates
+/// them with conditional requests.
+///
+/// If a response carries an `ETag` or `Last-Modified` header, its body is
+/// stored by the request URL. Later requests to the same URL send the
+/// `If-None-Match` or `If-Modified-Since` header. When the server answers with
+/// `304 Not Modified`, the cached response is used instead, so the data does not
+/// have to be downloaded again. The cache works with any query type, e.g.
+/// [`crate::Query`] or [`crate::CustomQuery`].
+///
+/// Requests with other methods are passed through without caching.
+///
+/// By default, the responses are kept in memory. Use [`ConditionalCache::with_store`]
+/// to keep them in another [`KvStore`], e.g. on disk to reuse them across runs.
+/// Failures of the store are logged and treated like a cache miss.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::terms::Terms, cache::ConditionalCache, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = ConditionalCache::new(Traduora::with_auth("localhost:8080", login)?);
+/// let endpoint = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+///
+/// let terms = endpoint.query(&client)?;
+/// // only downloads the terms again if they changed in the meantime
+/// let terms = endpoint.query(&client)?;
+///
+/// assert_eq!(terms.len(), 2);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug)]
+pub struct ConditionalCache<C, S = MemoryStore> {
+    client: C,
+    store: S,
+}
+
+impl<C> ConditionalCache<C> {
+    /// Wraps the client with an empty in-memory cache.
+    pub fn new(client: C) -> Self {
+        Self::with_store(client, MemoryStore::new())
+    }
+}
+
+impl<C, S: KvStore> ConditionalCache<C, S> {
+    /// Wraps the client with a cache that keeps the responses in the store.
+    ///
+    /// Responses cached in the store by a previous run are reused.
+    pub const fn with_store(client: C, store: S) -> Self {
+        Self { client, store }
+    }
+
+    /// Returns the store of the cache.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the wrapped client.
+    pub const fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Unwraps the client and drops the store.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// Unwraps the client and the store.
+    pub fn into_parts(self) -> (C, S) {
+        (self.client, self.store)
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.keys().len()
+    }
+
+    /// Whether no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.keys().is_empty()
+    }
+
+    /// Removes all cached responses.
+    pub fn clear(&self) {
+        for key in self.keys() {
+            if let Err(e) = self.store.remove(&key) {
+                warn!(target: "traduora", "failed to remove {} from cache: {}", key, e);
+            }
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.store.list(NAMESPACE).unwrap_or_else(|e| {
+            warn!(target: "traduora", "failed to list cached responses: {}", e);
+            Vec::new()
+        })
+    }
+
+    fn load(&self, key: &str) -> Option<CacheEntry> {
+        match self.store.get(key) {
+            Ok(data) => data.as_deref().and_then(CacheEntry::decode),
+            Err(e) => {
+                warn!(target: "traduora", "failed to read {} from cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    fn save(&self, key: &str, entry: &CacheEntry) {
+        let result = entry
+            .encode()
+            .map_err(std::io::Error::from)
+            .and_then(|data| self.store.put(key, &data, None));
+        if let Err(e) = result {
+            warn!(target: "traduora", "failed to write {} to cache: {}", key, e);
+        }
+    }
+
+    /// Adds the conditional headers to a `GET` request.
+    /// Returns the cache key and the cached entry if the request can be cached.
+    fn prepare(&self, request: &mut RequestBuilder) -> Option<(String, Option<CacheEntry>)> {
+        if request.method_ref() != Some(&Method::GET) {
+            return None;
+        }
+        let key = format!("{}{}", NAMESPACE, request.uri_ref()?);
+        let entry = self.load(&key);
+        if let (Some(entry), Some(headers)) = (&entry, request.headers_mut()) {
+            headers.extend(entry.conditional_headers());
+        }
+        Some((key, entry))
+    }
+
+    fn update(
+        &self,
+        cached: Option<(String, Option<CacheEntry>)>,
+        response: Response<Bytes>,
+    ) -> Response<Bytes> {
+        let (key, entry) = match cached {
+            Some(cached) => cached,
+            None => return response,
+        };
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = entry {
+                debug!(target: "traduora", "using cached response for {}", key);
+                return entry.to_response();
+            }
+        } else if let Some(entry) = CacheEntry::from_response(&response) {
+            self.save(&key, &entry);
+        }
+        response
+    }
+}
+
+impl<C: RestClient, S: KvStore> RestClient for ConditionalCache<C, S> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+impl<C: Client, S: KvStore> Client for ConditionalCache<C, S> {
+    fn rest(
+        &self,
+        mut request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let cached = self.prepare(&mut request);
+        let response = self.client.rest(request, body)?;
+        Ok(self.update(cached, response))
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync + Send, S: KvStore> AsyncClient for ConditionalCache<C, S> {
+    async fn rest_async(
+        &self,
+        mut request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let cached = self.prepare(&mut request);
+        let response = self.client.rest_async(request, body).await?;
+        Ok(self.update(cached, response))
+    }
+}
+
+/// Prefix of the keys of [`CachedClient`] in the store.
+const SNAPSHOT_NAMESPACE: &str = "snapshot-cache/";
+
+/// What a [`CachedClient`] does with a request.
+enum Lookup {
+    /// The fresh cached response to a `GET` request.
+    Hit(Response<Bytes>),
+    /// A `GET` request whose response is cached under the key.
+    Miss(String),
+    /// A write request that invalidates the cached responses below the prefix,
+    /// or all cached responses if the prefix is unknown.
+    Write(Option<String>),
+}
+
+/// A client wrapper that answers `GET` requests from a cache until the cached
+/// response is older than a time to live.
+///
+/// Unlike [`ConditionalCache`], a fresh cached response is returned without
+/// contacting the server at all, so the data may be outdated by up to the TTL.
+/// This suits read-heavy services that query the same terms, translations or
+/// locales over and over again. Only successful responses are cached.
+///
+/// Requests with other methods are passed through and invalidate the cached
+/// responses of the same project, e.g. adding a term removes the cached term
+/// list and translations of its project. Changes made by other clients are
+/// not noticed, use [`CachedClient::invalidate`] to drop a response early.
+///
+/// By default, the responses are kept in memory. Use [`CachedClient::with_store`]
+/// to keep them in another [`KvStore`], e.g. on disk to reuse them across runs.
+/// Failures of the store are logged and treated like a cache miss.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use std::time::Duration;
+/// use traduora::{api::terms::Terms, cache::CachedClient, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = CachedClient::new(
+///     Traduora::with_auth("localhost:8080", login)?,
+///     Duration::from_secs(60),
+/// );
+/// let endpoint = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+///
+/// let terms = endpoint.query(&client)?;
+/// // answered from the cache for the next minute
+/// let terms = endpoint.query(&client)?;
+/// assert_eq!(terms.len(), 2);
+///
+/// // the next query downloads the terms again
+/// client.invalidate(&endpoint);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug)]
+pub struct CachedClient<C, S = MemoryStore> {
+    client: C,
+    store: S,
+    ttl: Duration,
+}
+
+impl<C> CachedClient<C> {
+    /// Wraps the client with an empty in-memory cache whose entries expire after `ttl`.
+    pub fn new(client: C, ttl: Duration) -> Self {
+        Self::with_store(client, MemoryStore::new(), ttl)
+    }
+}
+
+impl<C, S: KvStore> CachedClient<C, S> {
+    /// Wraps the client with a cache that keeps the responses in the store
+    /// until they expire after `ttl`.
+    ///
+    /// Responses cached in the store by a previous run are reused until they expire.
+    pub const fn with_store(client: C, store: S, ttl: Duration) -> Self {
+        Self { client, store, ttl }
+    }
+
+    /// Returns how long responses are cached.
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the store of the cache.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the wrapped client.
+    pub const fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Unwraps the client and drops the store.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// Unwraps the client and the store.
+    pub fn into_parts(self) -> (C, S) {
+        (self.client, self.store)
+    }
+
+    /// Number of cached responses that did not expire yet.
+    pub fn len(&self) -> usize {
+        self.keys(SNAPSHOT_NAMESPACE).len()
+    }
+
+    /// Whether no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.keys(SNAPSHOT_NAMESPACE).is_empty()
+    }
+
+    /// Removes all cached responses.
+    pub fn clear(&self) {
+        self.remove_below(SNAPSHOT_NAMESPACE);
+    }
+
+    fn keys(&self, prefix: &str) -> Vec<String> {
+        self.store.list(prefix).unwrap_or_else(|e| {
+            warn!(target: "traduora", "failed to list cached responses: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Removes the cached responses of the path and all paths below it.
+    fn remove_below(&self, prefix: &str) {
+        let below = |key: &str| {
+            prefix.ends_with('/')
+                || key[prefix.len()..].is_empty()
+                || key[prefix.len()..].starts_with(&['/', '?'][..])
+        };
+        for key in self.keys(prefix).into_iter().filter(|k| below(k)) {
+            if let Err(e) = self.store.remove(&key) {
+                warn!(target: "traduora", "failed to remove {} from cache: {}", key, e);
+            }
+        }
+    }
+
+    fn load(&self, key: &str) -> Option<Response<Bytes>> {
+        match self.store.get(key) {
+            Ok(data) => data.as_deref().and_then(decode_response),
+            Err(e) => {
+                warn!(target: "traduora", "failed to read {} from cache: {}", key, e);
+                None
+            }
+        }
+    }
+
+    fn save(&self, key: &str, response: &Response<Bytes>) {
+        let result = encode_response(response)
+            .map_err(std::io::Error::from)
+            .and_then(|data| self.store.put(key, &data, Some(self.ttl)));
+        if let Err(e) = result {
+            warn!(target: "traduora", "failed to write {} to cache: {}", key, e);
+        }
+    }
+
+    fn update(&self, lookup: Lookup, response: &Response<Bytes>) {
+        match lookup {
+            Lookup::Miss(key) if response.status().is_success() => self.save(&key, response),
+            Lookup::Write(Some(prefix)) => self.remove_below(&prefix),
+            Lookup::Write(None) => self.clear(),
+            _ => {}
+        }
+    }
+}
+
+impl<C: RestClient, S: KvStore> CachedClient<C, S> {
+    /// Removes the cached responses of the endpoint, so the next query
+    /// fetches it from the server again.
+    ///
+    /// Responses of the endpoint with different query parameters and of
+    /// the paths below it are removed as well.
+    pub fn invalidate<E: Endpoint>(&self, endpoint: &E) {
+        if let Ok(url) = self.client.rest_endpoint(&endpoint.endpoint()) {
+            self.remove_below(&format!("{}{}", SNAPSHOT_NAMESPACE, url));
+        }
+    }
+
+    fn lookup(&self, request: &RequestBuilder) -> Lookup {
+        let uri = match request.uri_ref() {
+            Some(uri) => uri.to_string(),
+            None => return Lookup::Write(None),
+        };
+        if request.method_ref() == Some(&Method::GET) {
+            let key = format!("{}{}", SNAPSHOT_NAMESPACE, uri);
+            return match self.load(&key) {
+                Some(response) => {
+                    debug!(target: "traduora", "using cached response for {}", key);
+                    Lookup::Hit(response)
+                }
+                None => Lookup::Miss(key),
+            };
+        }
+        Lookup::Write(self.project_prefix(&uri))
+    }
+
+    /// The key prefix of the responses of the project, or of the first two
+    /// path segments of requests outside of projects.
+    fn project_prefix(&self, uri: &str) -> Option<String> {
+        let base = self.client.rest_endpoint("").ok()?;
+        let path = uri.strip_prefix(base.as_str())?;
+        let path = path.split('?').next().unwrap_or_default();
+        let scope: Vec<_> = path.split('/').take(2).collect();
+        Some(format!("{}{}{}", SNAPSHOT_NAMESPACE, base, scope.join("/")))
+    }
+}
+
+impl<C: RestClient, S: KvStore> RestClient for CachedClient<C, S> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+impl<C: Client, S: KvStore> Client for CachedClient<C, S> {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let lookup = match self.lookup(&request) {
+            Lookup::Hit(response) => return Ok(response),
+            lookup => lookup,
+        };
+        let response = self.client.rest(request, body);
+        if let Ok(response) = &response {
+            self.update(lookup, response);
+        }
+        response
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync + Send, S: KvStore> AsyncClient for CachedClient<C, S> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let lookup = match self.lookup(&request) {
+            Lookup::Hit(response) => return Ok(response),
+            lookup => lookup,
+        };
+        let response = self.client.rest_async(request, body).await;
+        if let Ok(response) = &response {
+            self.update(lookup, response);
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    use bytes::Bytes;
+    use http::{
+        header, request::Builder as RequestBuilder, HeaderMap, Method, Response, StatusCode,
+    };
+    use serde_json::json;
+    use url::Url;
+
+    use crate::{
+        api::{
+            self,
+            terms::{DeleteTerm, Terms},
+            translations::Locales,
+        },
+        auth::Authenticated,
+        cache::{CachedClient, ConditionalCache},
+        fixtures,
+        mock::{Expectation, MockTraduora},
+        store::MemoryStore,
+        ApiError, Client, CustomQuery, Query, RestClient,
+    };
+
+    const ETAG: &str = "\"33a64df5\"";
+
+    /// Answers with `304 Not Modified` if the ETag matches.
+    #[derive(Default)]
+    struct EtagServer {
+        requests: RefCell<Vec<HeaderMap>>,
+    }
+
+    impl RestClient for EtagServer {
+        type Error = Infallible;
+        type AccessLevel = Authenticated;
+
+        fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+            Ok(format!("http://localhost/api/v1/{}", endpoint).parse()?)
+        }
+    }
+
+    impl Client for EtagServer {
+        fn rest(
+            &self,
+            request: RequestBuilder,
+            _: Bytes,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            let headers = request.headers_ref().cloned().unwrap_or_default();
+            let not_modified = headers.get(header::IF_NONE_MATCH).map(|v| v == ETAG);
+            self.requests.borrow_mut().push(headers);
+
+            let response = Response::builder().header(header::ETAG, ETAG);
+            Ok(if not_modified == Some(true) {
+                response
+                    .status(StatusCode::NOT_MODIFIED)
+                    .body(Bytes::new())
+                    .unwrap()
+            } else {
+                response
+                    .body(Bytes::from_static(include_bytes!("../data/terms.json")))
+                    .unwrap()
+            })
+        }
+    }
+
+    #[test]
+    fn not_modified_returns_cached_model() {
+        let client = ConditionalCache::new(EtagServer::default());
+        let endpoint = Terms::new("project".into());
+
+        let first = endpoint.query(&client).unwrap();
+        let second = endpoint.query(&client).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(client.len(), 1);
+        let requests = client.inner().requests.borrow();
+        assert_eq!(requests[0].get(header::IF_NONE_MATCH), None);
+        assert_eq!(requests[1].get(header::IF_NONE_MATCH).unwrap(), ETAG);
+    }
+
+    #[test]
+    fn stored_responses_are_reused_by_new_cache() {
+        let store = MemoryStore::new();
+        let endpoint = Terms::new("project".into());
+        let client = ConditionalCache::with_store(EtagServer::default(), store);
+        let first = endpoint.query(&client).unwrap();
+
+        let (_, store) = client.into_parts();
+        let client = ConditionalCache::with_store(EtagServer::default(), store);
+        let second = endpoint.query(&client).unwrap();
+
+        assert_eq!(first, second);
+        let requests = client.inner().requests.borrow();
+        assert_eq!(requests[0].get(header::IF_NONE_MATCH).unwrap(), ETAG);
+    }
+
+    #[test]
+    fn cleared_cache_sends_unconditional_request() {
+        let client = ConditionalCache::new(EtagServer::default());
+        let endpoint = Terms::new("project".into());
+
+        endpoint.query(&client).unwrap();
+        client.clear();
+        endpoint.query(&client).unwrap();
+
+        let requests = client.inner().requests.borrow();
+        assert_eq!(requests[1].get(header::IF_NONE_MATCH), None);
+    }
+
+    #[test]
+    fn fresh_responses_are_served_from_cache() {
+        let mock = MockTraduora::new();
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/terms")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+        )
+        .expect(
+            Expectation::new(Method::GET, "projects/p/translations")
+                .times(2)
+                .respond(StatusCode::NOT_FOUND, "{}"),
+        );
+        let client = CachedClient::new(mock, Duration::from_secs(60));
+        let terms = Terms::new("p".into());
+
+        terms.query(&client).unwrap();
+        terms.query(&client).unwrap();
+        assert_eq!(client.len(), 1);
+        client.invalidate(&terms);
+        assert!(client.is_empty());
+        terms.query(&client).unwrap();
+
+        Locales("p".into()).query(&client).unwrap_err();
+        Locales("p".into()).query(&client).unwrap_err();
+        assert_eq!(client.len(), 1);
+        client.inner().verify().unwrap();
+    }
+
+    #[test]
+    fn writes_invalidate_their_project() {
+        let mock = MockTraduora::new();
+        mock.expect(
+            Expectation::new(Method::GET, "projects/*/terms")
+                .times(3)
+                .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+        )
+        .expect(Expectation::new(Method::DELETE, "projects/p/terms/t"));
+        let client = CachedClient::new(mock, Duration::from_secs(60));
+
+        Terms::new("p".into()).query(&client).unwrap();
+        Terms::new("other".into()).query(&client).unwrap();
+        api::ignore(DeleteTerm::new("p".into(), "t".into()))
+            .query_custom(&client)
+            .unwrap();
+        assert_eq!(client.len(), 1);
+        Terms::new("p".into()).query(&client).unwrap();
+        Terms::new("other".into()).query(&client).unwrap();
+        client.inner().verify().unwrap();
+    }
+
+    #[test]
+    fn expired_responses_are_fetched_again() {
+        let mock = MockTraduora::new();
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/terms")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+        );
+        let client = CachedClient::new(mock, Duration::from_millis(10));
+
+        Terms::new("p".into()).query(&client).unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        Terms::new("p".into()).query(&client).unwrap();
+        client.inner().verify().unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+//! Aborting long-running operations.
+//!
+//! Bulk and sync operations have `*_cancellable` variants that take a
+//! [`CancellationToken`]. Once the token is cancelled, they stop sending new
+//! requests, let running requests finish and return what was done so far
+//! in a [`Cancellable`] summary, e.g. when the user of an interactive tool
+//! presses Ctrl-C.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{api::terms::CreateTerms, cancel::CancellationToken, progress::NoProgress};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+//! let token = CancellationToken::new();
+//!
+//! // e.g. in a signal handler
+//! token.clone().cancel();
+//!
+//! let outcome = CreateTerms::new(project, ["this.is.a.new.term"])
+//!     .create_cancellable(&client, &token, &NoProgress);
+//! assert!(outcome.is_cancelled());
+//! assert_eq!(outcome.skipped, 1);
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// A shared flag that tells operations to stop.
+///
+/// Clones share the flag, so one clone can be cancelled from another
+/// thread or task while an operation checks another clone. A token can also
+/// be created from a [`watch::Receiver`], it is cancelled once the channel
+/// holds `true`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    watch: Option<watch::Receiver<bool>>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and all of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the token was cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst) || self.watch.as_ref().map_or(false, |w| *w.borrow())
+    }
+}
+
+impl From<watch::Receiver<bool>> for CancellationToken {
+    fn from(watch: watch::Receiver<bool>) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            watch: Some(watch),
+        }
+    }
+}
+
+/// The outcome of an operation that may have been cancelled.
+#[derive(Debug)]
+pub struct Cancellable<T> {
+    /// The result of the items that were processed.
+    pub result: T,
+    /// Number of items that were not processed because the operation was
+    /// cancelled. `0` if the operation completed.
+    pub skipped: usize,
+}
+
+impl<T> Cancellable<T> {
+    /// Whether the operation stopped before processing all items.
+    #[must_use]
+    pub const fn is_cancelled(&self) -> bool {
+        self.skipped > 0
+    }
+
+    /// The result of the items that were processed.
+    pub fn into_inner(self) -> T {
+        self.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use tokio::sync::watch;
+
+    use super::CancellationToken;
+    use crate::{
+        api::terms::CreateTerms,
+        fixtures,
+        mock::{Expectation, MockTraduora},
+        progress::Progress,
+    };
+
+    #[test]
+    fn clones_and_channels_cancel_the_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+
+        let (sender, receiver) = watch::channel(false);
+        let token = CancellationToken::from(receiver);
+        assert!(!token.is_cancelled());
+        sender.send(true).unwrap();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn no_requests_are_sent_after_cancellation() {
+        let mock = MockTraduora::new();
+        mock.expect(
+            Expectation::new(Method::POST, "projects/p/terms")
+                .respond_json(StatusCode::OK, fixtures::data(fixtures::term("1", "a"))),
+        );
+        let token = CancellationToken::new();
+        let cancel_after_first = |progress: &Progress| {
+            if progress.done == 1 {
+                token.cancel();
+            }
+        };
+
+        let outcome = CreateTerms::new("p".into(), ["a", "b", "c"]).create_cancellable(
+            &mock,
+            &token,
+            &cancel_after_first,
+        );
+
+        assert!(outcome.is_cancelled());
+        assert_eq!(outcome.skipped, 2);
+        assert!(outcome.result.is_ok());
+        mock.verify().unwrap();
+    }
+}
@@ -5,7 +5,11 @@ use bytes::Bytes;
 use http::{request::Builder as RequestBuilder, Response};
 use url::Url;
 
-use crate::{auth::Scope, ApiError};
+use crate::{
+    api::{ProjectId, Role},
+    auth::Scope,
+    ApiError,
+};
 
 /// A trait representing a client which can communicate with a Traduora instance via REST.
 pub trait RestClient {
@@ -23,6 +27,14 @@ pub trait RestClient {
     /// This method returns an error if it fails to concatenate the
     /// host name to the specific endpoint.
     fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>>;
+
+    /// The role of the client within the project, if it is known.
+    ///
+    /// Requests for endpoints of the project that need a more privileged role
+    /// fail before they are sent.
+    fn project_role(&self, _project: &ProjectId) -> Option<Role> {
+        None
+    }
 }
 
 /// A trait representing a client which can communicate with a Traduora instance.
@@ -37,7 +49,7 @@ pub trait Client: RestClient {
     fn rest(
         &self,
         request: RequestBuilder,
-        body: Vec<u8>,
+        body: Bytes,
     ) -> Result<Response<Bytes>, ApiError<Self::Error>>;
 }
 
@@ -54,7 +66,7 @@ pub trait AsyncClient: RestClient {
     async fn rest_async(
         &self,
         request: RequestBuilder,
-        body: Vec<u8>,
+        body: Bytes,
     ) -> Result<Response<Bytes>, ApiError<Self::Error>>;
 }
 
@@ -89,6 +101,54 @@ pub mod doctests {
             (&Method::GET, "/api/v1/auth/providers") => include_bytes!("../data/providers.json"),
             (&Method::POST, "/api/v1/auth/signup") => include_bytes!("../data/signup_user.json"),
             (&Method::POST, "/api/v1/auth/token") => include_bytes!("../data/access_token.json"),
+            (&Method::GET, _) if is_match("/api/v1/projects/*/clients") => {
+                include_bytes!("../data/project_clients.json")
+            }
+            (&Method::POST, _) if is_match("/api/v1/projects/*/clients") => {
+                include_bytes!("../data/create_project_client.json")
+            }
+            (&Method::PATCH, _) if is_match("/api/v1/projects/*/clients/*") => {
+                include_bytes!("../data/edit_project_client.json")
+            }
+            (&Method::DELETE, _) if is_match("/api/v1/projects/*/clients/*") => b"",
+            (&Method::POST, _) if is_match("/api/v1/projects/*/clients/*/rotate-secret") => {
+                include_bytes!("../data/create_project_client.json")
+            }
+            (&Method::GET, _) if is_match("/api/v1/projects/*/exports") => {
+                include_bytes!("../data/export.json")
+            }
+            (&Method::POST, _) if is_match("/api/v1/projects/*/imports") => {
+                include_bytes!("../data/import.json")
+            }
+            (&Method::GET, _) if is_match("/api/v1/projects/*/invites") => {
+                include_bytes!("../data/invites.json")
+            }
+            (&Method::POST, _) if is_match("/api/v1/projects/*/invites") => {
+                include_bytes!("../data/create_invite.json")
+            }
+            (&Method::PATCH, _) if is_match("/api/v1/projects/*/invites/*") => {
+                include_bytes!("../data/edit_invite.json")
+            }
+            (&Method::DELETE, _) if is_match("/api/v1/projects/*/invites/*") => b"",
+            (&Method::GET, _) if is_match("/api/v1/projects/*/labels") => {
+                include_bytes!("../data/labels.json")
+            }
+            (&Method::POST, _) if is_match("/api/v1/projects/*/labels") => {
+                include_bytes!("../data/create_label.json")
+            }
+            (&Method::PATCH, _) if is_match("/api/v1/projects/*/labels/*") => {
+                include_bytes!("../data/edit_label.json")
+            }
+            (&Method::DELETE, _) if is_match("/api/v1/projects/*/labels/*") => b"",
+            (&Method::POST | &Method::DELETE, _)
+                if is_match("/api/v1/projects/*/labels/*/terms/*")
+                    || is_match("/api/v1/projects/*/labels/*/terms/*/translations/*") =>
+            {
+                b""
+            }
+            (&Method::GET, _) if is_match("/api/v1/projects/*/stats") => {
+                include_bytes!("../data/stats.json")
+            }
             (&Method::GET, _) if is_match("/api/v1/projects/*/terms") => {
                 include_bytes!("../data/terms.json")
             }
@@ -154,7 +214,7 @@ pub mod doctests {
         pub fn new(host: &str) -> Result<Self, TraduoraError> {
             Ok(Self {
                 url: host.into(),
-                phantom: PhantomData::default(),
+                phantom: PhantomData,
             })
         }
     }
@@ -167,7 +227,7 @@ pub mod doctests {
         pub fn with_auth(host: &str, _: Login) -> Result<Self, TraduoraError> {
             Ok(Self {
                 url: host.into(),
-                phantom: PhantomData::default(),
+                phantom: PhantomData,
             })
         }
     }
@@ -176,7 +236,7 @@ pub mod doctests {
         fn rest(
             &self,
             builder: RequestBuilder,
-            _: Vec<u8>,
+            _: Bytes,
         ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
             let request = builder.body(()).map_err(|e| ApiError::client(e.into()))?;
             Ok(generate_response(request.method(), request.uri().path()))
@@ -198,10 +258,59 @@ pub mod doctests {
         async fn rest_async(
             &self,
             builder: RequestBuilder,
-            _: Vec<u8>,
+            _: Bytes,
         ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
             let request = builder.body(()).map_err(|e| ApiError::client(e.into()))?;
             Ok(generate_response(request.method(), request.uri().path()))
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use http::Method;
+
+        use super::generate_response;
+
+        #[test]
+        fn fixtures_of_all_project_endpoints_are_valid_json() {
+            let project = "/api/v1/projects/b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+            let routes = [
+                (Method::GET, "/stats"),
+                (Method::GET, "/clients"),
+                (Method::POST, "/clients"),
+                (Method::PATCH, "/clients/c"),
+                (Method::POST, "/clients/c/rotate-secret"),
+                (Method::GET, "/invites"),
+                (Method::POST, "/invites"),
+                (Method::PATCH, "/invites/i"),
+                (Method::GET, "/labels"),
+                (Method::POST, "/labels"),
+                (Method::PATCH, "/labels/l"),
+                (Method::POST, "/imports"),
+            ];
+            for (method, path) in &routes {
+                let response = generate_response(method, &format!("{}{}", project, path));
+                let json: serde_json::Value = serde_json::from_slice(response.body())
+                    .unwrap_or_else(|e| panic!("{} {}: {}", method, path, e));
+                assert!(!json["data"].is_null(), "{} {}", method, path);
+            }
+
+            // exports are the exported file itself, without a `data` wrapper
+            let response = generate_response(&Method::GET, &format!("{}/exports", project));
+            let export: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+            assert!(export.is_object());
+
+            for (method, path) in &[
+                (Method::DELETE, "/clients/c"),
+                (Method::DELETE, "/invites/i"),
+                (Method::DELETE, "/labels/l"),
+                (Method::POST, "/labels/l/terms/t"),
+                (Method::DELETE, "/labels/l/terms/t"),
+                (Method::DELETE, "/labels/l/terms/t/translations/de"),
+            ] {
+                let response = generate_response(method, &format!("{}{}", project, path));
+                assert!(response.body().is_empty(), "{} {}", method, path);
+            }
+        }
+    }
 }
@@ -0,0 +1,324 @@
+//! Merging of identical requests that are sent at the same time.
+//!
+//! See [`CoalescingClient`].
+
+use std::collections::{hash_map::Entry, HashMap};
+use std::fmt::Write;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, HeaderMap, Method, Response, StatusCode, Version};
+use tokio::sync::broadcast;
+use url::Url;
+
+use crate::logging::debug;
+use crate::{
+    api::{ProjectId, Role},
+    ApiError, AsyncClient, RestClient,
+};
+
+/// The response of the request that was actually sent, shared with all
+/// callers that sent the same request. `None` if the request failed.
+type Shared = Option<Arc<SharedResponse>>;
+type InFlight = HashMap<String, broadcast::Sender<Shared>>;
+
+#[derive(Debug)]
+struct SharedResponse {
+    status: StatusCode,
+    version: Version,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl SharedResponse {
+    fn new(response: &Response<Bytes>) -> Self {
+        Self {
+            status: response.status(),
+            version: response.version(),
+            headers: response.headers().clone(),
+            body: response.body().clone(),
+        }
+    }
+
+    fn to_response(&self) -> Response<Bytes> {
+        let mut response = Response::new(self.body.clone());
+        *response.status_mut() = self.status;
+        *response.version_mut() = self.version;
+        *response.headers_mut() = self.headers.clone();
+        response
+    }
+}
+
+/// Removes the in-flight entry of a request when it completes or is cancelled.
+struct InFlightGuard<'a> {
+    in_flight: &'a Mutex<InFlight>,
+    key: String,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        lock(self.in_flight).remove(&self.key);
+    }
+}
+
+/// The key of `GET` requests that may share a response: the URL and the
+/// request headers, sorted so that their order does not matter.
+fn key(request: &RequestBuilder) -> Option<String> {
+    if request.method_ref() != Some(&Method::GET) {
+        return None;
+    }
+    let mut key = request.uri_ref()?.to_string();
+    let mut headers: Vec<_> = request
+        .headers_ref()
+        .map(|headers| {
+            headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value))
+                .collect()
+        })
+        .unwrap_or_default();
+    headers.sort_by(|a, b| (a.0, a.1.as_bytes()).cmp(&(b.0, b.1.as_bytes())));
+    for (name, value) in headers {
+        // the debug output escapes the value, so keys cannot be ambiguous
+        let _ = write!(key, "\n{}: {:?}", name, value);
+    }
+    Some(key)
+}
+
+fn lock(in_flight: &Mutex<InFlight>) -> MutexGuard<'_, InFlight> {
+    in_flight.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An asynchronous client wrapper that sends identical `GET` requests only once.
+///
+/// The first `GET` request to a URL waits for a short time window before it is sent.
+/// All `GET` requests to the same URL with the same request headers that arrive
+/// during this window or while the request is in flight receive a copy of its
+/// response instead of sending their own request. This reduces the load on the
+/// Traduora instance when many independent parts of an application request the
+/// same data at the same time.
+///
+/// If the shared request fails, each waiting caller sends its own request.
+/// Requests with other methods are passed through unchanged.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as AsyncTraduora, TraduoraError};
+/// use std::time::Duration;
+/// use traduora::{api::projects::Projects, coalesce::CoalescingClient, AsyncQuery};
+///
+/// # async fn main_async() -> Result<(), TraduoraError> {
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = CoalescingClient::new(AsyncTraduora::with_auth("localhost:8080", login)?)
+///     .window(Duration::from_millis(20));
+///
+/// // only one request is sent to the server
+/// let (projects, same_projects) = futures_util::join!(
+///     Projects.query_async(&client),
+///     Projects.query_async(&client),
+/// );
+/// assert_eq!(projects?, same_projects?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CoalescingClient<C> {
+    client: C,
+    window: Duration,
+    in_flight: Mutex<InFlight>,
+}
+
+impl<C> CoalescingClient<C> {
+    /// Wraps the client with a time window of 10ms.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            window: Duration::from_millis(10),
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets how long the first request to a URL waits for identical requests
+    /// before it is sent.
+    ///
+    /// A window of zero only merges requests while one is in flight.
+    #[must_use]
+    pub const fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Returns the wrapped client.
+    pub const fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Unwraps the client.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+}
+
+impl<C: RestClient> RestClient for CoalescingClient<C> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync + Send> AsyncClient for CoalescingClient<C> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let key = match key(&request) {
+            Some(key) => key,
+            None => return self.client.rest_async(request, body).await,
+        };
+
+        let joined = match lock(&self.in_flight).entry(key.clone()) {
+            Entry::Occupied(entry) => Err(entry.get().subscribe()),
+            Entry::Vacant(entry) => {
+                let (sender, _) = broadcast::channel(1);
+                entry.insert(sender.clone());
+                Ok(sender)
+            }
+        };
+
+        match joined {
+            Ok(sender) => {
+                let guard = InFlightGuard {
+                    in_flight: &self.in_flight,
+                    key,
+                };
+                tokio::time::sleep(self.window).await;
+                let result = self.client.rest_async(request, body).await;
+                drop(guard);
+                let shared = result
+                    .as_ref()
+                    .ok()
+                    .map(|rsp| Arc::new(SharedResponse::new(rsp)));
+                // no one else may be waiting for the response
+                let _ = sender.send(shared);
+                result
+            }
+            Err(mut receiver) => {
+                if let Ok(Some(shared)) = receiver.recv().await {
+                    // only the URL, the headers may contain credentials
+                    let url = key.split('\n').next().unwrap_or_default();
+                    debug!(target: "traduora", "sharing response for {}", url);
+                    return Ok(shared.to_response());
+                }
+                self.client.rest_async(request, body).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use bytes::Bytes;
+    use http::{header, request::Builder as RequestBuilder, HeaderValue, Response};
+    use url::Url;
+
+    use crate::{
+        api::{self, terms::Terms},
+        auth::Authenticated,
+        coalesce::CoalescingClient,
+        ApiError, AsyncClient, AsyncQuery, RestClient,
+    };
+
+    #[derive(Default)]
+    struct CountingClient {
+        requests: AtomicU32,
+    }
+
+    impl RestClient for CountingClient {
+        type Error = Infallible;
+        type AccessLevel = Authenticated;
+
+        fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+            Ok(format!("http://localhost/api/v1/{}", endpoint).parse()?)
+        }
+    }
+
+    #[async_trait]
+    impl AsyncClient for CountingClient {
+        async fn rest_async(
+            &self,
+            _: RequestBuilder,
+            _: Bytes,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(Response::new(Bytes::from_static(include_bytes!(
+                "../data/terms.json"
+            ))))
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_requests_are_sent_once() {
+        let client = CoalescingClient::new(CountingClient::default());
+        let endpoint = Terms::new("project".into());
+
+        let (first, second, third) = futures_util::join!(
+            endpoint.query_async(&client),
+            endpoint.query_async(&client),
+            endpoint.query_async(&client),
+        );
+
+        assert_eq!(first.unwrap(), second.unwrap());
+        assert!(third.is_ok());
+        assert_eq!(client.inner().requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn different_requests_are_sent_separately() {
+        let client = CoalescingClient::new(CountingClient::default());
+
+        let (project, other_project) = (Terms::new("project".into()), Terms::new("other".into()));
+
+        let (first, second) = futures_util::join!(
+            project.query_async(&client),
+            other_project.query_async(&client),
+        );
+
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(client.inner().requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn requests_with_different_headers_are_sent_separately() {
+        let client = CoalescingClient::new(CountingClient::default());
+        let endpoint = Terms::new("project".into());
+        let german = api::with_headers(endpoint.clone(), Default::default())
+            .header(header::ACCEPT_LANGUAGE, HeaderValue::from_static("de"));
+        let english = api::with_headers(endpoint.clone(), Default::default())
+            .header(header::ACCEPT_LANGUAGE, HeaderValue::from_static("en"));
+
+        let (first, second, third) = futures_util::join!(
+            german.query_async(&client),
+            english.query_async(&client),
+            endpoint.query_async(&client),
+        );
+
+        assert!(first.is_ok() && second.is_ok() && third.is_ok());
+        assert_eq!(client.inner().requests.load(Ordering::SeqCst), 3);
+    }
+}
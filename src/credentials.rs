@@ -0,0 +1,507 @@
+//! Sources of login information.
+//!
+//! A [`CredentialProvider`] looks up the [`Login`] for a client, e.g. from
+//! environment variables ([`EnvProvider`]), a file ([`FileProvider`]) or by
+//! asking the user ([`PromptProvider`]). [`ChainProvider`] tries several
+//! providers in order. Pass a provider to
+//! [`TraduoraBuilder::credentials`](crate::TraduoraBuilder::credentials).
+//!
+//! # Examples
+//! ```no_run
+//! use traduora::{credentials::{self, ChainProvider, EnvProvider, FileProvider, PromptProvider}, TraduoraBuilder};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let provider = ChainProvider::new()
+//!     .then(EnvProvider)
+//!     .then(FileProvider::new("/etc/traduora/credentials.json"))
+//!     .then(PromptProvider::new(|| None));
+//!
+//! let host = credentials::host_from_env().unwrap_or_else(|| "localhost:8080".to_owned());
+//! let client = TraduoraBuilder::new(&host).credentials(&provider)?.build()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `keyring` feature, `KeyringProvider` keeps the credentials and
+//! the access token in the keychain of the operating system.
+
+use std::fmt::{self, Debug};
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "keyring")]
+use crate::auth::StoredToken;
+use crate::Login;
+
+/// Environment variable with the host of the Traduora instance.
+pub const ENV_URL: &str = "TRADUORA_URL";
+/// Environment variable with the id of a project client.
+pub const ENV_CLIENT_ID: &str = "TRADUORA_CLIENT_ID";
+/// Environment variable with the secret of a project client.
+pub const ENV_CLIENT_SECRET: &str = "TRADUORA_CLIENT_SECRET";
+/// Environment variable with the email address of a user.
+pub const ENV_MAIL: &str = "TRADUORA_MAIL";
+/// Environment variable with the password of a user.
+pub const ENV_PASSWORD: &str = "TRADUORA_PASSWORD";
+
+/// The error which is returned when looking up credentials fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CredentialError {
+    /// No provider had credentials.
+    #[error("no credentials found")]
+    NotFound,
+    /// Only one half of the credentials is configured.
+    #[error("{} is set but {} is missing", present, missing)]
+    Incomplete {
+        /// The configured half, e.g. an environment variable.
+        present: String,
+        /// The missing half.
+        missing: String,
+    },
+    /// The credentials file could not be read.
+    #[error("failed to read credentials file {}: {}", path.display(), source)]
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Inner error.
+        source: io::Error,
+    },
+    /// The credentials file has an invalid format.
+    #[error("invalid credentials file {}: {}", path.display(), source)]
+    Format {
+        /// Path of the file.
+        path: PathBuf,
+        /// Inner error.
+        source: serde_json::Error,
+    },
+    /// The keychain of the operating system could not be accessed.
+    #[cfg(feature = "keyring")]
+    #[error("failed to access keyring entry {}: {}", entry, source)]
+    Keyring {
+        /// Name of the entry.
+        entry: String,
+        /// Inner error.
+        source: keyring::Error,
+    },
+    /// A keychain entry has an invalid format.
+    #[cfg(feature = "keyring")]
+    #[error("invalid keyring entry {}: {}", entry, source)]
+    KeyringFormat {
+        /// Name of the entry.
+        entry: String,
+        /// Inner error.
+        source: serde_json::Error,
+    },
+}
+
+/// A source of login information.
+pub trait CredentialProvider {
+    /// Looks up the login information.
+    ///
+    /// Returns `None` if this source has no credentials, so the next source can be tried.
+    ///
+    /// # Errors
+    /// This method returns an error if the source has invalid or incomplete credentials.
+    fn login(&self) -> Result<Option<Login>, CredentialError>;
+}
+
+/// The host of the Traduora instance from the environment variable [`ENV_URL`].
+#[must_use]
+pub fn host_from_env() -> Option<String> {
+    std::env::var(ENV_URL).ok().filter(|url| !url.is_empty())
+}
+
+/// Reads the credentials from environment variables.
+///
+/// See [`Login::from_env`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EnvProvider;
+
+impl CredentialProvider for EnvProvider {
+    fn login(&self) -> Result<Option<Login>, CredentialError> {
+        Login::from_env()
+    }
+}
+
+/// Reads the credentials from a JSON file.
+///
+/// The file contains either the credentials of a user or of a project client:
+/// ```json
+/// { "mail": "user@traduora.example", "password": "password" }
+/// ```
+/// ```json
+/// { "client_id": "f411de34-369d-436b-9aa6-4ae3d6d204be", "client_secret": "Hq4UFo6Z7sHODKdpAQEgaVR8onl8njLI" }
+/// ```
+/// A missing file has no credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileProvider {
+    path: PathBuf,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum CredentialsFile {
+    Password {
+        mail: String,
+        password: String,
+    },
+    ClientCredentials {
+        client_id: String,
+        client_secret: String,
+    },
+}
+
+impl From<&Login> for CredentialsFile {
+    fn from(login: &Login) -> Self {
+        match login.clone() {
+            Login::Password { mail, password } => Self::Password { mail, password },
+            Login::ClientCredentials {
+                client_id,
+                client_secret,
+            } => Self::ClientCredentials {
+                client_id,
+                client_secret,
+            },
+        }
+    }
+}
+
+impl From<CredentialsFile> for Login {
+    fn from(file: CredentialsFile) -> Self {
+        match file {
+            CredentialsFile::Password { mail, password } => Self::password(mail, password),
+            CredentialsFile::ClientCredentials {
+                client_id,
+                client_secret,
+            } => Self::client_credentials(client_id, client_secret),
+        }
+    }
+}
+
+impl FileProvider {
+    /// Creates a provider reading the file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CredentialProvider for FileProvider {
+    fn login(&self) -> Result<Option<Login>, CredentialError> {
+        let content = match std::fs::read(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => {
+                return Err(CredentialError::Io {
+                    path: self.path.clone(),
+                    source,
+                })
+            }
+        };
+        let file: CredentialsFile =
+            serde_json::from_slice(&content).map_err(|source| CredentialError::Format {
+                path: self.path.clone(),
+                source,
+            })?;
+        Ok(Some(file.into()))
+    }
+}
+
+/// Asks the user for the credentials with a callback, e.g. on the terminal.
+pub struct PromptProvider<F> {
+    prompt: F,
+}
+
+impl<F> PromptProvider<F>
+where
+    F: Fn() -> Option<Login>,
+{
+    /// Creates a provider calling `prompt`, which returns `None` if the user cancels.
+    pub const fn new(prompt: F) -> Self {
+        Self { prompt }
+    }
+}
+
+impl<F> Debug for PromptProvider<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PromptProvider").finish()
+    }
+}
+
+impl<F> CredentialProvider for PromptProvider<F>
+where
+    F: Fn() -> Option<Login>,
+{
+    fn login(&self) -> Result<Option<Login>, CredentialError> {
+        Ok((self.prompt)())
+    }
+}
+
+/// Tries several providers in order and uses the first credentials found.
+///
+/// Errors of a provider stop the search, so misconfigured credentials are
+/// reported instead of silently falling back to the next provider.
+#[derive(Default)]
+pub struct ChainProvider {
+    providers: Vec<Box<dyn CredentialProvider>>,
+}
+
+impl ChainProvider {
+    /// Creates a chain without providers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a provider that is tried if all previous ones have no credentials.
+    #[must_use]
+    pub fn then<P>(mut self, provider: P) -> Self
+    where
+        P: CredentialProvider + 'static,
+    {
+        self.providers.push(Box::new(provider));
+        self
+    }
+}
+
+impl Debug for ChainProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainProvider")
+            .field("providers", &self.providers.len())
+            .finish()
+    }
+}
+
+impl CredentialProvider for ChainProvider {
+    fn login(&self) -> Result<Option<Login>, CredentialError> {
+        for provider in &self.providers {
+            if let Some(login) = provider.login()? {
+                return Ok(Some(login));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Keeps the credentials and the access token in the keychain of the operating system.
+///
+/// The login of `account` is stored in the entry `account` of the service and
+/// its access token in the entry `account.token`, both as JSON. Storing the
+/// token allows reusing it across program runs until it expires.
+///
+/// # Examples
+/// ```no_run
+/// use traduora::{credentials::{ChainProvider, CredentialProvider, EnvProvider, KeyringProvider}, TraduoraBuilder};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let keyring = KeyringProvider::new("traduora", "localhost:8080");
+/// let client = match keyring.token()? {
+///     Some(token) if !token.is_expired() => TraduoraBuilder::new("localhost:8080")
+///         .with_access_token(token)
+///         .build()?,
+///     _ => {
+///         let provider = ChainProvider::new()
+///             .then(EnvProvider)
+///             .then(keyring.clone());
+///         let client = TraduoraBuilder::new("localhost:8080")
+///             .credentials(&provider)?
+///             .build()?;
+///         keyring.store_token(client.access_token())?;
+///         client
+///     }
+/// };
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyringProvider {
+    service: String,
+    account: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringProvider {
+    /// Creates a provider for the entries of `account` in `service`.
+    pub fn new(service: impl Into<String>, account: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            account: account.into(),
+        }
+    }
+
+    /// Stores the login, replacing a previously stored one.
+    ///
+    /// # Errors
+    /// This method returns an error if the keychain cannot be accessed.
+    pub fn store_login(&self, login: &Login) -> Result<(), CredentialError> {
+        self.write(&self.account, &CredentialsFile::from(login))
+    }
+
+    /// Reads the stored access token. `None` if no token is stored.
+    ///
+    /// # Errors
+    /// This method returns an error if the keychain cannot be accessed or the entry is invalid.
+    pub fn token(&self) -> Result<Option<StoredToken>, CredentialError> {
+        self.read(&self.token_entry())
+    }
+
+    /// Stores the access token, replacing a previously stored one.
+    ///
+    /// # Errors
+    /// This method returns an error if the keychain cannot be accessed.
+    pub fn store_token(&self, token: &StoredToken) -> Result<(), CredentialError> {
+        self.write(&self.token_entry(), token)
+    }
+
+    /// Removes the login and the access token from the keychain.
+    ///
+    /// # Errors
+    /// This method returns an error if the keychain cannot be accessed.
+    pub fn delete(&self) -> Result<(), CredentialError> {
+        for name in [self.account.clone(), self.token_entry()] {
+            match self.entry(&name)?.delete_password() {
+                Ok(()) | Err(keyring::Error::NoEntry) => {}
+                Err(source) => {
+                    return Err(CredentialError::Keyring {
+                        entry: name,
+                        source,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn token_entry(&self) -> String {
+        format!("{}.token", self.account)
+    }
+
+    fn entry(&self, name: &str) -> Result<keyring::Entry, CredentialError> {
+        keyring::Entry::new(&self.service, name).map_err(|source| CredentialError::Keyring {
+            entry: name.to_owned(),
+            source,
+        })
+    }
+
+    fn read<T>(&self, name: &str) -> Result<Option<T>, CredentialError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let secret = match self.entry(name)?.get_password() {
+            Ok(secret) => secret,
+            Err(keyring::Error::NoEntry) => return Ok(None),
+            Err(source) => {
+                return Err(CredentialError::Keyring {
+                    entry: name.to_owned(),
+                    source,
+                })
+            }
+        };
+        serde_json::from_str(&secret)
+            .map(Some)
+            .map_err(|source| CredentialError::KeyringFormat {
+                entry: name.to_owned(),
+                source,
+            })
+    }
+
+    fn write<T>(&self, name: &str, value: &T) -> Result<(), CredentialError>
+    where
+        T: Serialize,
+    {
+        let secret =
+            serde_json::to_string(value).map_err(|source| CredentialError::KeyringFormat {
+                entry: name.to_owned(),
+                source,
+            })?;
+        self.entry(name)?
+            .set_password(&secret)
+            .map_err(|source| CredentialError::Keyring {
+                entry: name.to_owned(),
+                source,
+            })
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl CredentialProvider for KeyringProvider {
+    fn login(&self) -> Result<Option<Login>, CredentialError> {
+        Ok(self
+            .read::<CredentialsFile>(&self.account)?
+            .map(Login::from))
+    }
+}
+
+/// Reads a pair of credentials with `lookup`. `None` if neither is set.
+pub(crate) fn pair<F>(
+    lookup: &F,
+    first: &str,
+    second: &str,
+) -> Result<Option<(String, String)>, CredentialError>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    match (lookup(first), lookup(second)) {
+        (Some(a), Some(b)) => Ok(Some((a, b))),
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(CredentialError::Incomplete {
+            present: first.to_owned(),
+            missing: second.to_owned(),
+        }),
+        (None, Some(_)) => Err(CredentialError::Incomplete {
+            present: second.to_owned(),
+            missing: first.to_owned(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        ChainProvider, CredentialError, CredentialProvider, CredentialsFile, FileProvider,
+        PromptProvider,
+    };
+    use crate::Login;
+
+    #[test]
+    fn env_and_chain() {
+        let env: BTreeMap<_, _> = [("TRADUORA_MAIL", "a@b.c"), ("TRADUORA_PASSWORD", "pw")]
+            .iter()
+            .map(|(k, v)| (*k, (*v).to_owned()))
+            .collect();
+        let lookup = |name: &str| env.get(name).cloned();
+        assert_eq!(
+            Login::from_lookup(lookup).unwrap(),
+            Some(Login::password("a@b.c", "pw"))
+        );
+
+        let lookup = |name: &str| (name == "TRADUORA_CLIENT_ID").then(|| "id".to_owned());
+        assert!(matches!(
+            Login::from_lookup(lookup),
+            Err(CredentialError::Incomplete { .. })
+        ));
+
+        let chain = ChainProvider::new()
+            .then(FileProvider::new("/does/not/exist.json"))
+            .then(PromptProvider::new(|| Some(Login::password("x", "y"))));
+        assert_eq!(chain.login().unwrap(), Some(Login::password("x", "y")));
+    }
+
+    #[test]
+    fn stored_login_round_trips() {
+        for login in [
+            Login::password("a@b.c", "pw"),
+            Login::client_credentials("id", "secret"),
+        ] {
+            let json = serde_json::to_string(&CredentialsFile::from(&login)).unwrap();
+            let file: CredentialsFile = serde_json::from_str(&json).unwrap();
+            assert_eq!(Login::from(file), login);
+        }
+    }
+}
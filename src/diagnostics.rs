@@ -0,0 +1,399 @@
+//! Checks whether a client configuration works with a Traduora instance.
+//!
+//! See [`smoke_test`].
+
+use std::fmt::{self, Display};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::api::{auth::Providers, locales::AllLocales, projects::Projects, users::Me};
+use crate::auth::{Authenticated, StoredToken};
+use crate::traduora::{Builder, RestError};
+use crate::{ApiError, AsyncQuery, AsyncTraduora, Login, Query, Traduora, TraduoraError};
+
+const EARLIER_CHECK_FAILED: &str = "an earlier check failed";
+const NO_CREDENTIALS: &str = "no credentials configured";
+
+/// Outcome of a single check of a [`SmokeTestReport`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "status", content = "reason", rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// The check succeeded.
+    Passed,
+    /// The check succeeded but the configuration is questionable.
+    Warning(String),
+    /// The check failed.
+    Failed(String),
+    /// The check was not run, e.g. because an earlier check failed.
+    Skipped(String),
+}
+
+impl CheckStatus {
+    /// Whether the check failed.
+    #[must_use]
+    pub const fn is_failed(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Passed => write!(f, "ok"),
+            Self::Warning(msg) => write!(f, "warning: {}", msg),
+            Self::Failed(msg) => write!(f, "failed: {}", msg),
+            Self::Skipped(msg) => write!(f, "skipped: {}", msg),
+        }
+    }
+}
+
+/// The result of [`smoke_test`] with one entry per check.
+///
+/// The checks are run in the order of the fields. If a check fails,
+/// the checks depending on it are skipped.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+pub struct SmokeTestReport {
+    /// The host URL is valid and the HTTP client can be created.
+    pub configuration: CheckStatus,
+    /// The Traduora instance answers requests.
+    pub connectivity: CheckStatus,
+    /// The connection is encrypted with a valid certificate.
+    pub tls: CheckStatus,
+    /// The credentials are accepted by the Traduora instance.
+    pub authentication: CheckStatus,
+    /// Basic endpoints like listing projects and locales are available.
+    pub endpoints: CheckStatus,
+}
+
+impl SmokeTestReport {
+    fn new() -> Self {
+        let skipped = CheckStatus::Skipped(EARLIER_CHECK_FAILED.into());
+        Self {
+            configuration: skipped.clone(),
+            connectivity: skipped.clone(),
+            tls: skipped.clone(),
+            authentication: skipped.clone(),
+            endpoints: skipped,
+        }
+    }
+
+    /// Whether none of the checks failed.
+    ///
+    /// Warnings and skipped checks do not count as failures.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.checks().iter().all(|(_, status)| !status.is_failed())
+    }
+
+    /// All checks with their names in the order they are run.
+    #[must_use]
+    pub fn checks(&self) -> [(&'static str, &CheckStatus); 5] {
+        [
+            ("configuration", &self.configuration),
+            ("connectivity", &self.connectivity),
+            ("tls", &self.tls),
+            ("authentication", &self.authentication),
+            ("endpoints", &self.endpoints),
+        ]
+    }
+
+    fn unreachable(&mut self, error: &ApiError<RestError>, reachable_without_validation: bool) {
+        if reachable_without_validation {
+            self.connectivity = CheckStatus::Passed;
+            self.tls = CheckStatus::Failed(format!("certificate is not valid: {}", error));
+        } else {
+            self.connectivity = CheckStatus::Failed(error.to_string());
+        }
+    }
+
+    fn reachable<L>(&mut self, builder: &Builder<'_, L>) {
+        self.connectivity = CheckStatus::Passed;
+        self.tls = if !builder.uses_https() {
+            CheckStatus::Warning("connection is not encrypted".into())
+        } else if !builder.validates_certs() {
+            CheckStatus::Warning("certificate validation is disabled".into())
+        } else {
+            CheckStatus::Passed
+        };
+    }
+
+    fn without_credentials(&mut self) {
+        self.authentication = CheckStatus::Skipped(NO_CREDENTIALS.into());
+        self.endpoints = CheckStatus::Skipped(NO_CREDENTIALS.into());
+    }
+
+    fn endpoint_results(&mut self, failures: &[(&str, Option<ApiError<RestError>>)]) {
+        let failures: Vec<_> = failures
+            .iter()
+            .filter_map(|(name, error)| error.as_ref().map(|e| format!("{}: {}", name, e)))
+            .collect();
+        self.endpoints = if failures.is_empty() {
+            CheckStatus::Passed
+        } else {
+            CheckStatus::Failed(failures.join("; "))
+        };
+    }
+}
+
+impl Display for SmokeTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, status) in self.checks() {
+            writeln!(f, "{}: {}", name, status)?;
+        }
+        Ok(())
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for () {}
+    impl Sealed for crate::Login {}
+    impl Sealed for crate::auth::StoredToken {}
+}
+
+/// Login information a [`TraduoraBuilder`](crate::TraduoraBuilder) can be configured with.
+///
+/// This trait is sealed and implemented for all states of the builder.
+#[async_trait]
+pub trait Credentials: sealed::Sealed + Clone + Send + Sync + Sized {
+    #[doc(hidden)]
+    fn build_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<Traduora<Authenticated>, TraduoraError>>;
+
+    #[doc(hidden)]
+    async fn build_async_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<AsyncTraduora<Authenticated>, TraduoraError>>;
+}
+
+#[async_trait]
+impl Credentials for () {
+    fn build_client(
+        _: &Builder<'_, Self>,
+    ) -> Option<Result<Traduora<Authenticated>, TraduoraError>> {
+        None
+    }
+
+    async fn build_async_client(
+        _: &Builder<'_, Self>,
+    ) -> Option<Result<AsyncTraduora<Authenticated>, TraduoraError>> {
+        None
+    }
+}
+
+#[async_trait]
+impl Credentials for Login {
+    fn build_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<Traduora<Authenticated>, TraduoraError>> {
+        Some(builder.clone().build())
+    }
+
+    async fn build_async_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<AsyncTraduora<Authenticated>, TraduoraError>> {
+        Some(builder.clone().build_async().await)
+    }
+}
+
+#[async_trait]
+impl Credentials for StoredToken {
+    fn build_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<Traduora<Authenticated>, TraduoraError>> {
+        Some(builder.build())
+    }
+
+    async fn build_async_client(
+        builder: &Builder<'_, Self>,
+    ) -> Option<Result<AsyncTraduora<Authenticated>, TraduoraError>> {
+        Some(builder.build_async().await)
+    }
+}
+
+fn is_reachable<T>(result: &Result<T, ApiError<RestError>>) -> bool {
+    !matches!(result, Err(ApiError::Client { .. }))
+}
+
+/// Checks whether a client with the configuration of the builder can work
+/// with the Traduora instance.
+///
+/// The following checks are run one after another:
+/// 1. the host URL is valid and the HTTP client can be created.
+/// 2. the Traduora instance answers requests.
+/// 3. the certificate is valid. If the instance cannot be reached, the request
+///    is repeated without certificate validation to tell TLS problems apart from
+///    connection problems.
+/// 4. the credentials are accepted. Skipped if the builder has no login information.
+/// 5. projects and locales can be listed.
+///
+/// This function never fails. Problems are reported in the returned [`SmokeTestReport`].
+///
+/// # Examples
+/// ```no_run
+/// use traduora::{diagnostics, Login, TraduoraBuilder};
+///
+/// let login = Login::password("user@traduora.example", "password");
+/// let builder = TraduoraBuilder::new("localhost:8080").authenticate(login);
+/// let report = diagnostics::smoke_test(&builder);
+///
+/// if !report.is_ok() {
+///     eprintln!("{}", report);
+/// }
+/// ```
+pub fn smoke_test<L: Credentials>(builder: &Builder<'_, L>) -> SmokeTestReport {
+    let mut report = SmokeTestReport::new();
+    let client = match builder.build_unauthenticated() {
+        Ok(client) => client,
+        Err(e) => {
+            report.configuration = CheckStatus::Failed(e.to_string());
+            return report;
+        }
+    };
+    report.configuration = CheckStatus::Passed;
+
+    if let Err(e @ ApiError::Client { .. }) = Providers.query(&client) {
+        let reachable_without_validation = builder.uses_https()
+            && builder.validates_certs()
+            && builder
+                .clone()
+                .validate_certs(false)
+                .build_unauthenticated()
+                .map_or(false, |client| is_reachable(&Providers.query(&client)));
+        report.unreachable(&e, reachable_without_validation);
+        return report;
+    }
+    report.reachable(builder);
+
+    let client = match L::build_client(builder) {
+        None => {
+            report.without_credentials();
+            return report;
+        }
+        Some(Err(e)) => {
+            report.authentication = CheckStatus::Failed(e.to_string());
+            return report;
+        }
+        Some(Ok(client)) => client,
+    };
+    if let Err(e) = Me.query(&client) {
+        report.authentication = CheckStatus::Failed(e.to_string());
+        return report;
+    }
+    report.authentication = CheckStatus::Passed;
+
+    report.endpoint_results(&[
+        ("projects", Projects.query(&client).err()),
+        ("locales", AllLocales.query(&client).err()),
+    ]);
+    report
+}
+
+/// Checks asynchronously whether a client with the configuration of the builder
+/// can work with the Traduora instance.
+///
+/// See [`smoke_test`] for the checks that are run.
+///
+/// # Examples
+/// ```no_run
+/// use traduora::{diagnostics, Login, TraduoraBuilder};
+///
+/// # async fn main_async() {
+/// let login = Login::password("user@traduora.example", "password");
+/// let builder = TraduoraBuilder::new("localhost:8080").authenticate(login);
+/// let report = diagnostics::smoke_test_async(&builder).await;
+///
+/// if !report.is_ok() {
+///     eprintln!("{}", report);
+/// }
+/// # }
+/// ```
+pub async fn smoke_test_async<L: Credentials>(builder: &Builder<'_, L>) -> SmokeTestReport {
+    let mut report = SmokeTestReport::new();
+    let client = match builder.build_unauthenticated_async() {
+        Ok(client) => client,
+        Err(e) => {
+            report.configuration = CheckStatus::Failed(e.to_string());
+            return report;
+        }
+    };
+    report.configuration = CheckStatus::Passed;
+
+    if let Err(e @ ApiError::Client { .. }) = Providers.query_async(&client).await {
+        let reachable_without_validation = match builder
+            .clone()
+            .validate_certs(false)
+            .build_unauthenticated_async()
+        {
+            Ok(client) if builder.uses_https() && builder.validates_certs() => {
+                is_reachable(&Providers.query_async(&client).await)
+            }
+            _ => false,
+        };
+        report.unreachable(&e, reachable_without_validation);
+        return report;
+    }
+    report.reachable(builder);
+
+    let client = match L::build_async_client(builder).await {
+        None => {
+            report.without_credentials();
+            return report;
+        }
+        Some(Err(e)) => {
+            report.authentication = CheckStatus::Failed(e.to_string());
+            return report;
+        }
+        Some(Ok(client)) => client,
+    };
+    if let Err(e) = Me.query_async(&client).await {
+        report.authentication = CheckStatus::Failed(e.to_string());
+        return report;
+    }
+    report.authentication = CheckStatus::Passed;
+
+    report.endpoint_results(&[
+        ("projects", Projects.query_async(&client).await.err()),
+        ("locales", AllLocales.query_async(&client).await.err()),
+    ]);
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::AccessToken, diagnostics::CheckStatus, TraduoraBuilder};
+
+    use super::{smoke_test, EARLIER_CHECK_FAILED};
+
+    #[test]
+    fn unreachable_instance_skips_remaining_checks() {
+        let token = AccessToken::new("token");
+        let builder = TraduoraBuilder::new("127.0.0.1:1")
+            .use_http(true)
+            .with_access_token(token);
+
+        let report = smoke_test(&builder);
+
+        assert!(!report.is_ok());
+        assert_eq!(report.configuration, CheckStatus::Passed);
+        assert!(report.connectivity.is_failed());
+        let skipped = CheckStatus::Skipped(EARLIER_CHECK_FAILED.into());
+        assert_eq!(report.tls, skipped);
+        assert_eq!(report.authentication, skipped);
+        assert_eq!(report.endpoints, skipped);
+    }
+
+    #[test]
+    fn invalid_host_fails_configuration() {
+        let report = smoke_test(&TraduoraBuilder::new("not a host"));
+
+        assert!(report.configuration.is_failed());
+        assert_eq!(
+            report.connectivity,
+            CheckStatus::Skipped(EARLIER_CHECK_FAILED.into())
+        );
+    }
+}
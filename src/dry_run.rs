@@ -0,0 +1,262 @@
+//! Previewing changes without applying them.
+//!
+//! See [`DryRunClient`].
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, Method, Response, StatusCode};
+use url::Url;
+
+use crate::logging::debug;
+use crate::{
+    api::{ProjectId, Role},
+    ApiError, AsyncClient, Client, RestClient,
+};
+
+/// A request that was recorded instead of being sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedRequest {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Full URL of the request.
+    pub url: String,
+    /// Body of the request. Empty if the request has no body.
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    /// The body parsed as JSON. `None` if it is empty or not JSON.
+    #[must_use]
+    pub fn body_json(&self) -> Option<serde_json::Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+}
+
+impl fmt::Display for RecordedRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.url)?;
+        if !self.body.is_empty() {
+            write!(f, " {}", String::from_utf8_lossy(&self.body))?;
+        }
+        Ok(())
+    }
+}
+
+/// A client wrapper that sends reading requests but only records modifying ones.
+///
+/// `GET`, `HEAD` and `OPTIONS` requests are passed to the wrapped client.
+/// All other requests, e.g. `POST`, `PATCH` and `DELETE`, are recorded and
+/// answered with an empty `204 No Content` response without contacting the server.
+/// Afterwards, the recorded requests can be inspected, e.g. to preview the
+/// changes of a CI pipeline before applying them.
+///
+/// Because no real response is available, modifying queries only succeed if
+/// they ignore the response, e.g. with [`api::ignore`](crate::api::ignore) or
+/// for endpoints without a model like [`DeleteTerm`](crate::api::terms::DeleteTerm)
+/// and [`DeleteTerms`](crate::api::terms::DeleteTerms).
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::{self, translations::EditTranslation}, dry_run::DryRunClient, CustomQuery};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = DryRunClient::new(Traduora::with_auth("localhost:8080", login)?);
+/// let endpoint = EditTranslation::new(
+///     "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     "de_DE".into(),
+///     "b686f455-b668-40f7-860d-8828263fc8c0".into(),
+///     "Hallo",
+/// );
+/// api::ignore(endpoint).query_custom(&client)?;
+///
+/// for request in client.recorded() {
+///     println!("would send {}", request);
+/// }
+/// assert_eq!(client.recorded().len(), 1);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug)]
+pub struct DryRunClient<C> {
+    client: C,
+    recorded: Mutex<Vec<RecordedRequest>>,
+}
+
+impl<C> DryRunClient<C> {
+    /// Wraps the client.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            recorded: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub const fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Unwraps the client.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// The modifying requests in the order they were recorded.
+    pub fn recorded(&self) -> Vec<RecordedRequest> {
+        self.lock().clone()
+    }
+
+    /// Returns the recorded requests and forgets them.
+    pub fn take(&self) -> Vec<RecordedRequest> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<RecordedRequest>> {
+        self.recorded.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records the request if it modifies data and returns the fake response.
+    fn record(&self, request: &RequestBuilder, body: &[u8]) -> Option<Response<Bytes>> {
+        let method = request.method_ref().cloned().unwrap_or_default();
+        if matches!(method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            return None;
+        }
+        let url = request
+            .uri_ref()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        debug!(target: "traduora", "dry run: not sending {} {}", method, url);
+        self.lock().push(RecordedRequest {
+            method,
+            url,
+            body: body.to_vec(),
+        });
+
+        let mut response = Response::new(Bytes::new());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        Some(response)
+    }
+}
+
+impl<C: RestClient> RestClient for DryRunClient<C> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+impl<C: Client> Client for DryRunClient<C> {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        match self.record(&request, &body) {
+            Some(response) => Ok(response),
+            None => self.client.rest(request, body),
+        }
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync + Send> AsyncClient for DryRunClient<C> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        match self.record(&request, &body) {
+            Some(response) => Ok(response),
+            None => self.client.rest_async(request, body).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use http::{request::Builder as RequestBuilder, Method, Response};
+    use url::Url;
+
+    use crate::{
+        api::terms::{DeleteTerm, DeleteTerms, Terms},
+        auth::Authenticated,
+        dry_run::DryRunClient,
+        ApiError, Client, CustomQuery, Query, RestClient,
+    };
+
+    #[derive(Default)]
+    struct CountingClient {
+        requests: Cell<u32>,
+    }
+
+    impl RestClient for CountingClient {
+        type Error = Infallible;
+        type AccessLevel = Authenticated;
+
+        fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+            Ok(format!("http://localhost/api/v1/{}", endpoint).parse()?)
+        }
+    }
+
+    impl Client for CountingClient {
+        fn rest(
+            &self,
+            _: RequestBuilder,
+            _: Bytes,
+        ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+            self.requests.set(self.requests.get() + 1);
+            Ok(Response::new(Bytes::from_static(include_bytes!(
+                "../data/terms.json"
+            ))))
+        }
+    }
+
+    #[test]
+    fn only_reading_requests_are_sent() {
+        let client = DryRunClient::new(CountingClient::default());
+
+        let terms = Terms::new("project".into()).query(&client).unwrap();
+        let deleted: Result<(), _> =
+            DeleteTerm::new("project".into(), terms[0].id.clone()).query_custom(&client);
+
+        assert!(deleted.is_ok());
+        assert_eq!(client.inner().requests.get(), 1);
+        let recorded = client.take();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].method, Method::DELETE);
+        assert!(recorded[0].url.ends_with(terms[0].id.value()));
+        assert!(client.recorded().is_empty());
+    }
+
+    #[test]
+    fn batch_deletions_are_recorded_in_order() {
+        let client = DryRunClient::new(CountingClient::default());
+        let terms = DeleteTerms::new("project".into(), ["a".into(), "b".into()]);
+
+        let result = terms.delete(&client);
+
+        assert!(result.is_ok());
+        assert_eq!(client.inner().requests.get(), 0);
+        let urls: Vec<_> = client.recorded().into_iter().map(|r| r.url).collect();
+        assert_eq!(
+            urls,
+            [
+                "http://localhost/api/v1/projects/project/terms/a",
+                "http://localhost/api/v1/projects/project/terms/b",
+            ]
+        );
+    }
+}
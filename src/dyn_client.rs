@@ -0,0 +1,281 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, Response};
+use url::Url;
+
+use crate::{
+    api::{ProjectId, Role},
+    auth::{Authenticated, Scope},
+    ApiError, AsyncClient, Client, RestClient,
+};
+
+/// The error of a client behind a [`DynClient`] or [`DynAsyncClient`].
+///
+/// It wraps the error type of the original client, which can be recovered
+/// with [`DynError::downcast_ref`].
+#[derive(Debug)]
+pub struct DynError(Box<dyn Error + Send + Sync>);
+
+impl DynError {
+    /// Wraps the error of a client.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        Self(Box::new(error))
+    }
+
+    /// The error of the original client.
+    #[must_use]
+    pub fn get_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+        &*self.0
+    }
+
+    /// The error of the original client if it has the type `E`.
+    #[must_use]
+    pub fn downcast_ref<E>(&self) -> Option<&E>
+    where
+        E: Error + 'static,
+    {
+        self.0.downcast_ref()
+    }
+}
+
+impl fmt::Display for DynError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Error for DynError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// The object-safe counterpart of [`RestClient`].
+///
+/// It is implemented for every [`RestClient`] and is the common part of
+/// [`DynClient`] and [`DynAsyncClient`]. The access level `A` of the client
+/// is a type parameter instead of an associated type.
+pub trait DynRestClient<A: Scope = Authenticated>: Send + Sync {
+    /// See [`RestClient::rest_endpoint`].
+    ///
+    /// # Errors
+    /// This method returns an error if it fails to concatenate the
+    /// host name to the specific endpoint.
+    fn dyn_rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<DynError>>;
+
+    /// See [`RestClient::project_role`].
+    fn dyn_project_role(&self, project: &ProjectId) -> Option<Role>;
+}
+
+/// The object-safe counterpart of [`Client`].
+///
+/// Every [`Client`] that is `Send + Sync` implements it, and `Arc<dyn DynClient>`
+/// implements [`Client`] again. This allows storing clients without generic
+/// parameters, e.g. to switch between a real and a mock client at runtime.
+/// The error type of the client is replaced by [`DynError`].
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use std::sync::Arc;
+/// use traduora::{api::{terms::Terms, ProjectId}, DynClient, Query};
+///
+/// struct TermCounter {
+///     client: Arc<dyn DynClient>,
+///     project: ProjectId,
+/// }
+///
+/// impl TermCounter {
+///     fn count(&self) -> usize {
+///         Terms::new(self.project.clone()).query(&self.client).map_or(0, |terms| terms.len())
+///     }
+/// }
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let counter = TermCounter {
+///     client: Arc::new(Traduora::with_auth("localhost:8080", login)?),
+///     project: "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+/// };
+/// assert_eq!(counter.count(), 2);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub trait DynClient<A: Scope = Authenticated>: DynRestClient<A> {
+    /// See [`Client::rest`].
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    fn dyn_rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<DynError>>;
+}
+
+/// The object-safe counterpart of [`AsyncClient`].
+///
+/// Every [`AsyncClient`] that is `Send + Sync` implements it, and
+/// `Arc<dyn DynAsyncClient>` implements [`AsyncClient`] again.
+/// See [`DynClient`].
+#[async_trait]
+pub trait DynAsyncClient<A: Scope = Authenticated>: DynRestClient<A> {
+    /// See [`AsyncClient::rest_async`].
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    async fn dyn_rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<DynError>>;
+}
+
+impl<C> DynRestClient<C::AccessLevel> for C
+where
+    C: RestClient + Send + Sync,
+{
+    fn dyn_rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<DynError>> {
+        self.rest_endpoint(endpoint).map_err(into_dyn)
+    }
+
+    fn dyn_project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.project_role(project)
+    }
+}
+
+impl<C> DynClient<C::AccessLevel> for C
+where
+    C: Client + Send + Sync,
+{
+    fn dyn_rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<DynError>> {
+        self.rest(request, body).map_err(into_dyn)
+    }
+}
+
+#[async_trait]
+impl<C> DynAsyncClient<C::AccessLevel> for C
+where
+    C: AsyncClient + Send + Sync,
+{
+    async fn dyn_rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<DynError>> {
+        self.rest_async(request, body).await.map_err(into_dyn)
+    }
+}
+
+fn into_dyn<E>(error: ApiError<E>) -> ApiError<DynError>
+where
+    E: Error + Send + Sync + 'static,
+{
+    error.map_client(DynError::new)
+}
+
+impl<A: Scope> RestClient for Arc<dyn DynClient<A>> {
+    type Error = DynError;
+    type AccessLevel = A;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        (**self).dyn_rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        (**self).dyn_project_role(project)
+    }
+}
+
+impl<A: Scope> Client for Arc<dyn DynClient<A>> {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        (**self).dyn_rest(request, body)
+    }
+}
+
+impl<A: Scope> RestClient for Arc<dyn DynAsyncClient<A>> {
+    type Error = DynError;
+    type AccessLevel = A;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        (**self).dyn_rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        (**self).dyn_project_role(project)
+    }
+}
+
+#[async_trait]
+impl<A: Scope> AsyncClient for Arc<dyn DynAsyncClient<A>> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        (**self).dyn_rest_async(request, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::{DynAsyncClient, DynClient};
+    use crate::{
+        api::terms::Terms,
+        fixtures,
+        mock::{Expectation, MockError, MockTraduora},
+        ApiError, AsyncQuery, Query,
+    };
+
+    #[tokio::test]
+    async fn queries_pass_through_trait_objects() {
+        let mock = Arc::new(MockTraduora::new());
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/terms")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+        );
+
+        let client: Arc<dyn DynClient> = mock.clone();
+        assert!(Terms::new("p".into()).query(&client).unwrap().is_empty());
+        let client: Arc<dyn DynAsyncClient> = mock.clone();
+        assert!(Terms::new("p".into())
+            .query_async(&client)
+            .await
+            .unwrap()
+            .is_empty());
+
+        match Terms::new("other".into()).query_async(&client).await {
+            Err(ApiError::Client { source }) => {
+                assert!(matches!(
+                    source.downcast_ref(),
+                    Some(MockError::Unexpected { .. })
+                ));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        mock.verify().unwrap();
+    }
+}
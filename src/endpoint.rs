@@ -2,11 +2,14 @@ use std::borrow::Cow;
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use http::{self, header, request::Builder, Method, Request, Response};
+use http::{self, header, request::Builder, HeaderMap, Method, Request, Response};
 use serde::de::DeserializeOwned;
+use url::Url;
 
 use crate::{
-    auth::Scope, ApiError, AsyncClient, AsyncCustomQuery, BodyError, Client, CustomQuery,
+    api::{ProjectId, Role},
+    auth::Scope,
+    ApiError, AsyncClient, AsyncCustomQuery, BodyError, Client, CustomQuery, RequestContext,
     RestClient,
 };
 
@@ -23,12 +26,68 @@ pub trait Endpoint {
     /// The body for the endpoint.
     ///
     /// Returns the `Content-Encoding` header for the data as well as the data itself.
+    /// Large bodies should be kept as [`Bytes`] by the endpoint, so that every
+    /// call, e.g. by retries, shares them instead of copying them.
     ///
     /// # Errors
     /// This method returns an error if the body could not be serialized to JSON.
-    fn body(&self) -> Result<Option<(&'static str, Vec<u8>)>, BodyError> {
+    fn body(&self) -> Result<Option<(&'static str, Bytes)>, BodyError> {
         Ok(None)
     }
+
+    /// The query parameters appended to the URL of the endpoint.
+    fn parameters(&self) -> QueryParams<'_> {
+        QueryParams::default()
+    }
+
+    /// Additional headers to send with the request.
+    ///
+    /// They are added after the `Content-Type` header and replace it if it is present.
+    /// Authentication headers are added later by the client.
+    fn headers(&self) -> HeaderMap {
+        HeaderMap::new()
+    }
+
+    /// The project of this endpoint and the least privileged role within it
+    /// that may access this endpoint.
+    ///
+    /// `None` if the endpoint does not belong to a project.
+    fn required_role(&self) -> Option<(&ProjectId, Role)> {
+        None
+    }
+}
+
+/// Query parameters of an endpoint, percent-encoded when they are added to the URL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryParams<'a> {
+    params: Vec<(&'static str, Cow<'a, str>)>,
+}
+
+impl<'a> QueryParams<'a> {
+    /// Adds a parameter.
+    pub fn push(&mut self, key: &'static str, value: impl Into<Cow<'a, str>>) -> &mut Self {
+        self.params.push((key, value.into()));
+        self
+    }
+
+    /// Adds a parameter if it has a value.
+    pub fn push_opt(
+        &mut self,
+        key: &'static str,
+        value: Option<impl Into<Cow<'a, str>>>,
+    ) -> &mut Self {
+        if let Some(value) = value {
+            self.push(key, value);
+        }
+        self
+    }
+
+    fn add_to_url(&self, url: &mut Url) {
+        if !self.params.is_empty() {
+            url.query_pairs_mut()
+                .extend_pairs(self.params.iter().map(|(key, value)| (key, value)));
+        }
+    }
 }
 
 impl<E, T, C> CustomQuery<T, C> for E
@@ -41,7 +100,8 @@ where
     fn query_custom(&self, client: &C) -> Result<T, ApiError<C::Error>> {
         let (req, data) = build_request_with_body(self, client)?;
         let rsp = client.rest(req, data)?;
-        process_response(&rsp, serde_json::from_value)
+        process_response(&rsp, serde_json::from_slice)
+            .map_err(|e| e.with_request(request_context(self, &rsp)))
     }
 }
 
@@ -56,48 +116,107 @@ where
     async fn query_custom_async(&self, client: &C) -> Result<T, ApiError<C::Error>> {
         let (req, data) = build_request_with_body(self, client)?;
         let rsp = client.rest_async(req, data).await?;
-        process_response(&rsp, serde_json::from_value)
+        process_response(&rsp, serde_json::from_slice)
+            .map_err(|e| e.with_request(request_context(self, &rsp)))
     }
 }
 
-pub fn process_response<T, E, F>(r: &Response<Bytes>, mapper: F) -> Result<T, ApiError<E>>
+/// The request of `endpoint` that got the response `r`, see [`ApiError::with_request`].
+pub fn request_context<E>(endpoint: &E, r: &Response<Bytes>) -> RequestContext
+where
+    E: Endpoint + ?Sized,
+{
+    RequestContext {
+        method: endpoint.method(),
+        endpoint: endpoint.endpoint().into_owned(),
+        status: r.status(),
+    }
+}
+
+pub fn process_response<'r, T, E, F>(r: &'r Response<Bytes>, mapper: F) -> Result<T, ApiError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    F: FnOnce(&'r [u8]) -> Result<T, serde_json::Error>,
+{
+    check_status(r)?;
+    // deserialize directly from the body without an intermediate `serde_json::Value`
+    mapper(body_or_null(r)).map_err(json_error::<T, E>)
+}
+
+/// Tells invalid JSON apart from JSON that does not match the type `T`.
+pub fn json_error<T, E>(error: serde_json::Error) -> ApiError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if error.is_data() {
+        ApiError::data_type::<T>(error)
+    } else {
+        ApiError::from(error)
+    }
+}
+
+pub fn check_status<E>(r: &Response<Bytes>) -> Result<(), ApiError<E>>
 where
-    T: DeserializeOwned,
     E: std::error::Error + Send + Sync + 'static,
-    F: FnOnce(serde_json::Value) -> Result<T, serde_json::Error>,
 {
-    let fill = Bytes::from("null");
-    let body = if r.body().is_empty() { &fill } else { r.body() };
-    let result_v = serde_json::from_slice(body);
     if r.status().is_success() {
-        // give general parse error or map to desired rust type or give type mapping error
-        mapper(result_v?).map_err(ApiError::data_type::<T>)
+        return Ok(());
+    }
+    // try to parse error as JSON or give general error
+    let v = serde_json::from_slice(body_or_null(r))
+        .map_err(|_| ApiError::server_error(r.status(), r.body()))?;
+    // give specific error message
+    Err(ApiError::from_traduora(r.status(), v))
+}
+
+fn body_or_null(r: &Response<Bytes>) -> &[u8] {
+    if r.body().is_empty() {
+        b"null"
     } else {
-        // try to parse error as JSON or give general error
-        let v = result_v.map_err(|_| ApiError::server_error(r.status(), r.body()))?;
-        // give specific error message
-        Err(ApiError::from_traduora(v))
+        r.body()
     }
 }
 
 pub fn build_request_with_body<E, C>(
     endpoint: &E,
     client: &C,
-) -> Result<(Builder, Vec<u8>), ApiError<C::Error>>
+) -> Result<(Builder, Bytes), ApiError<C::Error>>
 where
     E: Endpoint,
     C: RestClient,
 {
-    let uri = client
-        .rest_endpoint(&endpoint.endpoint())?
+    if let Some((project, required)) = endpoint.required_role() {
+        match client.project_role(project) {
+            Some(role) if !role.grants(required) => {
+                return Err(ApiError::MissingRole {
+                    endpoint: endpoint.endpoint().into_owned(),
+                    project: project.clone(),
+                    required,
+                    role,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let mut url = client.rest_endpoint(&endpoint.endpoint())?;
+    endpoint.parameters().add_to_url(&mut url);
+    let uri = url
         .as_str()
         .parse::<http::Uri>()
         .expect("failed to parse a url::Url as an http::Uri");
 
-    let req = Request::builder().method(endpoint.method()).uri(uri);
+    let mut req = Request::builder().method(endpoint.method()).uri(uri);
 
-    Ok(match endpoint.body()? {
-        Some((mime, body)) => (req.header(header::CONTENT_TYPE, mime), body),
-        None => (req, Vec::new()),
-    })
+    let body = match endpoint.body()? {
+        Some((mime, body)) => {
+            req = req.header(header::CONTENT_TYPE, mime);
+            body
+        }
+        None => Bytes::new(),
+    };
+    if let Some(headers) = req.headers_mut() {
+        headers.extend(endpoint.headers());
+    }
+    Ok((req, body))
 }
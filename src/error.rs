@@ -1,8 +1,13 @@
 use std::any;
 use std::error::Error;
+use std::fmt;
+use std::io;
 
+use http::{Method, StatusCode};
 use thiserror::Error;
 
+use crate::api::{ProjectId, Role};
+
 /// Errors which may occur when creating form data.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -16,6 +21,132 @@ pub enum BodyError {
     },
 }
 
+/// Machine-readable error codes returned by Traduora.
+///
+/// # Examples
+/// ```
+/// use traduora::TraduoraErrorCode;
+///
+/// assert_eq!(TraduoraErrorCode::parse("AlreadyExists"), TraduoraErrorCode::AlreadyExists);
+/// assert_eq!(TraduoraErrorCode::AlreadyExists.as_str(), "AlreadyExists");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TraduoraErrorCode {
+    /// The request is malformed or has invalid values.
+    BadRequest,
+    /// The access token or the credentials are missing or invalid.
+    Unauthorized,
+    /// A limit of the project plan is reached, e.g. the number of terms.
+    PaymentRequired,
+    /// The user lacks the permission, e.g. because of their project role.
+    Forbidden,
+    /// The requested item does not exist.
+    NotFound,
+    /// An item with the same name or key already exists.
+    AlreadyExists,
+    /// The request body is too large, e.g. an imported file.
+    PayloadTooLarge,
+    /// Too many requests were sent.
+    TooManyRequests,
+    /// The server failed to handle the request.
+    InternalServerError,
+    /// An error code unknown to this crate.
+    Other(String),
+}
+
+impl TraduoraErrorCode {
+    /// Parses an error code as returned in the `code` field by Traduora.
+    #[must_use]
+    pub fn parse(code: &str) -> Self {
+        match code {
+            "BadRequest" => Self::BadRequest,
+            "Unauthorized" | "AuthorizationFailure" => Self::Unauthorized,
+            "PaymentRequired" => Self::PaymentRequired,
+            "Forbidden" => Self::Forbidden,
+            "NotFound" => Self::NotFound,
+            "AlreadyExists" => Self::AlreadyExists,
+            "PayloadTooLarge" => Self::PayloadTooLarge,
+            "TooManyRequests" => Self::TooManyRequests,
+            "InternalServerError" => Self::InternalServerError,
+            _ => Self::Other(code.to_owned()),
+        }
+    }
+
+    /// The error code Traduora uses for responses with this status code.
+    #[must_use]
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::BadRequest,
+            StatusCode::UNAUTHORIZED => Self::Unauthorized,
+            StatusCode::PAYMENT_REQUIRED => Self::PaymentRequired,
+            StatusCode::FORBIDDEN => Self::Forbidden,
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::AlreadyExists,
+            StatusCode::PAYLOAD_TOO_LARGE => Self::PayloadTooLarge,
+            StatusCode::TOO_MANY_REQUESTS => Self::TooManyRequests,
+            s if s.is_server_error() => Self::InternalServerError,
+            s => Self::Other(s.as_str().to_owned()),
+        }
+    }
+
+    /// The error code as returned by Traduora.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::BadRequest => "BadRequest",
+            Self::Unauthorized => "Unauthorized",
+            Self::PaymentRequired => "PaymentRequired",
+            Self::Forbidden => "Forbidden",
+            Self::NotFound => "NotFound",
+            Self::AlreadyExists => "AlreadyExists",
+            Self::PayloadTooLarge => "PayloadTooLarge",
+            Self::TooManyRequests => "TooManyRequests",
+            Self::InternalServerError => "InternalServerError",
+            Self::Other(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for TraduoraErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The request that caused an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Path of the endpoint relative to the API root, e.g. `projects/{id}/terms`.
+    pub endpoint: String,
+    /// Status code of the response.
+    pub status: StatusCode,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} returned {}",
+            self.method, self.endpoint, self.status
+        )
+    }
+}
+
+/// Appends the request to an error message if it is known.
+struct InRequest<'a>(&'a Option<RequestContext>);
+
+impl fmt::Display for InRequest<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(request) => write!(f, " ({})", request),
+            None => Ok(()),
+        }
+    }
+}
+
 /// Errors which may occur when using API endpoints.
 #[derive(Debug, Error)]
 #[non_exhaustive]
@@ -44,46 +175,117 @@ where
         source: BodyError,
     },
     /// JSON deserialization from Traduora failed.
-    #[error("could not parse JSON response: {}", source)]
+    #[error("could not parse JSON response: {}{}", source, InRequest(request))]
     Json {
         /// The source of the error.
-        #[from]
         source: serde_json::Error,
+        /// The request whose response failed to parse.
+        request: Option<RequestContext>,
     },
     /// Traduora returned an error message.
-    #[error("traduora server error: {}", msg)]
+    #[error("traduora server error ({}): {}{}", code, msg, InRequest(request))]
     Traduora {
+        /// The error code from Traduora, or derived from the status code
+        /// if the response has none.
+        code: TraduoraErrorCode,
         /// The error message from Traduora.
         msg: String,
+        /// The failed request.
+        request: Option<RequestContext>,
     },
     /// Traduora returned an error without JSON information.
-    #[error("traduora internal server error {}", status)]
+    #[error("traduora internal server error {}{}", status, InRequest(request))]
     TraduoraService {
         /// The status code for the return.
         status: http::StatusCode,
         /// The error data from Traduora.
         data: Vec<u8>,
+        /// The failed request.
+        request: Option<RequestContext>,
     },
     /// Traduora returned an error object.
-    #[error("traduora server error: {:?}", obj)]
+    #[error("traduora server error: {:?}{}", obj, InRequest(request))]
     TraduoraObject {
         /// The error object from Traduora.
         obj: serde_json::Value,
+        /// The failed request.
+        request: Option<RequestContext>,
     },
     /// Traduora returned an HTTP error with JSON we did not recognize.
-    #[error("traduora server error: {:?}", obj)]
+    #[error("traduora server error: {:?}{}", obj, InRequest(request))]
     TraduoraUnrecognized {
         /// The full object from Traduora.
         obj: serde_json::Value,
+        /// The failed request.
+        request: Option<RequestContext>,
     },
     /// Failed to parse an expected data type from JSON.
-    #[error("could not parse {} data from JSON: {}", typename, source)]
+    #[error(
+        "could not parse {} data from JSON: {}{}",
+        typename,
+        source,
+        InRequest(request)
+    )]
     DataType {
         /// The source of the error.
         source: serde_json::Error,
         /// The name of the type that could not be deserialized.
         typename: &'static str,
+        /// The request whose response failed to parse.
+        request: Option<RequestContext>,
     },
+    /// The project role of the client does not allow the request, so it was not sent.
+    #[error(
+        "{} requires the role {:?} but the client has the role {:?} in project {}",
+        endpoint,
+        required,
+        role,
+        project
+    )]
+    MissingRole {
+        /// Path of the endpoint.
+        endpoint: String,
+        /// The project of the endpoint.
+        project: ProjectId,
+        /// The least privileged role that may access the endpoint.
+        required: Role,
+        /// The role of the client.
+        role: Role,
+    },
+    /// The response body is larger than the configured limit, so it was not read.
+    #[error("the response is larger than the limit of {} bytes", limit)]
+    ResponseTooLarge {
+        /// The maximum size of a response body in bytes.
+        limit: usize,
+    },
+    /// The request did not finish before its deadline and was aborted.
+    #[error("the request did not finish before its deadline")]
+    Timeout,
+    /// A retried request failed and looking up whether an earlier attempt
+    /// succeeded failed as well, see [`RetryCreate`](crate::api::RetryCreate).
+    ///
+    /// The error is classified like the error of the request, except that it
+    /// is only transient if the lookup error is.
+    #[error("{}; looking up an earlier attempt failed: {}", error, lookup)]
+    LookupFailed {
+        /// The error of the request.
+        error: Box<ApiError<E>>,
+        /// The error of the lookup.
+        #[source]
+        lookup: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl<E> From<serde_json::Error> for ApiError<E>
+where
+    E: Error + Send + Sync + 'static,
+{
+    fn from(source: serde_json::Error) -> Self {
+        Self::Json {
+            source,
+            request: None,
+        }
+    }
 }
 
 impl<E> ApiError<E>
@@ -95,26 +297,217 @@ where
         Self::Client { source }
     }
 
+    pub(crate) fn lookup_failed(error: Self, lookup: Self) -> Self {
+        Self::LookupFailed {
+            error: Box::new(error),
+            lookup: Box::new(lookup),
+        }
+    }
+
     pub(crate) fn server_error(status: http::StatusCode, body: &bytes::Bytes) -> Self {
         Self::TraduoraService {
             status,
             data: body.into_iter().copied().collect(),
+            request: None,
         }
     }
 
-    pub(crate) fn from_traduora(value: serde_json::Value) -> Self {
+    /// The request that caused the error, if it is known.
+    #[must_use]
+    pub const fn request(&self) -> Option<&RequestContext> {
+        match self {
+            Self::Json { request, .. }
+            | Self::Traduora { request, .. }
+            | Self::TraduoraService { request, .. }
+            | Self::TraduoraObject { request, .. }
+            | Self::TraduoraUnrecognized { request, .. }
+            | Self::DataType { request, .. } => request.as_ref(),
+            Self::LookupFailed { error, .. } => error.request(),
+            _ => None,
+        }
+    }
+
+    /// The error may go away when repeating the request, e.g. a connection
+    /// failure, an overloaded server or a rate limit.
+    ///
+    /// Errors of the client are only transient if they are caused by the
+    /// network, i.e. a failed connection or a timeout. Errors building the
+    /// request or authenticating it would just happen again.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Client { source } => return is_network_error(source),
+            Self::Timeout => return true,
+            Self::LookupFailed { lookup, .. } => {
+                return lookup
+                    .downcast_ref::<Self>()
+                    .map_or(false, Self::is_transient)
+            }
+            _ => {}
+        }
+        matches!(
+            self.code(),
+            Some(TraduoraErrorCode::InternalServerError | TraduoraErrorCode::TooManyRequests)
+        ) || self.status().map_or(false, |status| {
+            status.is_server_error()
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || status == StatusCode::REQUEST_TIMEOUT
+        })
+    }
+
+    /// The access token or the credentials are missing, invalid or expired.
+    #[must_use]
+    pub fn is_unauthorized(&self) -> bool {
+        self.is(&TraduoraErrorCode::Unauthorized, StatusCode::UNAUTHORIZED)
+    }
+
+    /// The client may not access the requested item, either according to
+    /// the server or because its project role does not allow it.
+    #[must_use]
+    pub fn is_forbidden(&self) -> bool {
+        matches!(self, Self::MissingRole { .. })
+            || matches!(self, Self::LookupFailed { error, .. } if error.is_forbidden())
+            || self.is(&TraduoraErrorCode::Forbidden, StatusCode::FORBIDDEN)
+    }
+
+    /// The request was aborted at its deadline, see [`AsyncQuery::query_with_deadline`](crate::AsyncQuery::query_with_deadline).
+    #[must_use]
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout)
+    }
+
+    /// The requested item does not exist.
+    #[must_use]
+    pub fn is_not_found(&self) -> bool {
+        self.is(&TraduoraErrorCode::NotFound, StatusCode::NOT_FOUND)
+    }
+
+    /// An item with the same name or key already exists.
+    #[must_use]
+    pub fn is_conflict(&self) -> bool {
+        self.is(&TraduoraErrorCode::AlreadyExists, StatusCode::CONFLICT)
+    }
+
+    fn is(&self, code: &TraduoraErrorCode, status: StatusCode) -> bool {
+        self.code() == Some(code) || self.status() == Some(status)
+    }
+
+    fn code(&self) -> Option<&TraduoraErrorCode> {
+        match self {
+            Self::Traduora { code, .. } => Some(code),
+            Self::LookupFailed { error, .. } => error.code(),
+            _ => None,
+        }
+    }
+
+    fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::TraduoraService { status, .. } => Some(*status),
+            // errors from parsing a successful response have no error status
+            Self::Json { .. } | Self::DataType { .. } => None,
+            Self::LookupFailed { error, .. } => error.status(),
+            _ => self.request().map(|r| r.status),
+        }
+    }
+
+    /// Converts the error of the client, e.g. to store clients with different error types together.
+    pub fn map_client<F, T>(self, f: F) -> ApiError<T>
+    where
+        F: FnOnce(E) -> T,
+        T: Error + Send + Sync + 'static,
+    {
+        match self {
+            Self::Client { source } => ApiError::Client { source: f(source) },
+            Self::UrlParse { source } => ApiError::UrlParse { source },
+            Self::Body { source } => ApiError::Body { source },
+            Self::Json { source, request } => ApiError::Json { source, request },
+            Self::Traduora { code, msg, request } => ApiError::Traduora { code, msg, request },
+            Self::TraduoraService {
+                status,
+                data,
+                request,
+            } => ApiError::TraduoraService {
+                status,
+                data,
+                request,
+            },
+            Self::TraduoraObject { obj, request } => ApiError::TraduoraObject { obj, request },
+            Self::TraduoraUnrecognized { obj, request } => {
+                ApiError::TraduoraUnrecognized { obj, request }
+            }
+            Self::DataType {
+                source,
+                typename,
+                request,
+            } => ApiError::DataType {
+                source,
+                typename,
+                request,
+            },
+            Self::MissingRole {
+                endpoint,
+                project,
+                required,
+                role,
+            } => ApiError::MissingRole {
+                endpoint,
+                project,
+                required,
+                role,
+            },
+            Self::ResponseTooLarge { limit } => ApiError::ResponseTooLarge { limit },
+            Self::Timeout => ApiError::Timeout,
+            Self::LookupFailed { error, lookup } => ApiError::LookupFailed {
+                error: Box::new(error.map_client(f)),
+                lookup,
+            },
+        }
+    }
+
+    /// Attaches the request to errors about its response.
+    pub(crate) fn with_request(mut self, context: RequestContext) -> Self {
+        match &mut self {
+            Self::Json { request, .. }
+            | Self::Traduora { request, .. }
+            | Self::TraduoraService { request, .. }
+            | Self::TraduoraObject { request, .. }
+            | Self::TraduoraUnrecognized { request, .. }
+            | Self::DataType { request, .. } => *request = Some(context),
+            _ => {}
+        }
+        self
+    }
+
+    pub(crate) fn from_traduora(status: StatusCode, value: serde_json::Value) -> Self {
         let error_value = value
             .pointer("/message")
             .or_else(|| value.pointer("/error"));
 
         match error_value {
-            Some(error_value) => match error_value.as_str() {
-                Some(msg) => Self::Traduora { msg: msg.into() },
-                None => Self::TraduoraObject {
+            Some(error_value) => match (error_value.as_str(), error_value.pointer("/code")) {
+                (Some(msg), _) => Self::Traduora {
+                    code: TraduoraErrorCode::from_status(status),
+                    msg: msg.into(),
+                    request: None,
+                },
+                (None, Some(serde_json::Value::String(code))) => Self::Traduora {
+                    code: TraduoraErrorCode::parse(code),
+                    msg: error_value
+                        .pointer("/message")
+                        .and_then(serde_json::Value::as_str)
+                        .unwrap_or_default()
+                        .into(),
+                    request: None,
+                },
+                (None, _) => Self::TraduoraObject {
                     obj: error_value.clone(),
+                    request: None,
                 },
             },
-            None => Self::TraduoraUnrecognized { obj: value },
+            None => Self::TraduoraUnrecognized {
+                obj: value,
+                request: None,
+            },
         }
     }
 
@@ -122,16 +515,49 @@ where
         Self::DataType {
             source,
             typename: any::type_name::<T>(),
+            request: None,
+        }
+    }
+}
+
+/// Whether the error or one of its sources is a failed connection or a timeout.
+fn is_network_error(error: &(dyn Error + 'static)) -> bool {
+    let mut error = Some(error);
+    while let Some(e) = error {
+        if let Some(e) = e.downcast_ref::<reqwest::Error>() {
+            if e.is_connect() || e.is_timeout() {
+                return true;
+            }
+        }
+        if let Some(e) = e.downcast_ref::<io::Error>() {
+            if matches!(
+                e.kind(),
+                io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::NotConnected
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::UnexpectedEof
+            ) {
+                return true;
+            }
         }
+        error = e.source();
     }
+    false
 }
 
 #[cfg(test)]
 mod tests {
+    use std::io;
+
+    use http::{Method, StatusCode};
     use serde_json::json;
     use thiserror::Error;
 
-    use crate::ApiError;
+    use super::RequestContext;
+    use crate::{ApiError, TraduoraErrorCode};
 
     #[derive(Debug, Error)]
     #[error("my error")]
@@ -143,8 +569,9 @@ mod tests {
             "error": "error contents",
         });
 
-        let err: ApiError<MyError> = ApiError::from_traduora(obj);
-        if let ApiError::Traduora { msg } = err {
+        let err: ApiError<MyError> = ApiError::from_traduora(StatusCode::BAD_REQUEST, obj);
+        if let ApiError::Traduora { code, msg, .. } = err {
+            assert_eq!(code, TraduoraErrorCode::BadRequest);
             assert_eq!(msg, "error contents");
         } else {
             panic!("unexpected error: {}", err);
@@ -157,8 +584,9 @@ mod tests {
             "message": "error contents",
         });
 
-        let err: ApiError<MyError> = ApiError::from_traduora(obj);
-        if let ApiError::Traduora { msg } = err {
+        let err: ApiError<MyError> = ApiError::from_traduora(StatusCode::BAD_REQUEST, obj);
+        if let ApiError::Traduora { code, msg, .. } = err {
+            assert_eq!(code, TraduoraErrorCode::BadRequest);
             assert_eq!(msg, "error contents");
         } else {
             panic!("unexpected error: {}", err);
@@ -174,8 +602,8 @@ mod tests {
             "message": err_obj,
         });
 
-        let err: ApiError<MyError> = ApiError::from_traduora(obj);
-        if let ApiError::TraduoraObject { obj } = err {
+        let err: ApiError<MyError> = ApiError::from_traduora(StatusCode::BAD_REQUEST, obj);
+        if let ApiError::TraduoraObject { obj, .. } = err {
             assert_eq!(obj, err_obj);
         } else {
             panic!("unexpected error: {}", err);
@@ -188,11 +616,89 @@ mod tests {
             "some_weird_key": "an even weirder value",
         });
 
-        let err: ApiError<MyError> = ApiError::from_traduora(err_obj.clone());
-        if let ApiError::TraduoraUnrecognized { obj } = err {
+        let err: ApiError<MyError> =
+            ApiError::from_traduora(StatusCode::BAD_REQUEST, err_obj.clone());
+        if let ApiError::TraduoraUnrecognized { obj, .. } = err {
             assert_eq!(obj, err_obj);
         } else {
             panic!("unexpected error: {}", err);
         }
     }
+
+    #[test]
+    fn traduora_error_code() {
+        let obj = json!({
+            "error": { "code": "AlreadyExists", "message": "Term already exists" },
+        });
+
+        let err: ApiError<MyError> = ApiError::from_traduora(StatusCode::CONFLICT, obj);
+        if let ApiError::Traduora { code, msg, .. } = err {
+            assert_eq!(code, TraduoraErrorCode::AlreadyExists);
+            assert_eq!(msg, "Term already exists");
+        } else {
+            panic!("unexpected error: {}", err);
+        }
+    }
+
+    #[test]
+    fn traduora_error_request_context() {
+        let obj = json!({
+            "error": { "code": "NotFound", "message": "Project not found" },
+        });
+        let request = RequestContext {
+            method: Method::GET,
+            endpoint: "projects/1".into(),
+            status: StatusCode::NOT_FOUND,
+        };
+
+        let err: ApiError<MyError> =
+            ApiError::from_traduora(StatusCode::NOT_FOUND, obj).with_request(request.clone());
+        assert_eq!(err.request(), Some(&request));
+        assert_eq!(
+            err.to_string(),
+            "traduora server error (NotFound): Project not found (GET projects/1 returned 404 Not Found)"
+        );
+    }
+
+    #[test]
+    fn traduora_error_classification() {
+        let conflict: ApiError<MyError> = ApiError::from_traduora(
+            StatusCode::CONFLICT,
+            json!({ "error": { "code": "AlreadyExists", "message": "Term already exists" } }),
+        );
+        assert!(conflict.is_conflict());
+        assert!(!conflict.is_transient() && !conflict.is_not_found());
+
+        let unavailable: ApiError<MyError> =
+            ApiError::server_error(StatusCode::SERVICE_UNAVAILABLE, &"".into());
+        assert!(unavailable.is_transient());
+
+        let unauthorized: ApiError<MyError> = ApiError::from_traduora(
+            StatusCode::UNAUTHORIZED,
+            json!({ "message": "Unauthorized" }),
+        );
+        assert!(unauthorized.is_unauthorized());
+    }
+
+    #[test]
+    fn only_network_errors_of_the_client_are_transient() {
+        #[derive(Debug, Error)]
+        #[error("communication failed")]
+        struct Communication(#[source] io::Error);
+
+        let reset: ApiError<Communication> =
+            ApiError::client(Communication(io::ErrorKind::ConnectionReset.into()));
+        assert!(reset.is_transient());
+
+        let invalid: ApiError<Communication> =
+            ApiError::client(Communication(io::ErrorKind::InvalidData.into()));
+        assert!(!invalid.is_transient());
+
+        let request = http::Request::builder()
+            .header("bad\nheader", "value")
+            .body(())
+            .unwrap_err();
+        let build: ApiError<http::Error> = ApiError::client(request);
+        assert!(!build.is_transient());
+    }
 }
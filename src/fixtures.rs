@@ -0,0 +1,118 @@
+//! JSON documents in the format of the Traduora API for tests.
+//!
+//! The functions return [`serde_json::Value`]s that can be adjusted before
+//! they are served by a mock server or a [`MockTraduora`](crate::mock::MockTraduora).
+//! Single objects and lists are wrapped in the `data` envelope of the API with [`data`].
+//! All timestamps are [`TIMESTAMP`].
+//!
+//! This module is only available with the `test-support` feature.
+//!
+//! # Examples
+//! ```
+//! use traduora::{api::terms::Term, fixtures};
+//!
+//! let mut term = fixtures::term("0fa39756-65db-423c-a6d9-534b62fe9ead", "hello.world");
+//! term["labels"] = serde_json::json!([fixtures::label("c16d0fc3", "ui", "#D81159")]);
+//! let body = fixtures::data(serde_json::json!([term]));
+//!
+//! let terms: Vec<Term> = serde_json::from_value(body["data"].clone()).unwrap();
+//! assert_eq!(terms[0].value, "hello.world");
+//! assert_eq!(terms[0].labels[0].value, "ui");
+//! ```
+
+use serde_json::{json, Value};
+
+/// Creation and modification time of all fixtures.
+pub const TIMESTAMP: &str = "2022-01-01T00:00:00.000Z";
+
+/// Wraps the value in the `data` envelope of the API.
+#[must_use]
+pub fn data(value: Value) -> Value {
+    json!({ "data": value })
+}
+
+/// The error response of the API, e.g. `("NotFound", "Project not found")`.
+#[must_use]
+pub fn error(code: &str, message: &str) -> Value {
+    json!({ "error": { "code": code, "message": message } })
+}
+
+/// Creation and modification time of an object.
+#[must_use]
+pub fn dates() -> Value {
+    json!({ "created": TIMESTAMP, "modified": TIMESTAMP })
+}
+
+/// The response of the token endpoint.
+#[must_use]
+pub fn access_token(token: &str) -> Value {
+    json!({ "access_token": token, "token_type": "bearer", "expires_in": "86400s" })
+}
+
+/// A user without projects.
+#[must_use]
+pub fn user(id: &str, name: &str, email: &str) -> Value {
+    json!({ "id": id, "name": name, "email": email, "numProjectsCreated": 0 })
+}
+
+/// An empty project administered by the user.
+#[must_use]
+pub fn project(id: &str, name: &str) -> Value {
+    json!({
+        "id": id,
+        "name": name,
+        "description": "",
+        "termsCount": 0,
+        "localesCount": 0,
+        "date": dates(),
+        "role": "admin",
+    })
+}
+
+/// A locale like `("de_DE", "German", "Germany")`.
+#[must_use]
+pub fn locale(code: &str, language: &str, region: &str) -> Value {
+    json!({ "code": code, "language": language, "region": region })
+}
+
+/// A locale of a project. Language and region are left empty.
+#[must_use]
+pub fn project_locale(id: &str, code: &str) -> Value {
+    json!({ "id": id, "locale": locale(code, "", ""), "date": dates() })
+}
+
+/// A label.
+#[must_use]
+pub fn label(id: &str, value: &str, color: &str) -> Value {
+    json!({ "id": id, "value": value, "color": color })
+}
+
+/// A term without labels.
+#[must_use]
+pub fn term(id: &str, value: &str) -> Value {
+    json!({ "id": id, "value": value, "labels": [], "date": dates() })
+}
+
+/// A translation without labels.
+#[must_use]
+pub fn translation(term_id: &str, value: &str) -> Value {
+    json!({ "termId": term_id, "value": value, "labels": [], "date": dates() })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::{projects::Project, translations::ProjectLocale, users::UserInfo};
+
+    #[test]
+    fn fixtures_match_models() {
+        let project: Project = serde_json::from_value(super::project("p", "App")).unwrap();
+        assert_eq!(project.name, "App");
+        assert!(project.extra.is_empty());
+        let locale: ProjectLocale =
+            serde_json::from_value(super::project_locale("l", "de_DE")).unwrap();
+        assert_eq!(locale.locale.code.value(), "de_DE");
+        let user: UserInfo =
+            serde_json::from_value(super::user("u", "Tester", "tester@mail.example")).unwrap();
+        assert_eq!(user.num_projects_created, Some(0));
+    }
+}
@@ -0,0 +1,174 @@
+//! Android `strings.xml` resources as used by Traduora's `androidxml` format.
+//!
+//! Only `<string>` resources are supported. Other resources like `<plurals>`
+//! or `<string-array>` are skipped when parsing. Styling tags like `<b>` are
+//! not preserved; only their text content is kept.
+//!
+//! # Examples
+//! ```
+//! use traduora::formats::android_xml;
+//!
+//! let file = r#"<resources>
+//!     <string name="greeting">Don\'t say \"hello\"\nto @me</string>
+//! </resources>"#;
+//!
+//! let translations = android_xml::parse(file.as_bytes())?;
+//! assert_eq!(translations["greeting"], "Don't say \"hello\"\nto @me");
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use super::xml::{self, escape as escape_xml};
+use super::{FormatError, TermKey};
+
+/// Parses an Android resource file.
+///
+/// The values are unescaped like Android does: backslash escapes are resolved,
+/// double quotes are removed and whitespace outside of double quotes is collapsed.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or is not a valid resource file.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let root = xml::parse(&text)?;
+
+    if root.name != "resources" {
+        return Err(FormatError::Syntax {
+            message: "root element is not <resources>".to_owned(),
+        });
+    }
+
+    root.elements()
+        .filter(|element| element.name == "string")
+        .map(|element| {
+            let name = element
+                .attribute("name")
+                .ok_or_else(|| FormatError::Syntax {
+                    message: "<string> has no name".to_owned(),
+                })?;
+            Ok((name.into(), unescape(&element.text())?))
+        })
+        .collect()
+}
+
+/// Writes an Android resource file with one `<string>` per translation.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    mut writer: W,
+) -> Result<(), FormatError> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+    writeln!(writer, "<resources>")?;
+    for (key, value) in translations {
+        writeln!(
+            writer,
+            r#"  <string name="{}">{}</string>"#,
+            escape_xml(key.value()),
+            escape_xml(&escape(value))
+        )?;
+    }
+    writeln!(writer, "</resources>")?;
+    Ok(())
+}
+
+/// Applies Android's escaping rules.
+///
+/// Values whose whitespace would be collapsed are wrapped in double quotes.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '@' | '?' if i == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    let collapsed = value.starts_with(' ') || value.ends_with(' ') || value.contains("  ");
+    if collapsed {
+        format!("\"{}\"", escaped)
+    } else {
+        escaped
+    }
+}
+
+fn unescape(raw: &str) -> Result<String, FormatError> {
+    let mut value = String::with_capacity(raw.len());
+    let mut quoted = false;
+    let mut chars = raw.trim().chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => quoted = !quoted,
+            '\\' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('t') => value.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(|| FormatError::Syntax {
+                            message: format!("invalid unicode escape \\u{}", hex),
+                        })?;
+                    value.push(c);
+                }
+                Some(c) => value.push(c),
+                None => {}
+            },
+            c if c.is_whitespace() && !quoted => {
+                if !value.ends_with(' ') {
+                    value.push(' ');
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse, write, TermKey};
+
+    #[test]
+    fn round_trip_escapes() {
+        let translations: BTreeMap<_, _> = [
+            ("apostrophe", "Don't <stop> & \"go\"\\"),
+            ("at", "@home? \u{e9}"),
+            ("spaces", "  two  spaces\tand tab\n"),
+        ]
+        .iter()
+        .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+        .collect();
+
+        let mut file = Vec::new();
+        write(&translations, &mut file).unwrap();
+        let written = String::from_utf8(file).unwrap();
+        assert!(written.contains(r#"<string name="at">\@home? é</string>"#));
+
+        assert_eq!(parse(written.as_bytes()).unwrap(), translations);
+    }
+
+    #[test]
+    fn whitespace_is_collapsed_outside_quotes() {
+        let file = "<resources>\n<string name=\"a\">\n  one   two \"  three \"\\u0041</string>\n<plurals name=\"p\"/>\n</resources>";
+        let translations = parse(file.as_bytes()).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations["a"], "one two   three A");
+    }
+}
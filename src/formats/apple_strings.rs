@@ -0,0 +1,220 @@
+//! Apple `.strings` files as used by Traduora's `strings` format.
+//!
+//! # Examples
+//! ```
+//! use traduora::formats::apple_strings;
+//!
+//! let file = r#"
+//! /* Title of the checkout screen */
+//! "checkout.title" = "Kasse";
+//! greeting = "Sag \"Hallo\"\n";
+//! "#;
+//!
+//! let translations = apple_strings::parse(file.as_bytes())?;
+//! assert_eq!(translations["checkout.title"], "Kasse");
+//! assert_eq!(translations["greeting"], "Sag \"Hallo\"\n");
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use super::{FormatError, TermKey};
+
+/// Parses a `.strings` file.
+///
+/// Comments are skipped. Keys may be unquoted if they consist only of
+/// letters, digits, `_`, `.` and `-`.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or is not a valid `.strings` file.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+    let mut translations = BTreeMap::new();
+    while parser.skip_whitespace_and_comments()? {
+        let key = parser.string()?;
+        parser.expect('=')?;
+        let value = parser.string()?;
+        parser.expect(';')?;
+        translations.insert(key.into(), value);
+    }
+    Ok(translations)
+}
+
+/// Writes a `.strings` file with one line per translation.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    mut writer: W,
+) -> Result<(), FormatError> {
+    for (key, value) in translations {
+        writeln!(
+            writer,
+            "\"{}\" = \"{}\";",
+            escape(key.value()),
+            escape(value)
+        )?;
+    }
+    Ok(())
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> FormatError {
+        FormatError::Syntax {
+            message: format!("line {}: {}", self.line, message),
+        }
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    /// Skips to the next token. Returns `false` at the end of the file.
+    fn skip_whitespace_and_comments(&mut self) -> Result<bool, FormatError> {
+        loop {
+            match self.chars.peek() {
+                None => return Ok(false),
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('/') => {
+                    self.next();
+                    match self.next() {
+                        Some('/') => while !matches!(self.next(), Some('\n') | None) {},
+                        Some('*') => {
+                            let mut previous = ' ';
+                            loop {
+                                match self.next() {
+                                    Some('/') if previous == '*' => break,
+                                    Some(c) => previous = c,
+                                    None => return Err(self.error("unterminated comment")),
+                                }
+                            }
+                        }
+                        _ => return Err(self.error("expected a comment")),
+                    }
+                }
+                Some(_) => return Ok(true),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), FormatError> {
+        self.skip_whitespace_and_comments()?;
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error(&format!("expected {}", expected))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, FormatError> {
+        self.skip_whitespace_and_comments()?;
+        if self.chars.peek() != Some(&'"') {
+            let mut unquoted = String::new();
+            while let Some(&c) = self.chars.peek() {
+                if !(c.is_alphanumeric() || matches!(c, '_' | '.' | '-')) {
+                    break;
+                }
+                unquoted.push(c);
+                self.next();
+            }
+            if unquoted.is_empty() {
+                return Err(self.error("expected a string"));
+            }
+            return Ok(unquoted);
+        }
+
+        self.next();
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('U') | Some('u') => {
+                        let hex: String = (0..4).filter_map(|_| self.next()).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| self.error("invalid unicode escape"))?;
+                        value.push(c);
+                    }
+                    Some(c) => value.push(c),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => value.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse, write, TermKey};
+
+    #[test]
+    fn round_trip_escapes() {
+        let translations: BTreeMap<_, _> = [
+            ("a.b", "Tab\there \"quoted\" \\ back"),
+            ("line", "one\ntwo // not a comment"),
+        ]
+        .iter()
+        .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+        .collect();
+
+        let mut file = Vec::new();
+        write(&translations, &mut file).unwrap();
+        assert_eq!(parse(file.as_slice()).unwrap(), translations);
+
+        assert_eq!(
+            parse(
+                r#"// c
+"x" = "\U00e9"; /* trailing */"#
+                    .as_bytes()
+            )
+            .unwrap()["x"],
+            "\u{e9}"
+        );
+        assert!(parse(r#""x" = "y""#.as_bytes()).is_err());
+    }
+}
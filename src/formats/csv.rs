@@ -0,0 +1,151 @@
+//! CSV files as used by Traduora's `csv` format.
+//!
+//! Each row contains the term key and its translation. There is no header row.
+//! Fields are quoted according to RFC 4180 if necessary.
+//!
+//! # Examples
+//! ```
+//! use traduora::formats::csv;
+//!
+//! let file = "greeting,Hallo\r\nfarewell,\"Tschüss, bis \"\"bald\"\"\"\r\n";
+//! let translations = csv::parse(file.as_bytes())?;
+//! assert_eq!(translations["farewell"], "Tschüss, bis \"bald\"");
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use super::{FormatError, TermKey};
+
+/// Parses a CSV file.
+///
+/// Empty lines are skipped. Both `\r\n` and `\n` are accepted as line breaks.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or a row does not have exactly two fields.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut translations = BTreeMap::new();
+    for (line, row) in rows(&text)? {
+        match <[String; 2]>::try_from(row) {
+            Ok([key, value]) => {
+                translations.insert(key.into(), value);
+            }
+            Err(row) if row.len() == 1 && row[0].is_empty() => {}
+            Err(row) => {
+                return Err(FormatError::Syntax {
+                    message: format!("line {}: expected 2 fields but found {}", line, row.len()),
+                })
+            }
+        }
+    }
+    Ok(translations)
+}
+
+/// Writes a CSV file with one row per translation.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    mut writer: W,
+) -> Result<(), FormatError> {
+    for (key, value) in translations {
+        write!(writer, "{},{}\r\n", quote(key.value()), quote(value))?;
+    }
+    Ok(())
+}
+
+fn quote(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\r' | '\n'))
+        || field.starts_with(' ')
+        || field.ends_with(' ')
+    {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Splits the text into rows of fields with the line number of each row.
+fn rows(text: &str) -> Result<Vec<(usize, Vec<String>)>, FormatError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if field.is_empty() => loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        field.push(c);
+                    }
+                    None => {
+                        return Err(FormatError::Syntax {
+                            message: format!("line {}: unterminated quoted field", row_line),
+                        })
+                    }
+                }
+            },
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push((row_line, std::mem::take(&mut row)));
+                line += 1;
+                row_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push((row_line, row));
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse, write, TermKey};
+
+    #[test]
+    fn round_trip_quotes() {
+        let translations: BTreeMap<_, _> = [
+            ("a.b", "plain"),
+            ("comma", "one, two"),
+            ("multi", "line\r\nbreak \"quoted\""),
+            ("spaces", " padded "),
+        ]
+        .iter()
+        .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+        .collect();
+
+        let mut file = Vec::new();
+        write(&translations, &mut file).unwrap();
+        assert_eq!(parse(file.as_slice()).unwrap(), translations);
+
+        let err = parse("a,b\n\nc,d,e\n".as_bytes()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid translation file: line 3: expected 2 fields but found 3"
+        );
+    }
+}
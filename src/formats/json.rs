@@ -0,0 +1,239 @@
+use std::collections::BTreeMap;
+use std::io;
+
+use serde_json::{Map, Value};
+
+use super::{FormatError, TermKey, NESTING_SEPARATOR};
+
+/// Parses a file in Traduora's `jsonflat` format.
+///
+/// The file must contain a single object mapping term keys to translations.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not valid JSON
+/// or contains a value that is not a string.
+///
+/// # Examples
+/// ```
+/// use traduora::formats::parse_json_flat;
+///
+/// let file = r#"{ "checkout.title": "Kasse", "greeting": "Hallo" }"#;
+/// let translations = parse_json_flat(file.as_bytes())?;
+/// assert_eq!(translations["checkout.title"], "Kasse");
+/// # Ok::<(), traduora::formats::FormatError>(())
+/// ```
+pub fn parse_json_flat<R: io::Read>(reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let object = parse_object(reader)?;
+    object
+        .into_iter()
+        .map(|(key, value)| match value {
+            Value::String(s) => Ok((key.into(), s)),
+            _ => Err(FormatError::InvalidValue { key: key.into() }),
+        })
+        .collect()
+}
+
+/// Parses a file in Traduora's `jsonnested` format.
+///
+/// Nested objects are flattened by joining the keys with
+/// [`NESTING_SEPARATOR`]. Arrays are flattened using the index
+/// of each element as key, like Traduora does.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not valid JSON
+/// or contains a value that is neither a string, an object nor an array.
+///
+/// # Examples
+/// ```
+/// use traduora::formats::parse_json_nested;
+///
+/// let file = r#"{ "checkout": { "title": "Kasse" }, "greeting": "Hallo" }"#;
+/// let translations = parse_json_nested(file.as_bytes())?;
+/// assert_eq!(translations["checkout.title"], "Kasse");
+/// # Ok::<(), traduora::formats::FormatError>(())
+/// ```
+pub fn parse_json_nested<R: io::Read>(reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let object = parse_object(reader)?;
+    let mut translations = BTreeMap::new();
+    for (key, value) in object {
+        flatten(key.into(), value, &mut translations)?;
+    }
+    Ok(translations)
+}
+
+/// Writes translations in Traduora's `jsonflat` format.
+///
+/// The output is indented with two spaces like the files exported by Traduora.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn to_json_flat<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    writer: W,
+) -> Result<(), FormatError> {
+    serde_json::to_writer_pretty(writer, translations)?;
+    Ok(())
+}
+
+/// Writes translations in Traduora's `jsonnested` format.
+///
+/// Term keys are split at [`NESTING_SEPARATOR`] into nested objects.
+/// The output is indented with two spaces like the files exported by Traduora.
+///
+/// # Errors
+/// This function returns an error if writing fails or if a term key is
+/// a prefix of another term key, e.g. `checkout` and `checkout.title`,
+/// because such terms cannot be represented as nested objects.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+/// use traduora::formats::{to_json_nested, TermKey};
+///
+/// let mut translations = BTreeMap::new();
+/// translations.insert(TermKey::new("checkout.title"), "Kasse".to_owned());
+///
+/// let mut file = Vec::new();
+/// to_json_nested(&translations, &mut file)?;
+/// assert_eq!(
+///     String::from_utf8(file).unwrap(),
+///     "{\n  \"checkout\": {\n    \"title\": \"Kasse\"\n  }\n}"
+/// );
+/// # Ok::<(), traduora::formats::FormatError>(())
+/// ```
+pub fn to_json_nested<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    writer: W,
+) -> Result<(), FormatError> {
+    let mut root = Map::new();
+    for (key, translation) in translations {
+        insert_nested(&mut root, key, translation)?;
+    }
+    serde_json::to_writer_pretty(writer, &root)?;
+    Ok(())
+}
+
+fn parse_object<R: io::Read>(reader: R) -> Result<Map<String, Value>, FormatError> {
+    match serde_json::from_reader(reader)? {
+        Value::Object(object) => Ok(object),
+        _ => Err(FormatError::Syntax {
+            message: "expected a JSON object".to_owned(),
+        }),
+    }
+}
+
+fn flatten(
+    key: TermKey,
+    value: Value,
+    translations: &mut BTreeMap<TermKey, String>,
+) -> Result<(), FormatError> {
+    match value {
+        Value::String(s) => {
+            translations.insert(key, s);
+        }
+        Value::Object(object) => {
+            for (child, value) in object {
+                flatten(key.child(&child), value, translations)?;
+            }
+        }
+        Value::Array(array) => {
+            for (index, value) in array.into_iter().enumerate() {
+                flatten(key.child(&index.to_string()), value, translations)?;
+            }
+        }
+        _ => return Err(FormatError::InvalidValue { key }),
+    }
+    Ok(())
+}
+
+fn insert_nested(
+    root: &mut Map<String, Value>,
+    key: &TermKey,
+    translation: &str,
+) -> Result<(), FormatError> {
+    let conflict = |prefix: &str| FormatError::Conflict {
+        key: prefix.into(),
+        nested: key.clone(),
+    };
+
+    // Keys are sorted, so a term is always inserted before the terms nested below it.
+    let mut segments = key.segments().peekable();
+    let mut object = root;
+    let mut prefix_len = 0;
+    while let Some(segment) = segments.next() {
+        prefix_len += segment.len();
+        if segments.peek().is_none() {
+            object.insert(segment.to_owned(), Value::String(translation.to_owned()));
+            return Ok(());
+        }
+
+        let child = object
+            .entry(segment.to_owned())
+            .or_insert_with(|| Value::Object(Map::new()));
+        object = match child {
+            Value::Object(child) => child,
+            _ => return Err(conflict(&key.value()[..prefix_len])),
+        };
+        prefix_len += NESTING_SEPARATOR.len_utf8();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse_json_flat, parse_json_nested, to_json_flat, to_json_nested, TermKey};
+    use crate::formats::FormatError;
+
+    fn translations() -> BTreeMap<TermKey, String> {
+        [
+            ("checkout.button.pay", "Bezahlen"),
+            ("checkout.title", "Kasse"),
+            ("greeting", "Hallo \"Welt\""),
+        ]
+        .iter()
+        .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+        .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut flat = Vec::new();
+        to_json_flat(&translations(), &mut flat).unwrap();
+        assert_eq!(parse_json_flat(flat.as_slice()).unwrap(), translations());
+
+        let mut nested = Vec::new();
+        to_json_nested(&translations(), &mut nested).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&nested).unwrap();
+        assert_eq!(value["checkout"]["button"]["pay"], "Bezahlen");
+        assert_eq!(
+            parse_json_nested(nested.as_slice()).unwrap(),
+            translations()
+        );
+    }
+
+    #[test]
+    fn nested_arrays_use_indices() {
+        let file = r#"{ "steps": ["Eins", { "label": "Zwei" }] }"#;
+        let translations = parse_json_nested(file.as_bytes()).unwrap();
+        assert_eq!(translations["steps.0"], "Eins");
+        assert_eq!(translations["steps.1.label"], "Zwei");
+        assert!(matches!(
+            parse_json_flat(file.as_bytes()),
+            Err(FormatError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn prefix_terms_conflict() {
+        let mut translations = translations();
+        translations.insert("checkout".into(), "Kasse".to_owned());
+
+        let err = to_json_nested(&translations, Vec::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            FormatError::Conflict { key, nested } if key.value() == "checkout" && nested.value() == "checkout.button.pay"
+        ));
+    }
+}
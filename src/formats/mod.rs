@@ -0,0 +1,93 @@
+//! Reading and writing translation files.
+//!
+//! The functions in this module convert between the file formats that
+//! Traduora uses for imports and exports and a simple map from term keys
+//! to translations of a single locale. Files produced here can be uploaded
+//! to Traduora and files exported by Traduora can be parsed here.
+//!
+//! Parsers read from any [`io::Read`] and serializers write
+//! to any [`io::Write`], so files, buffers and HTTP bodies
+//! can be used alike.
+//!
+//! | Traduora format | Module                                         |
+//! |-----------------|------------------------------------------------|
+//! | `jsonflat`      | [`parse_json_flat`], [`to_json_flat`]          |
+//! | `jsonnested`    | [`parse_json_nested`], [`to_json_nested`]      |
+//! | `po`            | [`po`]                                         |
+//! | `xliff12`       | [`xliff`]                                      |
+//! | `androidxml`    | [`android_xml`]                                |
+//! | `strings`       | [`apple_strings`]                              |
+//! | `csv`           | [`csv`]                                        |
+//! | `properties`    | [`properties`]                                 |
+//!
+//! [`write`] writes any of these formats, and [`write_stdout`] is meant for
+//! piping the output into other tools, e.g. `jq`.
+
+pub mod android_xml;
+pub mod apple_strings;
+pub mod csv;
+mod json;
+mod output;
+pub mod po;
+pub mod properties;
+pub mod xliff;
+mod xml;
+
+use std::io;
+
+use thiserror::Error;
+
+pub use json::{parse_json_flat, parse_json_nested, to_json_flat, to_json_nested};
+pub use output::{write, write_stdout, write_to_pipe};
+
+use crate::api::exports::ExportFormat;
+pub use crate::api::TermKey;
+
+/// Separator that Traduora uses to build term keys from nested structures.
+pub const NESTING_SEPARATOR: char = '.';
+
+/// The error which is returned when reading or writing a translation file fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum FormatError {
+    /// Reading or writing the file failed.
+    #[error("failed to access translation file: {}", source)]
+    Io {
+        /// Inner error.
+        #[from]
+        source: io::Error,
+    },
+    /// The file is not valid JSON.
+    #[error("invalid JSON translation file: {}", source)]
+    Json {
+        /// Inner error.
+        #[from]
+        source: serde_json::Error,
+    },
+    /// The file does not have the structure required by the format.
+    #[error("invalid translation file: {}", message)]
+    Syntax {
+        /// Description of the problem.
+        message: String,
+    },
+    /// The translation of a term is not a string.
+    #[error("translation of term `{}` is not a string", key)]
+    InvalidValue {
+        /// Key of the term.
+        key: TermKey,
+    },
+    /// A term key is a prefix of another term key so both cannot be nested.
+    #[error("term `{}` conflicts with nested term `{}`", key, nested)]
+    Conflict {
+        /// Key of the term that has a translation.
+        key: TermKey,
+        /// Key of the term that would have to be nested below it.
+        nested: TermKey,
+    },
+    /// The format cannot be written by this crate.
+    #[error("the format {} is not supported", format.as_str())]
+    Unsupported {
+        /// The format.
+        format: ExportFormat,
+    },
+}
@@ -0,0 +1,209 @@
+//! Writing translations in any format, e.g. to standard output for shell pipelines.
+//!
+//! # Examples
+//! ```no_run
+//! use std::collections::BTreeMap;
+//! use traduora::{api::exports::ExportFormat, formats::{self, TermKey}};
+//!
+//! let mut translations = BTreeMap::new();
+//! translations.insert(TermKey::new("greeting"), "Hallo".to_owned());
+//!
+//! // e.g. `my-tool | jq '.greeting'`
+//! formats::write_stdout(ExportFormat::JsonFlat, "de", &translations)?;
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use super::{
+    android_xml, apple_strings, csv, po, properties, to_json_flat, to_json_nested, xliff,
+    FormatError, TermKey,
+};
+use crate::api::exports::ExportFormat;
+
+/// Writes the translations of a locale in the given format.
+///
+/// `language` is the locale code written into the headers of PO and XLIFF
+/// files; it is used as source and target language of XLIFF files like in
+/// Traduora's exports. The other formats do not contain it.
+///
+/// # Errors
+/// This function returns an error if writing fails, the translations cannot
+/// be represented in the format or the format is not supported, i.e. YAML.
+///
+/// # Examples
+/// ```
+/// use std::collections::BTreeMap;
+/// use traduora::{api::exports::ExportFormat, formats::{self, TermKey}};
+///
+/// let mut translations = BTreeMap::new();
+/// translations.insert(TermKey::new("greeting"), "Hallo".to_owned());
+///
+/// let mut file = Vec::new();
+/// formats::write(ExportFormat::Properties, "de", &translations, &mut file)?;
+/// assert_eq!(file, b"greeting=Hallo\n");
+/// # Ok::<(), traduora::formats::FormatError>(())
+/// ```
+pub fn write<W: io::Write>(
+    format: ExportFormat,
+    language: &str,
+    translations: &BTreeMap<TermKey, String>,
+    writer: W,
+) -> Result<(), FormatError> {
+    match format {
+        ExportFormat::AndroidXml => android_xml::write(translations, writer),
+        ExportFormat::Csv => csv::write(translations, writer),
+        ExportFormat::Xliff12 => {
+            let file =
+                xliff::XliffFile::from_translations(language, translations, language, translations);
+            xliff::write(&file, writer)
+        }
+        ExportFormat::JsonFlat => to_json_flat(translations, writer),
+        ExportFormat::JsonNested => to_json_nested(translations, writer),
+        ExportFormat::Properties => properties::write(translations, writer),
+        ExportFormat::Gettext => po::write(
+            &po::PoFile::from_translations(language, translations),
+            writer,
+        ),
+        ExportFormat::Strings => apple_strings::write(translations, writer),
+        ExportFormat::YamlFlat | ExportFormat::YamlNested => {
+            Err(FormatError::Unsupported { format })
+        }
+    }
+}
+
+/// Writes the translations to a pipe or another stream that may be closed early.
+///
+/// The output is buffered and flushed at the end. If the reader closes the
+/// stream, e.g. `head` after it has read enough lines, the error is ignored
+/// because the reader did not want the rest of the output.
+///
+/// # Errors
+/// This function returns an error like [`write`] except for a closed stream.
+pub fn write_to_pipe<W: io::Write>(
+    format: ExportFormat,
+    language: &str,
+    translations: &BTreeMap<TermKey, String>,
+    writer: W,
+) -> Result<(), FormatError> {
+    let mut writer = io::BufWriter::new(writer);
+    let result = write(format, language, translations, &mut writer)
+        .and_then(|()| writer.flush().map_err(FormatError::from));
+    let error = match result {
+        // the JSON writers wrap errors of the writer
+        Err(FormatError::Json { source }) if source.is_io() => io::Error::from(source),
+        Err(FormatError::Io { source }) => source,
+        result => return result,
+    };
+    match error.kind() {
+        io::ErrorKind::BrokenPipe => Ok(()),
+        _ => Err(error.into()),
+    }
+}
+
+/// Writes the translations to standard output, see [`write_to_pipe`].
+///
+/// # Errors
+/// This function returns an error like [`write`] except for a closed standard output.
+pub fn write_stdout(
+    format: ExportFormat,
+    language: &str,
+    translations: &BTreeMap<TermKey, String>,
+) -> Result<(), FormatError> {
+    let stdout = io::stdout();
+    let lock = stdout.lock();
+    write_to_pipe(format, language, translations, lock)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::io;
+
+    use super::{write, write_to_pipe, TermKey};
+    use crate::{
+        api::exports::ExportFormat,
+        formats::{self, FormatError},
+    };
+
+    /// Accepts `capacity` bytes and then behaves like a pipe whose reader exited.
+    struct ClosedPipe {
+        capacity: usize,
+    }
+
+    impl io::Write for ClosedPipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.capacity == 0 {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            let written = buf.len().min(self.capacity);
+            self.capacity -= written;
+            Ok(written)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn translations() -> BTreeMap<TermKey, String> {
+        [("checkout.title", "Kasse"), ("greeting", "Hallo, \"Welt\"")]
+            .iter()
+            .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn every_supported_format_round_trips() {
+        let translations = translations();
+        for format in &ExportFormat::ALL {
+            let mut file = Vec::new();
+            let result = write(*format, "de", &translations, &mut file);
+            let parsed = match format {
+                ExportFormat::AndroidXml => formats::android_xml::parse(file.as_slice()),
+                ExportFormat::Csv => formats::csv::parse(file.as_slice()),
+                ExportFormat::Xliff12 => {
+                    formats::xliff::parse(file.as_slice()).map(|f| f.translations())
+                }
+                ExportFormat::JsonFlat => formats::parse_json_flat(file.as_slice()),
+                ExportFormat::JsonNested => formats::parse_json_nested(file.as_slice()),
+                ExportFormat::Properties => formats::properties::parse(file.as_slice()),
+                ExportFormat::Gettext => {
+                    formats::po::parse(file.as_slice()).map(|f| f.translations())
+                }
+                ExportFormat::Strings => formats::apple_strings::parse(file.as_slice()),
+                ExportFormat::YamlFlat | ExportFormat::YamlNested => {
+                    assert!(matches!(result, Err(FormatError::Unsupported { .. })));
+                    continue;
+                }
+            };
+            result.unwrap();
+            assert_eq!(parsed.unwrap(), translations, "{}", format.as_str());
+        }
+    }
+
+    #[test]
+    fn closed_pipe_is_not_an_error() {
+        let translations = translations();
+        for format in &[ExportFormat::JsonFlat, ExportFormat::Csv] {
+            let pipe = ClosedPipe { capacity: 4 };
+            write_to_pipe(*format, "de", &translations, pipe).unwrap();
+        }
+        // larger than the buffer, so the JSON writer fails before the flush
+        let many: BTreeMap<_, _> = (0..1000)
+            .map(|i| {
+                (
+                    TermKey::new(format!("term.{}", i)),
+                    "translation".to_owned(),
+                )
+            })
+            .collect();
+        let pipe = ClosedPipe { capacity: 4 };
+        write_to_pipe(ExportFormat::JsonNested, "de", &many, pipe).unwrap();
+
+        let error =
+            write_to_pipe(ExportFormat::YamlFlat, "de", &translations, io::sink()).unwrap_err();
+        assert_eq!(error.to_string(), "the format yamlflat is not supported");
+    }
+}
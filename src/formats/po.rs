@@ -0,0 +1,437 @@
+//! Gettext PO files as used by Traduora's `po` format.
+//!
+//! In contrast to the other formats, a PO file carries more than a mapping
+//! from term keys to translations: headers, comments and plural forms.
+//! [`parse`] and [`write`](fn@write) therefore work with a [`PoFile`], which can be
+//! converted from and to the map used by the rest of the [`formats`](super) module.
+//!
+//! # Examples
+//! ```
+//! use traduora::formats::po;
+//!
+//! let file = r#"
+//! msgid ""
+//! msgstr ""
+//! "Language: de_DE\n"
+//!
+//! #. shown on the checkout page
+//! msgid "checkout.title"
+//! msgstr "Kasse"
+//! "#;
+//!
+//! let po = po::parse(file.as_bytes())?;
+//! assert_eq!(po.language(), Some("de_DE"));
+//! assert_eq!(po.entries[0].extracted_comments, ["shown on the checkout page"]);
+//! assert_eq!(po.translations()["checkout.title"], "Kasse");
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use super::{FormatError, TermKey};
+
+const LANGUAGE: &str = "Language";
+
+/// Contents of a PO file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoFile {
+    /// Headers of the file, e.g. `Language` or `Plural-Forms`, in file order.
+    pub headers: Vec<(String, String)>,
+    /// Translation entries in file order. The header entry is not included.
+    pub entries: Vec<PoEntry>,
+}
+
+/// A single message of a PO file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoEntry {
+    /// Disambiguating context of the message (`msgctxt`).
+    pub context: Option<String>,
+    /// The untranslated message (`msgid`), i.e. the term key.
+    pub id: TermKey,
+    /// Plural form of the untranslated message (`msgid_plural`).
+    pub id_plural: Option<String>,
+    /// The translations (`msgstr`). Contains one element per plural form
+    /// if [`id_plural`](Self::id_plural) is set and a single element otherwise.
+    pub translations: Vec<String>,
+    /// Comments of the translator (`# ...`).
+    pub comments: Vec<String>,
+    /// Comments extracted from the source code (`#. ...`).
+    pub extracted_comments: Vec<String>,
+    /// Source code references (`#: ...`).
+    pub references: Vec<String>,
+    /// Flags like `fuzzy` (`#, ...`).
+    pub flags: Vec<String>,
+}
+
+impl PoEntry {
+    /// Creates an entry with a single translation.
+    pub fn new(id: impl Into<TermKey>, translation: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            translations: vec![translation.into()],
+            ..Self::default()
+        }
+    }
+
+    /// The first translation, i.e. the singular form. Empty if there is none.
+    #[must_use]
+    pub fn translation(&self) -> &str {
+        self.translations.first().map_or("", String::as_str)
+    }
+}
+
+impl PoFile {
+    /// Creates a file with the headers Traduora uses and one entry per translation.
+    #[must_use]
+    pub fn from_translations(language: &str, translations: &BTreeMap<TermKey, String>) -> Self {
+        Self {
+            headers: vec![
+                (
+                    "Content-Type".to_owned(),
+                    "text/plain; charset=utf-8".to_owned(),
+                ),
+                (LANGUAGE.to_owned(), language.to_owned()),
+            ],
+            entries: translations
+                .iter()
+                .map(|(key, translation)| PoEntry::new(key.clone(), translation.clone()))
+                .collect(),
+        }
+    }
+
+    /// Value of the `Language` header.
+    #[must_use]
+    pub fn language(&self) -> Option<&str> {
+        self.header(LANGUAGE)
+    }
+
+    /// Value of a header. Header names are compared case-insensitively.
+    #[must_use]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The singular translation of each entry without context, like Traduora imports them.
+    ///
+    /// Entries with a [`context`](PoEntry::context) are skipped.
+    #[must_use]
+    pub fn translations(&self) -> BTreeMap<TermKey, String> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.context.is_none())
+            .map(|entry| (entry.id.clone(), entry.translation().to_owned()))
+            .collect()
+    }
+}
+
+/// Parses a PO file.
+///
+/// Obsolete entries (`#~`) and previous strings (`#|`) are ignored.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or is not a valid PO file.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<PoFile, FormatError> {
+    let mut text = String::new();
+    reader
+        .read_to_string(&mut text)
+        .map_err(|source| match source.kind() {
+            io::ErrorKind::InvalidData => syntax(0, "file is not valid UTF-8"),
+            _ => source.into(),
+        })?;
+
+    let mut parser = Parser::default();
+    for (index, line) in text.lines().enumerate() {
+        parser.line(index + 1, line.trim())?;
+    }
+    parser.finish_entry();
+    Ok(parser.file)
+}
+
+/// Writes a PO file.
+///
+/// Strings containing line breaks are split into one line per line break
+/// like gettext tools do.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(file: &PoFile, mut writer: W) -> Result<(), FormatError> {
+    let header: String = file
+        .headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\n", name, value))
+        .collect();
+    write_string(&mut writer, "msgid", "")?;
+    write_string(&mut writer, "msgstr", &header)?;
+
+    for entry in &file.entries {
+        writeln!(writer)?;
+        for comment in &entry.comments {
+            writeln!(writer, "# {}", comment)?;
+        }
+        for comment in &entry.extracted_comments {
+            writeln!(writer, "#. {}", comment)?;
+        }
+        if !entry.references.is_empty() {
+            writeln!(writer, "#: {}", entry.references.join(" "))?;
+        }
+        if !entry.flags.is_empty() {
+            writeln!(writer, "#, {}", entry.flags.join(", "))?;
+        }
+        if let Some(context) = &entry.context {
+            write_string(&mut writer, "msgctxt", context)?;
+        }
+        write_string(&mut writer, "msgid", entry.id.value())?;
+        match &entry.id_plural {
+            Some(plural) => {
+                write_string(&mut writer, "msgid_plural", plural)?;
+                for (index, translation) in entry.translations.iter().enumerate() {
+                    write_string(&mut writer, &format!("msgstr[{}]", index), translation)?;
+                }
+            }
+            None => write_string(&mut writer, "msgstr", entry.translation())?,
+        }
+    }
+    Ok(())
+}
+
+fn write_string<W: io::Write>(writer: &mut W, keyword: &str, value: &str) -> io::Result<()> {
+    let lines: Vec<_> = value.split_inclusive('\n').collect();
+    if lines.len() > 1 || value.ends_with('\n') {
+        writeln!(writer, "{} \"\"", keyword)?;
+        for line in lines {
+            writeln!(writer, "\"{}\"", escape(line))?;
+        }
+        Ok(())
+    } else {
+        writeln!(writer, "{} \"{}\"", keyword, escape(value))
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(line: usize, quoted: &str) -> Result<String, FormatError> {
+    let inner = quoted
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .filter(|_| quoted.len() >= 2)
+        .ok_or_else(|| syntax(line, "expected a quoted string"))?;
+
+    let mut value = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        value.push(match chars.next() {
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('a') => '\u{7}',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('v') => '\u{b}',
+            _ => return Err(syntax(line, "invalid escape sequence")),
+        });
+    }
+    Ok(value)
+}
+
+fn syntax(line: usize, message: &str) -> FormatError {
+    FormatError::Syntax {
+        message: format!("line {}: {}", line, message),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Translation(usize),
+}
+
+#[derive(Debug, Default)]
+struct Parser {
+    file: PoFile,
+    entry: PoEntry,
+    has_id: bool,
+    has_translation: bool,
+    field: Option<Field>,
+}
+
+impl Parser {
+    fn line(&mut self, number: usize, line: &str) -> Result<(), FormatError> {
+        if line.is_empty() {
+            self.finish_entry();
+            return Ok(());
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if self.has_translation {
+                self.finish_entry();
+            }
+            let (kind, text) = comment.split_at(comment.chars().next().map_or(0, char::len_utf8));
+            let text = text.trim();
+            match kind {
+                "." => self.entry.extracted_comments.push(text.to_owned()),
+                ":" => self
+                    .entry
+                    .references
+                    .extend(text.split_whitespace().map(str::to_owned)),
+                "," => self
+                    .entry
+                    .flags
+                    .extend(text.split(',').map(str::trim).map(str::to_owned)),
+                "~" | "|" => {}
+                _ => self.entry.comments.push(comment.trim().to_owned()),
+            }
+            self.field = None;
+            return Ok(());
+        }
+
+        if line.starts_with('"') {
+            let value = unescape(number, line)?;
+            let target = self
+                .field
+                .map(|field| self.target(field))
+                .ok_or_else(|| syntax(number, "string without keyword"))?;
+            target.push_str(&value);
+            return Ok(());
+        }
+
+        let (keyword, rest) = line
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| syntax(number, "expected a keyword and a string"))?;
+        let field = match keyword {
+            "msgctxt" => Field::Context,
+            "msgid" => Field::Id,
+            "msgid_plural" => Field::IdPlural,
+            "msgstr" => Field::Translation(0),
+            _ => keyword
+                .strip_prefix("msgstr[")
+                .and_then(|s| s.strip_suffix(']'))
+                .and_then(|s| s.parse().ok())
+                .map(Field::Translation)
+                .ok_or_else(|| syntax(number, &format!("unknown keyword {}", keyword)))?,
+        };
+
+        match field {
+            Field::Context | Field::Id if self.has_translation => self.finish_entry(),
+            Field::Context | Field::Id => {}
+            Field::IdPlural | Field::Translation(_) if !self.has_id => {
+                return Err(syntax(number, &format!("{} without msgid", keyword)));
+            }
+            Field::IdPlural => {}
+            Field::Translation(_) => self.has_translation = true,
+        }
+        if let Field::Id = field {
+            self.has_id = true;
+        }
+
+        let value = unescape(number, rest.trim())?;
+        *self.target(field) = value;
+        self.field = Some(field);
+        Ok(())
+    }
+
+    fn target(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::Context => self.entry.context.get_or_insert_with(String::new),
+            Field::Id => self.entry.id.value_mut(),
+            Field::IdPlural => self.entry.id_plural.get_or_insert_with(String::new),
+            Field::Translation(index) => {
+                if self.entry.translations.len() <= index {
+                    self.entry.translations.resize(index + 1, String::new());
+                }
+                &mut self.entry.translations[index]
+            }
+        }
+    }
+
+    fn finish_entry(&mut self) {
+        let entry = std::mem::take(&mut self.entry);
+        let has_id = std::mem::take(&mut self.has_id);
+        self.has_translation = false;
+        self.field = None;
+        if !has_id {
+            return;
+        }
+
+        if entry.id.value().is_empty() && entry.context.is_none() && self.file.headers.is_empty() {
+            self.file.headers = entry
+                .translation()
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+                .collect();
+        } else {
+            self.file.entries.push(entry);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse, write, PoEntry, PoFile};
+
+    #[test]
+    fn round_trip_keeps_comments_and_plurals() {
+        let mut file = PoFile::from_translations("de_DE", &BTreeMap::new());
+        file.entries.push(PoEntry {
+            id: "cart.items".into(),
+            id_plural: Some("cart.items.plural".to_owned()),
+            translations: vec!["{n} Artikel".to_owned(), "{n} Artikel".to_owned()],
+            comments: vec!["checked by legal".to_owned()],
+            references: vec!["src/cart.rs:12".to_owned(), "src/cart.rs:40".to_owned()],
+            flags: vec!["fuzzy".to_owned()],
+            ..PoEntry::default()
+        });
+        file.entries.push(PoEntry {
+            context: Some("menu".to_owned()),
+            ..PoEntry::new("greeting", "Hallo \"Welt\"\nZeile\tzwei")
+        });
+
+        let mut buffer = Vec::new();
+        write(&file, &mut buffer).unwrap();
+        let parsed = parse(buffer.as_slice()).unwrap();
+
+        assert_eq!(parsed, file);
+        assert_eq!(parsed.language(), Some("de_DE"));
+        let translations = parsed.translations();
+        assert_eq!(translations.len(), 1);
+        assert_eq!(translations["cart.items"], "{n} Artikel");
+    }
+
+    #[test]
+    fn multi_line_strings_are_concatenated() {
+        let file =
+            "msgid \"\"\n\"a.\"\n\"b\"\nmsgstr \"\"\n\"eins \"\n\"zwei\"\n\n#~ msgid \"old\"\n";
+        let parsed = parse(file.as_bytes()).unwrap();
+        assert!(parsed.headers.is_empty());
+        assert_eq!(parsed.entries, [PoEntry::new("a.b", "eins zwei")]);
+        assert!(parse("msgstr \"x\"".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+//! Java properties files as used by Traduora's `properties` format.
+//!
+//! Files are read and written as UTF-8. `\uXXXX` escapes are resolved when
+//! parsing, so files written in the traditional ISO 8859-1 style with escaped
+//! non-ASCII characters can be read as well.
+//!
+//! # Examples
+//! ```
+//! use traduora::formats::properties;
+//!
+//! let file = "# comment\ncheckout.title = Kasse\ngreeting: Hallo \\\n    Welt\n";
+//! let translations = properties::parse(file.as_bytes())?;
+//! assert_eq!(translations["checkout.title"], "Kasse");
+//! assert_eq!(translations["greeting"], "Hallo Welt");
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use super::{FormatError, TermKey};
+
+/// Parses a properties file.
+///
+/// Comments, line continuations and all separators (`=`, `:` and whitespace)
+/// are supported.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or contains an invalid escape sequence.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<BTreeMap<TermKey, String>, FormatError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    let mut translations = BTreeMap::new();
+    for (number, line) in logical_lines(&text) {
+        let (key, value) = split(&line);
+        let unescape = |raw| {
+            unescape(raw).ok_or_else(|| FormatError::Syntax {
+                message: format!("line {}: invalid escape sequence", number),
+            })
+        };
+        translations.insert(unescape(key)?.into(), unescape(value)?);
+    }
+    Ok(translations)
+}
+
+/// Writes a properties file with one `key=value` line per translation.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(
+    translations: &BTreeMap<TermKey, String>,
+    mut writer: W,
+) -> Result<(), FormatError> {
+    for (key, value) in translations {
+        writeln!(
+            writer,
+            "{}={}",
+            escape(key.value(), true),
+            escape(value, false)
+        )?;
+    }
+    Ok(())
+}
+
+fn escape(value: &str, is_key: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for (i, c) in value.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{c}' => escaped.push_str("\\f"),
+            ' ' if is_key || i == 0 => escaped.push_str("\\ "),
+            '=' | ':' if is_key => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '#' | '!' if i == 0 => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Joins continued lines and skips comments and blank lines.
+fn logical_lines(text: &str) -> Vec<(usize, String)> {
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_start();
+        let (number, mut logical) = match current.take() {
+            Some(continued) => continued,
+            None if line.is_empty() || line.starts_with('#') || line.starts_with('!') => continue,
+            None => (index + 1, String::new()),
+        };
+
+        let trailing_backslashes = line.chars().rev().take_while(|&c| c == '\\').count();
+        if trailing_backslashes % 2 == 1 {
+            logical.push_str(&line[..line.len() - 1]);
+            current = Some((number, logical));
+        } else {
+            logical.push_str(line);
+            lines.push((number, logical));
+        }
+    }
+    lines.extend(current);
+    lines
+}
+
+/// Splits a logical line into the raw key and value.
+fn split(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | ':' => return (&line[..i], line[i + 1..].trim_start()),
+            c if c.is_whitespace() => {
+                let rest = line[i..].trim_start();
+                let rest = rest
+                    .strip_prefix(|c| c == '=' || c == ':')
+                    .map_or(rest, str::trim_start);
+                return (&line[..i], rest);
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+fn unescape(raw: &str) -> Option<String> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some('f') => value.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                value.push(
+                    u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)?,
+                );
+            }
+            Some(c) => value.push(c),
+            None => {}
+        }
+    }
+    Some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{parse, write, TermKey};
+
+    #[test]
+    fn round_trip_escapes() {
+        let translations: BTreeMap<_, _> = [
+            ("key with spaces", " leading space"),
+            ("a=b:c", "#not a comment\nnext line\\"),
+            ("unicode", "\u{e9}t\u{e9}"),
+        ]
+        .iter()
+        .map(|(k, v)| (TermKey::new(*k), (*v).to_owned()))
+        .collect();
+
+        let mut file = Vec::new();
+        write(&translations, &mut file).unwrap();
+        assert_eq!(parse(file.as_slice()).unwrap(), translations);
+
+        let parsed = parse("! c\n  a b\nc\\u00e9\nempty=\n".as_bytes()).unwrap();
+        assert_eq!(parsed["a"], "b");
+        assert_eq!(parsed["c\u{e9}"], "");
+        assert_eq!(parsed["empty"], "");
+    }
+}
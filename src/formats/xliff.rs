@@ -0,0 +1,269 @@
+//! XLIFF 1.2 files as used by Traduora's `xliff12` format.
+//!
+//! An XLIFF file contains the translations of a source and a target language
+//! side by side. [`parse`] and [`write`](fn@write) work with an [`XliffFile`], which can
+//! be converted from and to the maps used by the rest of the
+//! [`formats`](super) module.
+//!
+//! Inline elements like `<g>` or `<x/>` inside of `<source>` and `<target>`
+//! are not preserved; only their text content is kept.
+//!
+//! # Examples
+//! ```
+//! use std::collections::BTreeMap;
+//! use traduora::formats::{xliff::{self, XliffFile}, TermKey};
+//!
+//! let mut en = BTreeMap::new();
+//! en.insert(TermKey::new("greeting"), "Hello".to_owned());
+//! let mut de = BTreeMap::new();
+//! de.insert(TermKey::new("greeting"), "Hallo".to_owned());
+//!
+//! let mut file = Vec::new();
+//! xliff::write(&XliffFile::from_translations("en", &en, "de", &de), &mut file)?;
+//!
+//! let parsed = xliff::parse(file.as_slice())?;
+//! assert_eq!(parsed.source_language, "en");
+//! assert_eq!(parsed.target_language.as_deref(), Some("de"));
+//! assert_eq!(parsed.translations(), de);
+//! # Ok::<(), traduora::formats::FormatError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+
+use super::xml::{self, escape, Element};
+use super::{FormatError, TermKey};
+
+const NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:1.2";
+/// Value of the `original` attribute of files written by [`write`].
+const ORIGINAL: &str = "translations";
+
+/// Contents of an XLIFF file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XliffFile {
+    /// Language of the source texts (`source-language`).
+    pub source_language: String,
+    /// Language of the translations (`target-language`).
+    pub target_language: Option<String>,
+    /// Translation units in file order.
+    pub units: Vec<TransUnit>,
+}
+
+/// A single term of an XLIFF file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransUnit {
+    /// Identifier of the unit, i.e. the term key.
+    pub id: TermKey,
+    /// Text in the source language.
+    pub source: String,
+    /// Text in the target language. `None` if the term is not translated.
+    pub target: Option<String>,
+    /// Note for translators.
+    pub note: Option<String>,
+}
+
+impl XliffFile {
+    /// Creates a file with one unit per term of `source` or `target`.
+    ///
+    /// Terms missing in `target` get no target text.
+    #[must_use]
+    pub fn from_translations(
+        source_language: &str,
+        source: &BTreeMap<TermKey, String>,
+        target_language: &str,
+        target: &BTreeMap<TermKey, String>,
+    ) -> Self {
+        let mut keys: Vec<_> = source.keys().chain(target.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        Self {
+            source_language: source_language.to_owned(),
+            target_language: Some(target_language.to_owned()),
+            units: keys
+                .into_iter()
+                .map(|key| TransUnit {
+                    id: key.clone(),
+                    source: source.get(key).cloned().unwrap_or_default(),
+                    target: target.get(key).cloned(),
+                    note: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// The source text of each unit.
+    #[must_use]
+    pub fn source_translations(&self) -> BTreeMap<TermKey, String> {
+        self.units
+            .iter()
+            .map(|unit| (unit.id.clone(), unit.source.clone()))
+            .collect()
+    }
+
+    /// The target text of each unit that has one.
+    #[must_use]
+    pub fn translations(&self) -> BTreeMap<TermKey, String> {
+        self.units
+            .iter()
+            .filter_map(|unit| Some((unit.id.clone(), unit.target.clone()?)))
+            .collect()
+    }
+}
+
+/// Parses an XLIFF 1.2 file.
+///
+/// The units of all `<file>` elements are combined. The languages are taken
+/// from the first `<file>` element.
+///
+/// # Errors
+/// This function returns an error if the file cannot be read, is not UTF-8
+/// or is not a valid XLIFF 1.2 file.
+pub fn parse<R: io::Read>(mut reader: R) -> Result<XliffFile, FormatError> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let root = xml::parse(&text)?;
+
+    if root.name != "xliff" {
+        return Err(syntax("root element is not <xliff>"));
+    }
+    match root.attribute("version") {
+        Some(version) if !version.starts_with("1.") => {
+            return Err(syntax(&format!("unsupported XLIFF version {}", version)));
+        }
+        _ => {}
+    }
+
+    let mut file = XliffFile::default();
+    for (index, element) in root.elements().filter(|e| e.name == "file").enumerate() {
+        if index == 0 {
+            file.source_language = element
+                .attribute("source-language")
+                .ok_or_else(|| syntax("<file> has no source-language"))?
+                .to_owned();
+            file.target_language = element.attribute("target-language").map(str::to_owned);
+        }
+        if let Some(body) = element.element("body") {
+            collect_units(body, &mut file.units)?;
+        }
+    }
+    Ok(file)
+}
+
+/// Writes an XLIFF 1.2 file with a single `<file>` element.
+///
+/// # Errors
+/// This function returns an error if writing fails.
+pub fn write<W: io::Write>(file: &XliffFile, mut writer: W) -> Result<(), FormatError> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<xliff xmlns="{}" version="1.2">"#, NAMESPACE)?;
+    write!(
+        writer,
+        r#"  <file original="{}" datatype="plaintext" source-language="{}""#,
+        ORIGINAL,
+        escape(&file.source_language)
+    )?;
+    if let Some(target_language) = &file.target_language {
+        write!(writer, r#" target-language="{}""#, escape(target_language))?;
+    }
+    writeln!(writer, ">")?;
+    writeln!(writer, "    <body>")?;
+    for unit in &file.units {
+        writeln!(
+            writer,
+            r#"      <trans-unit id="{}">"#,
+            escape(unit.id.value())
+        )?;
+        writeln!(writer, "        <source>{}</source>", escape(&unit.source))?;
+        if let Some(target) = &unit.target {
+            writeln!(writer, "        <target>{}</target>", escape(target))?;
+        }
+        if let Some(note) = &unit.note {
+            writeln!(writer, "        <note>{}</note>", escape(note))?;
+        }
+        writeln!(writer, "      </trans-unit>")?;
+    }
+    writeln!(writer, "    </body>")?;
+    writeln!(writer, "  </file>")?;
+    writeln!(writer, "</xliff>")?;
+    Ok(())
+}
+
+/// Collects the units of a `<body>` or `<group>` element.
+fn collect_units(parent: &Element, units: &mut Vec<TransUnit>) -> Result<(), FormatError> {
+    for element in parent.elements() {
+        match element.name.as_str() {
+            "group" => collect_units(element, units)?,
+            "trans-unit" => units.push(TransUnit {
+                id: element
+                    .attribute("id")
+                    .ok_or_else(|| syntax("<trans-unit> has no id"))?
+                    .into(),
+                source: element
+                    .element("source")
+                    .ok_or_else(|| syntax("<trans-unit> has no <source>"))?
+                    .text(),
+                target: element.element("target").map(Element::text),
+                note: element.element("note").map(Element::text),
+            }),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn syntax(message: &str) -> FormatError {
+    FormatError::Syntax {
+        message: message.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, write, TransUnit, XliffFile};
+
+    #[test]
+    fn round_trip_with_groups_and_notes() {
+        let file = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:1.2" version="1.2">
+  <file original="app" datatype="plaintext" source-language="en" target-language="de">
+    <body>
+      <group id="checkout">
+        <trans-unit id="checkout.pay">
+          <source>Pay <g id="1">now</g> &amp; save</source>
+          <target>Jetzt <g id="1">zahlen</g></target>
+          <note>Button</note>
+        </trans-unit>
+      </group>
+      <trans-unit id="greeting"><source>Hello</source></trans-unit>
+    </body>
+  </file>
+</xliff>"#;
+
+        let parsed = parse(file.as_bytes()).unwrap();
+        let expected = XliffFile {
+            source_language: "en".to_owned(),
+            target_language: Some("de".to_owned()),
+            units: vec![
+                TransUnit {
+                    id: "checkout.pay".into(),
+                    source: "Pay now & save".to_owned(),
+                    target: Some("Jetzt zahlen".to_owned()),
+                    note: Some("Button".to_owned()),
+                },
+                TransUnit {
+                    id: "greeting".into(),
+                    source: "Hello".to_owned(),
+                    target: None,
+                    note: None,
+                },
+            ],
+        };
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.translations().len(), 1);
+
+        let mut written = Vec::new();
+        write(&parsed, &mut written).unwrap();
+        assert_eq!(parse(written.as_slice()).unwrap(), expected);
+    }
+}
@@ -0,0 +1,288 @@
+//! Minimal XML support for the XML based formats.
+//!
+//! Only the subset of XML used by translation files is supported:
+//! elements, attributes, text, CDATA sections, comments, processing instructions
+//! and the predefined and numeric character references. Namespaces are not
+//! resolved, so element names are compared including their prefix.
+
+use super::FormatError;
+
+/// A node of an XML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Node {
+    Element(Element),
+    Text(String),
+}
+
+/// An XML element with its attributes and children.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Element {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<Node>,
+}
+
+impl Element {
+    /// Value of an attribute.
+    pub(crate) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The child elements.
+    pub(crate) fn elements(&self) -> impl Iterator<Item = &Element> {
+        self.children.iter().filter_map(|node| match node {
+            Node::Element(element) => Some(element),
+            Node::Text(_) => None,
+        })
+    }
+
+    /// The first child element with the given name.
+    pub(crate) fn element(&self, name: &str) -> Option<&Element> {
+        self.elements().find(|element| element.name == name)
+    }
+
+    /// The concatenated text of this element and all its descendants.
+    pub(crate) fn text(&self) -> String {
+        let mut text = String::new();
+        self.collect_text(&mut text);
+        text
+    }
+
+    fn collect_text(&self, text: &mut String) {
+        for node in &self.children {
+            match node {
+                Node::Element(element) => element.collect_text(text),
+                Node::Text(t) => text.push_str(t),
+            }
+        }
+    }
+}
+
+/// Parses a document and returns its root element.
+pub(crate) fn parse(input: &str) -> Result<Element, FormatError> {
+    let mut parser = Parser { input, pos: 0 };
+    parser.skip_misc()?;
+    if !parser.rest().starts_with('<') {
+        return Err(parser.error("expected root element"));
+    }
+    let root = parser.element()?;
+    parser.skip_misc()?;
+    if parser.pos < input.len() {
+        return Err(parser.error("unexpected content after root element"));
+    }
+    Ok(root)
+}
+
+/// Escapes text for element content and attribute values.
+pub(crate) fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> FormatError {
+        let line = self.input[..self.pos].matches('\n').count() + 1;
+        FormatError::Syntax {
+            message: format!("line {}: {}", line, message),
+        }
+    }
+
+    /// Skips the part of `rest` until and including `end`.
+    fn skip_past(&mut self, end: &str) -> Result<&'a str, FormatError> {
+        let index = self
+            .rest()
+            .find(end)
+            .ok_or_else(|| self.error(&format!("missing {}", end)))?;
+        let skipped = &self.rest()[..index];
+        self.pos += index + end.len();
+        Ok(skipped)
+    }
+
+    /// Skips whitespace, comments, processing instructions and the doctype.
+    fn skip_misc(&mut self) -> Result<(), FormatError> {
+        loop {
+            let trimmed = self.rest().trim_start();
+            self.pos = self.input.len() - trimmed.len();
+            if trimmed.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if trimmed.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if trimmed.starts_with("<!DOCTYPE") {
+                self.skip_past(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str, FormatError> {
+        let len = self
+            .rest()
+            .find(|c: char| c.is_whitespace() || matches!(c, '>' | '/' | '='))
+            .unwrap_or(self.rest().len());
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+        let name = &self.rest()[..len];
+        self.pos += len;
+        Ok(name)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let trimmed = self.rest().trim_start();
+        self.pos = self.input.len() - trimmed.len();
+    }
+
+    fn element(&mut self) -> Result<Element, FormatError> {
+        self.pos += 1; // <
+        let mut element = Element {
+            name: self.name()?.to_owned(),
+            ..Element::default()
+        };
+
+        loop {
+            self.skip_whitespace();
+            if self.rest().starts_with("/>") {
+                self.pos += 2;
+                return Ok(element);
+            }
+            if self.rest().starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let name = self.name()?.to_owned();
+            self.skip_whitespace();
+            if !self.rest().starts_with('=') {
+                return Err(self.error("expected = after attribute name"));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.rest().chars().next() {
+                Some(q @ '"') | Some(q @ '\'') => q,
+                _ => return Err(self.error("expected quoted attribute value")),
+            };
+            self.pos += 1;
+            let raw = self.skip_past(&quote.to_string())?;
+            let value = self.unescape(raw)?;
+            element.attributes.push((name, value));
+        }
+
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != element.name {
+                    return Err(self.error(&format!(
+                        "expected </{}> but found </{}>",
+                        element.name, name
+                    )));
+                }
+                self.skip_whitespace();
+                self.skip_past(">")?;
+                return Ok(element);
+            } else if rest.starts_with("<!--") || rest.starts_with("<?") {
+                let end = if rest.starts_with("<?") { "?>" } else { "-->" };
+                self.skip_past(end)?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += "<![CDATA[".len();
+                let text = self.skip_past("]]>")?;
+                push_text(&mut element, text.to_owned());
+            } else if rest.starts_with('<') {
+                let child = self.element()?;
+                element.children.push(Node::Element(child));
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("missing </{}>", element.name)));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                let text = self.unescape(&rest[..len])?;
+                push_text(&mut element, text);
+            }
+        }
+    }
+
+    fn unescape(&self, raw: &str) -> Result<String, FormatError> {
+        let mut value = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(start) = rest.find('&') {
+            value.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let end = rest
+                .find(';')
+                .ok_or_else(|| self.error("unterminated character reference"))?;
+            let reference = &rest[1..end];
+            let c = match reference {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => reference
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| reference.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            value.push(c.ok_or_else(|| {
+                self.error(&format!("unknown character reference &{};", reference))
+            })?);
+            rest = &rest[end + 1..];
+        }
+        value.push_str(rest);
+        Ok(value)
+    }
+}
+
+fn push_text(element: &mut Element, text: String) {
+    if let Some(Node::Text(previous)) = element.children.last_mut() {
+        previous.push_str(&text);
+    } else {
+        element.children.push(Node::Text(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape, parse, Node};
+
+    #[test]
+    fn parses_elements_text_and_references() {
+        let root = parse(
+            "<?xml version=\"1.0\"?>\n<!-- c -->\n<a x='1 &amp; 2'><b/>t&lt;&#x41;&#66;<![CDATA[<c>]]><c y=\"\">z</c></a>\n",
+        )
+        .unwrap();
+
+        assert_eq!(root.name, "a");
+        assert_eq!(root.attribute("x"), Some("1 & 2"));
+        assert_eq!(root.elements().count(), 2);
+        assert_eq!(root.children[1], Node::Text("t<AB<c>".to_owned()));
+        assert_eq!(root.element("c").unwrap().text(), "z");
+        assert_eq!(root.text(), "t<AB<c>z");
+
+        assert!(parse("<a><b></a>").is_err());
+        assert_eq!(escape("<\"&\">"), "&lt;&quot;&amp;&quot;&gt;");
+    }
+}
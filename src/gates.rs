@@ -0,0 +1,283 @@
+//! Pass/fail decisions for CI pipelines.
+//!
+//! A gate evaluates a report against a threshold and returns a [`Verdict`]
+//! with the reasons for a failure. Several verdicts are collected in
+//! [`GateResults`], which provides the exit code for the process.
+//!
+//! # Examples
+//! ```
+//! use traduora::{gates::{self, GateResults}, sync::SyncPlanner};
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! # use traduora::sync::TranslationSnapshot;
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! # let client = Traduora::with_auth("localhost:8080", login)?;
+//! # let local = TranslationSnapshot::new();
+//! let plan = SyncPlanner::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+//!     .plan(&client, &local)?;
+//!
+//! let results = GateResults::new().check("up to date", gates::no_drift(&plan));
+//! print!("{}", results);
+//! # let exit = |_| {};
+//! exit(results.exit_code());
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    api::{locales::LocaleCode, BulkResult},
+    diagnostics::SmokeTestReport,
+    ops::Promotion,
+    reports::CompletionReport,
+    sync::SyncPlan,
+    validate::LengthReport,
+};
+
+/// Exit code of a process whose gates all passed.
+pub const EXIT_PASSED: i32 = 0;
+/// Exit code of a process with at least one failed gate.
+pub const EXIT_FAILED: i32 = 1;
+
+/// The outcome of a single gate.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "status", content = "reasons", rename_all = "snake_case")]
+pub enum Verdict {
+    /// The report meets the threshold.
+    Passed,
+    /// The report does not meet the threshold for the given reasons.
+    Failed(Vec<String>),
+}
+
+impl Verdict {
+    /// Fails with the given reasons or passes if there are none.
+    pub fn from_reasons<I>(reasons: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: fmt::Display,
+    {
+        let reasons: Vec<_> = reasons.into_iter().map(|r| r.to_string()).collect();
+        if reasons.is_empty() {
+            Self::Passed
+        } else {
+            Self::Failed(reasons)
+        }
+    }
+
+    /// Whether the gate passed.
+    #[must_use]
+    pub const fn is_passed(&self) -> bool {
+        matches!(self, Self::Passed)
+    }
+
+    /// The reasons why the gate failed. Empty if it passed.
+    #[must_use]
+    pub fn reasons(&self) -> &[String] {
+        match self {
+            Self::Passed => &[],
+            Self::Failed(reasons) => reasons,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Passed => write!(f, "passed"),
+            Self::Failed(reasons) => write!(f, "failed: {}", reasons.join("; ")),
+        }
+    }
+}
+
+/// Named verdicts of all gates that were checked.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GateResults {
+    /// The verdicts in the order they were checked.
+    pub gates: Vec<(String, Verdict)>,
+}
+
+impl GateResults {
+    /// Creates an empty list of results.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { gates: Vec::new() }
+    }
+
+    /// Adds the verdict of a gate.
+    #[must_use]
+    pub fn check(mut self, name: impl Into<String>, verdict: Verdict) -> Self {
+        self.gates.push((name.into(), verdict));
+        self
+    }
+
+    /// Whether all gates passed.
+    #[must_use]
+    pub fn is_passed(&self) -> bool {
+        self.gates.iter().all(|(_, verdict)| verdict.is_passed())
+    }
+
+    /// The gates that failed.
+    pub fn failed(&self) -> impl Iterator<Item = (&str, &Verdict)> {
+        self.gates
+            .iter()
+            .filter(|(_, verdict)| !verdict.is_passed())
+            .map(|(name, verdict)| (name.as_str(), verdict))
+    }
+
+    /// [`EXIT_PASSED`] if all gates passed and [`EXIT_FAILED`] otherwise.
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        if self.is_passed() {
+            EXIT_PASSED
+        } else {
+            EXIT_FAILED
+        }
+    }
+}
+
+impl fmt::Display for GateResults {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, verdict) in &self.gates {
+            writeln!(f, "{}: {}", name, verdict)?;
+        }
+        Ok(())
+    }
+}
+
+/// Fails if any check of the smoke test failed.
+///
+/// Warnings and skipped checks do not fail the gate.
+#[must_use]
+pub fn smoke_test(report: &SmokeTestReport) -> Verdict {
+    Verdict::from_reasons(
+        report
+            .checks()
+            .iter()
+            .filter(|(_, status)| status.is_failed())
+            .map(|(name, status)| format!("{}: {}", name, status)),
+    )
+}
+
+/// Fails if any item of the bulk operation failed.
+#[must_use]
+pub fn no_failures<K, T, E>(result: &BulkResult<K, T, E>) -> Verdict
+where
+    K: fmt::Display,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    Verdict::from_reasons(
+        result
+            .failed()
+            .map(|(key, error)| format!("{}: {}", key, error)),
+    )
+}
+
+/// Fails if the remote project differs from the local files,
+/// i.e. if the sync plan contains any operation.
+#[must_use]
+pub fn no_drift(plan: &SyncPlan) -> Verdict {
+    Verdict::from_reasons(&plan.operations)
+}
+
+/// Fails if the sync plan has conflicts that were left unresolved,
+/// i.e. if it was planned with [`ConflictPolicy::Fail`](crate::sync::ConflictPolicy::Fail)
+/// and translations differ locally and remotely.
+#[must_use]
+pub fn no_conflicts(plan: &SyncPlan) -> Verdict {
+    Verdict::from_reasons(plan.unresolved_conflicts())
+}
+
+/// Fails if the promotion cannot copy all translations because
+/// terms or locales are missing in the target project.
+#[must_use]
+pub fn promotion_complete(promotion: &Promotion) -> Verdict {
+    Verdict::from_reasons(
+        promotion
+            .missing_terms
+            .iter()
+            .map(|term| format!("term {} is missing in the target project", term))
+            .chain(
+                promotion
+                    .missing_locales
+                    .iter()
+                    .map(|locale| format!("locale {} is missing in the target project", locale)),
+            ),
+    )
+}
+
+/// Fails if the completion of any of the given locales is below `minimum`,
+/// e.g. `0.95` for 95%.
+///
+/// If `locales` is empty, all locales of the report are checked.
+/// Locales that are missing in the report fail the gate.
+#[must_use]
+pub fn completion(report: &CompletionReport, locales: &[LocaleCode], minimum: f64) -> Verdict {
+    let checked: Vec<_> = if locales.is_empty() {
+        report.locales.keys().collect()
+    } else {
+        locales.iter().collect()
+    };
+    Verdict::from_reasons(checked.into_iter().flat_map(|locale| {
+        match report.locales.get(locale) {
+            Some(completion) => at_least(
+                &format!("completion of {}", locale),
+                completion.completion(),
+                minimum,
+            )
+            .reasons()
+            .to_vec(),
+            None => vec![format!("locale {} does not exist", locale)],
+        }
+    }))
+}
+
+/// Fails if any translation of the report is longer than allowed.
+#[must_use]
+pub fn within_length_limits(report: &LengthReport) -> Verdict {
+    Verdict::from_reasons(report.violations().map(|(locale, violation)| {
+        format!(
+            "{} ({}) has {} characters but at most {} are allowed",
+            violation.term, locale, violation.length, violation.max
+        )
+    }))
+}
+
+/// Fails if `value` is below `minimum`.
+///
+/// `what` describes the value in the reason, e.g. `"completion of de_DE"`.
+#[must_use]
+pub fn at_least(what: &str, value: f64, minimum: f64) -> Verdict {
+    if value < minimum {
+        Verdict::Failed(vec![format!(
+            "{} is {} but must be at least {}",
+            what, value, minimum
+        )])
+    } else {
+        Verdict::Passed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{at_least, GateResults, Verdict, EXIT_FAILED, EXIT_PASSED};
+
+    #[test]
+    fn exit_code_reflects_failed_gates() {
+        let passed = GateResults::new()
+            .check("empty", Verdict::from_reasons(Vec::<String>::new()))
+            .check("completion", at_least("completion", 0.97, 0.95));
+        assert_eq!(passed.exit_code(), EXIT_PASSED);
+
+        let failed = passed.check("de_DE", at_least("completion of de_DE", 0.5, 0.95));
+        assert_eq!(failed.exit_code(), EXIT_FAILED);
+        let failures: Vec<_> = failed.failed().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "de_DE");
+        assert_eq!(
+            failures[0].1.reasons(),
+            ["completion of de_DE is 0.5 but must be at least 0.95"]
+        );
+    }
+}
@@ -4,18 +4,56 @@
 
 mod client;
 mod custom_query;
+mod dyn_client;
 mod endpoint;
 mod error;
+mod logging;
 #[macro_use]
 mod macros;
 mod query;
+mod query_iter;
+mod query_with_meta;
+mod raw_query;
 mod traduora;
 
+pub mod anonymize;
 pub mod api;
 pub mod auth;
+pub mod backup;
+pub mod batch;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cache;
+pub mod cancel;
+pub mod coalesce;
+pub mod credentials;
+pub mod diagnostics;
+pub mod dry_run;
+#[cfg(feature = "test-support")]
+pub mod fixtures;
+pub mod formats;
+pub mod gates;
+pub mod machine_translate;
+pub mod messages;
+pub mod metrics;
+#[cfg(feature = "sqlite")]
+pub mod mirror;
+#[cfg(feature = "test-support")]
+pub mod mock;
+pub mod ops;
+pub mod progress;
+pub mod registry;
+pub mod reports;
+pub mod search;
+pub mod snapshot;
+pub mod store;
+pub mod sync;
+pub mod tm;
+pub mod validate;
+pub mod vcr;
 
 pub(crate) use client::RestClient;
-pub(crate) use endpoint::Endpoint;
+pub(crate) use endpoint::{Endpoint, QueryParams};
 
 /// Alias for [`Token`](api::auth::Token).
 /// The shorter and clearer name improves readability when
@@ -24,11 +62,17 @@ pub type Login = api::auth::Token;
 
 pub use crate::traduora::AsyncTraduora;
 pub use crate::traduora::Builder as TraduoraBuilder;
+#[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+pub use crate::traduora::TlsBackend;
 pub use crate::traduora::Traduora;
 pub use crate::traduora::TraduoraError;
 pub use client::{AsyncClient, Client};
 pub use custom_query::{AsyncCustomQuery, CustomQuery};
-pub use error::{ApiError, BodyError};
+pub use dyn_client::{DynAsyncClient, DynClient, DynError, DynRestClient};
+pub use error::{ApiError, BodyError, RequestContext, TraduoraErrorCode};
 pub use query::{AsyncQuery, Query};
+pub use query_iter::{AsyncQueryIter, Items, QueryIter};
+pub use query_with_meta::{AsyncQueryWithMeta, QueryWithMeta, ResponseMeta};
+pub use raw_query::{AsyncRawQuery, RawQuery};
 
 pub use client::doctests::TestClient;
@@ -0,0 +1,7 @@
+//! Emits log messages with [`tracing`] if the `tracing` feature is enabled
+//! and with [`log`] otherwise.
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use log::{debug, warn};
+#[cfg(feature = "tracing")]
+pub(crate) use tracing::{debug, warn};
@@ -0,0 +1,530 @@
+//! Filling missing translations with machine translation.
+//!
+//! This crate does not ship a client for any translation service. Implement
+//! [`Mt`] (or [`AsyncMt`]) for the service of your choice, e.g. DeepL or Google
+//! Translate, and pass it to [`MachineTranslator::fill`]. [`Dictionary`] and
+//! [`Identity`] are simple reference implementations.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::machine_translate::{Dictionary, MachineTranslator};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let mut dictionary = Dictionary::new();
+//! dictionary.insert("en".into(), "de_DE".into(), "Hello", "Hallo");
+//!
+//! let result = MachineTranslator::new("en".into())
+//!     .fill(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(), &dictionary)?;
+//! for failure in &result.failed {
+//!     eprintln!("{}", failure);
+//! }
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+
+use async_trait::async_trait;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    api::{
+        self,
+        labels::{CreateLabel, LabelId, LabelTranslation, Labels},
+        locales::LocaleCode,
+        translations::EditTranslation,
+        ProjectId, TermId,
+    },
+    auth::Authenticated,
+    ops::ProjectData,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// Label that marks machine translations by default.
+pub const DEFAULT_LABEL: &str = "machine-translated";
+/// Color of labels created by [`MachineTranslator`].
+pub const LABEL_COLOR: &str = "#8F2D56";
+
+/// A machine translation service.
+pub trait Mt {
+    /// The errors which may occur when translating.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Translates `text` from the locale `from` into the locale `to`.
+    ///
+    /// # Errors
+    /// This method returns an error if the text cannot be translated.
+    fn translate(
+        &self,
+        text: &str,
+        from: &LocaleCode,
+        to: &LocaleCode,
+    ) -> Result<String, Self::Error>;
+}
+
+/// An asynchronous machine translation service.
+#[async_trait]
+pub trait AsyncMt {
+    /// The errors which may occur when translating.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Translates `text` from the locale `from` into the locale `to` asynchronously.
+    ///
+    /// # Errors
+    /// This method returns an error if the text cannot be translated.
+    async fn translate_async(
+        &self,
+        text: &str,
+        from: &LocaleCode,
+        to: &LocaleCode,
+    ) -> Result<String, Self::Error>;
+}
+
+/// Translates texts by looking them up in a fixed dictionary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dictionary {
+    entries: BTreeMap<(LocaleCode, LocaleCode, String), String>,
+}
+
+/// The error which is returned by [`Dictionary`] for texts it does not know.
+#[derive(Debug, Error)]
+#[error("no translation from {} to {} for '{}'", from, to, text)]
+pub struct UnknownText {
+    /// Locale of the text.
+    pub from: LocaleCode,
+    /// Requested locale.
+    pub to: LocaleCode,
+    /// The text to translate.
+    pub text: String,
+}
+
+impl Dictionary {
+    /// Creates an empty dictionary.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the translation of `text` from `from` into `to`.
+    pub fn insert(
+        &mut self,
+        from: LocaleCode,
+        to: LocaleCode,
+        text: impl Into<String>,
+        translation: impl Into<String>,
+    ) {
+        self.entries
+            .insert((from, to, text.into()), translation.into());
+    }
+
+    fn lookup(
+        &self,
+        text: &str,
+        from: &LocaleCode,
+        to: &LocaleCode,
+    ) -> Result<String, UnknownText> {
+        self.entries
+            .get(&(from.clone(), to.clone(), text.to_owned()))
+            .cloned()
+            .ok_or_else(|| UnknownText {
+                from: from.clone(),
+                to: to.clone(),
+                text: text.to_owned(),
+            })
+    }
+}
+
+impl Mt for Dictionary {
+    type Error = UnknownText;
+
+    fn translate(
+        &self,
+        text: &str,
+        from: &LocaleCode,
+        to: &LocaleCode,
+    ) -> Result<String, UnknownText> {
+        self.lookup(text, from, to)
+    }
+}
+
+#[async_trait]
+impl AsyncMt for Dictionary {
+    type Error = UnknownText;
+
+    async fn translate_async(
+        &self,
+        text: &str,
+        from: &LocaleCode,
+        to: &LocaleCode,
+    ) -> Result<String, UnknownText> {
+        self.lookup(text, from, to)
+    }
+}
+
+/// Copies the text unchanged.
+///
+/// Useful to prefill locales that are mostly identical to the source locale,
+/// e.g. `en_GB` from `en_US`, or to test a setup without a translation service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Identity;
+
+impl Mt for Identity {
+    type Error = Infallible;
+
+    fn translate(&self, text: &str, _: &LocaleCode, _: &LocaleCode) -> Result<String, Infallible> {
+        Ok(text.to_owned())
+    }
+}
+
+#[async_trait]
+impl AsyncMt for Identity {
+    type Error = Infallible;
+
+    async fn translate_async(
+        &self,
+        text: &str,
+        _: &LocaleCode,
+        _: &LocaleCode,
+    ) -> Result<String, Infallible> {
+        Ok(text.to_owned())
+    }
+}
+
+/// Finds untranslated terms and fills them with machine translations of the source locale.
+///
+/// Terms without a translation in the source locale are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MachineTranslator {
+    source: LocaleCode,
+    targets: Option<Vec<LocaleCode>>,
+    label: Option<String>,
+}
+
+/// A translation that was filled in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FilledTranslation {
+    /// The term string.
+    pub term: String,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The machine translation.
+    pub value: String,
+}
+
+/// A translation the service failed to provide.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FailedTranslation {
+    /// The term string.
+    pub term: String,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The error of the service.
+    pub error: String,
+}
+
+impl fmt::Display for FailedTranslation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.term, self.locale, self.error)
+    }
+}
+
+/// The outcome of [`MachineTranslator::fill`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FillResult {
+    /// Translations that were machine translated and saved.
+    pub filled: Vec<FilledTranslation>,
+    /// Translations the service failed to translate. They are left untranslated.
+    pub failed: Vec<FailedTranslation>,
+}
+
+/// A translation to request from the service.
+struct Job {
+    term_id: TermId,
+    term: String,
+    text: String,
+    locale: LocaleCode,
+}
+
+impl MachineTranslator {
+    /// Creates a driver translating from `source` into all other locales of the project.
+    ///
+    /// The translations are marked with the label [`DEFAULT_LABEL`].
+    #[must_use]
+    pub fn new(source: LocaleCode) -> Self {
+        Self {
+            source,
+            targets: None,
+            label: Some(DEFAULT_LABEL.to_owned()),
+        }
+    }
+
+    /// Only fills translations of the given locales.
+    #[must_use]
+    pub fn targets(mut self, targets: Vec<LocaleCode>) -> Self {
+        self.targets = Some(targets);
+        self
+    }
+
+    /// The label that marks machine translations, e.g. for a later review.
+    ///
+    /// The label is created if it does not exist. `None` disables labeling.
+    #[must_use]
+    pub fn label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
+
+    /// Translates all untranslated terms and saves the translations.
+    ///
+    /// Translations the service fails to translate are reported in
+    /// [`FillResult::failed`] and do not stop the process.
+    ///
+    /// # Errors
+    /// This method returns an error if a request to Traduora fails.
+    /// Translations saved before the failing request are kept.
+    pub fn fill<C, M>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+        mt: &M,
+    ) -> Result<FillResult, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+        M: Mt,
+    {
+        let data = ProjectData::fetch(client, project, |l| self.fetches(l))?;
+        let label = match &self.label {
+            Some(label) => {
+                let labels = Labels(project.clone()).query(client)?;
+                Some(match labels.into_iter().find(|l| l.value == *label) {
+                    Some(existing) => existing.id,
+                    None => {
+                        CreateLabel::new(project.clone(), &**label, LABEL_COLOR)
+                            .query(client)?
+                            .id
+                    }
+                })
+            }
+            None => None,
+        };
+
+        let mut result = FillResult::default();
+        for job in self.jobs(&data) {
+            match mt.translate(&job.text, &self.source, &job.locale) {
+                Ok(value) => {
+                    EditTranslation::new(
+                        project.clone(),
+                        job.locale.clone(),
+                        job.term_id.clone(),
+                        &*value,
+                    )
+                    .query(client)?;
+                    if let Some(label) = &label {
+                        api::ignore(label_translation(project, label, &job))
+                            .query_custom(client)?;
+                    }
+                    result.filled.push(job.filled(value));
+                }
+                Err(e) => result.failed.push(job.failed(&e)),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Translates all untranslated terms and saves the translations asynchronously.
+    ///
+    /// See [`fill`](Self::fill) for details.
+    ///
+    /// # Errors
+    /// This method returns an error if a request to Traduora fails.
+    /// Translations saved before the failing request are kept.
+    pub async fn fill_async<C, M>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+        mt: &M,
+    ) -> Result<FillResult, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+        M: AsyncMt + Sync,
+    {
+        let data = ProjectData::fetch_async(client, project, |l| self.fetches(l)).await?;
+        let label = match &self.label {
+            Some(label) => {
+                let labels = Labels(project.clone()).query_async(client).await?;
+                Some(match labels.into_iter().find(|l| l.value == *label) {
+                    Some(existing) => existing.id,
+                    None => {
+                        CreateLabel::new(project.clone(), &**label, LABEL_COLOR)
+                            .query_async(client)
+                            .await?
+                            .id
+                    }
+                })
+            }
+            None => None,
+        };
+
+        let mut result = FillResult::default();
+        for job in self.jobs(&data) {
+            match mt
+                .translate_async(&job.text, &self.source, &job.locale)
+                .await
+            {
+                Ok(value) => {
+                    EditTranslation::new(
+                        project.clone(),
+                        job.locale.clone(),
+                        job.term_id.clone(),
+                        &*value,
+                    )
+                    .query_async(client)
+                    .await?;
+                    if let Some(label) = &label {
+                        api::ignore(label_translation(project, label, &job))
+                            .query_custom_async(client)
+                            .await?;
+                    }
+                    result.filled.push(job.filled(value));
+                }
+                Err(e) => result.failed.push(job.failed(&e)),
+            }
+        }
+        Ok(result)
+    }
+
+    fn fetches(&self, locale: &LocaleCode) -> bool {
+        *locale == self.source
+            || self
+                .targets
+                .as_ref()
+                .map_or(true, |targets| targets.contains(locale))
+    }
+
+    /// The missing translations in term and locale order.
+    fn jobs(&self, data: &ProjectData) -> Vec<Job> {
+        let non_empty = |locale| -> BTreeMap<_, _> {
+            data.translations
+                .get(locale)
+                .into_iter()
+                .flatten()
+                .filter(|t| !t.value.is_empty())
+                .map(|t| (&t.term_id, &t.value))
+                .collect()
+        };
+        let source = non_empty(&self.source);
+
+        let mut jobs = Vec::new();
+        for locale in data.translations.keys().filter(|l| **l != self.source) {
+            let translated = non_empty(locale);
+            for term in &data.terms {
+                match source.get(&term.id) {
+                    Some(text) if !translated.contains_key(&term.id) => jobs.push(Job {
+                        term_id: term.id.clone(),
+                        term: term.value.clone(),
+                        text: (*text).clone(),
+                        locale: locale.clone(),
+                    }),
+                    _ => {}
+                }
+            }
+        }
+        jobs.sort_by(|a, b| (&a.term, &a.locale).cmp(&(&b.term, &b.locale)));
+        jobs
+    }
+}
+
+impl Job {
+    fn filled(self, value: String) -> FilledTranslation {
+        FilledTranslation {
+            term: self.term,
+            locale: self.locale,
+            value,
+        }
+    }
+
+    fn failed(self, error: &dyn std::error::Error) -> FailedTranslation {
+        FailedTranslation {
+            term: self.term,
+            locale: self.locale,
+            error: error.to_string(),
+        }
+    }
+}
+
+fn label_translation(project: &ProjectId, label: &LabelId, job: &Job) -> LabelTranslation {
+    LabelTranslation::new(
+        project.clone(),
+        label.clone(),
+        job.term_id.clone(),
+        job.locale.clone(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{Dictionary, MachineTranslator, Mt};
+    use crate::ops::ProjectData;
+
+    #[test]
+    fn jobs_cover_untranslated_terms_with_source() {
+        let date = json!({ "created": "2022-01-01T00:00:00Z", "modified": "2022-01-01T00:00:00Z" });
+        let mut data = ProjectData {
+            terms: serde_json::from_value(json!([
+                { "id": "1", "value": "hello", "labels": [], "date": date },
+                { "id": "2", "value": "bye", "labels": [], "date": date },
+                { "id": "3", "value": "untranslated", "labels": [], "date": date },
+            ]))
+            .unwrap(),
+            ..ProjectData::default()
+        };
+        let translations = |values: serde_json::Value| serde_json::from_value(values).unwrap();
+        data.translations.insert(
+            "en".into(),
+            translations(json!([
+                { "termId": "1", "value": "Hello", "labels": [], "date": date },
+                { "termId": "2", "value": "Bye", "labels": [], "date": date },
+            ])),
+        );
+        data.translations.insert(
+            "de".into(),
+            translations(json!([
+                { "termId": "1", "value": "", "labels": [], "date": date },
+                { "termId": "2", "value": "Tschüss", "labels": [], "date": date },
+            ])),
+        );
+        data.translations.insert("fr".into(), vec![]);
+
+        let jobs = MachineTranslator::new("en".into()).jobs(&data);
+        let jobs: Vec<_> = jobs
+            .iter()
+            .map(|j| (j.term.as_str(), j.locale.value(), j.text.as_str()))
+            .collect();
+        assert_eq!(
+            jobs,
+            [
+                ("bye", "fr", "Bye"),
+                ("hello", "de", "Hello"),
+                ("hello", "fr", "Hello")
+            ]
+        );
+
+        let mut dictionary = Dictionary::new();
+        dictionary.insert("en".into(), "de".into(), "Hello", "Hallo");
+        assert_eq!(
+            dictionary
+                .translate("Hello", &"en".into(), &"de".into())
+                .unwrap(),
+            "Hallo"
+        );
+        assert!(dictionary
+            .translate("Hello", &"en".into(), &"fr".into())
+            .is_err());
+    }
+}
@@ -1,4 +1,47 @@
 macro_rules! impl_wrapper {
+    ( $name:ident, $doc:expr, uuid$(,)? ) => {
+        impl_wrapper!($name, $doc);
+
+        impl $name {
+            /// Create a new id after checking that it is a UUID
+            /// like `b1001dd9-e1c0-4fb0-a60d-eaaec304d332`.
+            ///
+            /// Use [`new`](Self::new) to accept any string.
+            ///
+            /// # Errors
+            /// This function returns an error if `id` is not a UUID.
+            pub fn parse(id: &str) -> Result<Self, $crate::api::ParseIdError> {
+                $crate::api::ParseIdError::check(id, stringify!($name)).map(|()| Self::new(id))
+            }
+
+            /// The id as a [`uuid::Uuid`].
+            ///
+            /// Only available with the `uuid` feature.
+            ///
+            /// # Errors
+            /// This function returns an error if the id is not a UUID.
+            #[cfg(feature = "uuid")]
+            pub fn to_uuid(&self) -> Result<::uuid::Uuid, $crate::api::ParseIdError> {
+                $crate::api::ParseIdError::check(&self.0, stringify!($name))?;
+                Ok(::uuid::Uuid::parse_str(&self.0).expect("checked UUID format"))
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = $crate::api::ParseIdError;
+
+            fn from_str(id: &str) -> Result<Self, Self::Err> {
+                Self::parse(id)
+            }
+        }
+
+        #[cfg(feature = "uuid")]
+        impl From<::uuid::Uuid> for $name {
+            fn from(id: ::uuid::Uuid) -> Self {
+                Self(id.to_string())
+            }
+        }
+    };
     ( $name:ident, $doc:expr$(,)? ) => {
         #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         #[doc = $doc]
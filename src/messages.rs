@@ -0,0 +1,253 @@
+//! Human-friendly messages for errors, e.g. to show them in a GUI.
+//!
+//! Every message has a stable key and an English default text. Applications
+//! that want to show the messages in other languages can translate the keys
+//! of the [`catalog`] themselves. Use [`ApiError::user_message`] to get the
+//! message for an error.
+
+use std::fmt;
+
+use http::StatusCode;
+
+use crate::{ApiError, TraduoraErrorCode};
+
+/// A non-technical description of an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UserMessage {
+    /// Stable key to look up a translation of the message.
+    pub key: &'static str,
+    /// English text of the message.
+    pub text: &'static str,
+}
+
+impl fmt::Display for UserMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+const fn message(key: &'static str, text: &'static str) -> UserMessage {
+    UserMessage { key, text }
+}
+
+const CONNECTION: UserMessage = message(
+    "error.connection",
+    "The Traduora server could not be reached. Please check your network connection and the server address.",
+);
+const CONFIGURATION: UserMessage = message(
+    "error.configuration",
+    "The server address is invalid. Please check your settings.",
+);
+const INVALID_INPUT: UserMessage = message(
+    "error.invalid_input",
+    "The entered data could not be processed. Please check your input.",
+);
+const UNEXPECTED_RESPONSE: UserMessage = message(
+    "error.unexpected_response",
+    "The Traduora server sent an unexpected answer. The server version may not be supported.",
+);
+const BAD_REQUEST: UserMessage = message(
+    "error.bad_request",
+    "The request was rejected by the Traduora server. Please check your input.",
+);
+const UNAUTHORIZED: UserMessage = message(
+    "error.unauthorized",
+    "You are not logged in or your session expired. Please log in again.",
+);
+const PAYMENT_REQUIRED: UserMessage = message(
+    "error.payment_required",
+    "The limits of the project plan are reached.",
+);
+const FORBIDDEN: UserMessage = message(
+    "error.forbidden",
+    "You do not have permission to perform this action.",
+);
+const NOT_FOUND: UserMessage = message(
+    "error.not_found",
+    "The requested item does not exist or was deleted.",
+);
+const ALREADY_EXISTS: UserMessage = message(
+    "error.already_exists",
+    "An item with the same name already exists.",
+);
+const TOO_LARGE: UserMessage = message("error.too_large", "The uploaded data is too large.");
+const TOO_MANY_REQUESTS: UserMessage = message(
+    "error.too_many_requests",
+    "Too many requests were sent. Please wait a moment and try again.",
+);
+const TIMEOUT: UserMessage = message(
+    "error.timeout",
+    "The Traduora server did not answer in time. Please try again later.",
+);
+const SERVER: UserMessage = message(
+    "error.server",
+    "The Traduora server could not complete the request. Please try again later.",
+);
+
+const CATALOG: &[UserMessage] = &[
+    CONNECTION,
+    CONFIGURATION,
+    INVALID_INPUT,
+    UNEXPECTED_RESPONSE,
+    BAD_REQUEST,
+    UNAUTHORIZED,
+    PAYMENT_REQUIRED,
+    FORBIDDEN,
+    NOT_FOUND,
+    ALREADY_EXISTS,
+    TOO_LARGE,
+    TOO_MANY_REQUESTS,
+    TIMEOUT,
+    SERVER,
+];
+
+/// All messages that may be returned by the functions of this module.
+///
+/// This is useful to prepare translations of the messages.
+///
+/// # Examples
+/// ```
+/// use traduora::messages;
+///
+/// for message in messages::catalog() {
+///     println!("{} = {}", message.key, message.text);
+/// }
+/// ```
+#[must_use]
+pub const fn catalog() -> &'static [UserMessage] {
+    CATALOG
+}
+
+/// Looks up a message by its key.
+#[must_use]
+pub fn by_key(key: &str) -> Option<UserMessage> {
+    CATALOG.iter().copied().find(|m| m.key == key)
+}
+
+/// Message for an HTTP status code returned by the Traduora server.
+///
+/// # Examples
+/// ```
+/// use http::StatusCode;
+/// use traduora::messages;
+///
+/// assert_eq!(messages::for_status(StatusCode::NOT_FOUND).key, "error.not_found");
+/// ```
+#[must_use]
+pub fn for_status(status: StatusCode) -> UserMessage {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => BAD_REQUEST,
+        StatusCode::UNAUTHORIZED => UNAUTHORIZED,
+        StatusCode::PAYMENT_REQUIRED => PAYMENT_REQUIRED,
+        StatusCode::FORBIDDEN => FORBIDDEN,
+        StatusCode::NOT_FOUND => NOT_FOUND,
+        StatusCode::CONFLICT => ALREADY_EXISTS,
+        StatusCode::PAYLOAD_TOO_LARGE => TOO_LARGE,
+        StatusCode::TOO_MANY_REQUESTS => TOO_MANY_REQUESTS,
+        _ => SERVER,
+    }
+}
+
+/// Message for an error code in an error object returned by the Traduora server.
+///
+/// Returns `None` if the code is unknown.
+#[must_use]
+pub fn for_code(code: &str) -> Option<UserMessage> {
+    for_error_code(&TraduoraErrorCode::parse(code))
+}
+
+/// Message for a parsed error code.
+///
+/// Returns `None` if the code is unknown.
+#[must_use]
+pub fn for_error_code(code: &TraduoraErrorCode) -> Option<UserMessage> {
+    Some(match code {
+        TraduoraErrorCode::BadRequest => BAD_REQUEST,
+        TraduoraErrorCode::Unauthorized => UNAUTHORIZED,
+        TraduoraErrorCode::PaymentRequired => PAYMENT_REQUIRED,
+        TraduoraErrorCode::Forbidden => FORBIDDEN,
+        TraduoraErrorCode::NotFound => NOT_FOUND,
+        TraduoraErrorCode::AlreadyExists => ALREADY_EXISTS,
+        TraduoraErrorCode::PayloadTooLarge => TOO_LARGE,
+        TraduoraErrorCode::TooManyRequests => TOO_MANY_REQUESTS,
+        TraduoraErrorCode::InternalServerError => SERVER,
+        _ => return None,
+    })
+}
+
+impl<E> ApiError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A non-technical description of the error that can be shown to users.
+    ///
+    /// See the [`messages`](crate::messages) module.
+    #[must_use]
+    pub fn user_message(&self) -> UserMessage {
+        match self {
+            Self::Client { .. } => CONNECTION,
+            Self::UrlParse { .. } => CONFIGURATION,
+            Self::Body { .. } => INVALID_INPUT,
+            Self::Json { .. } | Self::DataType { .. } | Self::ResponseTooLarge { .. } => {
+                UNEXPECTED_RESPONSE
+            }
+            Self::TraduoraService { status, .. } => for_status(*status),
+            Self::TraduoraObject { obj, .. } => obj
+                .pointer("/code")
+                .and_then(serde_json::Value::as_str)
+                .and_then(for_code)
+                .unwrap_or(SERVER),
+            Self::Traduora { code, .. } => for_error_code(code).unwrap_or(SERVER),
+            Self::TraduoraUnrecognized { .. } => SERVER,
+            Self::MissingRole { .. } => FORBIDDEN,
+            Self::Timeout => TIMEOUT,
+            Self::LookupFailed { error, .. } => error.user_message(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use http::StatusCode;
+    use serde_json::json;
+
+    use super::{by_key, catalog};
+    use crate::ApiError;
+
+    type Error = ApiError<std::io::Error>;
+
+    #[test]
+    fn catalog_keys_are_unique() {
+        let keys: BTreeSet<_> = catalog().iter().map(|m| m.key).collect();
+        assert_eq!(keys.len(), catalog().len());
+        assert_eq!(by_key("error.not_found").unwrap().key, "error.not_found");
+    }
+
+    #[test]
+    fn error_code_determines_message() {
+        let error = Error::from_traduora(
+            StatusCode::NOT_FOUND,
+            json!({
+                "error": { "code": "NotFound", "message": "Project not found" }
+            }),
+        );
+        assert_eq!(error.user_message().key, "error.not_found");
+
+        let error = Error::from_traduora(
+            StatusCode::BAD_GATEWAY,
+            json!({ "error": { "code": "SomethingNew" } }),
+        );
+        assert_eq!(error.user_message().key, "error.server");
+    }
+
+    #[test]
+    fn status_determines_message() {
+        let error = Error::server_error(StatusCode::FORBIDDEN, &"".into());
+        assert_eq!(error.user_message().key, "error.forbidden");
+
+        let error = Error::client(std::io::ErrorKind::TimedOut.into());
+        assert_eq!(error.user_message().key, "error.connection");
+    }
+}
@@ -0,0 +1,62 @@
+//! Collecting metrics about the requests sent to Traduora.
+//!
+//! Implement [`MetricsSink`] and pass it to
+//! [`TraduoraBuilder::metrics_sink`](crate::TraduoraBuilder::metrics_sink)
+//! to forward request outcomes to a monitoring system like Prometheus or StatsD.
+
+use std::fmt::Debug;
+use std::time::Duration;
+
+use http::{Method, StatusCode};
+
+/// The outcome of a single request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RequestMetrics {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Path of the requested URL, e.g. `/api/v1/projects`.
+    pub path: String,
+    /// Status code of the response.
+    /// `None` if the request failed before a response was received.
+    pub status: Option<StatusCode>,
+    /// Time between preparing the request and receiving the full response.
+    pub latency: Duration,
+    /// Size of the request body in bytes.
+    pub request_bytes: usize,
+    /// Size of the response body in bytes.
+    pub response_bytes: usize,
+}
+
+/// Receives the metrics of every request a client sends.
+///
+/// The sink is called synchronously after each request, so it should
+/// only update counters and not block.
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use traduora::{metrics::{MetricsSink, RequestMetrics}, TraduoraBuilder};
+///
+/// #[derive(Debug, Default)]
+/// struct ErrorCounter(AtomicUsize);
+///
+/// impl MetricsSink for ErrorCounter {
+///     fn record(&self, metrics: &RequestMetrics) {
+///         if metrics.status.map_or(true, |s| !s.is_success()) {
+///             self.0.fetch_add(1, Ordering::Relaxed);
+///         }
+///     }
+/// }
+///
+/// # fn main() -> Result<(), traduora::TraduoraError> {
+/// let client = TraduoraBuilder::new("localhost:8080")
+///     .metrics_sink(ErrorCounter::default())
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait MetricsSink: Debug + Send + Sync {
+    /// Records the outcome of a request.
+    fn record(&self, metrics: &RequestMetrics);
+}
@@ -0,0 +1,618 @@
+//! A local copy of projects in an SQLite database.
+//!
+//! See [`Mirror`]. Requires the `sqlite` feature.
+//! The mirror includes a full-text index for [`Mirror::search`].
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use thiserror::Error;
+
+pub use rusqlite;
+
+use crate::{
+    api::{locales::LocaleCode, ProjectId, TermId},
+    auth::Authenticated,
+    ops::ProjectData,
+    ApiError, AsyncClient, Client,
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS projects (
+    project_id TEXT PRIMARY KEY,
+    refreshed_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS terms (
+    id INTEGER PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    term_id TEXT NOT NULL,
+    value TEXT NOT NULL,
+    modified TEXT NOT NULL,
+    UNIQUE (project_id, term_id)
+);
+CREATE TABLE IF NOT EXISTS locales (
+    project_id TEXT NOT NULL,
+    code TEXT NOT NULL,
+    PRIMARY KEY (project_id, code)
+);
+CREATE TABLE IF NOT EXISTS translations (
+    id INTEGER PRIMARY KEY,
+    project_id TEXT NOT NULL,
+    term_id TEXT NOT NULL,
+    locale TEXT NOT NULL,
+    value TEXT NOT NULL,
+    modified TEXT NOT NULL,
+    UNIQUE (project_id, term_id, locale)
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS terms_fts USING fts5(
+    value, content = 'terms', content_rowid = 'id', tokenize = 'unicode61 remove_diacritics 2'
+);
+CREATE TRIGGER IF NOT EXISTS terms_fts_insert AFTER INSERT ON terms BEGIN
+    INSERT INTO terms_fts (rowid, value) VALUES (new.id, new.value);
+END;
+CREATE TRIGGER IF NOT EXISTS terms_fts_delete AFTER DELETE ON terms BEGIN
+    INSERT INTO terms_fts (terms_fts, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+CREATE TRIGGER IF NOT EXISTS terms_fts_update AFTER UPDATE ON terms BEGIN
+    INSERT INTO terms_fts (terms_fts, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO terms_fts (rowid, value) VALUES (new.id, new.value);
+END;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS translations_fts USING fts5(
+    value, content = 'translations', content_rowid = 'id', tokenize = 'unicode61 remove_diacritics 2'
+);
+CREATE TRIGGER IF NOT EXISTS translations_fts_insert AFTER INSERT ON translations BEGIN
+    INSERT INTO translations_fts (rowid, value) VALUES (new.id, new.value);
+END;
+CREATE TRIGGER IF NOT EXISTS translations_fts_delete AFTER DELETE ON translations BEGIN
+    INSERT INTO translations_fts (translations_fts, rowid, value) VALUES ('delete', old.id, old.value);
+END;
+CREATE TRIGGER IF NOT EXISTS translations_fts_update AFTER UPDATE ON translations BEGIN
+    INSERT INTO translations_fts (translations_fts, rowid, value) VALUES ('delete', old.id, old.value);
+    INSERT INTO translations_fts (rowid, value) VALUES (new.id, new.value);
+END;
+";
+
+/// The error which is returned when refreshing a mirror fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MirrorError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Fetching the project from Traduora failed.
+    #[error("failed to fetch project: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// Accessing the database failed.
+    #[error("failed to access mirror database: {}", source)]
+    Database {
+        /// Inner error.
+        #[from]
+        source: rusqlite::Error,
+    },
+}
+
+/// What a refresh changed in the mirror.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RefreshStats {
+    /// Number of terms and translations that were added or modified.
+    pub changed: usize,
+    /// Number of terms and translations that were removed.
+    pub removed: usize,
+}
+
+/// A translation stored in the mirror.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MirroredTranslation {
+    /// Unique id of the term.
+    pub term_id: TermId,
+    /// The term string.
+    pub term: String,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The translated text.
+    pub value: String,
+}
+
+/// A match of [`Mirror::search`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    /// Unique id of the matching term.
+    pub term_id: TermId,
+    /// The term string.
+    pub term: String,
+    /// Locale of the matching translation.
+    /// `None` if the term string itself matched.
+    pub locale: Option<LocaleCode>,
+    /// The matching text, i.e. the term string or the translation.
+    pub text: String,
+    /// Relevance of the match. Lower is better.
+    pub rank: f64,
+}
+
+/// A local copy of the terms and translations of projects.
+///
+/// After a [`Mirror::refresh`], queries are answered from the SQLite database
+/// without contacting the server. This makes reports and searches over large
+/// projects fast and allows to work offline. Later refreshes only write the
+/// terms and translations that changed since the previous refresh.
+///
+/// Use [`Mirror::connection`] for queries that are not covered by the methods.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::mirror::Mirror;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let mut mirror = Mirror::in_memory()?;
+/// mirror.refresh(&client, &project)?;
+///
+/// assert_eq!(mirror.terms(&project)?.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Mirror {
+    connection: Connection,
+}
+
+impl Mirror {
+    /// Opens the database file or creates it if it does not exist.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be opened.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, rusqlite::Error> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// Creates a mirror that is only kept in memory.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be created.
+    pub fn in_memory() -> Result<Self, rusqlite::Error> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    /// Uses an existing connection, e.g. with custom flags.
+    ///
+    /// # Errors
+    /// This method returns an error if the tables cannot be created.
+    pub fn with_connection(connection: Connection) -> Result<Self, rusqlite::Error> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// The underlying database connection for custom queries.
+    pub const fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Fetches the project and updates the mirror.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the project or writing the database fails.
+    /// The mirror is left unchanged in this case.
+    pub fn refresh<C>(
+        &mut self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<RefreshStats, MirrorError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let data = ProjectData::fetch(client, project, |_| true)?;
+        Ok(self.store(project, &data)?)
+    }
+
+    /// Fetches the project asynchronously and updates the mirror.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the project or writing the database fails.
+    /// The mirror is left unchanged in this case.
+    pub async fn refresh_async<C>(
+        &mut self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<RefreshStats, MirrorError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let data = ProjectData::fetch_async(client, project, |_| true).await?;
+        Ok(self.store(project, &data)?)
+    }
+
+    /// When the project was refreshed the last time.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn last_refresh(
+        &self,
+        project: &ProjectId,
+    ) -> Result<Option<DateTime<Utc>>, rusqlite::Error> {
+        let refreshed_at: Option<String> = self
+            .connection
+            .query_row(
+                "SELECT refreshed_at FROM projects WHERE project_id = ?",
+                [project.value()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(refreshed_at
+            .and_then(|r| DateTime::parse_from_rfc3339(&r).ok())
+            .map(|r| r.with_timezone(&Utc)))
+    }
+
+    /// All term strings of the project in ascending order.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn terms(&self, project: &ProjectId) -> Result<Vec<String>, rusqlite::Error> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT value FROM terms WHERE project_id = ? ORDER BY value")?;
+        let terms = statement.query_map([project.value()], |row| row.get(0))?;
+        terms.collect()
+    }
+
+    /// All locales of the project in ascending order.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn locales(&self, project: &ProjectId) -> Result<Vec<LocaleCode>, rusqlite::Error> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT code FROM locales WHERE project_id = ? ORDER BY code")?;
+        let locales = statement.query_map([project.value()], |row| {
+            row.get::<_, String>(0).map(LocaleCode::from)
+        })?;
+        locales.collect()
+    }
+
+    /// All non-empty translations of the locale ordered by term.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn translations(
+        &self,
+        project: &ProjectId,
+        locale: &LocaleCode,
+    ) -> Result<Vec<MirroredTranslation>, rusqlite::Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT t.term_id, t.value, tr.value FROM translations tr
+             JOIN terms t ON t.project_id = tr.project_id AND t.term_id = tr.term_id
+             WHERE tr.project_id = ?1 AND tr.locale = ?2 AND tr.value <> ''
+             ORDER BY t.value",
+        )?;
+        let translations = statement.query_map([project.value(), locale.value()], |row| {
+            Ok(MirroredTranslation {
+                term_id: TermId::new(row.get::<_, String>(0)?),
+                term: row.get(1)?,
+                locale: locale.clone(),
+                value: row.get(2)?,
+            })
+        })?;
+        translations.collect()
+    }
+
+    /// The translation of the term or `None` if it is not translated.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn translation(
+        &self,
+        project: &ProjectId,
+        term: &str,
+        locale: &LocaleCode,
+    ) -> Result<Option<String>, rusqlite::Error> {
+        self.connection
+            .query_row(
+                "SELECT tr.value FROM translations tr
+                 JOIN terms t ON t.project_id = tr.project_id AND t.term_id = tr.term_id
+                 WHERE tr.project_id = ?1 AND t.value = ?2 AND tr.locale = ?3 AND tr.value <> ''",
+                [project.value(), term, locale.value()],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// Term strings without a translation in the locale in ascending order.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    pub fn untranslated(
+        &self,
+        project: &ProjectId,
+        locale: &LocaleCode,
+    ) -> Result<Vec<String>, rusqlite::Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT t.value FROM terms t
+             WHERE t.project_id = ?1 AND NOT EXISTS (
+                 SELECT 1 FROM translations tr
+                 WHERE tr.project_id = t.project_id AND tr.term_id = t.term_id
+                   AND tr.locale = ?2 AND tr.value <> ''
+             )
+             ORDER BY t.value",
+        )?;
+        let terms = statement.query_map([project.value(), locale.value()], |row| row.get(0))?;
+        terms.collect()
+    }
+
+    /// Searches term strings and translations of all locales.
+    ///
+    /// Every word of the query has to occur in the text. Words match as prefix,
+    /// so `hel wor` finds `hello.world`. Case and diacritics are ignored.
+    /// The hits are ordered by relevance; at most `limit` hits are returned.
+    ///
+    /// # Errors
+    /// This method returns an error if the database cannot be read.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use traduora::mirror::Mirror;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// # let client = Traduora::with_auth("localhost:8080", login)?;
+    /// # let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+    /// # let mut mirror = Mirror::in_memory()?;
+    /// # mirror.refresh(&client, &project)?;
+    /// let hits = mirror.search(&project, "this term", 10)?;
+    ///
+    /// assert_eq!(hits[0].term, "this.is.a.term");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn search(
+        &self,
+        project: &ProjectId,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>, rusqlite::Error> {
+        let query = fts_query(query);
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut statement = self.connection.prepare(
+            "SELECT term_id, term, locale, text, rank FROM (
+                 SELECT t.term_id, t.value AS term, NULL AS locale, t.value AS text, terms_fts.rank
+                 FROM terms_fts JOIN terms t ON t.id = terms_fts.rowid
+                 WHERE terms_fts MATCH ?2 AND t.project_id = ?1
+                 UNION ALL
+                 SELECT t.term_id, t.value, tr.locale, tr.value, translations_fts.rank
+                 FROM translations_fts
+                 JOIN translations tr ON tr.id = translations_fts.rowid
+                 JOIN terms t ON t.project_id = tr.project_id AND t.term_id = tr.term_id
+                 WHERE translations_fts MATCH ?2 AND tr.project_id = ?1
+             )
+             ORDER BY rank, term, locale
+             LIMIT ?3",
+        )?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let hits = statement.query_map(params![project.value(), query, limit], |row| {
+            Ok(SearchHit {
+                term_id: TermId::new(row.get::<_, String>(0)?),
+                term: row.get(1)?,
+                locale: row.get::<_, Option<String>>(2)?.map(LocaleCode::from),
+                text: row.get(3)?,
+                rank: row.get(4)?,
+            })
+        })?;
+        hits.collect()
+    }
+
+    fn store(
+        &mut self,
+        project: &ProjectId,
+        data: &ProjectData,
+    ) -> Result<RefreshStats, rusqlite::Error> {
+        let project = project.value();
+        let tx = self.connection.transaction()?;
+        let mut stats = RefreshStats::default();
+
+        let existing = ids(
+            &tx,
+            "SELECT term_id FROM terms WHERE project_id = ?",
+            project,
+        )?;
+        {
+            let mut upsert = tx.prepare(
+                "INSERT INTO terms (project_id, term_id, value, modified) VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT (project_id, term_id) DO UPDATE
+                 SET value = excluded.value, modified = excluded.modified
+                 WHERE value <> excluded.value OR modified <> excluded.modified",
+            )?;
+            for term in &data.terms {
+                stats.changed += upsert.execute(params![
+                    project,
+                    term.id.value(),
+                    term.value,
+                    term.date.modified.to_rfc3339()
+                ])?;
+            }
+        }
+        let current: BTreeSet<_> = data.terms.iter().map(|t| t.id.value()).collect();
+        for removed in existing.iter().filter(|id| !current.contains(id.as_str())) {
+            stats.removed += tx.execute(
+                "DELETE FROM terms WHERE project_id = ?1 AND term_id = ?2",
+                [project, removed],
+            )?;
+        }
+
+        tx.execute("DELETE FROM locales WHERE project_id = ?", [project])?;
+        let existing = ids(
+            &tx,
+            "SELECT locale || ' ' || term_id FROM translations WHERE project_id = ?",
+            project,
+        )?;
+        let mut current = BTreeSet::new();
+        {
+            let mut locale =
+                tx.prepare("INSERT INTO locales (project_id, code) VALUES (?1, ?2)")?;
+            let mut upsert = tx.prepare(
+                "INSERT INTO translations (project_id, term_id, locale, value, modified)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT (project_id, term_id, locale) DO UPDATE
+                 SET value = excluded.value, modified = excluded.modified
+                 WHERE value <> excluded.value OR modified <> excluded.modified",
+            )?;
+            for (code, translations) in &data.translations {
+                locale.execute([project, code.value()])?;
+                for translation in translations {
+                    stats.changed += upsert.execute(params![
+                        project,
+                        translation.term_id.value(),
+                        code.value(),
+                        translation.value,
+                        translation.date.modified.to_rfc3339()
+                    ])?;
+                    current.insert(format!("{} {}", code, translation.term_id));
+                }
+            }
+        }
+        for removed in existing.iter().filter(|id| !current.contains(*id)) {
+            let (locale, term_id) = removed.split_once(' ').unwrap_or_default();
+            stats.removed += tx.execute(
+                "DELETE FROM translations WHERE project_id = ?1 AND locale = ?2 AND term_id = ?3",
+                [project, locale, term_id],
+            )?;
+        }
+
+        tx.execute(
+            "INSERT INTO projects (project_id, refreshed_at) VALUES (?1, ?2)
+             ON CONFLICT (project_id) DO UPDATE SET refreshed_at = excluded.refreshed_at",
+            [project, &Utc::now().to_rfc3339()],
+        )?;
+        tx.commit()?;
+        Ok(stats)
+    }
+}
+
+/// Turns every word of the user input into a quoted prefix query
+/// so that FTS syntax in the input is not interpreted.
+fn fts_query(input: &str) -> String {
+    input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("\"{}\"*", word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn ids(tx: &Transaction<'_>, query: &str, project: &str) -> Result<Vec<String>, rusqlite::Error> {
+    let mut statement = tx.prepare(query)?;
+    let ids = statement.query_map([project], |row| row.get(0))?;
+    ids.collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::Mirror;
+    use crate::{
+        api::{terms::Term, translations::Translation, AccessDates},
+        ops::ProjectData,
+    };
+
+    fn dates() -> AccessDates {
+        AccessDates {
+            created: Utc::now(),
+            modified: Utc::now(),
+        }
+    }
+
+    fn data(terms: &[(&str, &str)], translations: &[(&str, &str)]) -> ProjectData {
+        ProjectData {
+            terms: terms
+                .iter()
+                .map(|(id, value)| Term::new((*id).into(), *value, dates()))
+                .collect(),
+            translations: std::iter::once((
+                "de".into(),
+                translations
+                    .iter()
+                    .map(|(term_id, value)| Translation::new((*term_id).into(), *value, dates()))
+                    .collect(),
+            ))
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn refresh_only_writes_changes() {
+        let mut mirror = Mirror::in_memory().unwrap();
+        let project = "project".into();
+        let first = data(&[("1", "hello"), ("2", "bye")], &[("1", "Hallo")]);
+
+        let stats = mirror.store(&project, &first).unwrap();
+        assert_eq!(stats.changed, 3);
+        let stats = mirror.store(&project, &first).unwrap();
+        assert_eq!(stats.changed, 0);
+
+        let second = data(&[("1", "hello")], &[]);
+        let stats = mirror.store(&project, &second).unwrap();
+        assert_eq!(stats.removed, 2);
+        assert_eq!(mirror.terms(&project).unwrap(), vec!["hello"]);
+    }
+
+    #[test]
+    fn search_finds_keys_and_translations() {
+        let mut mirror = Mirror::in_memory().unwrap();
+        let project = "project".into();
+        let data = data(
+            &[("1", "greeting.hello"), ("2", "farewell")],
+            &[("1", "Grüße"), ("2", "Auf Wiedersehen")],
+        );
+        mirror.store(&project, &data).unwrap();
+
+        let hits = mirror.search(&project, "gru", 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].term, "greeting.hello");
+        assert_eq!(hits[0].locale, Some("de".into()));
+
+        let hits = mirror.search(&project, "GREET", 10).unwrap();
+        assert_eq!(hits[0].locale, None);
+
+        mirror.store(&project, &ProjectData::default()).unwrap();
+        assert!(mirror
+            .search(&project, "wiedersehen", 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn untranslated_terms_are_found() {
+        let mut mirror = Mirror::in_memory().unwrap();
+        let project = "project".into();
+        let de = "de".into();
+        let data = data(
+            &[("1", "hello"), ("2", "bye")],
+            &[("1", "Hallo"), ("2", "")],
+        );
+        mirror.store(&project, &data).unwrap();
+
+        assert_eq!(mirror.untranslated(&project, &de).unwrap(), vec!["bye"]);
+        assert_eq!(
+            mirror
+                .translation(&project, "hello", &de)
+                .unwrap()
+                .as_deref(),
+            Some("Hallo")
+        );
+        assert_eq!(mirror.translations(&project, &de).unwrap().len(), 1);
+        assert!(mirror.last_refresh(&project).unwrap().is_some());
+    }
+}
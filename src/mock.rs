@@ -0,0 +1,400 @@
+//! Testing code that talks to Traduora without a live server.
+//!
+//! A [`MockTraduora`] is a client that answers requests from a list of
+//! [`Expectation`]s. Each expectation matches an HTTP method, an endpoint path
+//! and optionally the JSON body of a request, and returns a canned response.
+//! Requests without a matching expectation fail with [`MockError::Unexpected`].
+//! After the test, [`MockTraduora::verify`] checks that every expectation was met.
+//!
+//! This module is only available with the `test-support` feature.
+//!
+//! # Examples
+//! ```
+//! use http::{Method, StatusCode};
+//! use serde_json::json;
+//! use traduora::{api::terms::Terms, mock::{Expectation, MockTraduora}, Query};
+//!
+//! let client = MockTraduora::new();
+//! client.expect(
+//!     Expectation::new(Method::GET, "projects/*/terms").respond_json(
+//!         StatusCode::OK,
+//!         json!({ "data": [] }),
+//!     ),
+//! );
+//!
+//! let terms = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+//!     .query(&client)
+//!     .unwrap();
+//! assert!(terms.is_empty());
+//! assert_eq!(client.calls()[0].path, "projects/b1001dd9-e1c0-4fb0-a60d-eaaec304d332/terms");
+//! client.verify().unwrap();
+//! ```
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, Method, Response, StatusCode};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+use url::Url;
+
+use crate::{auth::Authenticated, client::RestClient, ApiError, AsyncClient, Client};
+
+/// Base URL of the endpoints of a [`MockTraduora`].
+const BASE_URL: &str = "http://traduora.mock/api/v1/";
+/// Path of [`BASE_URL`].
+const BASE_PATH: &str = "/api/v1/";
+
+/// The error which is returned by a [`MockTraduora`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum MockError {
+    /// No expectation matches the request.
+    #[error("unexpected request {} {}", method, path)]
+    Unexpected {
+        /// HTTP method of the request.
+        method: Method,
+        /// Endpoint path of the request.
+        path: String,
+    },
+    /// An expectation was not met as often as required.
+    #[error(
+        "expected {} {} {} more time(s)",
+        expectation.method,
+        expectation.path,
+        remaining
+    )]
+    Unsatisfied {
+        /// The unmet expectation.
+        expectation: Box<Expectation>,
+        /// How many more requests were expected.
+        remaining: usize,
+    },
+    /// The request could not be built.
+    #[error("`http` error: {}", source)]
+    Http {
+        /// Inner error.
+        #[from]
+        source: http::Error,
+    },
+}
+
+/// A request that a [`MockTraduora`] expects and the response it returns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    method: Method,
+    path: String,
+    body: Option<Value>,
+    times: usize,
+    status: StatusCode,
+    response: Bytes,
+    delay: Duration,
+}
+
+impl Expectation {
+    /// Expects one request with the given method and endpoint path.
+    ///
+    /// The path is relative to the API root, e.g. `projects/*/terms`.
+    /// A `*` matches any single path segment. The query string is ignored.
+    /// Unless configured otherwise, the response is `200 OK` without a body.
+    pub fn new(method: Method, path: impl Into<String>) -> Self {
+        Self {
+            method,
+            path: path.into(),
+            body: None,
+            times: 1,
+            status: StatusCode::OK,
+            response: Bytes::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Only matches requests whose body is equal to this JSON value.
+    #[must_use]
+    pub fn body(mut self, body: Value) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    /// Expects the request `times` times instead of once.
+    #[must_use]
+    pub const fn times(mut self, times: usize) -> Self {
+        self.times = times;
+        self
+    }
+
+    /// Responds with the status code and the given bytes as body.
+    #[must_use]
+    pub fn respond(mut self, status: StatusCode, body: impl Into<Bytes>) -> Self {
+        self.status = status;
+        self.response = body.into();
+        self
+    }
+
+    /// Waits for the duration before responding, e.g. to test timeouts.
+    ///
+    /// Async queries wait without blocking the runtime.
+    #[must_use]
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Responds with the status code and the value serialized as JSON body.
+    ///
+    /// # Panics
+    /// This method panics if the value cannot be serialized.
+    #[must_use]
+    pub fn respond_json(self, status: StatusCode, body: impl Serialize) -> Self {
+        let body = serde_json::to_vec(&body).expect("failed to serialize mock response");
+        self.respond(status, body)
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        let actual: Vec<_> = request.path.split('/').collect();
+        let expected: Vec<_> = self.path.split('/').collect();
+        self.method == request.method
+            && actual.len() == expected.len()
+            && actual
+                .iter()
+                .zip(expected)
+                .all(|(a, e)| e == "*" || *a == e)
+            && self
+                .body
+                .as_ref()
+                .map_or(true, |b| Some(b) == request.body.as_ref())
+    }
+}
+
+/// A request received by a [`MockTraduora`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    /// HTTP method of the request.
+    pub method: Method,
+    /// Endpoint path relative to the API root, without query string.
+    pub path: String,
+    /// Query string of the request, if any.
+    pub query: Option<String>,
+    /// Body of the request if it is JSON.
+    pub body: Option<Value>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    expectations: Vec<(Expectation, usize)>,
+    calls: Vec<Request>,
+}
+
+/// A client that answers requests with canned responses instead of sending them.
+///
+/// It has the [`Authenticated`] access level so that every endpoint can be queried.
+/// It can be shared between threads and used by sync and async queries.
+#[derive(Debug, Default)]
+pub struct MockTraduora {
+    state: Mutex<State>,
+}
+
+impl MockTraduora {
+    /// Creates a client without expectations.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an expectation.
+    ///
+    /// Expectations are matched in the order they were added.
+    /// An expectation is skipped once it was met as often as expected.
+    pub fn expect(&self, expectation: Expectation) -> &Self {
+        let times = expectation.times;
+        self.state().expectations.push((expectation, times));
+        self
+    }
+
+    /// All requests received so far, including unexpected ones.
+    #[must_use]
+    pub fn calls(&self) -> Vec<Request> {
+        self.state().calls.clone()
+    }
+
+    /// Checks that all expectations were met.
+    ///
+    /// # Errors
+    /// This method returns [`MockError::Unsatisfied`] for the first expectation
+    /// that was not met as often as expected.
+    pub fn verify(&self) -> Result<(), MockError> {
+        match self.state().expectations.iter().find(|(_, r)| *r > 0) {
+            Some((expectation, remaining)) => Err(MockError::Unsatisfied {
+                expectation: Box::new(expectation.clone()),
+                remaining: *remaining,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        // a panicking test must not hide the calls from other threads
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Records the request and returns the response of the matching expectation
+    /// together with the time to wait before returning it.
+    fn respond(
+        &self,
+        builder: RequestBuilder,
+        body: &[u8],
+    ) -> (Duration, Result<Response<Bytes>, ApiError<MockError>>) {
+        let request = match builder.body(()) {
+            Ok(request) => request,
+            Err(e) => return (Duration::ZERO, Err(ApiError::client(e.into()))),
+        };
+        let uri = request.uri();
+        let request = Request {
+            method: request.method().clone(),
+            path: uri.path().trim_start_matches(BASE_PATH).to_owned(),
+            query: uri.query().map(ToOwned::to_owned),
+            body: serde_json::from_slice(body).ok(),
+        };
+
+        let mut state = self.state();
+        state.calls.push(request.clone());
+        let expectation = state
+            .expectations
+            .iter_mut()
+            .find(|(e, remaining)| *remaining > 0 && e.matches(&request));
+        let (expectation, remaining) = match expectation {
+            Some(found) => found,
+            None => {
+                let error = MockError::Unexpected {
+                    method: request.method,
+                    path: request.path,
+                };
+                return (Duration::ZERO, Err(ApiError::client(error)));
+            }
+        };
+        *remaining -= 1;
+        let response = Response::builder()
+            .status(expectation.status)
+            .body(expectation.response.clone())
+            .map_err(|e| ApiError::client(e.into()));
+        (expectation.delay, response)
+    }
+}
+
+impl RestClient for MockTraduora {
+    type Error = MockError;
+    type AccessLevel = Authenticated;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        Ok(Url::parse(BASE_URL)?.join(endpoint)?)
+    }
+}
+
+impl Client for MockTraduora {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (delay, response) = self.respond(request, &body);
+        std::thread::sleep(delay);
+        response
+    }
+}
+
+#[async_trait]
+impl AsyncClient for MockTraduora {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (delay, response) = self.respond(request, &body);
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::{Expectation, MockError, MockTraduora};
+    use crate::{
+        api::terms::{CreateTerm, Terms},
+        fixtures, ApiError, AsyncQuery, Query,
+    };
+
+    #[test]
+    fn requests_are_matched_and_verified() {
+        let client = MockTraduora::new();
+        client
+            .expect(
+                Expectation::new(Method::POST, "projects/*/terms")
+                    .body(json!({ "value": "hello" }))
+                    .respond(StatusCode::NOT_FOUND, "{}"),
+            )
+            .expect(Expectation::new(Method::DELETE, "projects/p/terms/t"));
+
+        let error = CreateTerm::new("bye", "p".into())
+            .query(&client)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ApiError::Client {
+                source: MockError::Unexpected { .. }
+            }
+        ));
+        let error = CreateTerm::new("hello", "p".into())
+            .query(&client)
+            .unwrap_err();
+        assert!(error.is_not_found());
+        assert_eq!(client.calls().len(), 2);
+
+        let error = client.verify().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected DELETE projects/p/terms/t 1 more time(s)"
+        );
+    }
+
+    #[tokio::test]
+    async fn slow_responses_are_aborted_at_deadline() {
+        let client = MockTraduora::new();
+        client
+            .expect(
+                Expectation::new(Method::GET, "projects/p/terms")
+                    .delay(Duration::from_secs(10))
+                    .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+            )
+            .expect(
+                Expectation::new(Method::GET, "projects/p/terms")
+                    .respond_json(StatusCode::OK, fixtures::data(json!([]))),
+            );
+
+        let deadline = Instant::now() + Duration::from_millis(20);
+        let error = Terms::new("p".into())
+            .query_with_deadline(&client, deadline)
+            .await
+            .unwrap_err();
+        assert!(error.is_timeout());
+        assert!(error.is_transient());
+        assert!(Instant::now() < deadline + Duration::from_secs(5));
+
+        let terms = Terms::new("p".into())
+            .query_with_deadline(&client, Instant::now() + Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert!(terms.is_empty());
+    }
+}
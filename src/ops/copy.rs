@@ -0,0 +1,184 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    api::{
+        locales::LocaleCode,
+        translations::{EditTranslations, Translation, Translations},
+        BulkResult, ProjectId, TermId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+use super::{ensure_locale, ensure_locale_async};
+
+fn copied_translations(
+    source: Vec<Translation>,
+    target: Option<Vec<Translation>>,
+    project: &ProjectId,
+    to: &LocaleCode,
+) -> EditTranslations {
+    let translated: BTreeSet<_> = target
+        .into_iter()
+        .flatten()
+        .filter(|t| !t.value.is_empty())
+        .map(|t| t.term_id)
+        .collect();
+    let values: Vec<(TermId, String)> = source
+        .into_iter()
+        .filter(|t| !t.value.is_empty() && !translated.contains(&t.term_id))
+        .map(|t| (t.term_id, t.value))
+        .collect();
+    EditTranslations::new(project.clone(), to.clone(), values)
+}
+
+/// Copies all translations of a locale into another locale of the same project.
+///
+/// The target locale is added to the project if it does not exist yet.
+/// Empty translations of the source locale are not copied. If `overwrite` is
+/// `false`, terms that already have a non-empty translation in the target
+/// locale are left unchanged. This is useful to bootstrap a regional variant,
+/// e.g. `en_GB` from `en_US`.
+///
+/// # Errors
+/// This function returns an error if fetching the translations or adding the
+/// target locale fails. Failures of single translations are returned in the
+/// [`BulkResult`].
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let result = ops::copy_locale(&client, &project, &"en_US".into(), &"en_GB".into(), false)?;
+/// assert!(result.is_ok());
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn copy_locale<C>(
+    client: &C,
+    project: &ProjectId,
+    from: &LocaleCode,
+    to: &LocaleCode,
+    overwrite: bool,
+) -> Result<BulkResult<TermId, Translation, C::Error>, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let source = Translations::new(project.clone(), from.clone()).query(client)?;
+    ensure_locale(client, project, to)?;
+    let target = if overwrite {
+        None
+    } else {
+        Some(Translations::new(project.clone(), to.clone()).query(client)?)
+    };
+    Ok(copied_translations(source, target, project, to).edit(client))
+}
+
+/// Copies all translations of a locale into another locale of the same project asynchronously.
+///
+/// See [`copy_locale`].
+///
+/// # Errors
+/// This function returns an error if fetching the translations or adding the
+/// target locale fails. Failures of single translations are returned in the
+/// [`BulkResult`].
+pub async fn copy_locale_async<C>(
+    client: &C,
+    project: &ProjectId,
+    from: &LocaleCode,
+    to: &LocaleCode,
+    overwrite: bool,
+) -> Result<BulkResult<TermId, Translation, C::Error>, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let source = Translations::new(project.clone(), from.clone())
+        .query_async(client)
+        .await?;
+    ensure_locale_async(client, project, to).await?;
+    let target = if overwrite {
+        None
+    } else {
+        Some(
+            Translations::new(project.clone(), to.clone())
+                .query_async(client)
+                .await?,
+        )
+    };
+    Ok(copied_translations(source, target, project, to)
+        .edit_async(client)
+        .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::copy_locale;
+    use crate::{
+        fixtures,
+        mock::{Expectation, MockTraduora},
+    };
+
+    #[test]
+    fn existing_translations_are_kept_unless_overwritten() {
+        let mock = MockTraduora::new();
+        let source = json!([
+            fixtures::translation("a", "Color"),
+            fixtures::translation("b", "Center"),
+            fixtures::translation("c", ""),
+        ]);
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/translations/en_US")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(source)),
+        )
+        .expect(
+            Expectation::new(Method::POST, "projects/p/translations")
+                .times(2)
+                .respond_json(
+                    StatusCode::CREATED,
+                    fixtures::data(fixtures::project_locale("l", "en_GB")),
+                ),
+        )
+        .expect(
+            Expectation::new(Method::GET, "projects/p/translations/en_GB").respond_json(
+                StatusCode::OK,
+                fixtures::data(json!([
+                    fixtures::translation("a", "Colour"),
+                    fixtures::translation("b", ""),
+                ])),
+            ),
+        )
+        .expect(
+            Expectation::new(Method::PATCH, "projects/p/translations/en_GB")
+                .body(json!({ "termId": "a", "value": "Color" }))
+                .respond_json(
+                    StatusCode::OK,
+                    fixtures::data(fixtures::translation("a", "Color")),
+                ),
+        )
+        .expect(
+            Expectation::new(Method::PATCH, "projects/p/translations/en_GB")
+                .body(json!({ "termId": "b", "value": "Center" }))
+                .times(2)
+                .respond_json(
+                    StatusCode::OK,
+                    fixtures::data(fixtures::translation("b", "Center")),
+                ),
+        );
+        let (project, from, to) = ("p".into(), "en_US".into(), "en_GB".into());
+
+        let kept = copy_locale(&mock, &project, &from, &to, false).unwrap();
+        assert_eq!(kept.results.len(), 1);
+        assert!(kept.is_ok());
+        let overwritten = copy_locale(&mock, &project, &from, &to, true).unwrap();
+        assert_eq!(overwritten.results.len(), 2);
+        mock.verify().unwrap();
+    }
+}
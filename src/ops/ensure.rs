@@ -0,0 +1,150 @@
+use crate::{
+    api::{
+        locales::LocaleCode,
+        terms::{CreateTerm, FindTerm, Term},
+        translations::{CreateLocale, Locales, ProjectLocale},
+        ProjectId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// Creates the term unless the project already has it.
+///
+/// Returns the new or the existing term. The term is created first and only
+/// looked up if Traduora reports that it already exists, so provisioning
+/// scripts can call this function repeatedly.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::ensure_term;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let term = ensure_term(&client, &project, "this.is.a.new.term")?;
+/// assert_eq!(term.value, "this.is.a.new.term");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn ensure_term<C>(
+    client: &C,
+    project: &ProjectId,
+    key: &str,
+) -> Result<Term, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    match CreateTerm::new(key, project.clone()).query(client) {
+        Err(e) if e.is_conflict() => {
+            let term = FindTerm::new(project.clone(), key).query_custom(client)?;
+            term.ok_or(e)
+        }
+        result => result,
+    }
+}
+
+/// Creates the term asynchronously unless the project already has it.
+///
+/// Returns the new or the existing term. The term is created first and only
+/// looked up if Traduora reports that it already exists, so provisioning
+/// scripts can call this function repeatedly.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+pub async fn ensure_term_async<C>(
+    client: &C,
+    project: &ProjectId,
+    key: &str,
+) -> Result<Term, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    match CreateTerm::new(key, project.clone())
+        .query_async(client)
+        .await
+    {
+        Err(e) if e.is_conflict() => {
+            let term = FindTerm::new(project.clone(), key)
+                .query_custom_async(client)
+                .await?;
+            term.ok_or(e)
+        }
+        result => result,
+    }
+}
+
+/// Adds the locale to the project unless it already has it.
+///
+/// Returns the new or the existing project locale. Like [`ensure_term`],
+/// the locale is only looked up if creating it fails because it exists.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::ensure_locale;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let locale = ensure_locale(&client, &project, &"en_US".into())?;
+/// assert_eq!(locale.locale.code.value(), "en_US");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn ensure_locale<C>(
+    client: &C,
+    project: &ProjectId,
+    code: &LocaleCode,
+) -> Result<ProjectLocale, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    match CreateLocale::new(project.clone(), code.clone()).query(client) {
+        Err(e) if e.is_conflict() => {
+            let locales = Locales(project.clone()).query(client)?;
+            locales
+                .into_iter()
+                .find(|l| l.locale.code == *code)
+                .ok_or(e)
+        }
+        result => result,
+    }
+}
+
+/// Adds the locale to the project asynchronously unless it already has it.
+///
+/// Returns the new or the existing project locale. Like [`ensure_term`],
+/// the locale is only looked up if creating it fails because it exists.
+///
+/// # Errors
+/// This function returns an error if a request fails.
+pub async fn ensure_locale_async<C>(
+    client: &C,
+    project: &ProjectId,
+    code: &LocaleCode,
+) -> Result<ProjectLocale, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    match CreateLocale::new(project.clone(), code.clone())
+        .query_async(client)
+        .await
+    {
+        Err(e) if e.is_conflict() => {
+            let locales = Locales(project.clone()).query_async(client).await?;
+            locales
+                .into_iter()
+                .find(|l| l.locale.code == *code)
+                .ok_or(e)
+        }
+        result => result,
+    }
+}
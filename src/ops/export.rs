@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use thiserror::Error;
+
+use crate::{
+    api::{
+        self,
+        exports::{Export, ExportFormat},
+        locales::LocaleCode,
+        translations::Locales,
+        ProjectId,
+    },
+    auth::Authenticated,
+    progress::{NoProgress, ProgressSink, Tracker},
+    ApiError, AsyncClient, AsyncQuery, AsyncRawQuery, Client, Query, RawQuery,
+};
+
+/// The error which is returned by [`export_all_locales`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ExportError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A request failed.
+    #[error("failed to export locales: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// An exported file could not be written.
+    #[error("failed to write export {}: {}", path.display(), source)]
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Inner error.
+        source: io::Error,
+    },
+}
+
+/// Where and how the exported files of [`export_all_locales`] are written.
+///
+/// Converted from a path, the files are named `{locale}.{ext}`,
+/// e.g. `de_DE.json`, and up to 4 exports are downloaded at the same time
+/// by [`export_all_locales_async`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocaleFiles {
+    dir: PathBuf,
+    file_name: String,
+    concurrency: usize,
+}
+
+impl LocaleFiles {
+    /// Writes the files into `dir`, which is created if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            file_name: "{locale}.{ext}".to_owned(),
+            concurrency: 4,
+        }
+    }
+
+    /// Sets the template for the file names.
+    ///
+    /// `{locale}` is replaced with the locale code, `{ext}` with the
+    /// [extension](ExportFormat::extension) and `{format}` with the
+    /// [name](ExportFormat::as_str) of the format,
+    /// e.g. `messages_{locale}.{ext}` becomes `messages_de_DE.properties`.
+    #[must_use]
+    pub fn file_name(mut self, template: impl Into<String>) -> Self {
+        self.file_name = template.into();
+        self
+    }
+
+    /// Sets how many exports are downloaded at the same time by
+    /// [`export_all_locales_async`].
+    ///
+    /// A value of `0` is treated as `1`.
+    #[must_use]
+    pub const fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// The path of the file for the locale.
+    #[must_use]
+    pub fn path(&self, locale: &LocaleCode, format: ExportFormat) -> PathBuf {
+        let name = self
+            .file_name
+            .replace("{locale}", locale.value())
+            .replace("{ext}", format.extension())
+            .replace("{format}", format.as_str());
+        self.dir.join(name)
+    }
+
+    fn create_dir<E>(&self) -> Result<(), ExportError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        std::fs::create_dir_all(&self.dir).map_err(|source| ExportError::Io {
+            path: self.dir.clone(),
+            source,
+        })
+    }
+
+    fn write<E>(&self, path: PathBuf, content: &[u8]) -> Result<PathBuf, ExportError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        match std::fs::write(&path, content) {
+            Ok(()) => Ok(path),
+            Err(source) => Err(ExportError::Io { path, source }),
+        }
+    }
+}
+
+impl From<PathBuf> for LocaleFiles {
+    fn from(dir: PathBuf) -> Self {
+        Self::new(dir)
+    }
+}
+
+impl From<&Path> for LocaleFiles {
+    fn from(dir: &Path) -> Self {
+        Self::new(dir)
+    }
+}
+
+impl From<&str> for LocaleFiles {
+    fn from(dir: &str) -> Self {
+        Self::new(dir)
+    }
+}
+
+impl From<String> for LocaleFiles {
+    fn from(dir: String) -> Self {
+        Self::new(dir)
+    }
+}
+
+/// Exports every locale of the project into a file, one after another.
+///
+/// Returns the path of the file of each locale.
+///
+/// # Errors
+/// This function returns an error if a request fails or a file cannot be written.
+/// The files written up to the error are kept.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::exports::ExportFormat, ops::{self, LocaleFiles}};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// # let dir = std::env::temp_dir().join(format!("traduora-export-{}", std::process::id()));
+/// let files = LocaleFiles::new(&dir).file_name("messages.{locale}.{ext}");
+/// let paths = ops::export_all_locales(
+///     &client,
+///     &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     ExportFormat::JsonFlat,
+///     files,
+/// ).unwrap();
+///
+/// assert!(paths[&"de_DE".into()].ends_with("messages.de_DE.json"));
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn export_all_locales<C>(
+    client: &C,
+    project: &ProjectId,
+    format: ExportFormat,
+    files: impl Into<LocaleFiles>,
+) -> Result<BTreeMap<LocaleCode, PathBuf>, ExportError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    export_all_locales_with_progress(client, project, format, files, &NoProgress)
+}
+
+/// Exports every locale of the project into a file, one after another, and reports the progress.
+///
+/// The total is the number of locales, it is known after the first request.
+/// Returns the path of the file of each locale.
+///
+/// # Errors
+/// This function returns an error if a request fails or a file cannot be written.
+/// The files written up to the error are kept.
+pub fn export_all_locales_with_progress<C>(
+    client: &C,
+    project: &ProjectId,
+    format: ExportFormat,
+    files: impl Into<LocaleFiles>,
+    progress: &dyn ProgressSink,
+) -> Result<BTreeMap<LocaleCode, PathBuf>, ExportError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let files = files.into();
+    files.create_dir()?;
+    let client = Tracker::new(client, progress, 0);
+    let locales = Locales(project.clone()).query(&client)?;
+    client.set_total(locales.len());
+    let mut paths = BTreeMap::new();
+    for locale in locales {
+        let code = locale.locale.code;
+        let endpoint = Export::new(project.clone(), code.clone(), format);
+        let response = api::raw(endpoint).query_raw(&client)?;
+        let path = files.write(files.path(&code, format), response.body())?;
+        client.item_done();
+        paths.insert(code, path);
+    }
+    Ok(paths)
+}
+
+/// Exports every locale of the project into a file asynchronously.
+///
+/// Several exports are downloaded at the same time, see [`LocaleFiles::concurrency`].
+/// Returns the path of the file of each locale.
+///
+/// # Errors
+/// This function returns an error if a request fails or a file cannot be written.
+/// The files written up to the error are kept.
+pub async fn export_all_locales_async<C>(
+    client: &C,
+    project: &ProjectId,
+    format: ExportFormat,
+    files: impl Into<LocaleFiles>,
+) -> Result<BTreeMap<LocaleCode, PathBuf>, ExportError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    export_all_locales_with_progress_async(client, project, format, files, &NoProgress).await
+}
+
+/// Exports every locale of the project into a file asynchronously and reports the progress.
+///
+/// Several exports are downloaded at the same time, see [`LocaleFiles::concurrency`].
+/// The total is the number of locales, it is known after the first request.
+/// Returns the path of the file of each locale.
+///
+/// # Errors
+/// This function returns an error if a request fails or a file cannot be written.
+/// The files written up to the error are kept.
+pub async fn export_all_locales_with_progress_async<C>(
+    client: &C,
+    project: &ProjectId,
+    format: ExportFormat,
+    files: impl Into<LocaleFiles>,
+    progress: &dyn ProgressSink,
+) -> Result<BTreeMap<LocaleCode, PathBuf>, ExportError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let files = files.into();
+    files.create_dir()?;
+    let client = &Tracker::new(client, progress, 0);
+    let locales = Locales(project.clone()).query_async(client).await?;
+    client.set_total(locales.len());
+    stream::iter(locales)
+        .map(|locale| {
+            let code = locale.locale.code;
+            let endpoint = Export::new(project.clone(), code.clone(), format);
+            let files = &files;
+            async move {
+                let response = api::raw(endpoint).query_raw_async(client).await?;
+                let path = files.write(files.path(&code, format), response.body())?;
+                client.item_done();
+                Ok((code, path))
+            }
+        })
+        .buffer_unordered(files.concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LocaleFiles;
+    use crate::api::exports::ExportFormat;
+
+    #[test]
+    fn file_names_are_rendered_from_the_template() {
+        let files = LocaleFiles::from("out").file_name("{format}/messages_{locale}.{ext}");
+        let path = files.path(&"de_DE".into(), ExportFormat::Properties);
+        assert_eq!(
+            path,
+            std::path::Path::new("out/properties/messages_de_DE.properties")
+        );
+        let path = LocaleFiles::from("out").path(&"en".into(), ExportFormat::JsonNested);
+        assert!(path.ends_with("en.json"));
+    }
+}
@@ -0,0 +1,110 @@
+use std::future::Future;
+
+use futures_util::stream::{self, StreamExt};
+
+use crate::{
+    api::{
+        projects::{Project, Projects},
+        BulkResult,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// Runs `f` for every project the user is a member of, one after another.
+///
+/// A failing project does not stop the remaining ones; the outcome of each
+/// project is returned in a [`BulkResult`] in the order of the project list.
+///
+/// # Errors
+/// This function returns an error if the projects cannot be listed.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::terms::Terms, ops, Query};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let result = ops::for_each_project(&client, |project| {
+///     Terms::new(project.id).query(&client).map(|terms| terms.len())
+/// })?;
+///
+/// for (project, error) in result.failed() {
+///     eprintln!("{}: {}", project.name, error);
+/// }
+/// assert!(result.is_ok());
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn for_each_project<C, F, T>(
+    client: &C,
+    mut f: F,
+) -> Result<BulkResult<Project, T, C::Error>, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+    F: FnMut(Project) -> Result<T, ApiError<C::Error>>,
+{
+    Ok(Projects
+        .query(client)?
+        .into_iter()
+        .map(|project| (project.clone(), f(project)))
+        .collect())
+}
+
+/// Runs `f` for every project the user is a member of asynchronously.
+///
+/// Up to `concurrency` projects are processed at the same time. A value of
+/// `0` is treated as `1`. A failing project does not stop the remaining ones;
+/// the outcome of each project is returned in a [`BulkResult`] in the order
+/// of the project list.
+///
+/// # Errors
+/// This function returns an error if the projects cannot be listed.
+pub async fn for_each_project_async<C, F, Fut, T>(
+    client: &C,
+    concurrency: usize,
+    f: F,
+) -> Result<BulkResult<Project, T, C::Error>, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    F: Fn(Project) -> Fut,
+    Fut: Future<Output = Result<T, ApiError<C::Error>>>,
+{
+    let projects = Projects.query_async(client).await?;
+    let results = stream::iter(projects)
+        .map(|project| {
+            let run = f(project.clone());
+            async move { (project, run.await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+    Ok(BulkResult { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::for_each_project_async;
+    use crate::{api::terms::Terms, AsyncQuery, Login, TestClient};
+
+    #[tokio::test]
+    async fn every_project_is_visited() {
+        let login = Login::password("tester@mail.example", "letmeinpls");
+        let client = TestClient::with_auth("localhost:8080", login).unwrap();
+        let visited = AtomicUsize::new(0);
+
+        let result = for_each_project_async(&client, 2, |project| {
+            visited.fetch_add(1, Ordering::SeqCst);
+            let client = &client;
+            async move { Terms::new(project.id).query_async(client).await }
+        })
+        .await
+        .unwrap();
+
+        assert!(result.is_ok());
+        assert!(!result.is_empty());
+        assert_eq!(result.len(), visited.load(Ordering::SeqCst));
+    }
+}
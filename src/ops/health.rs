@@ -0,0 +1,181 @@
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use http::{header, HeaderMap, Response, Version};
+
+use crate::{
+    api::{
+        self,
+        auth::{AuthProvider, Providers},
+    },
+    auth::Unauthenticated,
+    endpoint,
+    query::DefaultModel,
+    ApiError, AsyncClient, AsyncRawQuery, Client, RawQuery,
+};
+
+/// What a Traduora server tells about itself.
+///
+/// Returned by [`server_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ServerInfo {
+    /// Time from sending the request until the full response was received.
+    pub latency: Duration,
+    /// HTTP version of the connection.
+    pub http_version: Version,
+    /// The `Server` header, e.g. the name of a reverse proxy.
+    pub server: Option<String>,
+    /// The `X-Powered-By` header, i.e. the framework of the server.
+    pub powered_by: Option<String>,
+    /// The external login providers that are enabled on the server.
+    pub providers: Vec<AuthProvider>,
+}
+
+impl ServerInfo {
+    fn new<E>(response: &Response<Bytes>, latency: Duration) -> Result<Self, ApiError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let providers = endpoint::process_response(response, Providers::map)?;
+        let headers = response.headers();
+        Ok(Self {
+            latency,
+            http_version: response.version(),
+            server: header_value(headers, header::SERVER.as_str()),
+            powered_by: header_value(headers, "x-powered-by"),
+            providers,
+        })
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(ToOwned::to_owned)
+}
+
+/// Checks that the Traduora server is reachable and answers requests.
+///
+/// This sends a single cheap request that needs no login, so it is
+/// suited for readiness checks, e.g. before a deployment script
+/// changes translations. Returns the round-trip time.
+///
+/// # Errors
+/// This function returns an error if the server cannot be reached
+/// or does not answer with a valid response.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let latency = ops::ping(&client)?;
+/// println!("Traduora answered in {:?}", latency);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn ping<C>(client: &C) -> Result<Duration, ApiError<C::Error>>
+where
+    C: Client,
+    Unauthenticated: From<C::AccessLevel>,
+{
+    server_info(client).map(|info| info.latency)
+}
+
+/// Checks asynchronously that the Traduora server is reachable and answers requests.
+///
+/// See [`ping`].
+///
+/// # Errors
+/// This function returns an error if the server cannot be reached
+/// or does not answer with a valid response.
+pub async fn ping_async<C>(client: &C) -> Result<Duration, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+    Unauthenticated: From<C::AccessLevel>,
+{
+    server_info_async(client).await.map(|info| info.latency)
+}
+
+/// Queries what the Traduora server tells about itself.
+///
+/// Traduora has no endpoint for its version, so the information is
+/// collected from the response headers of the same request as [`ping`].
+///
+/// # Errors
+/// This function returns an error if the server cannot be reached
+/// or does not answer with a valid response.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let info = ops::server_info(&client)?;
+/// for provider in &info.providers {
+///     println!("login with {} is enabled", provider.slug);
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn server_info<C>(client: &C) -> Result<ServerInfo, ApiError<C::Error>>
+where
+    C: Client,
+    Unauthenticated: From<C::AccessLevel>,
+{
+    let start = Instant::now();
+    let response = api::raw(Providers).query_raw(client)?;
+    ServerInfo::new(&response, start.elapsed())
+}
+
+/// Queries asynchronously what the Traduora server tells about itself.
+///
+/// See [`server_info`].
+///
+/// # Errors
+/// This function returns an error if the server cannot be reached
+/// or does not answer with a valid response.
+pub async fn server_info_async<C>(client: &C) -> Result<ServerInfo, ApiError<C::Error>>
+where
+    C: AsyncClient + Sync,
+    Unauthenticated: From<C::AccessLevel>,
+{
+    let start = Instant::now();
+    let response = api::raw(Providers).query_raw_async(client).await?;
+    ServerInfo::new(&response, start.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode, Version};
+    use serde_json::json;
+
+    use super::server_info;
+    use crate::mock::{Expectation, MockTraduora};
+
+    #[test]
+    fn errors_are_reported() {
+        let client = MockTraduora::new();
+        client
+            .expect(
+                Expectation::new(Method::GET, "auth/providers")
+                    .respond_json(StatusCode::OK, json!([])),
+            )
+            .expect(
+                Expectation::new(Method::GET, "auth/providers")
+                    .respond(StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable"),
+            );
+
+        let info = server_info(&client).unwrap();
+        assert_eq!(info.http_version, Version::HTTP_11);
+        assert!(info.providers.is_empty());
+        assert_eq!(info.server, None);
+
+        let error = server_info(&client).unwrap_err();
+        assert!(error.is_transient());
+    }
+}
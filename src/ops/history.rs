@@ -0,0 +1,360 @@
+use std::collections::BTreeMap;
+use std::io;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::ProjectData;
+use crate::{
+    api::{locales::LocaleCode, ProjectId},
+    auth::Authenticated,
+    store::KvStore,
+    ApiError, AsyncClient, Client,
+};
+
+/// All translations of a project at a point in time.
+///
+/// Translations are identified by their term string and locale.
+/// Untranslated terms are not part of the snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// When the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+    /// Translations by term string and locale.
+    pub translations: BTreeMap<String, BTreeMap<LocaleCode, String>>,
+}
+
+impl Snapshot {
+    /// Creates an empty snapshot.
+    #[must_use]
+    pub fn new(taken_at: DateTime<Utc>) -> Self {
+        Self {
+            taken_at,
+            translations: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a translation to the snapshot.
+    #[must_use]
+    pub fn with(
+        mut self,
+        term: impl Into<String>,
+        locale: LocaleCode,
+        value: impl Into<String>,
+    ) -> Self {
+        self.translations
+            .entry(term.into())
+            .or_default()
+            .insert(locale, value.into());
+        self
+    }
+
+    fn from_project(data: ProjectData) -> Self {
+        let terms: BTreeMap<_, _> = data.terms.into_iter().map(|t| (t.id, t.value)).collect();
+        let mut snapshot = Self::new(Utc::now());
+        for (locale, translations) in data.translations {
+            for translation in translations {
+                let term = match terms.get(&translation.term_id) {
+                    Some(term) if !translation.value.is_empty() => term,
+                    _ => continue,
+                };
+                snapshot
+                    .translations
+                    .entry(term.clone())
+                    .or_default()
+                    .insert(locale.clone(), translation.value);
+            }
+        }
+        snapshot
+    }
+}
+
+/// Takes a snapshot of all translations of the project.
+///
+/// # Errors
+/// This method returns an error if fetching the terms or translations fails.
+pub fn fetch_snapshot<C>(client: &C, project: &ProjectId) -> Result<Snapshot, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    ProjectData::fetch(client, project, |_| true).map(Snapshot::from_project)
+}
+
+/// Takes a snapshot of all translations of the project asynchronously.
+///
+/// # Errors
+/// This method returns an error if fetching the terms or translations fails.
+pub async fn fetch_snapshot_async<C>(
+    client: &C,
+    project: &ProjectId,
+) -> Result<Snapshot, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    ProjectData::fetch_async(client, project, |_| true)
+        .await
+        .map(Snapshot::from_project)
+}
+
+/// A change of a single translation between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revision {
+    /// When the snapshot that first contained the change was taken.
+    pub observed_at: DateTime<Utc>,
+    /// The translation since then. `None` if it was removed.
+    pub value: Option<String>,
+}
+
+/// A revision of a term in one of its locales.
+///
+/// Returned by [`HistoryStore::timeline`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEntry<'a> {
+    /// Locale of the translation.
+    pub locale: &'a LocaleCode,
+    /// The translation before the change. `None` if it did not exist.
+    pub old_value: Option<&'a str>,
+    /// The changed translation.
+    pub revision: &'a Revision,
+}
+
+/// The error which is returned when recording a snapshot fails.
+#[derive(Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HistoryError {
+    /// The snapshot is older than the latest recorded snapshot.
+    #[error(
+        "snapshot taken at {} is older than the latest snapshot taken at {}",
+        taken_at,
+        latest
+    )]
+    OutOfOrder {
+        /// When the rejected snapshot was taken.
+        taken_at: DateTime<Utc>,
+        /// When the latest recorded snapshot was taken.
+        latest: DateTime<Utc>,
+    },
+}
+
+/// Reconstructs the history of translations from periodic snapshots.
+///
+/// Traduora does not keep old versions of translations. Recording snapshots,
+/// e.g. from a nightly job, allows to look up what a translation was at any
+/// time since the first snapshot. Only changes are stored, so recording
+/// unchanged snapshots is cheap. Use [`HistoryStore::save`] and [`HistoryStore::load`]
+/// to persist it between runs.
+///
+/// The precision is limited by the snapshot interval: a change is dated
+/// to the first snapshot that contained it.
+///
+/// # Examples
+/// ```
+/// use traduora::ops::{HistoryStore, Snapshot};
+///
+/// let mut history = HistoryStore::new();
+/// let monday = "2022-05-02T00:00:00Z".parse().unwrap();
+/// let friday = "2022-05-06T00:00:00Z".parse().unwrap();
+/// history.record(&Snapshot::new(monday).with("greeting", "de_DE".into(), "Hallo"))?;
+/// history.record(&Snapshot::new(friday).with("greeting", "de_DE".into(), "Guten Tag"))?;
+///
+/// let wednesday = "2022-05-04T00:00:00Z".parse().unwrap();
+/// assert_eq!(history.value_at("greeting", &"de_DE".into(), wednesday), Some("Hallo"));
+/// assert_eq!(history.timeline("greeting").len(), 2);
+/// # Ok::<(), traduora::ops::HistoryError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryStore {
+    latest: Option<DateTime<Utc>>,
+    revisions: BTreeMap<String, BTreeMap<LocaleCode, Vec<Revision>>>,
+}
+
+impl HistoryStore {
+    /// Creates an empty history.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// When the latest recorded snapshot was taken.
+    #[must_use]
+    pub const fn latest(&self) -> Option<DateTime<Utc>> {
+        self.latest
+    }
+
+    /// Stores the changes of the snapshot compared to the previous one.
+    ///
+    /// Translations that are missing from the snapshot are recorded as removed.
+    ///
+    /// # Errors
+    /// This method returns an error if the snapshot is older than the latest
+    /// recorded snapshot.
+    pub fn record(&mut self, snapshot: &Snapshot) -> Result<(), HistoryError> {
+        if let Some(latest) = self.latest.filter(|l| *l > snapshot.taken_at) {
+            return Err(HistoryError::OutOfOrder {
+                taken_at: snapshot.taken_at,
+                latest,
+            });
+        }
+        let observed_at = snapshot.taken_at;
+
+        for (term, locales) in &mut self.revisions {
+            for (locale, revisions) in locales {
+                let current = snapshot.translations.get(term).and_then(|t| t.get(locale));
+                if current.is_none() && last_value(revisions).is_some() {
+                    revisions.push(Revision {
+                        observed_at,
+                        value: None,
+                    });
+                }
+            }
+        }
+
+        for (term, locales) in &snapshot.translations {
+            for (locale, value) in locales {
+                let revisions = self
+                    .revisions
+                    .entry(term.clone())
+                    .or_default()
+                    .entry(locale.clone())
+                    .or_default();
+                if last_value(revisions) != Some(value) {
+                    revisions.push(Revision {
+                        observed_at,
+                        value: Some(value.clone()),
+                    });
+                }
+            }
+        }
+
+        self.latest = Some(observed_at);
+        Ok(())
+    }
+
+    /// The translation as it was recorded at the given time.
+    ///
+    /// Returns `None` if the translation did not exist or the time
+    /// is before the first snapshot containing it.
+    #[must_use]
+    pub fn value_at(&self, term: &str, locale: &LocaleCode, at: DateTime<Utc>) -> Option<&str> {
+        self.revisions(term, locale)
+            .iter()
+            .take_while(|r| r.observed_at <= at)
+            .last()
+            .and_then(|r| r.value.as_deref())
+    }
+
+    /// All revisions of the translation in chronological order.
+    #[must_use]
+    pub fn revisions(&self, term: &str, locale: &LocaleCode) -> &[Revision] {
+        self.revisions
+            .get(term)
+            .and_then(|l| l.get(locale))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// All changes of the term's translations across locales in chronological order.
+    #[must_use]
+    pub fn timeline(&self, term: &str) -> Vec<TimelineEntry<'_>> {
+        let mut timeline: Vec<_> = self
+            .revisions
+            .get(term)
+            .into_iter()
+            .flatten()
+            .flat_map(|(locale, revisions)| {
+                revisions.iter().enumerate().map(move |(i, revision)| {
+                    let old_value = i
+                        .checked_sub(1)
+                        .and_then(|prev| revisions[prev].value.as_deref());
+                    TimelineEntry {
+                        locale,
+                        old_value,
+                        revision,
+                    }
+                })
+            })
+            .collect();
+        timeline.sort_by_key(|e| e.revision.observed_at);
+        timeline
+    }
+
+    /// Reads a history that was saved with [`HistoryStore::save`].
+    ///
+    /// Returns an empty history if the key does not exist.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be read or the value is not a history.
+    pub fn load<S: KvStore>(store: &S, key: &str) -> io::Result<Self> {
+        Ok(store.get_json(key)?.unwrap_or_default())
+    }
+
+    /// Writes the history to the store.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be written.
+    pub fn save<S: KvStore>(&self, store: &S, key: &str) -> io::Result<()> {
+        store.put_json(key, self, None)
+    }
+
+    /// Terms that were part of any recorded snapshot.
+    pub fn terms(&self) -> impl Iterator<Item = &str> {
+        self.revisions.keys().map(String::as_str)
+    }
+}
+
+fn last_value(revisions: &[Revision]) -> Option<&String> {
+    revisions.last().and_then(|r| r.value.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::{HistoryError, HistoryStore, Snapshot};
+
+    fn day(day: u32) -> DateTime<Utc> {
+        format!("2022-05-{:02}T12:00:00Z", day).parse().unwrap()
+    }
+
+    #[test]
+    fn unchanged_translations_are_not_stored_twice() {
+        let mut history = HistoryStore::new();
+        let snapshot = |d| Snapshot::new(day(d)).with("hello", "de".into(), "Hallo");
+        history.record(&snapshot(1)).unwrap();
+        history.record(&snapshot(2)).unwrap();
+
+        assert_eq!(history.revisions("hello", &"de".into()).len(), 1);
+        assert_eq!(history.latest(), Some(day(2)));
+    }
+
+    #[test]
+    fn removed_translations_are_recorded() {
+        let mut history = HistoryStore::new();
+        history
+            .record(&Snapshot::new(day(1)).with("hello", "de".into(), "Hallo"))
+            .unwrap();
+        history.record(&Snapshot::new(day(3))).unwrap();
+
+        let de = "de".into();
+        assert_eq!(history.value_at("hello", &de, day(2)), Some("Hallo"));
+        assert_eq!(history.value_at("hello", &de, day(3)), None);
+        let timeline = history.timeline("hello");
+        assert_eq!(timeline[1].old_value, Some("Hallo"));
+        assert_eq!(timeline[1].revision.value, None);
+    }
+
+    #[test]
+    fn older_snapshots_are_rejected() {
+        let mut history = HistoryStore::new();
+        history.record(&Snapshot::new(day(2))).unwrap();
+
+        let error = history.record(&Snapshot::new(day(1))).unwrap_err();
+        assert_eq!(
+            error,
+            HistoryError::OutOfOrder {
+                taken_at: day(1),
+                latest: day(2)
+            }
+        );
+    }
+}
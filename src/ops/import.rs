@@ -0,0 +1,274 @@
+use std::collections::BTreeSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::{
+    api::{
+        exports::ExportFormat,
+        imports::{Import, ImportSummary},
+        locales::LocaleCode,
+        translations::{CreateLocale, Locales},
+        BulkResult, ProjectId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// The error which is returned by [`import_dir`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum ImportError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// The locales of the project could not be listed.
+    #[error("failed to import files: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// The directory or a file in it could not be read.
+    #[error("failed to read {}: {}", path.display(), source)]
+    Io {
+        /// Path of the directory or file.
+        path: PathBuf,
+        /// Inner error.
+        source: io::Error,
+    },
+}
+
+/// A file of the directory with the locale it contains.
+struct LocaleFile {
+    path: PathBuf,
+    locale: LocaleCode,
+    content: Vec<u8>,
+}
+
+/// Reads the files of the directory for which `mapping` returns a locale, sorted by path.
+fn read_dir<E, M>(dir: &Path, mut mapping: M) -> Result<Vec<LocaleFile>, ImportError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    M: FnMut(&Path) -> Option<LocaleCode>,
+{
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |source| ImportError::Io { path, source }
+    };
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut files = Vec::new();
+    for path in paths {
+        if let Some(locale) = mapping(&path) {
+            let content = std::fs::read(&path).map_err(io_error(&path))?;
+            files.push(LocaleFile {
+                path,
+                locale,
+                content,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// Imports every file of the directory into the locale that `mapping` returns for it.
+///
+/// Files for which `mapping` returns `None` and subdirectories are ignored.
+/// Locales that the project does not have yet are created before their file is imported.
+/// The files are imported one after another in the order of their paths.
+///
+/// A failing file does not stop the remaining ones; the outcome of each
+/// file is returned in a [`BulkResult`].
+///
+/// # Errors
+/// This function returns an error if the directory or a file cannot be read
+/// or the locales of the project cannot be listed. Nothing is imported in that case.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::exports::ExportFormat, ops};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// # let dir = std::env::temp_dir().join(format!("traduora-import-{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir).unwrap();
+/// # std::fs::write(dir.join("de_DE.json"), r#"{"hello.world": "Hallo Welt"}"#).unwrap();
+/// # std::fs::write(dir.join("README.md"), "Translations of the app").unwrap();
+/// let result = ops::import_dir(
+///     &client,
+///     &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+///     &dir,
+///     ExportFormat::JsonFlat,
+///     |path| match path.extension() {
+///         Some(ext) if ext == "json" => path.file_stem()?.to_str().map(Into::into),
+///         _ => None,
+///     },
+/// ).unwrap();
+///
+/// assert_eq!(result.len(), 1);
+/// for (path, summary) in result.succeeded() {
+///     println!("{}: {} new terms", path.display(), summary.terms.added);
+/// }
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn import_dir<C, M>(
+    client: &C,
+    project: &ProjectId,
+    dir: impl AsRef<Path>,
+    format: ExportFormat,
+    mapping: M,
+) -> Result<BulkResult<PathBuf, ImportSummary, C::Error>, ImportError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+    M: FnMut(&Path) -> Option<LocaleCode>,
+{
+    let files = read_dir(dir.as_ref(), mapping)?;
+    let mut existing: BTreeSet<_> = Locales(project.clone())
+        .query(client)?
+        .into_iter()
+        .map(|l| l.locale.code)
+        .collect();
+
+    let mut import = |file: LocaleFile| {
+        if !existing.contains(&file.locale) {
+            match CreateLocale::new(project.clone(), file.locale.clone()).query(client) {
+                Err(e) if !e.is_conflict() => return Err(e),
+                _ => existing.insert(file.locale.clone()),
+            };
+        }
+        Import::new(project.clone(), file.locale, format, file.content).query(client)
+    };
+    Ok(files
+        .into_iter()
+        .map(|file| (file.path.clone(), import(file)))
+        .collect())
+}
+
+/// Imports every file of the directory asynchronously into the locale that `mapping` returns for it.
+///
+/// Files for which `mapping` returns `None` and subdirectories are ignored.
+/// Locales that the project does not have yet are created before their file is imported.
+/// The files are imported one after another in the order of their paths because
+/// concurrent imports could try to create the same new terms.
+///
+/// A failing file does not stop the remaining ones; the outcome of each
+/// file is returned in a [`BulkResult`].
+///
+/// # Errors
+/// This function returns an error if the directory or a file cannot be read
+/// or the locales of the project cannot be listed. Nothing is imported in that case.
+pub async fn import_dir_async<C, M>(
+    client: &C,
+    project: &ProjectId,
+    dir: impl AsRef<Path>,
+    format: ExportFormat,
+    mapping: M,
+) -> Result<BulkResult<PathBuf, ImportSummary, C::Error>, ImportError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    M: FnMut(&Path) -> Option<LocaleCode>,
+{
+    let files = read_dir(dir.as_ref(), mapping)?;
+    let mut existing: BTreeSet<_> = Locales(project.clone())
+        .query_async(client)
+        .await?
+        .into_iter()
+        .map(|l| l.locale.code)
+        .collect();
+
+    let mut results = Vec::with_capacity(files.len());
+    for file in files {
+        if !existing.contains(&file.locale) {
+            match CreateLocale::new(project.clone(), file.locale.clone())
+                .query_async(client)
+                .await
+            {
+                Err(e) if !e.is_conflict() => {
+                    results.push((file.path, Err(e)));
+                    continue;
+                }
+                _ => existing.insert(file.locale.clone()),
+            };
+        }
+        let result = Import::new(project.clone(), file.locale, format, file.content)
+            .query_async(client)
+            .await;
+        results.push((file.path, result));
+    }
+    Ok(BulkResult { results })
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::import_dir;
+    use crate::{
+        api::exports::ExportFormat,
+        fixtures,
+        mock::{Expectation, MockTraduora},
+    };
+
+    #[test]
+    fn missing_locales_are_created_before_import() {
+        let dir = std::env::temp_dir().join(format!("traduora-import-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("de_DE.properties"), "hello=Hallo").unwrap();
+        std::fs::write(dir.join("fr.properties"), "hello=Salut").unwrap();
+
+        let summary =
+            json!({ "terms": { "added": 0, "skipped": 1 }, "translations": { "upserted": 1 } });
+        let client = MockTraduora::new();
+        client
+            .expect(
+                Expectation::new(Method::GET, "projects/p/translations").respond_json(
+                    StatusCode::OK,
+                    fixtures::data(json!([fixtures::project_locale("l", "de_DE")])),
+                ),
+            )
+            .expect(
+                Expectation::new(Method::POST, "projects/p/translations")
+                    .body(json!({ "code": "fr" }))
+                    .respond_json(
+                        StatusCode::CREATED,
+                        fixtures::data(fixtures::project_locale("m", "fr")),
+                    ),
+            )
+            .expect(
+                Expectation::new(Method::POST, "projects/p/imports")
+                    .times(2)
+                    .respond_json(StatusCode::OK, fixtures::data(summary)),
+            );
+
+        let result = import_dir(&client, &"p".into(), &dir, ExportFormat::Properties, |p| {
+            p.file_stem()?.to_str().map(Into::into)
+        })
+        .unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_ok());
+        assert_eq!(result.len(), 2);
+        client.verify().unwrap();
+        let queries: Vec<_> = client.calls().into_iter().filter_map(|c| c.query).collect();
+        assert_eq!(
+            queries,
+            [
+                "locale=de_DE&format=properties",
+                "locale=fr&format=properties"
+            ]
+        );
+    }
+}
@@ -0,0 +1,356 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{ApiError, Endpoint};
+
+/// A mutating request that is recorded in a [`Journal`] before it is sent.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct JournalOperation {
+    /// HTTP method of the request, e.g. `PATCH`.
+    pub method: String,
+    /// Endpoint of the request relative to the API root.
+    pub endpoint: String,
+    /// Human readable description of the change.
+    pub description: String,
+}
+
+impl JournalOperation {
+    /// Describes the request the endpoint sends.
+    pub fn new<E: Endpoint>(endpoint: &E, description: impl Into<String>) -> Self {
+        Self {
+            method: endpoint.method().to_string(),
+            endpoint: endpoint.endpoint().into_owned(),
+            description: description.into(),
+        }
+    }
+}
+
+/// Whether a journaled operation reached the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationState {
+    /// The server confirmed the operation.
+    Applied,
+    /// The request was started but its outcome is unknown.
+    /// The server may or may not have applied it.
+    Uncertain,
+    /// The request was never started.
+    NotApplied,
+}
+
+/// The error which is returned when reading or writing a journal fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum JournalError {
+    /// Accessing the journal file failed.
+    #[error("failed to access journal: {}", source)]
+    Io {
+        /// Inner error.
+        #[from]
+        source: io::Error,
+    },
+    /// A record of the journal could not be parsed.
+    #[error("invalid journal record: {}", source)]
+    Record {
+        /// Inner error.
+        #[from]
+        source: serde_json::Error,
+    },
+    /// The journal does not start with the list of intended operations.
+    #[error("journal does not contain the intended operations")]
+    MissingIntent,
+    /// A record refers to an operation that was not announced.
+    #[error("journal refers to unknown operation {}", index)]
+    UnknownOperation {
+        /// Index of the operation.
+        index: usize,
+    },
+}
+
+/// The error which is returned by journaled plan execution.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum JournaledError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Writing the journal failed. The operation was not sent.
+    #[error("{}", source)]
+    Journal {
+        /// Inner error.
+        #[from]
+        source: JournalError,
+    },
+    /// Sending the operation failed.
+    #[error("{}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Record {
+    Intent(Vec<JournalOperation>),
+    Started(usize),
+    Completed(usize),
+    Finished,
+}
+
+/// A write-ahead journal of mutating operations.
+///
+/// The full list of intended operations is written to the journal file
+/// before the first of them is sent. Afterwards, every operation is marked
+/// as started before its request is sent and as completed once the server
+/// confirmed it. Every record is flushed to disk immediately.
+///
+/// If the process crashes or the future applying the operations is dropped,
+/// [`Journal::recover`] reads the file and reports which operations were
+/// applied, which may have been applied and which were never sent.
+///
+/// # Examples
+/// ```
+/// use traduora::{api::terms::DeleteTerm, ops::{Journal, JournalOperation, OperationState}};
+///
+/// # let path = std::env::temp_dir().join(format!("traduora-journal-doc-{}", std::process::id()));
+/// let endpoint = DeleteTerm::new("project".into(), "term".into());
+/// let operations = vec![JournalOperation::new(&endpoint, "delete unused term")];
+///
+/// let mut journal = Journal::create(&path, operations)?;
+/// journal.start(0)?;
+/// // the process crashes while the request is sent
+/// drop(journal);
+///
+/// let recovery = Journal::recover(&path)?;
+/// assert_eq!(recovery.state(0), Some(OperationState::Uncertain));
+/// assert!(!recovery.is_finished());
+/// # std::fs::remove_file(&path).unwrap();
+/// # Ok::<(), traduora::ops::JournalError>(())
+/// ```
+#[derive(Debug)]
+pub struct Journal {
+    file: File,
+    operations: usize,
+}
+
+impl Journal {
+    /// Creates the journal file and records the intended operations.
+    ///
+    /// # Errors
+    /// This method returns an error if the file already exists or cannot be written.
+    pub fn create(
+        path: impl AsRef<Path>,
+        operations: Vec<JournalOperation>,
+    ) -> Result<Self, JournalError> {
+        let file = OpenOptions::new().write(true).create_new(true).open(path)?;
+        let mut journal = Self {
+            file,
+            operations: operations.len(),
+        };
+        journal.append(&Record::Intent(operations))?;
+        Ok(journal)
+    }
+
+    /// Marks the operation as started. Call this right before sending it.
+    ///
+    /// # Errors
+    /// This method returns an error if the index is out of range or writing fails.
+    pub fn start(&mut self, index: usize) -> Result<(), JournalError> {
+        self.check(index)?;
+        self.append(&Record::Started(index))
+    }
+
+    /// Marks the operation as applied. Call this once the server confirmed it.
+    ///
+    /// # Errors
+    /// This method returns an error if the index is out of range or writing fails.
+    pub fn complete(&mut self, index: usize) -> Result<(), JournalError> {
+        self.check(index)?;
+        self.append(&Record::Completed(index))
+    }
+
+    /// Marks the whole plan as done.
+    ///
+    /// # Errors
+    /// This method returns an error if writing fails.
+    pub fn finish(mut self) -> Result<(), JournalError> {
+        self.append(&Record::Finished)
+    }
+
+    /// Reads a journal to find out how far the operations got.
+    ///
+    /// An incomplete last record, e.g. due to a crash while writing it, is ignored.
+    ///
+    /// # Errors
+    /// This method returns an error if the file cannot be read or is not a journal.
+    pub fn recover(path: impl AsRef<Path>) -> Result<Recovery, JournalError> {
+        let mut lines = BufReader::new(File::open(path)?).lines().peekable();
+        let operations = match lines.next().transpose()? {
+            Some(line) => match serde_json::from_str(&line)? {
+                Record::Intent(operations) => operations,
+                _ => return Err(JournalError::MissingIntent),
+            },
+            None => return Err(JournalError::MissingIntent),
+        };
+
+        let mut recovery = Recovery {
+            states: vec![OperationState::NotApplied; operations.len()],
+            operations,
+            finished: false,
+        };
+        while let Some(line) = lines.next().transpose()? {
+            let record = match serde_json::from_str(&line) {
+                Ok(record) => record,
+                Err(_) if lines.peek().is_none() => break,
+                Err(e) => return Err(e.into()),
+            };
+            let (index, state) = match record {
+                Record::Started(index) => (index, OperationState::Uncertain),
+                Record::Completed(index) => (index, OperationState::Applied),
+                Record::Finished => {
+                    recovery.finished = true;
+                    continue;
+                }
+                Record::Intent(_) => return Err(JournalError::MissingIntent),
+            };
+            *recovery
+                .states
+                .get_mut(index)
+                .ok_or(JournalError::UnknownOperation { index })? = state;
+        }
+        Ok(recovery)
+    }
+
+    fn check(&self, index: usize) -> Result<(), JournalError> {
+        if index < self.operations {
+            Ok(())
+        } else {
+            Err(JournalError::UnknownOperation { index })
+        }
+    }
+
+    fn append(&mut self, record: &Record) -> Result<(), JournalError> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
+
+/// What a journal tells about the operations it recorded.
+///
+/// Returned by [`Journal::recover`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Recovery {
+    operations: Vec<JournalOperation>,
+    states: Vec<OperationState>,
+    finished: bool,
+}
+
+impl Recovery {
+    /// Whether all operations were processed and the journal was closed.
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// State of the operation with the given index.
+    pub fn state(&self, index: usize) -> Option<OperationState> {
+        self.states.get(index).copied()
+    }
+
+    /// All intended operations with their states in the original order.
+    pub fn operations(&self) -> impl Iterator<Item = (&JournalOperation, OperationState)> {
+        self.operations.iter().zip(self.states.iter().copied())
+    }
+
+    /// Operations that the server confirmed.
+    pub fn applied(&self) -> impl Iterator<Item = &JournalOperation> {
+        self.with_state(OperationState::Applied)
+    }
+
+    /// Operations whose outcome is unknown.
+    /// Check them on the server before running the plan again.
+    pub fn uncertain(&self) -> impl Iterator<Item = &JournalOperation> {
+        self.with_state(OperationState::Uncertain)
+    }
+
+    /// Operations that were never sent.
+    pub fn not_applied(&self) -> impl Iterator<Item = &JournalOperation> {
+        self.with_state(OperationState::NotApplied)
+    }
+
+    fn with_state(&self, state: OperationState) -> impl Iterator<Item = &JournalOperation> {
+        self.operations()
+            .filter(move |(_, s)| *s == state)
+            .map(|(op, _)| op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use super::{Journal, JournalOperation, OperationState};
+
+    fn journal_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("traduora-journal-{}-{}", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn operation(description: &str) -> JournalOperation {
+        JournalOperation {
+            method: "PATCH".into(),
+            endpoint: "projects/p/translations/de".into(),
+            description: description.into(),
+        }
+    }
+
+    #[test]
+    fn recovery_reports_state_of_each_operation() {
+        let path = journal_path("states");
+        let ops = vec![operation("first"), operation("second"), operation("third")];
+        let mut journal = Journal::create(&path, ops).unwrap();
+        journal.start(0).unwrap();
+        journal.complete(0).unwrap();
+        journal.start(1).unwrap();
+        drop(journal);
+
+        let recovery = Journal::recover(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(!recovery.is_finished());
+        assert_eq!(recovery.applied().next().unwrap().description, "first");
+        assert_eq!(recovery.uncertain().next().unwrap().description, "second");
+        assert_eq!(recovery.not_applied().next().unwrap().description, "third");
+    }
+
+    #[test]
+    fn truncated_last_record_is_ignored() {
+        let path = journal_path("truncated");
+        let mut journal = Journal::create(&path, vec![operation("only")]).unwrap();
+        journal.start(0).unwrap();
+        drop(journal);
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"compl")
+            .unwrap();
+
+        let recovery = Journal::recover(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(recovery.state(0), Some(OperationState::Uncertain));
+    }
+}
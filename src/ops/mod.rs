@@ -0,0 +1,119 @@
+//! Higher-level operations that combine several endpoints.
+//!
+//! The functions in this module query the Traduora API multiple times
+//! to accomplish common workflows that have no single endpoint.
+
+mod copy;
+mod ensure;
+mod export;
+mod fan_out;
+mod health;
+mod history;
+mod import;
+mod journal;
+mod permissions;
+mod promote;
+mod prune;
+mod pseudo;
+mod rename;
+
+use std::collections::BTreeMap;
+
+pub use copy::{copy_locale, copy_locale_async};
+pub use ensure::{ensure_locale, ensure_locale_async, ensure_term, ensure_term_async};
+pub use export::{
+    export_all_locales, export_all_locales_async, export_all_locales_with_progress,
+    export_all_locales_with_progress_async, ExportError, LocaleFiles,
+};
+pub use fan_out::{for_each_project, for_each_project_async};
+pub use health::{ping, ping_async, server_info, server_info_async, ServerInfo};
+pub use history::{
+    fetch_snapshot, fetch_snapshot_async, HistoryError, HistoryStore, Revision, Snapshot,
+    TimelineEntry,
+};
+pub use import::{import_dir, import_dir_async, ImportError};
+pub use journal::{
+    Journal, JournalError, JournalOperation, JournaledError, OperationState, Recovery,
+};
+pub use permissions::{probe_permissions, probe_permissions_async, Capability, Permissions};
+pub use promote::{
+    plan_promotion, plan_promotion_async, promote, promote_async, Promotion, PromotionError,
+    PromotionFilter, TranslationChange,
+};
+pub use prune::{
+    plan_pruning, plan_pruning_async, prune_terms, prune_terms_async, PruneError, Pruning,
+};
+pub use pseudo::{pseudolocalize, pseudolocalize_async, pseudolocalize_text};
+pub use rename::{rename_by_key, rename_by_key_async, rename_term, rename_term_async, RenameError};
+
+use crate::{
+    api::{
+        locales::LocaleCode,
+        terms::{Term, Terms},
+        translations::{Locales, Translation, Translations},
+        ProjectId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// Terms and translations of a project as returned by the list endpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ProjectData {
+    pub(crate) terms: Vec<Term>,
+    pub(crate) translations: BTreeMap<LocaleCode, Vec<Translation>>,
+}
+
+impl ProjectData {
+    /// Fetches all terms and the translations of all project locales that pass the filter.
+    pub(crate) fn fetch<C, F>(
+        client: &C,
+        project: &ProjectId,
+        mut filter: F,
+    ) -> Result<Self, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+        F: FnMut(&LocaleCode) -> bool,
+    {
+        let terms = Terms::new(project.clone()).query(client)?;
+        let mut translations = BTreeMap::new();
+        for locale in Locales(project.clone()).query(client)? {
+            let code = locale.locale.code;
+            if filter(&code) {
+                let values = Translations::new(project.clone(), code.clone()).query(client)?;
+                translations.insert(code, values);
+            }
+        }
+        Ok(Self {
+            terms,
+            translations,
+        })
+    }
+
+    /// Fetches all terms and the translations of all project locales that pass the filter.
+    pub(crate) async fn fetch_async<C, F>(
+        client: &C,
+        project: &ProjectId,
+        mut filter: F,
+    ) -> Result<Self, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+        F: FnMut(&LocaleCode) -> bool,
+    {
+        let terms = Terms::new(project.clone()).query_async(client).await?;
+        let mut translations = BTreeMap::new();
+        for locale in Locales(project.clone()).query_async(client).await? {
+            let code = locale.locale.code;
+            if filter(&code) {
+                let values = Translations::new(project.clone(), code.clone())
+                    .query_async(client)
+                    .await?;
+                translations.insert(code, values);
+            }
+        }
+        Ok(Self {
+            terms,
+            translations,
+        })
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    api::{projects::ShowProject, ProjectId, Role},
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// An action a user may be allowed to perform on a project.
+///
+/// For the mapping of roles to actions, see
+/// <https://docs.traduora.co/docs/api/v1/roles-permissions>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Capability {
+    /// View the project, its terms, translations and locales and export them.
+    Read,
+    /// Change the translations of terms and import translations.
+    EditTranslations,
+    /// Add, edit and delete terms and labels.
+    ManageTerms,
+    /// Add and delete project locales.
+    ManageLocales,
+    /// Change the project settings, manage the team and API clients and delete the project.
+    Admin,
+}
+
+impl Capability {
+    /// All capabilities from the least to the most privileged one.
+    pub const ALL: [Self; 5] = [
+        Self::Read,
+        Self::EditTranslations,
+        Self::ManageTerms,
+        Self::ManageLocales,
+        Self::Admin,
+    ];
+
+    /// Whether a user with the given role has this capability.
+    #[must_use]
+    pub const fn granted_to(self, role: Role) -> bool {
+        match role {
+            Role::Admin => true,
+            Role::Editor => !matches!(self, Self::Admin),
+            Role::Viewer => matches!(self, Self::Read),
+        }
+    }
+}
+
+/// What the current user may do on a project.
+///
+/// Returned by [`probe_permissions`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Permissions {
+    /// The project the permissions apply to.
+    pub project: ProjectId,
+    /// Role of the user within the project.
+    pub role: Role,
+}
+
+impl Permissions {
+    /// Whether the user may perform the action.
+    #[must_use]
+    pub const fn can(&self, capability: Capability) -> bool {
+        capability.granted_to(self.role)
+    }
+
+    /// All actions the user may perform.
+    #[must_use]
+    pub fn capabilities(&self) -> BTreeSet<Capability> {
+        Capability::ALL
+            .into_iter()
+            .filter(|c| self.can(*c))
+            .collect()
+    }
+}
+
+/// Determines what the current user may do on the project.
+///
+/// The capabilities are derived from the role of the user in the project,
+/// so no action is actually attempted.
+///
+/// # Errors
+/// This function returns an error if the project could not be queried,
+/// e.g. because the user is no member of the project.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::{self, Capability};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let permissions = ops::probe_permissions(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+///
+/// assert!(permissions.can(Capability::ManageTerms));
+/// assert!(permissions.can(Capability::Admin));
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn probe_permissions<C>(
+    client: &C,
+    project: &ProjectId,
+) -> Result<Permissions, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let project = ShowProject(project.clone()).query(client)?;
+    Ok(Permissions {
+        project: project.id,
+        role: project.role,
+    })
+}
+
+/// Determines asynchronously what the current user may do on the project.
+///
+/// See [`probe_permissions`].
+///
+/// # Errors
+/// This function returns an error if the project could not be queried,
+/// e.g. because the user is no member of the project.
+pub async fn probe_permissions_async<C>(
+    client: &C,
+    project: &ProjectId,
+) -> Result<Permissions, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let project = ShowProject(project.clone()).query_async(client).await?;
+    Ok(Permissions {
+        project: project.id,
+        role: project.role,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{Capability, Permissions};
+    use crate::api::Role;
+
+    fn capabilities(role: Role) -> BTreeSet<Capability> {
+        Permissions {
+            project: "project".into(),
+            role,
+        }
+        .capabilities()
+    }
+
+    #[test]
+    fn roles_map_to_capabilities() {
+        assert_eq!(capabilities(Role::Admin), Capability::ALL.into());
+        assert_eq!(
+            capabilities(Role::Editor),
+            [
+                Capability::Read,
+                Capability::EditTranslations,
+                Capability::ManageTerms,
+                Capability::ManageLocales
+            ]
+            .into()
+        );
+        assert_eq!(capabilities(Role::Viewer), [Capability::Read].into());
+    }
+}
@@ -0,0 +1,505 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use super::{Journal, JournalOperation, JournaledError, ProjectData};
+use crate::{
+    api::{self, locales::LocaleCode, translations::EditTranslation, ProjectId, TermId},
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncCustomQuery, Client, CustomQuery,
+};
+
+/// Selects which translations are promoted by [`promote`].
+///
+/// Traduora has no approval workflow of its own, so approved translations
+/// are usually tagged with a label like `approved`.
+///
+/// # Examples
+/// ```
+/// use traduora::ops::PromotionFilter;
+///
+/// let filter = PromotionFilter::new()
+///     .label("approved")
+///     .locale("de_DE".into());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct PromotionFilter {
+    labels: BTreeSet<String>,
+    locales: Option<BTreeSet<LocaleCode>>,
+}
+
+impl PromotionFilter {
+    /// Creates a filter that lets all translations of all locales pass.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only promotes translations tagged with this label.
+    ///
+    /// If several labels are given, translations need to carry at least one of them.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.labels.insert(label.into());
+        self
+    }
+
+    /// Only promotes translations of this locale.
+    ///
+    /// May be called multiple times to promote several locales.
+    pub fn locale(mut self, locale: LocaleCode) -> Self {
+        self.locales
+            .get_or_insert_with(BTreeSet::new)
+            .insert(locale);
+        self
+    }
+
+    fn matches_locale(&self, locale: &LocaleCode) -> bool {
+        self.locales.as_ref().map_or(true, |l| l.contains(locale))
+    }
+
+    fn matches_labels(&self, labels: &[api::labels::Label]) -> bool {
+        self.labels.is_empty() || labels.iter().any(|l| self.labels.contains(&l.value))
+    }
+}
+
+/// A single translation that is copied to the target project.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct TranslationChange {
+    /// The term string that identifies the term in both projects.
+    pub term: String,
+    /// Unique id of the term in the target project.
+    pub term_id: TermId,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The current translation in the target project.
+    /// Empty if the term is not translated yet.
+    pub old_value: String,
+    /// The translation from the source project.
+    pub new_value: String,
+}
+
+/// The difference between the translations of two projects.
+///
+/// Returned by [`plan_promotion`] without modifying the target project,
+/// so it can be reviewed before it is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Promotion {
+    /// The project that receives the translations.
+    pub target_project: ProjectId,
+    /// The translations that differ between source and target project.
+    pub changes: Vec<TranslationChange>,
+    /// Terms of the source project that do not exist in the target project.
+    /// Their translations are not promoted.
+    pub missing_terms: BTreeSet<String>,
+    /// Locales of the source project that do not exist in the target project.
+    /// Their translations are not promoted.
+    pub missing_locales: BTreeSet<LocaleCode>,
+}
+
+/// The error which is returned when promoting translations fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PromotionError<S, T>
+where
+    S: std::error::Error + Send + Sync + 'static,
+    T: std::error::Error + Send + Sync + 'static,
+{
+    /// Reading from the source instance failed.
+    #[error("failed to read source project: {}", source)]
+    Source {
+        /// Inner error.
+        source: ApiError<S>,
+    },
+    /// Accessing the target instance failed.
+    #[error("failed to access target project: {}", source)]
+    Target {
+        /// Inner error.
+        source: ApiError<T>,
+    },
+}
+
+impl Promotion {
+    fn new(
+        source: &ProjectData,
+        target: &ProjectData,
+        target_project: ProjectId,
+        filter: &PromotionFilter,
+    ) -> Self {
+        let source_keys: BTreeMap<_, _> = source.terms.iter().map(|t| (&t.id, &t.value)).collect();
+        let target_ids: BTreeMap<_, _> = target.terms.iter().map(|t| (&t.value, &t.id)).collect();
+
+        let mut promotion = Self {
+            target_project,
+            changes: Vec::new(),
+            missing_terms: BTreeSet::new(),
+            missing_locales: BTreeSet::new(),
+        };
+
+        for (locale, translations) in &source.translations {
+            let target_values: BTreeMap<_, _> = match target.translations.get(locale) {
+                Some(t) => t.iter().map(|t| (&t.term_id, &t.value)).collect(),
+                None => {
+                    promotion.missing_locales.insert(locale.clone());
+                    continue;
+                }
+            };
+
+            let promoted = translations
+                .iter()
+                .filter(|t| !t.value.is_empty() && filter.matches_labels(&t.labels));
+            for translation in promoted {
+                let key = match source_keys.get(&translation.term_id) {
+                    Some(key) => *key,
+                    None => continue,
+                };
+                let term_id = match target_ids.get(key) {
+                    Some(id) => *id,
+                    None => {
+                        promotion.missing_terms.insert(key.clone());
+                        continue;
+                    }
+                };
+                let old_value = target_values.get(term_id).map_or("", |v| v.as_str());
+                if old_value != translation.value {
+                    promotion.changes.push(TranslationChange {
+                        term: key.clone(),
+                        term_id: term_id.clone(),
+                        locale: locale.clone(),
+                        old_value: old_value.to_owned(),
+                        new_value: translation.value.clone(),
+                    });
+                }
+            }
+        }
+
+        promotion
+    }
+
+    fn endpoints(&self) -> impl Iterator<Item = EditTranslation> + '_ {
+        self.changes.iter().map(move |c| {
+            EditTranslation::new(
+                self.target_project.clone(),
+                c.locale.clone(),
+                c.term_id.clone(),
+                c.new_value.clone(),
+            )
+        })
+    }
+
+    /// Writes the changes to the target project.
+    ///
+    /// # Errors
+    /// This method returns an error if updating a translation fails.
+    /// Changes before the failing one are already applied.
+    pub fn apply<C>(&self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.endpoints()
+            .try_for_each(|endpoint| api::ignore(endpoint).query_custom(client))
+    }
+
+    /// Writes the changes to the target project asynchronously.
+    ///
+    /// # Errors
+    /// This method returns an error if updating a translation fails.
+    /// Changes before the failing one are already applied.
+    pub async fn apply_async<C>(&self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        for endpoint in self.endpoints() {
+            api::ignore(endpoint).query_custom_async(client).await?;
+        }
+        Ok(())
+    }
+
+    /// Writes the changes to the target project and records them in a [`Journal`].
+    ///
+    /// The journal file is created before the first change is sent. If the process
+    /// crashes, [`Journal::recover`] reports which changes were applied.
+    ///
+    /// # Errors
+    /// This method returns an error if the journal cannot be written or
+    /// updating a translation fails. Changes before the failing one are already applied.
+    pub fn apply_journaled<C>(
+        &self,
+        client: &C,
+        journal: impl AsRef<Path>,
+    ) -> Result<(), JournaledError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let mut journal = Journal::create(journal, self.journal_operations())?;
+        for (index, endpoint) in self.endpoints().enumerate() {
+            journal.start(index)?;
+            api::ignore(endpoint).query_custom(client)?;
+            journal.complete(index)?;
+        }
+        Ok(journal.finish()?)
+    }
+
+    /// Writes the changes to the target project asynchronously and records them in a [`Journal`].
+    ///
+    /// The journal file is created before the first change is sent. If the process
+    /// crashes or the future is dropped, [`Journal::recover`] reports which changes were applied.
+    ///
+    /// # Errors
+    /// This method returns an error if the journal cannot be written or
+    /// updating a translation fails. Changes before the failing one are already applied.
+    pub async fn apply_journaled_async<C>(
+        &self,
+        client: &C,
+        journal: impl AsRef<Path> + Send,
+    ) -> Result<(), JournaledError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let mut journal = Journal::create(journal, self.journal_operations())?;
+        for (index, endpoint) in self.endpoints().enumerate() {
+            journal.start(index)?;
+            api::ignore(endpoint).query_custom_async(client).await?;
+            journal.complete(index)?;
+        }
+        Ok(journal.finish()?)
+    }
+
+    fn journal_operations(&self) -> Vec<JournalOperation> {
+        self.endpoints()
+            .zip(&self.changes)
+            .map(|(endpoint, change)| {
+                let description = format!(
+                    "set {} translation of {} to {:?}",
+                    change.locale, change.term, change.new_value
+                );
+                JournalOperation::new(&endpoint, description)
+            })
+            .collect()
+    }
+}
+
+/// Computes which translations [`promote`] would copy without modifying the target project.
+///
+/// Terms are matched by their term string because their ids differ between instances.
+///
+/// # Errors
+/// This method returns an error if fetching the terms or translations of either project fails.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::{self, PromotionFilter};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let staging = Traduora::with_auth("staging.traduora.example", login.clone())?;
+/// let prod = Traduora::with_auth("traduora.example", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+/// let filter = PromotionFilter::new().label("Example label");
+///
+/// let promotion = ops::plan_promotion(&staging, &project, &prod, &project, &filter).unwrap();
+///
+/// // both instances have the same translations
+/// assert!(promotion.changes.is_empty());
+/// assert!(promotion.missing_terms.is_empty());
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn plan_promotion<S, T>(
+    source: &S,
+    source_project: &ProjectId,
+    target: &T,
+    target_project: &ProjectId,
+    filter: &PromotionFilter,
+) -> Result<Promotion, PromotionError<S::Error, T::Error>>
+where
+    S: Client<AccessLevel = Authenticated>,
+    T: Client<AccessLevel = Authenticated>,
+{
+    let source_data = ProjectData::fetch(source, source_project, |l| filter.matches_locale(l))
+        .map_err(|source| PromotionError::Source { source })?;
+    let target_data = ProjectData::fetch(target, target_project, |l| {
+        source_data.translations.contains_key(l)
+    })
+    .map_err(|source| PromotionError::Target { source })?;
+    Ok(Promotion::new(
+        &source_data,
+        &target_data,
+        target_project.clone(),
+        filter,
+    ))
+}
+
+/// Computes which translations [`promote_async`] would copy without modifying the target project.
+///
+/// Terms are matched by their term string because their ids differ between instances.
+///
+/// # Errors
+/// This method returns an error if fetching the terms or translations of either project fails.
+pub async fn plan_promotion_async<S, T>(
+    source: &S,
+    source_project: &ProjectId,
+    target: &T,
+    target_project: &ProjectId,
+    filter: &PromotionFilter,
+) -> Result<Promotion, PromotionError<S::Error, T::Error>>
+where
+    S: AsyncClient<AccessLevel = Authenticated> + Sync,
+    T: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let source_data =
+        ProjectData::fetch_async(source, source_project, |l| filter.matches_locale(l))
+            .await
+            .map_err(|source| PromotionError::Source { source })?;
+    let target_data = ProjectData::fetch_async(target, target_project, |l| {
+        source_data.translations.contains_key(l)
+    })
+    .await
+    .map_err(|source| PromotionError::Target { source })?;
+    Ok(Promotion::new(
+        &source_data,
+        &target_data,
+        target_project.clone(),
+        filter,
+    ))
+}
+
+/// Copies the translations that pass the filter from the source to the target project.
+///
+/// Only translations of terms and locales that already exist in the target project
+/// are copied. Returns what was changed.
+///
+/// # Errors
+/// This method returns an error if fetching the projects or updating a translation fails.
+pub fn promote<S, T>(
+    source: &S,
+    source_project: &ProjectId,
+    target: &T,
+    target_project: &ProjectId,
+    filter: &PromotionFilter,
+) -> Result<Promotion, PromotionError<S::Error, T::Error>>
+where
+    S: Client<AccessLevel = Authenticated>,
+    T: Client<AccessLevel = Authenticated>,
+{
+    let promotion = plan_promotion(source, source_project, target, target_project, filter)?;
+    promotion
+        .apply(target)
+        .map_err(|source| PromotionError::Target { source })?;
+    Ok(promotion)
+}
+
+/// Copies the translations that pass the filter from the source to the target project asynchronously.
+///
+/// Only translations of terms and locales that already exist in the target project
+/// are copied. Returns what was changed.
+///
+/// # Errors
+/// This method returns an error if fetching the projects or updating a translation fails.
+pub async fn promote_async<S, T>(
+    source: &S,
+    source_project: &ProjectId,
+    target: &T,
+    target_project: &ProjectId,
+    filter: &PromotionFilter,
+) -> Result<Promotion, PromotionError<S::Error, T::Error>>
+where
+    S: AsyncClient<AccessLevel = Authenticated> + Sync,
+    T: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let promotion =
+        plan_promotion_async(source, source_project, target, target_project, filter).await?;
+    promotion
+        .apply_async(target)
+        .await
+        .map_err(|source| PromotionError::Target { source })?;
+    Ok(promotion)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::{Promotion, PromotionFilter};
+    use crate::{
+        api::{labels::Label, terms::Term, translations::Translation, AccessDates},
+        ops::ProjectData,
+    };
+
+    fn dates() -> AccessDates {
+        AccessDates {
+            created: Utc::now(),
+            modified: Utc::now(),
+        }
+    }
+
+    fn term(id: &str, key: &str) -> Term {
+        Term::new(id.into(), key, dates())
+    }
+
+    fn translation(term_id: &str, value: &str, label: Option<&str>) -> Translation {
+        let labels = label
+            .map(|l| Label::new("label".into(), l, "#000000"))
+            .into_iter()
+            .collect();
+        Translation::new(term_id.into(), value, dates()).with_labels(labels)
+    }
+
+    fn project(terms: Vec<Term>, locales: &[(&str, Vec<Translation>)]) -> ProjectData {
+        ProjectData {
+            terms,
+            translations: locales
+                .iter()
+                .map(|(l, t)| ((*l).into(), t.clone()))
+                .collect::<BTreeMap<_, _>>(),
+        }
+    }
+
+    #[test]
+    fn only_labeled_changes_are_promoted() {
+        let source = project(
+            vec![term("s1", "hello"), term("s2", "bye"), term("s3", "new")],
+            &[(
+                "de",
+                vec![
+                    translation("s1", "Hallo", Some("approved")),
+                    translation("s2", "Tschüss", None),
+                    translation("s3", "Neu", Some("approved")),
+                ],
+            )],
+        );
+        let target = project(
+            vec![term("t1", "hello"), term("t2", "bye")],
+            &[("de", vec![translation("t1", "Hi", None)])],
+        );
+        let filter = PromotionFilter::new().label("approved");
+
+        let promotion = Promotion::new(&source, &target, "target".into(), &filter);
+
+        assert_eq!(promotion.changes.len(), 1);
+        assert_eq!(promotion.changes[0].term, "hello");
+        assert_eq!(promotion.changes[0].term_id.value(), "t1");
+        assert_eq!(promotion.changes[0].old_value, "Hi");
+        assert_eq!(promotion.changes[0].new_value, "Hallo");
+        assert_eq!(
+            promotion.missing_terms.into_iter().collect::<Vec<_>>(),
+            vec!["new"]
+        );
+    }
+
+    #[test]
+    fn missing_locales_are_reported() {
+        let source = project(
+            vec![term("s1", "hello")],
+            &[("fr", vec![translation("s1", "Bonjour", None)])],
+        );
+        let target = project(vec![term("t1", "hello")], &[]);
+
+        let promotion = Promotion::new(&source, &target, "target".into(), &PromotionFilter::new());
+
+        assert!(promotion.changes.is_empty());
+        assert!(promotion.missing_locales.contains(&"fr".into()));
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    api::{
+        terms::{DeleteTerms, Term, Terms},
+        BulkResult, ProjectId, TermId,
+    },
+    auth::Authenticated,
+    cancel::{Cancellable, CancellationToken},
+    progress::{NoProgress, ProgressSink},
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+type Deletions<E> = BulkResult<Term, (), E>;
+
+/// The error which is returned when pruning terms fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum PruneError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A request failed.
+    #[error("failed to prune terms: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// More terms would be deleted than allowed without forcing it.
+    #[error(
+        "refusing to delete {} of {} terms, more than {:.0}% of the project",
+        obsolete,
+        total,
+        max_ratio * 100.0
+    )]
+    TooManyDeletions {
+        /// Number of terms that would be deleted.
+        obsolete: usize,
+        /// Number of terms in the project.
+        total: usize,
+        /// The largest share of terms that may be deleted.
+        max_ratio: f64,
+    },
+}
+
+/// Terms of a project that are missing from the local source of truth.
+///
+/// Returned by [`plan_pruning`] without modifying the project. Its
+/// [`Display`](fmt::Display) implementation lists the terms that would be
+/// deleted, so it can be printed as the output of a dry run.
+///
+/// Deleting a large part of a project is usually a mistake, e.g. because the
+/// wrong key file was passed. Therefore, [`apply`](Pruning::apply) refuses to
+/// delete more than 10% of the terms unless the limit is changed with
+/// [`max_ratio`](Pruning::max_ratio) or ignored with [`force`](Pruning::force).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Pruning {
+    /// The pruned project.
+    pub project: ProjectId,
+    /// The terms that are deleted.
+    pub obsolete: Vec<Term>,
+    /// Number of terms in the project.
+    pub total: usize,
+    #[serde(skip)]
+    max_ratio: f64,
+    #[serde(skip)]
+    force: bool,
+}
+
+impl Pruning {
+    fn new(project: &ProjectId, terms: Vec<Term>, keep_keys: &BTreeSet<String>) -> Self {
+        let total = terms.len();
+        Self {
+            project: project.clone(),
+            obsolete: terms
+                .into_iter()
+                .filter(|t| !keep_keys.contains(&t.value))
+                .collect(),
+            total,
+            max_ratio: 0.1,
+            force: false,
+        }
+    }
+
+    /// Sets the largest share of terms between `0.0` and `1.0` that may be deleted.
+    #[must_use]
+    pub const fn max_ratio(mut self, max_ratio: f64) -> Self {
+        self.max_ratio = max_ratio;
+        self
+    }
+
+    /// Whether the terms are deleted even if their share exceeds the limit.
+    #[must_use]
+    pub const fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Whether all terms of the project are kept.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.obsolete.is_empty()
+    }
+
+    /// The share of terms that are deleted between `0.0` and `1.0`.
+    #[must_use]
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.obsolete.len() as f64 / self.total as f64
+        }
+    }
+
+    fn check<E>(&self) -> Result<(), PruneError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        if self.force || self.ratio() <= self.max_ratio {
+            Ok(())
+        } else {
+            Err(PruneError::TooManyDeletions {
+                obsolete: self.obsolete.len(),
+                total: self.total,
+                max_ratio: self.max_ratio,
+            })
+        }
+    }
+
+    /// Deletes the obsolete terms by their ids.
+    fn deletions(&self) -> DeleteTerms {
+        DeleteTerms::new(
+            self.project.clone(),
+            self.obsolete.iter().map(|term| term.id.clone()),
+        )
+    }
+
+    /// Keys the results of the deletions by the terms instead of their ids.
+    fn by_term<E>(
+        &self,
+        deletions: Cancellable<BulkResult<TermId, (), E>>,
+    ) -> Cancellable<Deletions<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let Cancellable { result, skipped } = deletions;
+        Cancellable {
+            result: self
+                .obsolete
+                .iter()
+                .cloned()
+                .zip(result.results)
+                .map(|(term, (_, result))| (term, result))
+                .collect(),
+            skipped,
+        }
+    }
+
+    /// Deletes the obsolete terms one after another.
+    ///
+    /// A failing term does not stop the deletion of the remaining ones.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub fn apply<C>(
+        &self,
+        client: &C,
+    ) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.apply_with_progress(client, &NoProgress)
+    }
+
+    /// Deletes the obsolete terms one after another and reports the progress.
+    ///
+    /// A failing term does not stop the deletion of the remaining ones.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub fn apply_with_progress<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.apply_cancellable(client, &CancellationToken::new(), progress)
+            .map(Cancellable::into_inner)
+    }
+
+    /// Deletes the obsolete terms one after another until the token is
+    /// cancelled and reports the progress.
+    ///
+    /// The result only contains the terms that were deleted before the cancellation.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub fn apply_cancellable<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Result<Cancellable<Deletions<C::Error>>, PruneError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.check()?;
+        let deletions = self
+            .deletions()
+            .delete_cancellable(client, cancel, progress);
+        Ok(self.by_term(deletions))
+    }
+
+    /// Deletes the obsolete terms asynchronously with up to 4 concurrent requests.
+    ///
+    /// A failing term does not stop the deletion of the remaining ones.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub async fn apply_async<C>(
+        &self,
+        client: &C,
+    ) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.apply_with_progress_async(client, &NoProgress).await
+    }
+
+    /// Deletes the obsolete terms asynchronously with up to 4 concurrent
+    /// requests and reports the progress.
+    ///
+    /// A failing term does not stop the deletion of the remaining ones.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub async fn apply_with_progress_async<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.apply_cancellable_async(client, &CancellationToken::new(), progress)
+            .await
+            .map(Cancellable::into_inner)
+    }
+
+    /// Deletes the obsolete terms asynchronously with up to 4 concurrent
+    /// requests until the token is cancelled and reports the progress.
+    ///
+    /// No new requests are sent after the cancellation, running requests are
+    /// finished. The result only contains the terms that were deleted.
+    ///
+    /// # Errors
+    /// This method returns an error without deleting anything if the share
+    /// of deleted terms exceeds the limit and the deletion is not forced.
+    pub async fn apply_cancellable_async<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Result<Cancellable<Deletions<C::Error>>, PruneError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.check()?;
+        let deletions = self
+            .deletions()
+            .delete_cancellable_async(client, cancel, progress)
+            .await;
+        Ok(self.by_term(deletions))
+    }
+}
+
+impl fmt::Display for Pruning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} terms are obsolete",
+            self.obsolete.len(),
+            self.total
+        )?;
+        for term in &self.obsolete {
+            write!(f, "\n- {}", term.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the terms of the project whose key is not in `keep_keys` without deleting them.
+///
+/// `keep_keys` is the local source of truth, e.g. the keys extracted from the
+/// source code. Print the result for a dry run or delete the terms with
+/// [`Pruning::apply`].
+///
+/// # Errors
+/// This function returns an error if the terms cannot be listed.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let pruning = ops::plan_pruning(&client, &project, ["this.is.a.term"])?;
+/// println!("{}", pruning);
+/// assert_eq!(pruning.obsolete.len(), 1);
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn plan_pruning<C, I, K>(
+    client: &C,
+    project: &ProjectId,
+    keep_keys: I,
+) -> Result<Pruning, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+    I: IntoIterator<Item = K>,
+    K: Into<String>,
+{
+    let keep_keys = keep_keys.into_iter().map(Into::into).collect();
+    let terms = Terms::new(project.clone()).query(client)?;
+    Ok(Pruning::new(project, terms, &keep_keys))
+}
+
+/// Lists the terms of the project whose key is not in `keep_keys` asynchronously without deleting them.
+///
+/// See [`plan_pruning`].
+///
+/// # Errors
+/// This function returns an error if the terms cannot be listed.
+pub async fn plan_pruning_async<C, I, K>(
+    client: &C,
+    project: &ProjectId,
+    keep_keys: I,
+) -> Result<Pruning, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    I: IntoIterator<Item = K>,
+    K: Into<String>,
+{
+    let keep_keys = keep_keys.into_iter().map(Into::into).collect();
+    let terms = Terms::new(project.clone()).query_async(client).await?;
+    Ok(Pruning::new(project, terms, &keep_keys))
+}
+
+/// Deletes all terms of the project whose key is not in `keep_keys`.
+///
+/// At most 10% of the terms are deleted unless `force` is `true`. Use
+/// [`plan_pruning`] to preview the deletion or to change the limit.
+///
+/// # Errors
+/// This function returns an error if the terms cannot be listed or too many
+/// terms would be deleted. Failures of single terms are returned in the
+/// [`BulkResult`].
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::{self, PruneError};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let result = ops::prune_terms(&client, &project, ["this.is.a.term"], false);
+/// assert!(matches!(result, Err(PruneError::TooManyDeletions { obsolete: 1, total: 2, .. })));
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn prune_terms<C, I, K>(
+    client: &C,
+    project: &ProjectId,
+    keep_keys: I,
+    force: bool,
+) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+    I: IntoIterator<Item = K>,
+    K: Into<String>,
+{
+    plan_pruning(client, project, keep_keys)?
+        .force(force)
+        .apply(client)
+}
+
+/// Deletes all terms of the project whose key is not in `keep_keys` asynchronously.
+///
+/// See [`prune_terms`].
+///
+/// # Errors
+/// This function returns an error if the terms cannot be listed or too many
+/// terms would be deleted. Failures of single terms are returned in the
+/// [`BulkResult`].
+pub async fn prune_terms_async<C, I, K>(
+    client: &C,
+    project: &ProjectId,
+    keep_keys: I,
+    force: bool,
+) -> Result<BulkResult<Term, (), C::Error>, PruneError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    I: IntoIterator<Item = K>,
+    K: Into<String>,
+{
+    plan_pruning_async(client, project, keep_keys)
+        .await?
+        .force(force)
+        .apply_async(client)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{Method, StatusCode};
+    use serde_json::json;
+
+    use super::{plan_pruning, PruneError};
+    use crate::{
+        fixtures,
+        mock::{Expectation, MockTraduora},
+    };
+
+    #[test]
+    fn deletions_above_the_limit_need_force() {
+        let mock = MockTraduora::new();
+        let terms = json!([
+            fixtures::term("1", "kept"),
+            fixtures::term("2", "obsolete"),
+            fixtures::term("3", "also.kept"),
+        ]);
+        mock.expect(
+            Expectation::new(Method::GET, "projects/p/terms")
+                .respond_json(StatusCode::OK, fixtures::data(terms)),
+        )
+        .expect(Expectation::new(Method::DELETE, "projects/p/terms/2"));
+        let pruning = plan_pruning(&mock, &"p".into(), ["kept", "also.kept"]).unwrap();
+        assert_eq!(pruning.to_string(), "1 of 3 terms are obsolete\n- obsolete");
+
+        let error = pruning.clone().apply(&mock).unwrap_err();
+        assert!(matches!(
+            error,
+            PruneError::TooManyDeletions {
+                obsolete: 1,
+                total: 3,
+                ..
+            }
+        ));
+        assert!(pruning.max_ratio(0.5).apply(&mock).unwrap().is_ok());
+        mock.verify().unwrap();
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    api::{
+        locales::LocaleCode,
+        translations::{EditTranslations, Translation, Translations},
+        BulkResult, ProjectId, TermId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+use super::{ensure_locale, ensure_locale_async};
+
+const LOWER: [char; 26] = [
+    'á', 'ƀ', 'ç', 'ð', 'é', 'ƒ', 'ĝ', 'ĥ', 'í', 'ĵ', 'ķ', 'ļ', 'ɱ', 'ñ', 'ö', 'þ', 'ǫ', 'ŕ', 'š',
+    'ţ', 'ú', 'ṽ', 'ŵ', 'ẋ', 'ý', 'ž',
+];
+const UPPER: [char; 26] = [
+    'Á', 'Ɓ', 'Ç', 'Ð', 'É', 'Ƒ', 'Ĝ', 'Ĥ', 'Í', 'Ĵ', 'Ķ', 'Ļ', 'Ṁ', 'Ñ', 'Ö', 'Þ', 'Ǫ', 'Ŕ', 'Š',
+    'Ţ', 'Ú', 'Ṽ', 'Ŵ', 'Ẋ', 'Ý', 'Ž',
+];
+
+/// Pseudo-localizes a text for testing the UI without real translations.
+///
+/// ASCII letters are replaced by accented look-alikes, the text is padded
+/// with `~` by 30% of its length (at least one character) and wrapped in
+/// brackets. Truncated or hard-coded texts are easy to spot that way.
+/// Placeholders like `{name}`, `{{name}}` and `%s` as well as HTML tags and
+/// entities are kept, so the application can still render the text.
+///
+/// # Examples
+/// ```
+/// use traduora::ops::pseudolocalize_text;
+///
+/// assert_eq!(pseudolocalize_text("Hello {name}!"), "[Ĥéļļö {name}!~~~~]");
+/// ```
+#[must_use]
+pub fn pseudolocalize_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2 + 2);
+    out.push('[');
+    let chars: Vec<_> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let len = protected_len(&chars[i..]);
+        if len > 0 {
+            out.extend(&chars[i..i + len]);
+            i += len;
+            continue;
+        }
+        let c = chars[i];
+        out.push(match c {
+            'a'..='z' => LOWER[usize::from(c as u8 - b'a')],
+            'A'..='Z' => UPPER[usize::from(c as u8 - b'A')],
+            c => c,
+        });
+        i += 1;
+    }
+    let padding = (chars.len() * 3 + 9) / 10;
+    out.extend(std::iter::repeat('~').take(padding.max(1)));
+    out.push(']');
+    out
+}
+
+/// Length of the placeholder, tag or entity at the start of `chars`, or 0.
+fn protected_len(chars: &[char]) -> usize {
+    let until = |close: char| chars.iter().position(|c| *c == close).map_or(0, |i| i + 1);
+    match chars {
+        ['{', ..] => {
+            let mut depth = 0;
+            for (i, c) in chars.iter().enumerate() {
+                match c {
+                    '{' => depth += 1,
+                    '}' if depth == 1 => return i + 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+            }
+            0
+        }
+        ['<', c, ..] if c.is_ascii_alphabetic() || *c == '/' => until('>'),
+        ['&', c, ..] if c.is_ascii_alphanumeric() || *c == '#' => {
+            let len = until(';');
+            let valid = len > 2
+                && chars[1..len - 1]
+                    .iter()
+                    .all(|c| c.is_ascii_alphanumeric() || *c == '#');
+            if valid {
+                len
+            } else {
+                0
+            }
+        }
+        ['%', '%', ..] => 2,
+        ['%', rest @ ..] => {
+            let flags = rest
+                .iter()
+                .take_while(|c| c.is_ascii_digit() || "$.-+#lh".contains(**c))
+                .count();
+            match rest.get(flags) {
+                Some(c) if c.is_ascii_alphabetic() => flags + 2,
+                _ => 0,
+            }
+        }
+        _ => 0,
+    }
+}
+
+fn pseudo_translations(
+    source: Vec<Translation>,
+    project: &ProjectId,
+    target: &LocaleCode,
+) -> EditTranslations {
+    let values: Vec<(TermId, String)> = source
+        .into_iter()
+        .filter(|t| !t.value.is_empty())
+        .map(|t| (t.term_id, pseudolocalize_text(&t.value)))
+        .collect();
+    EditTranslations::new(project.clone(), target.clone(), values)
+}
+
+/// Fills a pseudo-locale with pseudo-localized translations of the source locale.
+///
+/// The target locale is added to the project if it does not exist yet, and
+/// existing translations are overwritten. Every non-empty translation of the
+/// source locale is converted with [`pseudolocalize_text`], e.g. to test
+/// whether the UI handles longer texts and accented characters. Use a locale
+/// code that is not used for real translations.
+///
+/// # Errors
+/// This function returns an error if fetching the source translations or
+/// adding the target locale fails. Failures of single translations are
+/// returned in the [`BulkResult`].
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let result = ops::pseudolocalize(&client, &project, &"en".into(), &"en_XA".into())?;
+/// assert!(result.is_ok());
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn pseudolocalize<C>(
+    client: &C,
+    project: &ProjectId,
+    source: &LocaleCode,
+    target: &LocaleCode,
+) -> Result<BulkResult<TermId, Translation, C::Error>, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let translations = Translations::new(project.clone(), source.clone()).query(client)?;
+    ensure_locale(client, project, target)?;
+    Ok(pseudo_translations(translations, project, target).edit(client))
+}
+
+/// Fills a pseudo-locale with pseudo-localized translations of the source locale asynchronously.
+///
+/// See [`pseudolocalize`].
+///
+/// # Errors
+/// This function returns an error if fetching the source translations or
+/// adding the target locale fails. Failures of single translations are
+/// returned in the [`BulkResult`].
+pub async fn pseudolocalize_async<C>(
+    client: &C,
+    project: &ProjectId,
+    source: &LocaleCode,
+    target: &LocaleCode,
+) -> Result<BulkResult<TermId, Translation, C::Error>, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let translations = Translations::new(project.clone(), source.clone())
+        .query_async(client)
+        .await?;
+    ensure_locale_async(client, project, target).await?;
+    Ok(pseudo_translations(translations, project, target)
+        .edit_async(client)
+        .await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pseudolocalize_text;
+
+    #[test]
+    fn markup_and_placeholders_are_kept() {
+        assert_eq!(
+            pseudolocalize_text("<b>Hi</b> %1$s &amp; {count, plural, one {# item}} 100%%"),
+            "[<b>Ĥí</b> %1$s &amp; {count, plural, one {# item}} 100%%~~~~~~~~~~~~~~~~~]"
+        );
+        assert_eq!(pseudolocalize_text(""), "[~]");
+        assert_eq!(pseudolocalize_text("a & b"), "[á & ƀ~~]");
+    }
+}
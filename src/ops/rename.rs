@@ -0,0 +1,166 @@
+use thiserror::Error;
+
+use crate::{
+    api::{
+        terms::{EditTerm, Term, Terms},
+        ProjectId, TermId,
+    },
+    auth::Authenticated,
+    ApiError, AsyncClient, AsyncQuery, Client, Query,
+};
+
+/// The error which is returned by [`rename_by_key`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum RenameError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// A request failed.
+    #[error("failed to rename term: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// The project has no term with the old key.
+    #[error("term {} does not exist", key)]
+    NotFound {
+        /// The old key.
+        key: String,
+    },
+    /// The project already has a term with the new key.
+    #[error("term {} already exists", key)]
+    AlreadyExists {
+        /// The new key.
+        key: String,
+    },
+}
+
+/// Changes the term string of a term. Its translations and labels are kept.
+///
+/// # Errors
+/// This function returns an error if the request fails.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::rename_term;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+/// let term = "0fa39756-65db-423c-a6d9-534b62fe9ead".into();
+///
+/// let term = rename_term(&client, &project, &term, "new.term.text")?;
+/// assert_eq!(term.value, "new.term.text");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn rename_term<C>(
+    client: &C,
+    project: &ProjectId,
+    term: &TermId,
+    new_key: &str,
+) -> Result<Term, ApiError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    EditTerm::new(project.clone(), term.clone(), new_key).query(client)
+}
+
+/// Changes the term string of a term asynchronously. Its translations and labels are kept.
+///
+/// # Errors
+/// This function returns an error if the request fails.
+pub async fn rename_term_async<C>(
+    client: &C,
+    project: &ProjectId,
+    term: &TermId,
+    new_key: &str,
+) -> Result<Term, ApiError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    EditTerm::new(project.clone(), term.clone(), new_key)
+        .query_async(client)
+        .await
+}
+
+/// Changes the term string `old_key` to `new_key`. Its translations and labels are kept.
+///
+/// Lists the terms of the project first to find the id of the term.
+///
+/// # Errors
+/// This function returns an error if a request fails, no term has the old key
+/// or another term already has the new key.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::ops::{rename_by_key, RenameError};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// let result = rename_by_key(&client, &project, "this.is.a.term", "this.is.another.term");
+/// assert!(matches!(result, Err(RenameError::AlreadyExists { .. })));
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn rename_by_key<C>(
+    client: &C,
+    project: &ProjectId,
+    old_key: &str,
+    new_key: &str,
+) -> Result<Term, RenameError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let terms = Terms::new(project.clone()).query(client)?;
+    let id = find_term(&terms, old_key, new_key)?;
+    Ok(rename_term(client, project, id, new_key)?)
+}
+
+/// Changes the term string `old_key` to `new_key` asynchronously. Its translations and labels are kept.
+///
+/// Lists the terms of the project first to find the id of the term.
+///
+/// # Errors
+/// This function returns an error if a request fails, no term has the old key
+/// or another term already has the new key.
+pub async fn rename_by_key_async<C>(
+    client: &C,
+    project: &ProjectId,
+    old_key: &str,
+    new_key: &str,
+) -> Result<Term, RenameError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let terms = Terms::new(project.clone()).query_async(client).await?;
+    let id = find_term(&terms, old_key, new_key)?;
+    Ok(rename_term_async(client, project, id, new_key).await?)
+}
+
+/// The id of the term to rename.
+fn find_term<'a, E>(
+    terms: &'a [Term],
+    old_key: &str,
+    new_key: &str,
+) -> Result<&'a TermId, RenameError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    if old_key != new_key && terms.iter().any(|t| t.value == new_key) {
+        return Err(RenameError::AlreadyExists {
+            key: new_key.to_owned(),
+        });
+    }
+    terms
+        .iter()
+        .find(|t| t.value == old_key)
+        .map(|t| &t.id)
+        .ok_or_else(|| RenameError::NotFound {
+            key: old_key.to_owned(),
+        })
+}
@@ -0,0 +1,245 @@
+//! Progress reporting for long-running operations.
+//!
+//! Bulk, sync and export operations have `*_with_progress` variants that
+//! report a [`Progress`] to a [`ProgressSink`] after every request and after
+//! every processed item, e.g. to render a progress bar in a CLI or to log a
+//! heartbeat in a service. Closures taking a `&Progress` are sinks, too.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{api::terms::CreateTerms, progress::Progress};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+//! let terms = CreateTerms::new(project, ["this.is.a.new.term"]);
+//!
+//! let result = terms.create_with_progress(&client, &|progress: &Progress| {
+//!     eprintln!("{}", progress);
+//! });
+//! assert!(result.is_ok());
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, Response};
+use url::Url;
+
+use crate::{
+    api::{ProjectId, Role},
+    ApiError, AsyncClient, Client, RestClient,
+};
+
+/// The state of an operation, reported to a [`ProgressSink`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Progress {
+    /// Number of items that are processed, e.g. created terms or exported locales.
+    pub done: usize,
+    /// Number of items of the operation. `0` while it is not known yet.
+    pub total: usize,
+    /// Path of the last request, e.g. `/api/v1/projects/{projectId}/terms`.
+    /// Empty before the first request.
+    pub endpoint: String,
+    /// Number of bytes of all request and response bodies so far.
+    pub bytes: u64,
+}
+
+impl Progress {
+    /// The share of processed items between `0.0` and `1.0`.
+    ///
+    /// `0.0` while the total is not known.
+    #[must_use]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.done as f64 / self.total as f64
+        }
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} ({} bytes)", self.done, self.total, self.bytes)?;
+        if !self.endpoint.is_empty() {
+            write!(f, " {}", self.endpoint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the [`Progress`] of an operation.
+///
+/// Implemented for closures taking a `&Progress`.
+pub trait ProgressSink: Sync {
+    /// Called after every request and after every processed item.
+    fn progress(&self, progress: &Progress);
+}
+
+impl<F> ProgressSink for F
+where
+    F: Fn(&Progress) + Sync,
+{
+    fn progress(&self, progress: &Progress) {
+        self(progress);
+    }
+}
+
+/// A [`ProgressSink`] that ignores the progress.
+///
+/// Used by the operations without a `*_with_progress` suffix.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn progress(&self, _: &Progress) {}
+}
+
+/// A client wrapper that reports the requests of an operation to a sink.
+pub(crate) struct Tracker<'a, C> {
+    client: &'a C,
+    sink: &'a dyn ProgressSink,
+    progress: Mutex<Progress>,
+}
+
+impl<'a, C> Tracker<'a, C> {
+    /// Tracks an operation with `total` items.
+    pub(crate) fn new(client: &'a C, sink: &'a dyn ProgressSink, total: usize) -> Self {
+        Self {
+            client,
+            sink,
+            progress: Mutex::new(Progress {
+                total,
+                ..Progress::default()
+            }),
+        }
+    }
+
+    /// Sets the number of items once it is known.
+    pub(crate) fn set_total(&self, total: usize) {
+        self.update(|p| p.total = total);
+    }
+
+    /// Counts a processed item.
+    pub(crate) fn item_done(&self) {
+        self.update(|p| p.done += 1);
+    }
+
+    fn update(&self, f: impl FnOnce(&mut Progress)) {
+        let progress = {
+            let mut progress = self.lock();
+            f(&mut progress);
+            progress.clone()
+        };
+        self.sink.progress(&progress);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Progress> {
+        self.progress.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record<E>(
+        &self,
+        endpoint: String,
+        sent: usize,
+        response: &Result<Response<Bytes>, ApiError<E>>,
+    ) where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let received = response.as_ref().map_or(0, |r| r.body().len());
+        self.update(|p| {
+            p.endpoint = endpoint;
+            p.bytes += (sent + received) as u64;
+        });
+    }
+}
+
+fn path(request: &RequestBuilder) -> String {
+    request
+        .uri_ref()
+        .map(|uri| uri.path().to_owned())
+        .unwrap_or_default()
+}
+
+impl<C: RestClient> RestClient for Tracker<'_, C> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+impl<C: Client> Client for Tracker<'_, C> {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (endpoint, sent) = (path(&request), body.len());
+        let response = self.client.rest(request, body);
+        self.record(endpoint, sent, &response);
+        response
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync> AsyncClient for Tracker<'_, C> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (endpoint, sent) = (path(&request), body.len());
+        let response = self.client.rest_async(request, body).await;
+        self.record(endpoint, sent, &response);
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use http::{Method, StatusCode};
+
+    use super::Progress;
+    use crate::{
+        api::terms::CreateTerms,
+        fixtures,
+        mock::{Expectation, MockTraduora},
+    };
+
+    #[test]
+    fn requests_and_items_are_reported() {
+        let mock = MockTraduora::new();
+        mock.expect(
+            Expectation::new(Method::POST, "projects/p/terms")
+                .times(2)
+                .respond_json(StatusCode::OK, fixtures::data(fixtures::term("1", "a"))),
+        );
+        let reports = Mutex::new(Vec::new());
+        let sink = |progress: &Progress| reports.lock().unwrap().push(progress.clone());
+
+        let result = CreateTerms::new("p".into(), ["a", "b"]).create_with_progress(&mock, &sink);
+
+        assert!(result.is_ok());
+        let reports = reports.into_inner().unwrap();
+        let done: Vec<_> = reports.iter().map(|p| (p.done, p.total)).collect();
+        assert_eq!(done, [(0, 2), (1, 2), (1, 2), (2, 2)]);
+        assert_eq!(reports[0].endpoint, "/api/v1/projects/p/terms");
+        assert!(reports[0].bytes > 0);
+        assert_eq!(reports[3].bytes, 2 * reports[0].bytes);
+        assert_eq!(reports[3].fraction(), 1.0);
+    }
+}
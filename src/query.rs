@@ -1,9 +1,10 @@
 //! See type level explanations, especially [`Query`] or [`AsyncQuery`].
 
+use std::time::Instant;
+
 use async_trait::async_trait;
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
-use serde_json::Value;
 
 use crate::endpoint;
 use crate::{ApiError, AsyncClient, AsyncCustomQuery, Client, CustomQuery, Endpoint};
@@ -13,18 +14,18 @@ use crate::{ApiError, AsyncClient, AsyncCustomQuery, Client, CustomQuery, Endpoi
 pub trait DefaultModel: Endpoint {
     type Model: DeserializeOwned;
 
-    /// This mapping function parses a [`Value`] to [`Self::Model`].
+    /// This mapping function parses the response body to `T` which is usually [`Self::Model`].
     ///
-    /// The default implementation parses the value into a model that is wrapped
+    /// The default implementation parses the body into a model that is wrapped
     /// in a "data" object. Most Traduora endpoints return their answer in this
     /// form. A notable exception is [`crate::api::auth::AccessToken`].
-    fn map(data: Value) -> Result<Self::Model, serde_json::Error> {
+    fn map<'de, T: Deserialize<'de>>(data: &'de [u8]) -> Result<T, serde_json::Error> {
         #[derive(Deserialize)]
-        #[serde(bound = "T: DeserializeOwned")]
+        #[serde(bound = "T: Deserialize<'de>")]
         struct Container<T> {
             data: T,
         }
-        serde_json::from_value::<Container<Self::Model>>(data).map(|h| h.data)
+        serde_json::from_slice::<Container<T>>(data).map(|h| h.data)
     }
 }
 
@@ -42,7 +43,8 @@ where
     fn query_custom(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
         let (req, data) = endpoint::build_request_with_body(self.endpoint, client)?;
         let rsp = client.rest(req, data)?;
-        endpoint::process_response(&rsp, E::map)
+        endpoint::process_response(&rsp, E::map::<E::Model>)
+            .map_err(|e| e.with_request(endpoint::request_context(self.endpoint, &rsp)))
     }
 }
 
@@ -56,7 +58,8 @@ where
     async fn query_custom_async(&self, client: &C) -> Result<E::Model, ApiError<C::Error>> {
         let (req, data) = endpoint::build_request_with_body(self.endpoint, client)?;
         let rsp = client.rest_async(req, data).await?;
-        endpoint::process_response(&rsp, E::map)
+        endpoint::process_response(&rsp, E::map::<E::Model>)
+            .map_err(|e| e.with_request(endpoint::request_context(self.endpoint, &rsp)))
     }
 }
 
@@ -78,6 +81,44 @@ where
     /// - the server returns a non-success status code.
     /// - the returned JSON fails to deserialize.
     fn query(&self, client: &C) -> Result<Self::Model, ApiError<C::Error>>;
+
+    /// Perform the query against the client but deserialize into a different model.
+    ///
+    /// In contrast to [`CustomQuery`], the response is unwrapped the same way as
+    /// for [`Query::query`], so `T` only has to describe the model itself. This
+    /// is useful for self-hosted forks of Traduora that add fields to their models.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON fails to deserialize.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use serde::Deserialize;
+    /// use traduora::{api::users::{Me, UserInfo}, Query};
+    ///
+    /// #[derive(Deserialize)]
+    /// struct ForkUserInfo {
+    ///     #[serde(flatten)]
+    ///     info: UserInfo,
+    ///     department: Option<String>,
+    /// }
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let user: ForkUserInfo = Me.query_as(&client)?;
+    ///
+    /// assert_eq!(user.info.name, "Tester");
+    /// assert_eq!(user.department, None);
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    fn query_as<T>(&self, client: &C) -> Result<T, ApiError<C::Error>>
+    where
+        T: DeserializeOwned;
 }
 
 /// A trait which represents a asynchronous query which may be made to a Traduora client.
@@ -99,6 +140,59 @@ where
     /// - the server returns a non-success status code.
     /// - the returned JSON fails to deserialize.
     async fn query_async(&self, client: &C) -> Result<Self::Model, ApiError<C::Error>>;
+
+    /// Perform the query asynchronously against the client but deserialize into a different model.
+    ///
+    /// See [`Query::query_as`].
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON fails to deserialize.
+    async fn query_as_async<T>(&self, client: &C) -> Result<T, ApiError<C::Error>>
+    where
+        T: DeserializeOwned + 'static;
+
+    /// Perform the query asynchronously but give up once the deadline has passed.
+    ///
+    /// At the deadline, the request is aborted by dropping it, which also
+    /// closes its connection. This bounds the latency of the query no matter
+    /// how slow the server or the network is.
+    ///
+    /// # Errors
+    /// This method returns [`ApiError::Timeout`] if the query did not finish
+    /// before the deadline and otherwise the same errors as [`AsyncQuery::query_async`].
+    ///
+    /// # Panics
+    /// This method panics if it is not called from within a Tokio runtime.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use std::time::{Duration, Instant};
+    /// use traduora::{api::terms::Terms, AsyncQuery};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let endpoint = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into());
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// let deadline = Instant::now() + Duration::from_millis(500);
+    /// match endpoint.query_with_deadline(&client, deadline).await {
+    ///     Ok(terms) => println!("{} terms", terms.len()),
+    ///     Err(e) if e.is_timeout() => println!("no terms in time, using cached ones"),
+    ///     Err(e) => return Err(e.into()),
+    /// }
+    /// # Ok::<(), TraduoraError>(())
+    /// # })?;
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    async fn query_with_deadline(
+        &self,
+        client: &C,
+        deadline: Instant,
+    ) -> Result<Self::Model, ApiError<C::Error>>;
 }
 
 impl<C, E> Query<C> for E
@@ -110,6 +204,16 @@ where
     fn query(&self, client: &C) -> Result<Self::Model, ApiError<C::Error>> {
         DefaultQuery { endpoint: self }.query_custom(client)
     }
+
+    fn query_as<T>(&self, client: &C) -> Result<T, ApiError<C::Error>>
+    where
+        T: DeserializeOwned,
+    {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let rsp = client.rest(req, data)?;
+        endpoint::process_response(&rsp, E::map::<T>)
+            .map_err(|e| e.with_request(endpoint::request_context(self, &rsp)))
+    }
 }
 
 #[async_trait]
@@ -124,4 +228,24 @@ where
             .query_custom_async(client)
             .await
     }
+
+    async fn query_as_async<T>(&self, client: &C) -> Result<T, ApiError<C::Error>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let rsp = client.rest_async(req, data).await?;
+        endpoint::process_response(&rsp, E::map::<T>)
+            .map_err(|e| e.with_request(endpoint::request_context(self, &rsp)))
+    }
+
+    async fn query_with_deadline(
+        &self,
+        client: &C,
+        deadline: Instant,
+    ) -> Result<Self::Model, ApiError<C::Error>> {
+        tokio::time::timeout_at(deadline.into(), self.query_async(client))
+            .await
+            .unwrap_or(Err(ApiError::Timeout))
+    }
 }
@@ -0,0 +1,158 @@
+//! See type level explanations, especially [`QueryIter`] or [`AsyncQueryIter`].
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::vec;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+use crate::endpoint;
+use crate::query::DefaultModel;
+use crate::{ApiError, AsyncClient, Client};
+
+/// An iterator that deserializes the items of a list response one at a time.
+///
+/// Only the positions of the items in the response body are determined upfront.
+/// Each item is deserialized when the iterator reaches it, so the full list of
+/// models never has to be kept in memory at once.
+///
+/// Returned by [`QueryIter::query_iter`] and [`AsyncQueryIter::query_iter_async`].
+#[derive(Debug, Clone)]
+pub struct Items<T> {
+    body: Bytes,
+    ranges: vec::IntoIter<Range<usize>>,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T> Items<T> {
+    fn new<E: DefaultModel>(body: Bytes) -> Result<Self, serde_json::Error> {
+        let items: Vec<&RawValue> = E::map(&body)?;
+        let start = body.as_ptr() as usize;
+        let ranges: Vec<_> = items
+            .iter()
+            .map(|item| {
+                let offset = item.get().as_ptr() as usize - start;
+                offset..offset + item.get().len()
+            })
+            .collect();
+        Ok(Self {
+            body,
+            ranges: ranges.into_iter(),
+            item: PhantomData,
+        })
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for Items<T> {
+    type Item = Result<T, serde_json::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.ranges
+            .next()
+            .map(|range| serde_json::from_slice(&self.body[range]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranges.size_hint()
+    }
+}
+
+impl<T: DeserializeOwned> ExactSizeIterator for Items<T> {}
+
+/// A trait which represents a query for a list which may be made to a Traduora client.
+///
+/// In contrast to [`crate::Query`], the items are deserialized lazily while
+/// iterating. This reduces the memory usage for huge lists of terms or translations.
+pub trait QueryIter<T, C>: DefaultModel<Model = Vec<T>>
+where
+    C: Client,
+{
+    /// Perform the query against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON does not contain a list.
+    ///
+    /// Errors deserializing a single item are returned by the iterator.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use traduora::{api::terms::Terms, QueryIter};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let mut terms = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into()).query_iter(&client)?;
+    ///
+    /// assert_eq!(terms.len(), 2);
+    /// let term = terms.next().unwrap().expect("term should be valid");
+    /// assert_eq!(term.value, "this.is.a.term");
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    fn query_iter(&self, client: &C) -> Result<Items<T>, ApiError<C::Error>>;
+}
+
+/// A trait which represents an asynchronous query for a list which may be made to a Traduora client.
+///
+/// In contrast to [`crate::AsyncQuery`], the items are deserialized lazily while
+/// iterating. This reduces the memory usage for huge lists of terms or translations.
+#[async_trait]
+pub trait AsyncQueryIter<T, C>: DefaultModel<Model = Vec<T>>
+where
+    C: AsyncClient,
+{
+    /// Perform the query asynchronously against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON does not contain a list.
+    ///
+    /// Errors deserializing a single item are returned by the iterator.
+    async fn query_iter_async(&self, client: &C) -> Result<Items<T>, ApiError<C::Error>>;
+}
+
+impl<T, C, E> QueryIter<T, C> for E
+where
+    T: DeserializeOwned,
+    C: Client,
+    E: DefaultModel<Model = Vec<T>>,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    fn query_iter(&self, client: &C) -> Result<Items<T>, ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let rsp = client.rest(req, data)?;
+        let context = |e: ApiError<_>| e.with_request(endpoint::request_context(self, &rsp));
+        endpoint::check_status(&rsp).map_err(context)?;
+        Items::new::<E>(rsp.body().clone())
+            .map_err(endpoint::json_error::<E::Model, _>)
+            .map_err(context)
+    }
+}
+
+#[async_trait]
+impl<T, C, E> AsyncQueryIter<T, C> for E
+where
+    T: DeserializeOwned,
+    C: AsyncClient + Sync,
+    E: DefaultModel<Model = Vec<T>> + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_iter_async(&self, client: &C) -> Result<Items<T>, ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let rsp = client.rest_async(req, data).await?;
+        let context = |e: ApiError<_>| e.with_request(endpoint::request_context(self, &rsp));
+        endpoint::check_status(&rsp).map_err(context)?;
+        Items::new::<E>(rsp.body().clone())
+            .map_err(endpoint::json_error::<E::Model, _>)
+            .map_err(context)
+    }
+}
@@ -0,0 +1,136 @@
+//! See type level explanations, especially [`QueryWithMeta`] or [`AsyncQueryWithMeta`].
+
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{HeaderMap, Response, StatusCode};
+
+use crate::endpoint;
+use crate::query::DefaultModel;
+use crate::{ApiError, AsyncClient, Client};
+
+/// Information about the response from which a model was deserialized.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    /// HTTP status code of the response.
+    pub status: StatusCode,
+    /// HTTP headers of the response, e.g. `ETag` or `Date`.
+    pub headers: HeaderMap,
+    /// Time between sending the request and receiving the full response.
+    ///
+    /// This includes the time the client needs to authenticate the request.
+    pub elapsed: Duration,
+}
+
+impl ResponseMeta {
+    fn new(response: &Response<Bytes>, elapsed: Duration) -> Self {
+        Self {
+            status: response.status(),
+            headers: response.headers().clone(),
+            elapsed,
+        }
+    }
+}
+
+/// A trait which represents a query which may be made to a Traduora client
+/// and additionally returns information about the response.
+///
+/// The model is deserialized the same way as for [`crate::Query`]. For full
+/// access to the unprocessed response, see [`crate::RawQuery`].
+pub trait QueryWithMeta<C>: DefaultModel
+where
+    C: Client,
+{
+    /// Perform the query against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON fails to deserialize.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+    /// use traduora::{api::users::Me, QueryWithMeta};
+    ///
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let client = Traduora::with_auth("localhost:8080", login)?;
+    /// let (user, meta) = Me.query_with_meta(&client)?;
+    ///
+    /// assert_eq!(user.name, "Tester");
+    /// assert!(meta.status.is_success());
+    /// # Ok::<(), TraduoraError>(())
+    /// ```
+    fn query_with_meta(
+        &self,
+        client: &C,
+    ) -> Result<(Self::Model, ResponseMeta), ApiError<C::Error>>;
+}
+
+/// A trait which represents an asynchronous query which may be made to a Traduora client
+/// and additionally returns information about the response.
+///
+/// The model is deserialized the same way as for [`crate::AsyncQuery`]. For full
+/// access to the unprocessed response, see [`crate::AsyncRawQuery`].
+#[async_trait]
+pub trait AsyncQueryWithMeta<C>: DefaultModel
+where
+    C: AsyncClient,
+{
+    /// Perform the query asynchronously against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    /// - the server returns a non-success status code.
+    /// - the returned JSON fails to deserialize.
+    async fn query_with_meta_async(
+        &self,
+        client: &C,
+    ) -> Result<(Self::Model, ResponseMeta), ApiError<C::Error>>;
+}
+
+impl<C, E> QueryWithMeta<C> for E
+where
+    C: Client,
+    E: DefaultModel,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    fn query_with_meta(
+        &self,
+        client: &C,
+    ) -> Result<(Self::Model, ResponseMeta), ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let start = Instant::now();
+        let rsp = client.rest(req, data)?;
+        let meta = ResponseMeta::new(&rsp, start.elapsed());
+        let model = endpoint::process_response(&rsp, E::map::<E::Model>)
+            .map_err(|e| e.with_request(endpoint::request_context(self, &rsp)))?;
+        Ok((model, meta))
+    }
+}
+
+#[async_trait]
+impl<C, E> AsyncQueryWithMeta<C> for E
+where
+    C: AsyncClient + Sync,
+    E: DefaultModel + Sync,
+    E::AccessControl: From<C::AccessLevel>,
+{
+    async fn query_with_meta_async(
+        &self,
+        client: &C,
+    ) -> Result<(Self::Model, ResponseMeta), ApiError<C::Error>> {
+        let (req, data) = endpoint::build_request_with_body(self, client)?;
+        let start = Instant::now();
+        let rsp = client.rest_async(req, data).await?;
+        let meta = ResponseMeta::new(&rsp, start.elapsed());
+        let model = endpoint::process_response(&rsp, E::map::<E::Model>)
+            .map_err(|e| e.with_request(endpoint::request_context(self, &rsp)))?;
+        Ok((model, meta))
+    }
+}
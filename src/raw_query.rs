@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::Response;
+
+use crate::{ApiError, AsyncClient, Client};
+
+/// A trait which represents a query which may be made to a Traduora client
+/// and returns the unprocessed response.
+///
+/// In contrast to [`crate::CustomQuery`], the response body is not deserialized
+/// and non-success status codes are not turned into errors. This gives access
+/// to the status, headers and body, e.g. for debugging or to consume data that
+/// is not JSON.
+///
+/// Use [`crate::api::raw`] to get an implementation of this trait for an endpoint.
+pub trait RawQuery<C>
+where
+    C: Client,
+{
+    /// Perform the query against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    fn query_raw(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>>;
+}
+
+/// A trait which represents an asynchronous query which may be made to a Traduora client
+/// and returns the unprocessed response.
+///
+/// In contrast to [`crate::AsyncCustomQuery`], the response body is not deserialized
+/// and non-success status codes are not turned into errors. This gives access
+/// to the status, headers and body, e.g. for debugging or to consume data that
+/// is not JSON.
+///
+/// Use [`crate::api::raw`] to get an implementation of this trait for an endpoint.
+#[async_trait]
+pub trait AsyncRawQuery<C>
+where
+    C: AsyncClient,
+{
+    /// Perform the query asynchronously against the client.
+    ///
+    /// # Errors
+    /// This method returns an error if
+    /// - fails to prepare the request.
+    /// - the request could not be sent to the server.
+    async fn query_raw_async(&self, client: &C) -> Result<Response<Bytes>, ApiError<C::Error>>;
+}
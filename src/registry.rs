@@ -0,0 +1,188 @@
+//! Managing clients for several Traduora instances at once.
+//!
+//! See [`InstanceRegistry`].
+
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use futures_util::future;
+use thiserror::Error;
+
+/// The error which is returned when an operation refers to an instance
+/// that was not registered.
+#[derive(Debug, Error, Clone, PartialEq, Eq, Hash)]
+#[error("no traduora instance registered with name `{}`", name)]
+pub struct UnknownInstance {
+    /// Name of the missing instance.
+    pub name: String,
+}
+
+/// A collection of named clients, e.g. one per environment.
+///
+/// Each client is built separately, so every instance has its own
+/// credentials and configuration. The registry then allows running
+/// the same operation against several instances.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::{api::users::Me, registry::InstanceRegistry, Query};
+///
+/// let staging = Traduora::with_auth("staging.traduora.example", Login::password("tester@mail.example", "letmeinpls"))?;
+/// let prod = Traduora::with_auth("traduora.example", Login::password("tester@mail.example", "muchmoresecure"))?;
+/// let registry = InstanceRegistry::new()
+///     .with("staging", staging)
+///     .with("prod", prod);
+///
+/// let users = registry.run_all(|_, client| Me.query(client));
+///
+/// assert_eq!(users.len(), 2);
+/// assert_eq!(users["prod"].as_ref().unwrap().name, "Tester");
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct InstanceRegistry<C> {
+    instances: BTreeMap<String, C>,
+}
+
+impl<C> Default for InstanceRegistry<C> {
+    fn default() -> Self {
+        Self {
+            instances: BTreeMap::new(),
+        }
+    }
+}
+
+impl<C> InstanceRegistry<C> {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a client under the given name and returns the registry.
+    ///
+    /// A client that was previously registered with the same name is replaced.
+    #[must_use]
+    pub fn with(mut self, name: impl Into<String>, client: C) -> Self {
+        self.register(name, client);
+        self
+    }
+
+    /// Adds a client under the given name.
+    ///
+    /// Returns the client that was previously registered with the same name, if any.
+    pub fn register(&mut self, name: impl Into<String>, client: C) -> Option<C> {
+        self.instances.insert(name.into(), client)
+    }
+
+    /// Removes the client with the given name from the registry and returns it.
+    pub fn remove(&mut self, name: &str) -> Option<C> {
+        self.instances.remove(name)
+    }
+
+    /// Returns the client with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&C> {
+        self.instances.get(name)
+    }
+
+    /// Returns the client with the given name or an error if it is not registered.
+    ///
+    /// # Errors
+    /// This method returns an error if no client is registered with the given name.
+    pub fn instance(&self, name: &str) -> Result<&C, UnknownInstance> {
+        self.get(name)
+            .ok_or_else(|| UnknownInstance { name: name.into() })
+    }
+
+    /// Iterates over all registered names in alphabetical order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.instances.keys().map(String::as_str)
+    }
+
+    /// Iterates over all registered clients together with their names.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &C)> {
+        self.instances.iter().map(|(n, c)| (n.as_str(), c))
+    }
+
+    /// Number of registered clients.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Whether no client is registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// Runs the operation against every registered client, one after another.
+    ///
+    /// Returns the result of each operation by the name of its instance.
+    pub fn run_all<T, F>(&self, mut op: F) -> BTreeMap<String, T>
+    where
+        F: FnMut(&str, &C) -> T,
+    {
+        self.iter()
+            .map(|(name, client)| (name.to_owned(), op(name, client)))
+            .collect()
+    }
+
+    /// Runs the operation against the clients with the given names, one after another.
+    ///
+    /// Returns the result of each operation by the name of its instance.
+    ///
+    /// # Errors
+    /// This method returns an error before running any operation if one of
+    /// the names is not registered.
+    pub fn run_on<T, F>(
+        &self,
+        names: &[&str],
+        mut op: F,
+    ) -> Result<BTreeMap<String, T>, UnknownInstance>
+    where
+        F: FnMut(&str, &C) -> T,
+    {
+        let clients = names
+            .iter()
+            .map(|&name| Ok((name, self.instance(name)?)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(clients
+            .into_iter()
+            .map(|(name, client)| (name.to_owned(), op(name, client)))
+            .collect())
+    }
+
+    /// Runs the operation against every registered client concurrently.
+    ///
+    /// Returns the result of each operation by the name of its instance.
+    ///
+    /// # Examples
+    /// ```
+    /// # use traduora::{Login, TestClient as AsyncTraduora, TraduoraError};
+    /// use traduora::{api::users::Me, registry::InstanceRegistry, AsyncQuery};
+    ///
+    /// # async fn main_async() -> Result<(), TraduoraError> {
+    /// # let login = Login::password("tester@mail.example", "letmeinpls");
+    /// let registry = InstanceRegistry::new()
+    ///     .with("staging", AsyncTraduora::with_auth("staging.traduora.example", login.clone())?)
+    ///     .with("prod", AsyncTraduora::with_auth("traduora.example", login)?);
+    ///
+    /// let users = registry.run_all_async(|_, client| Me.query_async(client)).await;
+    ///
+    /// assert_eq!(users.len(), 2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn run_all_async<'c, T, F, Fut>(&'c self, op: F) -> BTreeMap<String, T>
+    where
+        F: Fn(&'c str, &'c C) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let names = self.instances.keys().cloned();
+        let results = future::join_all(self.iter().map(|(name, client)| op(name, client))).await;
+        names.zip(results).collect()
+    }
+}
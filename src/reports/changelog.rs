@@ -0,0 +1,245 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::{
+    api::{locales::LocaleCode, TermKey},
+    sync::TranslationSnapshot,
+};
+
+/// A term whose key changed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct RenamedTerm {
+    /// The key in the older snapshot.
+    pub from: TermKey,
+    /// The key in the newer snapshot.
+    pub to: TermKey,
+}
+
+/// A translation that was added, changed or removed between two snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct TranslationChange {
+    /// The term key in the newer snapshot.
+    pub term: TermKey,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The translation in the older snapshot. `None` if it did not exist.
+    pub before: Option<String>,
+    /// The translation in the newer snapshot. `None` if it was removed.
+    pub after: Option<String>,
+}
+
+/// The human-readable changes between two [`TranslationSnapshot`]s, e.g. for release notes.
+///
+/// A removed and an added term with identical, non-empty translations are
+/// listed as renamed. Translations of added terms are listed as changed,
+/// translations of removed terms are not. Render it with
+/// [`to_markdown`](ChangeLog::to_markdown) or as JSON via [`Report`](super::Report).
+///
+/// # Examples
+/// ```
+/// use traduora::{reports::ChangeLog, sync::TranslationSnapshot};
+///
+/// let mut before = TranslationSnapshot::new();
+/// before.set("greeting", "de".into(), "Hallo");
+/// before.set("cart.title", "de".into(), "Warenkorb");
+/// let mut after = TranslationSnapshot::new();
+/// after.set("greeting", "de".into(), "Guten Tag");
+/// after.set("checkout.cart.title", "de".into(), "Warenkorb");
+///
+/// let changes = ChangeLog::between(&before, &after);
+/// assert_eq!(changes.renamed[0].to, "checkout.cart.title".into());
+/// assert_eq!(changes.changed[0].after.as_deref(), Some("Guten Tag"));
+/// println!("{}", changes.to_markdown());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ChangeLog {
+    /// Terms that only exist in the newer snapshot, sorted.
+    pub added: Vec<TermKey>,
+    /// Terms that only exist in the older snapshot, sorted.
+    pub removed: Vec<TermKey>,
+    /// Terms whose key changed, sorted by the old key.
+    pub renamed: Vec<RenamedTerm>,
+    /// Changed translations, sorted by term and locale.
+    pub changed: Vec<TranslationChange>,
+}
+
+impl ChangeLog {
+    /// Compares the older snapshot `a` with the newer snapshot `b`.
+    #[must_use]
+    pub fn between(a: &TranslationSnapshot, b: &TranslationSnapshot) -> Self {
+        let mut log = Self::default();
+        let mut added: BTreeSet<_> = b
+            .terms
+            .keys()
+            .filter(|t| !a.terms.contains_key(*t))
+            .collect();
+
+        for (term, translations) in a.terms.iter().filter(|(t, _)| !b.terms.contains_key(*t)) {
+            let mut candidates = added.iter().filter(|t| &b.terms[**t] == translations);
+            match (candidates.next(), candidates.next()) {
+                (Some(to), None) if !translations.is_empty() => {
+                    let to = (*to).clone();
+                    added.remove(&to);
+                    log.renamed.push(RenamedTerm {
+                        from: term.clone(),
+                        to,
+                    });
+                }
+                _ => log.removed.push(term.clone()),
+            }
+        }
+        log.added = added.into_iter().cloned().collect();
+
+        for (term, after) in &b.terms {
+            let old_term = log
+                .renamed
+                .iter()
+                .find(|r| &r.to == term)
+                .map_or(term, |r| &r.from);
+            let before = a.terms.get(old_term);
+            let locales: BTreeSet<_> = after
+                .keys()
+                .chain(before.into_iter().flat_map(|t| t.keys()))
+                .collect();
+            for locale in locales {
+                let old = before.and_then(|t| t.get(locale));
+                let new = after.get(locale);
+                if old != new {
+                    log.changed.push(TranslationChange {
+                        term: term.clone(),
+                        locale: locale.clone(),
+                        before: old.cloned(),
+                        after: new.cloned(),
+                    });
+                }
+            }
+        }
+        log
+    }
+
+    /// Whether the snapshots have the same terms and translations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.renamed.is_empty()
+            && self.changed.is_empty()
+    }
+
+    /// Renders the changes as Markdown with a section per kind of change.
+    ///
+    /// Sections without changes are left out. Changed translations are
+    /// listed in a table.
+    #[must_use]
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        if self.is_empty() {
+            out.push_str("No changes.\n");
+            return out;
+        }
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                if !out.is_empty() {
+                    out.push('\n');
+                }
+                let _ = writeln!(out, "## {}\n", title);
+                for line in lines {
+                    let _ = writeln!(out, "{}", line);
+                }
+            }
+        };
+        section(
+            "Added terms",
+            self.added.iter().map(|t| format!("- `{}`", t)).collect(),
+        );
+        section(
+            "Removed terms",
+            self.removed.iter().map(|t| format!("- `{}`", t)).collect(),
+        );
+        section(
+            "Renamed terms",
+            self.renamed
+                .iter()
+                .map(|r| format!("- `{}` → `{}`", r.from, r.to))
+                .collect(),
+        );
+        let mut table = Vec::new();
+        if !self.changed.is_empty() {
+            table.push("| Term | Locale | Before | After |".to_owned());
+            table.push("|------|--------|--------|-------|".to_owned());
+        }
+        table.extend(self.changed.iter().map(|c| {
+            format!(
+                "| `{}` | {} | {} | {} |",
+                c.term,
+                c.locale,
+                cell(c.before.as_deref()),
+                cell(c.after.as_deref())
+            )
+        }));
+        section("Changed translations", table);
+        out
+    }
+}
+
+/// Escapes a translation for a Markdown table cell.
+fn cell(value: Option<&str>) -> String {
+    value.map_or_else(
+        || "–".to_owned(),
+        |v| v.replace('|', "\\|").replace('\n', "<br>"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeLog, RenamedTerm};
+    use crate::sync::TranslationSnapshot;
+
+    #[test]
+    fn renames_are_detected_once() {
+        let mut before = TranslationSnapshot::new();
+        before.set("old", "de".into(), "Text");
+        before.set("gone", "de".into(), "Weg");
+        before.add_term("empty");
+        let mut after = TranslationSnapshot::new();
+        after.set("new", "de".into(), "Text");
+        after.set("copy", "en".into(), "Text");
+        after.add_term("untranslated");
+
+        let log = ChangeLog::between(&before, &after);
+        assert_eq!(
+            log.renamed,
+            [RenamedTerm {
+                from: "old".into(),
+                to: "new".into()
+            }]
+        );
+        assert_eq!(log.removed, ["empty".into(), "gone".into()]);
+        assert_eq!(log.added, ["copy".into(), "untranslated".into()]);
+        assert_eq!(log.changed.len(), 1);
+    }
+
+    #[test]
+    fn markdown_lists_sections_with_changes() {
+        let mut before = TranslationSnapshot::new();
+        before.set("greeting", "de".into(), "Hallo");
+        let mut after = before.clone();
+        after.set("greeting", "de".into(), "Hallo | Tschüss");
+        after.set("greeting", "en".into(), "Hello");
+
+        assert_eq!(
+            ChangeLog::between(&before, &after).to_markdown(),
+            "## Changed translations\n\n\
+             | Term | Locale | Before | After |\n\
+             |------|--------|--------|-------|\n\
+             | `greeting` | de | Hallo | Hallo \\| Tschüss |\n\
+             | `greeting` | en | – | Hello |\n"
+        );
+        assert_eq!(
+            ChangeLog::between(&before, &before).to_markdown(),
+            "No changes.\n"
+        );
+    }
+}
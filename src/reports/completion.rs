@@ -0,0 +1,376 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    api::{locales::LocaleCode, ProjectId},
+    auth::Authenticated,
+    ops::ProjectData,
+    ApiError, AsyncClient, Client,
+};
+
+/// How completely a project is translated into each of its locales.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::reports::CompletionReport;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let report = CompletionReport::generate(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+///
+/// for (locale, completion) in &report.locales {
+///     println!("{}: {:.0}%", locale, completion.completion() * 100.0);
+///     for term in &completion.missing {
+///         println!("  missing: {}", term);
+///     }
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompletionReport {
+    /// The project the report was generated for.
+    pub project: ProjectId,
+    /// Number of terms of the project.
+    pub terms: usize,
+    /// Completion of each locale of the project.
+    pub locales: BTreeMap<LocaleCode, LocaleCompletion>,
+}
+
+/// How completely a project is translated into a single locale.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LocaleCompletion {
+    /// Number of terms with a non-empty translation.
+    pub translated: usize,
+    /// Number of terms of the project.
+    pub total: usize,
+    /// Terms without a translation or with an empty one, sorted.
+    pub missing: Vec<String>,
+    /// Terms whose translation was last modified before the term itself, sorted.
+    ///
+    /// The translation might not fit the term anymore, e.g. after the term was renamed.
+    pub stale: Vec<String>,
+}
+
+impl LocaleCompletion {
+    /// Ratio of translated terms between `0.0` and `1.0`.
+    ///
+    /// A project without terms is complete.
+    #[must_use]
+    pub fn completion(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.translated as f64 / self.total as f64
+        }
+    }
+
+    /// Whether all terms are translated.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl CompletionReport {
+    /// Fetches all terms and translations of the project and computes their completion.
+    ///
+    /// # Errors
+    /// This function returns an error if a request fails.
+    pub fn generate<C>(client: &C, project: &ProjectId) -> Result<Self, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let data = ProjectData::fetch(client, project, |_| true)?;
+        Ok(Self::new(project.clone(), &data))
+    }
+
+    /// Fetches all terms and translations of the project asynchronously and computes their completion.
+    ///
+    /// # Errors
+    /// This function returns an error if a request fails.
+    pub async fn generate_async<C>(
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Self, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let data = ProjectData::fetch_async(client, project, |_| true).await?;
+        Ok(Self::new(project.clone(), &data))
+    }
+
+    /// Ratio of translated terms over all locales between `0.0` and `1.0`.
+    #[must_use]
+    pub fn completion(&self) -> f64 {
+        let total: usize = self.locales.values().map(|l| l.total).sum();
+        let translated: usize = self.locales.values().map(|l| l.translated).sum();
+        if total == 0 {
+            1.0
+        } else {
+            translated as f64 / total as f64
+        }
+    }
+
+    fn new(project: ProjectId, data: &ProjectData) -> Self {
+        let locales = data
+            .translations
+            .iter()
+            .map(|(locale, translations)| {
+                let translations: BTreeMap<_, _> = translations
+                    .iter()
+                    .filter(|t| !t.value.is_empty())
+                    .map(|t| (&t.term_id, t))
+                    .collect();
+
+                let mut completion = LocaleCompletion {
+                    total: data.terms.len(),
+                    ..LocaleCompletion::default()
+                };
+                let mut missing = BTreeSet::new();
+                let mut stale = BTreeSet::new();
+                for term in &data.terms {
+                    match translations.get(&term.id) {
+                        Some(translation) => {
+                            completion.translated += 1;
+                            if translation.date.modified < term.date.modified {
+                                stale.insert(term.value.clone());
+                            }
+                        }
+                        None => {
+                            missing.insert(term.value.clone());
+                        }
+                    }
+                }
+                completion.missing = missing.into_iter().collect();
+                completion.stale = stale.into_iter().collect();
+                (locale.clone(), completion)
+            })
+            .collect();
+
+        Self {
+            project,
+            terms: data.terms.len(),
+            locales,
+        }
+    }
+}
+
+/// The error which is returned by [`assert_complete`].
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum CompletenessError<E>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// The completion report could not be generated.
+    #[error("failed to generate completion report: {}", source)]
+    Api {
+        /// Inner error.
+        #[from]
+        source: ApiError<E>,
+    },
+    /// Some locales are translated less completely than required.
+    #[error("translations are incomplete: {}", summary(.locales, .unknown_locales))]
+    Incomplete {
+        /// The locales below the threshold with their untranslated terms.
+        locales: BTreeMap<LocaleCode, LocaleCompletion>,
+        /// Required locales that do not exist in the project.
+        unknown_locales: Vec<LocaleCode>,
+    },
+}
+
+fn summary(locales: &BTreeMap<LocaleCode, LocaleCompletion>, unknown: &[LocaleCode]) -> String {
+    locales
+        .iter()
+        .map(|(locale, completion)| {
+            format!(
+                "{} is {:.1}% translated ({} missing)",
+                locale,
+                completion.completion() * 100.0,
+                completion.missing.len()
+            )
+        })
+        .chain(
+            unknown
+                .iter()
+                .map(|locale| format!("{} does not exist", locale)),
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Checks that the given locales of the project are translated at least to the threshold.
+///
+/// `threshold` is the required ratio of translated terms between `0.0` and `1.0`,
+/// e.g. `1.0` to require all terms to be translated. If `locales` is empty,
+/// all locales of the project are checked.
+///
+/// Returns the full report if all locales are complete enough.
+///
+/// # Errors
+/// This function returns [`CompletenessError::Incomplete`] with the untranslated
+/// terms per locale if a locale is below the threshold or does not exist,
+/// and [`CompletenessError::Api`] if a request fails.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::reports::{assert_complete, CompletenessError};
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+///
+/// match assert_complete(&client, &project, &["de_DE".into()], 0.95) {
+///     Ok(_) => println!("all translations are there"),
+///     Err(CompletenessError::Incomplete { locales, .. }) => {
+///         for (locale, completion) in locales {
+///             eprintln!("{} is missing {}", locale, completion.missing.join(", "));
+///         }
+///         # let exit = |_| {};
+///         exit(1);
+///     }
+///     Err(e) => panic!("{}", e),
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub fn assert_complete<C>(
+    client: &C,
+    project: &ProjectId,
+    locales: &[LocaleCode],
+    threshold: f64,
+) -> Result<CompletionReport, CompletenessError<C::Error>>
+where
+    C: Client<AccessLevel = Authenticated>,
+{
+    let report = CompletionReport::generate(client, project)?;
+    report.check(locales, threshold)
+}
+
+/// Checks asynchronously that the given locales of the project are translated at least to the threshold.
+///
+/// See [`assert_complete`] for details.
+///
+/// # Errors
+/// This function returns [`CompletenessError::Incomplete`] with the untranslated
+/// terms per locale if a locale is below the threshold or does not exist,
+/// and [`CompletenessError::Api`] if a request fails.
+pub async fn assert_complete_async<C>(
+    client: &C,
+    project: &ProjectId,
+    locales: &[LocaleCode],
+    threshold: f64,
+) -> Result<CompletionReport, CompletenessError<C::Error>>
+where
+    C: AsyncClient<AccessLevel = Authenticated> + Sync,
+{
+    let report = CompletionReport::generate_async(client, project).await?;
+    report.check(locales, threshold)
+}
+
+impl CompletionReport {
+    fn check<E>(self, locales: &[LocaleCode], threshold: f64) -> Result<Self, CompletenessError<E>>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let required: Vec<_> = if locales.is_empty() {
+            self.locales.keys().cloned().collect()
+        } else {
+            locales.to_vec()
+        };
+
+        let mut incomplete = BTreeMap::new();
+        let mut unknown_locales = Vec::new();
+        for locale in required {
+            match self.locales.get(&locale) {
+                Some(completion) if completion.completion() < threshold => {
+                    incomplete.insert(locale, completion.clone());
+                }
+                Some(_) => {}
+                None => unknown_locales.push(locale),
+            }
+        }
+
+        if incomplete.is_empty() && unknown_locales.is_empty() {
+            Ok(self)
+        } else {
+            Err(CompletenessError::Incomplete {
+                locales: incomplete,
+                unknown_locales,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use super::{CompletenessError, CompletionReport};
+    use crate::ops::ProjectData;
+
+    #[test]
+    fn missing_and_stale_translations() {
+        let date = |d: &str| json!({ "created": d, "modified": d });
+        let term = |id: &str, value: &str, modified: &str| {
+            serde_json::from_value(json!({
+                "id": id, "value": value, "labels": [], "date": date(modified)
+            }))
+            .unwrap()
+        };
+        let translation = |id: &str, value: &str, modified: &str| {
+            serde_json::from_value(json!({
+                "termId": id, "value": value, "labels": [], "date": date(modified)
+            }))
+            .unwrap()
+        };
+
+        let mut translations = BTreeMap::new();
+        translations.insert(
+            "de".into(),
+            vec![
+                translation("1", "Eins", "2022-01-01T00:00:00Z"),
+                translation("2", "", "2022-01-01T00:00:00Z"),
+                translation("3", "Drei", "2022-01-01T00:00:00Z"),
+            ],
+        );
+        translations.insert("fr".into(), vec![]);
+        let data = ProjectData {
+            terms: vec![
+                term("1", "one", "2021-01-01T00:00:00Z"),
+                term("2", "two", "2021-01-01T00:00:00Z"),
+                term("3", "three", "2023-01-01T00:00:00Z"),
+                term("4", "four", "2021-01-01T00:00:00Z"),
+            ],
+            translations,
+        };
+
+        let report = CompletionReport::new("p".into(), &data);
+        let de = &report.locales[&"de".into()];
+        assert_eq!(de.translated, 2);
+        assert_eq!(de.missing, ["four", "two"]);
+        assert_eq!(de.stale, ["three"]);
+        assert!((de.completion() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(report.locales[&"fr".into()].missing.len(), 4);
+        assert!((report.completion() - 0.25).abs() < f64::EPSILON);
+
+        let err = report
+            .clone()
+            .check::<std::io::Error>(&["de".into(), "it".into()], 0.5)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "translations are incomplete: it does not exist"
+        );
+        let err = report.check::<std::io::Error>(&[], 0.5).unwrap_err();
+        assert!(matches!(
+            err,
+            CompletenessError::Incomplete { locales, .. } if locales.len() == 1 && locales[&"fr".into()].missing.len() == 4
+        ));
+    }
+}
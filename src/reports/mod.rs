@@ -0,0 +1,224 @@
+//! Machine-readable output of the results of higher-level operations.
+//!
+//! Every type implementing [`Report`] can be written as JSON with a stable
+//! envelope, so CI systems can parse it reliably across crate upgrades:
+//!
+//! ```json
+//! {
+//!   "kind": "sync-plan",
+//!   "version": 1,
+//!   "report": { ... }
+//! }
+//! ```
+//!
+//! - `kind` identifies the report type and never changes.
+//! - `version` is incremented whenever a field of the report is removed,
+//!   renamed or changes its meaning. Adding fields does not change the version,
+//!   so consumers should ignore unknown fields.
+//! - `report` contains the report itself.
+//!
+//! The following reports are available:
+//!
+//! | Kind               | Version | Type                                       |
+//! |--------------------|---------|--------------------------------------------|
+//! | `smoke-test`       | 1       | [`SmokeTestReport`]                        |
+//! | `completion`       | 1       | [`CompletionReport`]                       |
+//! | `changelog`        | 1       | [`ChangeLog`]                              |
+//! | `length`           | 1       | [`LengthReport`]                           |
+//! | `promotion`        | 1       | [`Promotion`]                              |
+//! | `sync-plan`        | 1       | [`SyncPlan`]                               |
+//! | `journal-recovery` | 1       | [`Recovery`]                               |
+//! | `bulk-result`      | 1       | [`BulkResult`]                             |
+//! | `gates`            | 1       | [`GateResults`]                            |
+//! | `snapshot-changes` | 1       | [`SnapshotChanges`]                        |
+//! | `bench`            | 1       | `bench::BenchReport` (feature `bench`)     |
+//!
+//! Identifiers like project ids, term ids and locale codes are written as strings.
+//! Timestamps are written in RFC 3339 format. Errors are written as objects with
+//! a human-readable `message` and the stable `message_key` of
+//! [`ApiError::user_message`](crate::ApiError::user_message).
+
+mod changelog;
+mod completion;
+
+use std::io;
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{
+    api::BulkResult,
+    diagnostics::SmokeTestReport,
+    gates::GateResults,
+    ops::{Promotion, Recovery},
+    snapshot::SnapshotChanges,
+    sync::SyncPlan,
+    validate::LengthReport,
+    ApiError,
+};
+
+pub use changelog::{ChangeLog, RenamedTerm, TranslationChange};
+pub use completion::{
+    assert_complete, assert_complete_async, CompletenessError, CompletionReport, LocaleCompletion,
+};
+
+/// A result that can be written in the versioned JSON format of this module.
+///
+/// # Examples
+/// ```
+/// use traduora::{reports::Report, sync::SyncPlanner};
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// # use traduora::sync::TranslationSnapshot;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// # let client = Traduora::with_auth("localhost:8080", login)?;
+/// # let local = TranslationSnapshot::new();
+/// let plan = SyncPlanner::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+///     .plan(&client, &local)?;
+///
+/// let json = plan.to_json_string().unwrap();
+/// assert!(json.starts_with(r#"{"kind":"sync-plan","version":1,"report":{"#));
+/// # Ok::<(), TraduoraError>(())
+/// ```
+pub trait Report: Serialize {
+    /// Stable identifier of the report type.
+    const KIND: &'static str;
+    /// Version of the schema of the report.
+    const VERSION: u32;
+
+    /// Writes the report with its envelope as JSON.
+    ///
+    /// # Errors
+    /// This method returns an error if writing fails.
+    fn write_json<W: io::Write>(&self, writer: W) -> serde_json::Result<()>
+    where
+        Self: Sized,
+    {
+        serde_json::to_writer(writer, &Envelope(self))
+    }
+
+    /// Returns the report with its envelope as JSON.
+    ///
+    /// # Errors
+    /// This method returns an error if the report cannot be serialized.
+    fn to_json_string(&self) -> serde_json::Result<String>
+    where
+        Self: Sized,
+    {
+        serde_json::to_string(&Envelope(self))
+    }
+}
+
+struct Envelope<'r, R>(&'r R);
+
+impl<R: Report> Serialize for Envelope<'_, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut envelope = serializer.serialize_struct("Envelope", 3)?;
+        envelope.serialize_field("kind", R::KIND)?;
+        envelope.serialize_field("version", &R::VERSION)?;
+        envelope.serialize_field("report", self.0)?;
+        envelope.end()
+    }
+}
+
+impl Report for SmokeTestReport {
+    const KIND: &'static str = "smoke-test";
+    const VERSION: u32 = 1;
+}
+
+impl Report for CompletionReport {
+    const KIND: &'static str = "completion";
+    const VERSION: u32 = 1;
+}
+
+impl Report for ChangeLog {
+    const KIND: &'static str = "changelog";
+    const VERSION: u32 = 1;
+}
+
+impl Report for LengthReport {
+    const KIND: &'static str = "length";
+    const VERSION: u32 = 1;
+}
+
+impl Report for Promotion {
+    const KIND: &'static str = "promotion";
+    const VERSION: u32 = 1;
+}
+
+impl Report for SyncPlan {
+    const KIND: &'static str = "sync-plan";
+    const VERSION: u32 = 1;
+}
+
+impl Report for Recovery {
+    const KIND: &'static str = "journal-recovery";
+    const VERSION: u32 = 1;
+}
+
+impl Report for GateResults {
+    const KIND: &'static str = "gates";
+    const VERSION: u32 = 1;
+}
+
+impl Report for SnapshotChanges {
+    const KIND: &'static str = "snapshot-changes";
+    const VERSION: u32 = 1;
+}
+
+impl<K, T, E> Report for BulkResult<K, T, E>
+where
+    K: Serialize,
+    T: Serialize,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    const KIND: &'static str = "bulk-result";
+    const VERSION: u32 = 1;
+}
+
+/// Serializes an error as `{"message": ..., "message_key": ...}`.
+pub(crate) fn serialize_error<S, E>(error: &ApiError<E>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut state = serializer.serialize_struct("Error", 2)?;
+    state.serialize_field("message", &error.to_string())?;
+    state.serialize_field("message_key", error.user_message().key)?;
+    state.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::Report;
+    use crate::{api::BulkResult, ApiError};
+
+    #[test]
+    fn bulk_result_lists_values_and_errors() {
+        let result: BulkResult<&str, u32, std::io::Error> = vec![
+            ("a", Ok(1)),
+            (
+                "b",
+                Err(ApiError::client(std::io::ErrorKind::TimedOut.into())),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&result.to_json_string().unwrap()).unwrap();
+
+        assert_eq!(json["kind"], "bulk-result");
+        assert_eq!(json["version"], 1);
+        assert_eq!(
+            json["report"]["results"][0],
+            json!({ "key": "a", "value": 1 })
+        );
+        assert_eq!(json["report"]["results"][1]["key"], "b");
+        assert_eq!(
+            json["report"]["results"][1]["error"]["message_key"],
+            "error.connection"
+        );
+    }
+}
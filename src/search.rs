@@ -0,0 +1,278 @@
+//! Searching the terms and translations of a project without refetching them.
+//!
+//! A [`ProjectIndex`] downloads all terms and translations of a project once
+//! and answers queries from memory, e.g. which terms match a key pattern,
+//! which translations contain a text or which terms are not translated yet.
+//! All queries return iterators in the order of the term list.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::search::ProjectIndex;
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let index = ProjectIndex::fetch(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+//!
+//! for term in index.keys_matching("hello.*") {
+//!     println!("{}", term.value);
+//! }
+//! for hit in index.values_containing("Welt") {
+//!     println!("[{}] {}: {}", hit.locale, hit.term.value, hit.translation.value);
+//! }
+//! let missing = index.untranslated(&"de_DE".into()).count();
+//! println!("{} terms are not translated into German", missing);
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    api::{
+        locales::LocaleCode, terms::Term, translations::Translation, ProjectId, TermId, TermKey,
+    },
+    auth::Authenticated,
+    ops::ProjectData,
+    ApiError, AsyncClient, Client,
+};
+
+/// A translation found by a [`ProjectIndex`] query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit<'a> {
+    /// The translated term.
+    pub term: &'a Term,
+    /// Locale of the translation.
+    pub locale: &'a LocaleCode,
+    /// The matching translation.
+    pub translation: &'a Translation,
+}
+
+/// All terms and translations of a project in memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectIndex {
+    terms: Vec<Term>,
+    translations: BTreeMap<LocaleCode, BTreeMap<TermId, Translation>>,
+}
+
+impl ProjectIndex {
+    /// Creates an index from terms and the translations of each locale.
+    #[must_use]
+    pub fn new(terms: Vec<Term>, translations: BTreeMap<LocaleCode, Vec<Translation>>) -> Self {
+        let translations = translations
+            .into_iter()
+            .map(|(locale, values)| {
+                let values = values.into_iter().map(|t| (t.term_id.clone(), t)).collect();
+                (locale, values)
+            })
+            .collect();
+        Self {
+            terms,
+            translations,
+        }
+    }
+
+    /// Downloads all terms and the translations of all locales of the project.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub fn fetch<C>(client: &C, project: &ProjectId) -> Result<Self, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let data = ProjectData::fetch(client, project, |_| true)?;
+        Ok(Self::new(data.terms, data.translations))
+    }
+
+    /// Downloads all terms and the translations of all locales of the project asynchronously.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub async fn fetch_async<C>(client: &C, project: &ProjectId) -> Result<Self, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let data = ProjectData::fetch_async(client, project, |_| true).await?;
+        Ok(Self::new(data.terms, data.translations))
+    }
+
+    /// All terms of the project.
+    pub fn terms(&self) -> impl Iterator<Item = &Term> {
+        self.terms.iter()
+    }
+
+    /// All locales of the project.
+    pub fn locales(&self) -> impl Iterator<Item = &LocaleCode> {
+        self.translations.keys()
+    }
+
+    /// The term with the key.
+    #[must_use]
+    pub fn term(&self, key: &str) -> Option<&Term> {
+        self.terms.iter().find(|t| t.value == key)
+    }
+
+    /// The translation of a term into the locale.
+    #[must_use]
+    pub fn translation(&self, term: &TermId, locale: &LocaleCode) -> Option<&Translation> {
+        self.translations.get(locale)?.get(term)
+    }
+
+    /// All translations of a term by locale.
+    pub fn translations_of<'a>(
+        &'a self,
+        term: &'a TermId,
+    ) -> impl Iterator<Item = (&'a LocaleCode, &'a Translation)> + 'a {
+        self.translations
+            .iter()
+            .filter_map(move |(locale, values)| values.get(term).map(|t| (locale, t)))
+    }
+
+    /// All non-empty translations in the order of the term list, then by locale.
+    pub fn hits(&self) -> impl Iterator<Item = Hit<'_>> {
+        self.terms.iter().flat_map(move |term| {
+            self.translations_of(&term.id)
+                .filter(|(_, t)| !t.value.is_empty())
+                .map(move |(locale, translation)| Hit {
+                    term,
+                    locale,
+                    translation,
+                })
+        })
+    }
+
+    /// Terms whose key matches the glob pattern, see [`TermKey::matches`].
+    pub fn keys_matching<'a>(&'a self, pattern: &'a str) -> impl Iterator<Item = &'a Term> + 'a {
+        self.terms
+            .iter()
+            .filter(move |t| TermKey::new(t.value.as_str()).matches(pattern))
+    }
+
+    /// Translations that contain the text.
+    pub fn values_containing<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Hit<'a>> + 'a {
+        self.hits()
+            .filter(move |hit| hit.translation.value.contains(text))
+    }
+
+    /// Translations that match the regular expression.
+    ///
+    /// Only available with the `regex` feature.
+    #[cfg(feature = "regex")]
+    pub fn values_matching<'a>(
+        &'a self,
+        regex: &'a regex::Regex,
+    ) -> impl Iterator<Item = Hit<'a>> + 'a {
+        self.hits()
+            .filter(move |hit| regex.is_match(&hit.translation.value))
+    }
+
+    /// Terms that are tagged with the label.
+    pub fn with_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a Term> + 'a {
+        self.terms
+            .iter()
+            .filter(move |t| t.labels.iter().any(|l| l.value == label))
+    }
+
+    /// Terms without a non-empty translation into the locale.
+    pub fn untranslated<'a>(
+        &'a self,
+        locale: &'a LocaleCode,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.terms.iter().filter(move |t| {
+            self.translation(&t.id, locale)
+                .map_or(true, |t| t.value.is_empty())
+        })
+    }
+
+    /// Terms that were changed at or after the time, either themselves or one of their translations.
+    pub fn modified_since(&self, since: DateTime<Utc>) -> impl Iterator<Item = &Term> {
+        self.terms.iter().filter(move |t| {
+            t.date.modified >= since
+                || self
+                    .translations_of(&t.id)
+                    .any(|(_, t)| t.date.modified >= since)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{Duration, Utc};
+
+    use super::ProjectIndex;
+    use crate::api::{labels::Label, terms::Term, translations::Translation, AccessDates};
+
+    fn index() -> ProjectIndex {
+        let old = AccessDates {
+            created: Utc::now() - Duration::days(10),
+            modified: Utc::now() - Duration::days(10),
+        };
+        let new = AccessDates {
+            created: old.created,
+            modified: Utc::now(),
+        };
+        let terms = vec![
+            Term::new("1".into(), "checkout.title", old),
+            Term::new("2".into(), "checkout.form.name", old).with_labels(vec![Label::new(
+                "l".into(),
+                "ui",
+                "#000000",
+            )]),
+            Term::new("3".into(), "greeting", old),
+        ];
+        let mut translations = BTreeMap::new();
+        translations.insert(
+            "de".into(),
+            vec![
+                Translation::new("1".into(), "Kasse", old),
+                Translation::new("2".into(), "", old),
+                Translation::new("3".into(), "Hallo Welt", new),
+            ],
+        );
+        translations.insert(
+            "en".into(),
+            vec![Translation::new("1".into(), "Checkout", old)],
+        );
+        ProjectIndex::new(terms, translations)
+    }
+
+    fn keys<'a>(terms: impl Iterator<Item = &'a Term>) -> Vec<&'a str> {
+        terms.map(|t| t.value.as_str()).collect()
+    }
+
+    #[test]
+    fn terms_are_queried() {
+        let index = index();
+        assert_eq!(keys(index.keys_matching("checkout.*")), ["checkout.title"]);
+        assert_eq!(keys(index.with_label("ui")), ["checkout.form.name"]);
+        assert_eq!(
+            keys(index.untranslated(&"de".into())),
+            ["checkout.form.name"]
+        );
+        assert_eq!(
+            keys(index.modified_since(Utc::now() - Duration::days(1))),
+            ["greeting"]
+        );
+    }
+
+    #[test]
+    fn translations_are_searched() {
+        let index = index();
+        let hits: Vec<_> = index
+            .values_containing("e")
+            .map(|hit| (hit.term.value.as_str(), hit.locale.value()))
+            .collect();
+        assert_eq!(
+            hits,
+            [
+                ("checkout.title", "de"),
+                ("checkout.title", "en"),
+                ("greeting", "de")
+            ]
+        );
+        assert_eq!(index.hits().count(), 3);
+    }
+}
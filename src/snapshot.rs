@@ -0,0 +1,499 @@
+//! Keeping successive [`TranslationSnapshot`]s to report what changed between deploys.
+//!
+//! A [`SnapshotStore`] records a snapshot, e.g. on every deploy, as the
+//! difference to the previously recorded one, so unchanged translations are
+//! not stored again. Any recorded snapshot can be restored, and
+//! [`SnapshotStore::changes`] lists the changes between two of them.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{snapshot::SnapshotStore, store::MemoryStore, sync::TranslationSnapshot};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+//! let history = SnapshotStore::new(MemoryStore::new(), "frontend");
+//!
+//! let mut snapshot = TranslationSnapshot::fetch(&client, &project)?;
+//! let first = history.record(chrono::Utc::now(), &snapshot).unwrap();
+//! snapshot.set("checkout.title", "de_DE".into(), "Kasse");
+//! let second = history.record(chrono::Utc::now(), &snapshot).unwrap();
+//!
+//! let changes = history.changes(first.index, second.index).unwrap();
+//! assert_eq!(
+//!     changes.to_string(),
+//!     "+ term checkout.title\n+ [de_DE] checkout.title: \"Kasse\"\n"
+//! );
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    api::{locales::LocaleCode, TermKey},
+    store::{FileStore, KvStore},
+    sync::TranslationSnapshot,
+};
+
+/// The error which is returned when recording or restoring a snapshot fails.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// The store could not be read or written.
+    #[error("failed to access the snapshot store: {}", source)]
+    Io {
+        /// Inner error.
+        #[from]
+        source: io::Error,
+    },
+    /// A recorded snapshot could not be read.
+    #[error("recorded snapshot {} is invalid: {}", key, source)]
+    Corrupt {
+        /// Key of the snapshot in the store.
+        key: String,
+        /// Inner error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// No snapshot was recorded with the index.
+    #[error("no snapshot with index {} was recorded", index)]
+    NotFound {
+        /// The requested index.
+        index: usize,
+    },
+    /// The snapshot is older than the latest recorded snapshot.
+    #[error(
+        "snapshot taken at {} is older than the latest snapshot taken at {}",
+        taken_at,
+        latest
+    )]
+    OutOfOrder {
+        /// When the rejected snapshot was taken.
+        taken_at: DateTime<Utc>,
+        /// When the latest recorded snapshot was taken.
+        latest: DateTime<Utc>,
+    },
+}
+
+/// A recorded snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnapshotInfo {
+    /// Position of the snapshot, starting at 0 for the first one.
+    pub index: usize,
+    /// When the snapshot was taken.
+    pub taken_at: DateTime<Utc>,
+    /// Number of changed terms and translations compared to the previous snapshot.
+    pub changes: usize,
+}
+
+/// The difference between a snapshot and its predecessor as it is written to the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Delta {
+    taken_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    removed_terms: Vec<TermKey>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    added_terms: Vec<TermKey>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    set: Vec<(TermKey, LocaleCode, String)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    unset: Vec<(TermKey, LocaleCode)>,
+}
+
+impl Delta {
+    fn between(
+        taken_at: DateTime<Utc>,
+        old: &TranslationSnapshot,
+        new: &TranslationSnapshot,
+    ) -> Self {
+        let mut delta = Self {
+            taken_at,
+            removed_terms: Vec::new(),
+            added_terms: Vec::new(),
+            set: Vec::new(),
+            unset: Vec::new(),
+        };
+        let empty = BTreeMap::new();
+        for term in old.terms.keys().filter(|t| !new.terms.contains_key(*t)) {
+            delta.removed_terms.push(term.clone());
+        }
+        for (term, translations) in &new.terms {
+            let previous = old.terms.get(term).unwrap_or_else(|| {
+                delta.added_terms.push(term.clone());
+                &empty
+            });
+            for (locale, value) in translations {
+                if previous.get(locale) != Some(value) {
+                    delta
+                        .set
+                        .push((term.clone(), locale.clone(), value.clone()));
+                }
+            }
+            for locale in previous.keys().filter(|l| !translations.contains_key(*l)) {
+                delta.unset.push((term.clone(), locale.clone()));
+            }
+        }
+        delta
+    }
+
+    fn apply(&self, snapshot: &mut TranslationSnapshot) {
+        for term in &self.removed_terms {
+            snapshot.terms.remove(term);
+        }
+        for term in &self.added_terms {
+            snapshot.add_term(term.clone());
+        }
+        for (term, locale, value) in &self.set {
+            snapshot.set(term.clone(), locale.clone(), value.clone());
+        }
+        for (term, locale) in &self.unset {
+            if let Some(translations) = snapshot.terms.get_mut(term) {
+                translations.remove(locale);
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.removed_terms.len() + self.added_terms.len() + self.set.len() + self.unset.len()
+    }
+}
+
+/// Records successive [`TranslationSnapshot`]s of a project in a [`KvStore`].
+///
+/// Every snapshot is stored as the difference to its predecessor under a
+/// key starting with `snapshots/<name>/`, so several projects can share a
+/// store. Restoring a snapshot replays all differences up to it.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct SnapshotStore<S = FileStore> {
+    store: S,
+    prefix: String,
+}
+
+impl<S: KvStore> SnapshotStore<S> {
+    /// Keeps the snapshots named `name` in the store.
+    ///
+    /// Snapshots recorded with the same name by a previous run are continued.
+    pub fn new(store: S, name: &str) -> Self {
+        Self {
+            store,
+            prefix: format!("snapshots/{}/", name),
+        }
+    }
+
+    /// Returns the underlying store.
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Stores the changes of the snapshot compared to the latest recorded one.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be accessed or the
+    /// snapshot is older than the latest recorded one.
+    pub fn record(
+        &self,
+        taken_at: DateTime<Utc>,
+        snapshot: &TranslationSnapshot,
+    ) -> Result<SnapshotInfo, SnapshotError> {
+        let deltas = self.deltas()?;
+        if let Some(latest) = deltas.last().map(|d| d.taken_at).filter(|l| *l > taken_at) {
+            return Err(SnapshotError::OutOfOrder { taken_at, latest });
+        }
+        let mut previous = TranslationSnapshot::new();
+        for delta in &deltas {
+            delta.apply(&mut previous);
+        }
+
+        let delta = Delta::between(taken_at, &previous, snapshot);
+        let index = deltas.len();
+        let data = serde_json::to_vec(&delta).map_err(io::Error::from)?;
+        self.store.put(&self.key(index), &data, None)?;
+        Ok(SnapshotInfo {
+            index,
+            taken_at,
+            changes: delta.len(),
+        })
+    }
+
+    /// All recorded snapshots from oldest to newest.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be read.
+    pub fn list(&self) -> Result<Vec<SnapshotInfo>, SnapshotError> {
+        Ok(self
+            .deltas()?
+            .iter()
+            .enumerate()
+            .map(|(index, delta)| SnapshotInfo {
+                index,
+                taken_at: delta.taken_at,
+                changes: delta.len(),
+            })
+            .collect())
+    }
+
+    /// Restores the snapshot with the index.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be read or no
+    /// snapshot was recorded with the index.
+    pub fn load(&self, index: usize) -> Result<TranslationSnapshot, SnapshotError> {
+        let deltas = self.deltas()?;
+        if index >= deltas.len() {
+            return Err(SnapshotError::NotFound { index });
+        }
+        let mut snapshot = TranslationSnapshot::new();
+        for delta in &deltas[..=index] {
+            delta.apply(&mut snapshot);
+        }
+        Ok(snapshot)
+    }
+
+    /// The changes from the snapshot `from` to the snapshot `to`.
+    ///
+    /// `from` may be newer than `to`, the changes are then reversed.
+    ///
+    /// # Errors
+    /// This method returns an error if the store cannot be read or one of
+    /// the snapshots was not recorded.
+    pub fn changes(&self, from: usize, to: usize) -> Result<SnapshotChanges, SnapshotError> {
+        let deltas = self.deltas()?;
+        let missing = from.max(to);
+        if missing >= deltas.len() {
+            return Err(SnapshotError::NotFound { index: missing });
+        }
+        let mut snapshot = TranslationSnapshot::new();
+        let mut snapshots = BTreeMap::new();
+        for (index, delta) in deltas.iter().enumerate().take(missing + 1) {
+            delta.apply(&mut snapshot);
+            if index == from || index == to {
+                snapshots.insert(index, snapshot.clone());
+            }
+        }
+        Ok(SnapshotChanges::between(
+            deltas[from].taken_at,
+            &snapshots[&from],
+            deltas[to].taken_at,
+            &snapshots[&to],
+        ))
+    }
+
+    fn key(&self, index: usize) -> String {
+        format!("{}{:010}", self.prefix, index)
+    }
+
+    fn deltas(&self) -> Result<Vec<Delta>, SnapshotError> {
+        let keys = self.store.list(&self.prefix)?;
+        let mut deltas = Vec::with_capacity(keys.len());
+        for (index, key) in keys.into_iter().enumerate() {
+            if key != self.key(index) {
+                return Err(SnapshotError::NotFound { index });
+            }
+            let data = self
+                .store
+                .get(&key)?
+                .ok_or(SnapshotError::NotFound { index })?;
+            let delta = serde_json::from_slice(&data)
+                .map_err(|source| SnapshotError::Corrupt { key, source })?;
+            deltas.push(delta);
+        }
+        Ok(deltas)
+    }
+}
+
+/// A change between two recorded snapshots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SnapshotChange {
+    /// The term was added.
+    AddTerm {
+        /// The new term key.
+        term: TermKey,
+    },
+    /// The term and its translations were removed.
+    RemoveTerm {
+        /// The removed term key.
+        term: TermKey,
+    },
+    /// A translation was added, changed or removed.
+    SetTranslation {
+        /// The term key.
+        term: TermKey,
+        /// Locale of the translation.
+        locale: LocaleCode,
+        /// The translation before. `None` if the term was not translated.
+        old_value: Option<String>,
+        /// The translation after. `None` if the translation was removed.
+        new_value: Option<String>,
+    },
+}
+
+impl fmt::Display for SnapshotChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AddTerm { term } => write!(f, "+ term {}", term),
+            Self::RemoveTerm { term } => write!(f, "- term {}", term),
+            Self::SetTranslation {
+                term,
+                locale,
+                old_value,
+                new_value,
+            } => match (old_value, new_value) {
+                (Some(old), Some(new)) => {
+                    write!(f, "~ [{}] {}: {:?} -> {:?}", locale, term, old, new)
+                }
+                (None, Some(new)) => write!(f, "+ [{}] {}: {:?}", locale, term, new),
+                (Some(old), None) => write!(f, "- [{}] {}: {:?}", locale, term, old),
+                (None, None) => Ok(()),
+            },
+        }
+    }
+}
+
+/// The changes between two recorded snapshots.
+///
+/// Returned by [`SnapshotStore::changes`]. The [`Display`](fmt::Display)
+/// implementation lists one change per line. Translations of removed terms
+/// are not listed separately.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SnapshotChanges {
+    /// When the older snapshot was taken.
+    pub from: DateTime<Utc>,
+    /// When the newer snapshot was taken.
+    pub to: DateTime<Utc>,
+    /// The changes sorted by term.
+    pub changes: Vec<SnapshotChange>,
+}
+
+impl SnapshotChanges {
+    fn between(
+        from: DateTime<Utc>,
+        old: &TranslationSnapshot,
+        to: DateTime<Utc>,
+        new: &TranslationSnapshot,
+    ) -> Self {
+        let terms: BTreeSet<_> = old.terms.keys().chain(new.terms.keys()).collect();
+        let empty = BTreeMap::new();
+        let mut changes = Vec::new();
+        for term in terms {
+            let (before, after) = match (old.terms.get(term), new.terms.get(term)) {
+                (Some(_), None) => {
+                    changes.push(SnapshotChange::RemoveTerm { term: term.clone() });
+                    continue;
+                }
+                (None, after) => {
+                    changes.push(SnapshotChange::AddTerm { term: term.clone() });
+                    (&empty, after.unwrap_or(&empty))
+                }
+                (Some(before), Some(after)) => (before, after),
+            };
+            let locales: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+            for locale in locales {
+                let (old_value, new_value) = (before.get(locale), after.get(locale));
+                if old_value != new_value {
+                    changes.push(SnapshotChange::SetTranslation {
+                        term: term.clone(),
+                        locale: locale.clone(),
+                        old_value: old_value.cloned(),
+                        new_value: new_value.cloned(),
+                    });
+                }
+            }
+        }
+        Self { from, to, changes }
+    }
+
+    /// Whether the snapshots are equal.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for SnapshotChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+
+    use super::{SnapshotChange, SnapshotError, SnapshotStore};
+    use crate::{store::MemoryStore, sync::TranslationSnapshot};
+
+    fn day(day: u32) -> DateTime<Utc> {
+        format!("2022-05-{:02}T12:00:00Z", day).parse().unwrap()
+    }
+
+    #[test]
+    fn snapshots_are_restored_from_deltas() {
+        let store = SnapshotStore::new(MemoryStore::new(), "app");
+        let mut snapshot = TranslationSnapshot::new();
+        snapshot.set("hello", "de".into(), "Hallo");
+        snapshot.set("bye", "de".into(), "Tschüss");
+        let first = snapshot.clone();
+        store.record(day(1), &snapshot).unwrap();
+
+        snapshot.terms.remove("bye");
+        snapshot.set("hello", "en".into(), "Hello");
+        snapshot.add_term("new");
+        let info = store.record(day(2), &snapshot).unwrap();
+        assert_eq!(info.changes, 3);
+        let info = store.record(day(3), &snapshot).unwrap();
+        assert_eq!(info.changes, 0);
+
+        assert_eq!(store.load(0).unwrap(), first);
+        assert_eq!(store.load(2).unwrap(), snapshot);
+        assert_eq!(store.list().unwrap().len(), 3);
+        assert!(matches!(
+            store.load(3),
+            Err(SnapshotError::NotFound { index: 3 })
+        ));
+        assert!(matches!(
+            store.record(day(2), &snapshot),
+            Err(SnapshotError::OutOfOrder { .. })
+        ));
+    }
+
+    #[test]
+    fn changes_between_snapshots_are_listed() {
+        let store = SnapshotStore::new(MemoryStore::new(), "app");
+        let mut snapshot = TranslationSnapshot::new();
+        snapshot.set("hello", "de".into(), "Hallo");
+        snapshot.set("bye", "de".into(), "Tschüss");
+        store.record(day(1), &snapshot).unwrap();
+        snapshot.set("hello", "de".into(), "Guten Tag");
+        store.record(day(2), &snapshot).unwrap();
+        snapshot.terms.remove("bye");
+        snapshot.set("new", "de".into(), "Neu");
+        store.record(day(3), &snapshot).unwrap();
+
+        let changes = store.changes(0, 2).unwrap();
+        assert_eq!((changes.from, changes.to), (day(1), day(3)));
+        assert_eq!(changes.changes.len(), 4);
+        assert_eq!(
+            changes.changes[0],
+            SnapshotChange::RemoveTerm { term: "bye".into() }
+        );
+        assert_eq!(
+            changes.to_string(),
+            "- term bye\n~ [de] hello: \"Hallo\" -> \"Guten Tag\"\n+ term new\n+ [de] new: \"Neu\"\n"
+        );
+        assert!(store.changes(1, 1).unwrap().is_empty());
+    }
+}
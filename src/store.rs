@@ -0,0 +1,331 @@
+//! Pluggable storage for caches and other state that outlives a request.
+//!
+//! The [`KvStore`] trait is a minimal key-value interface. It is used by
+//! [`ConditionalCache`](crate::cache::ConditionalCache) and
+//! [`HistoryStore`](crate::ops::HistoryStore). The crate provides an
+//! in-memory and a filesystem implementation; other backends like sled
+//! or SQLite can be plugged in by implementing the trait.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A key-value store with optional expiry of entries.
+///
+/// Keys are arbitrary strings. By convention, subsystems prefix their keys
+/// with a namespace followed by a slash, e.g. `http-cache/`, so a single store
+/// can be shared. Expired entries must not be returned by [`KvStore::get`]
+/// or [`KvStore::list`].
+pub trait KvStore: Debug + Send + Sync {
+    /// Returns the value of the key or `None` if it does not exist or expired.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be read.
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+
+    /// Stores the value. It expires after `ttl` if given.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be written.
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> io::Result<()>;
+
+    /// Removes the key. Removing a missing key is not an error.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be written.
+    fn remove(&self, key: &str) -> io::Result<()>;
+
+    /// Returns all keys that start with the prefix in ascending order.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be read.
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>>;
+
+    /// Returns the value of the key deserialized from JSON.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be read or the value
+    /// is not valid JSON for `T`.
+    fn get_json<T: DeserializeOwned>(&self, key: &str) -> io::Result<Option<T>>
+    where
+        Self: Sized,
+    {
+        self.get(key)?
+            .map(|v| serde_json::from_slice(&v).map_err(io::Error::from))
+            .transpose()
+    }
+
+    /// Stores the value serialized as JSON.
+    ///
+    /// # Errors
+    /// This method returns an error if the backend cannot be written.
+    fn put_json<T: Serialize>(&self, key: &str, value: &T, ttl: Option<Duration>) -> io::Result<()>
+    where
+        Self: Sized,
+    {
+        self.put(key, &serde_json::to_vec(value)?, ttl)
+    }
+}
+
+type Entries = HashMap<String, (Vec<u8>, Option<Instant>)>;
+
+/// A store that keeps all entries in memory.
+///
+/// The entries are lost when the store is dropped.
+///
+/// # Examples
+/// ```
+/// use std::time::Duration;
+/// use traduora::store::{KvStore, MemoryStore};
+///
+/// let store = MemoryStore::new();
+/// store.put("state/last-run", b"2022-05-02", Some(Duration::from_secs(60)))?;
+///
+/// assert_eq!(store.get("state/last-run")?.unwrap(), b"2022-05-02");
+/// assert_eq!(store.list("state/")?, vec!["state/last-run"]);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    entries: Mutex<Entries>,
+}
+
+impl MemoryStore {
+    /// Creates an empty store.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, Entries> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        entries.retain(|_, (_, expiry)| expiry.map_or(true, |e| e > now));
+        entries
+    }
+}
+
+impl KvStore for MemoryStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        Ok(self.entries().get(key).map(|(value, _)| value.clone()))
+    }
+
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> io::Result<()> {
+        let expiry = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries()
+            .insert(key.to_owned(), (value.to_vec(), expiry));
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        self.entries().remove(key);
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let mut keys: Vec<_> = self
+            .entries()
+            .keys()
+            .filter(|k| k.starts_with(prefix))
+            .cloned()
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// A store that keeps every entry in a file of a directory.
+///
+/// Values are written to a temporary file first and then moved into place,
+/// so readers never see partially written values. The expiry time is stored
+/// in the file, so entries expire across restarts of the application.
+///
+/// # Examples
+/// ```
+/// use traduora::store::{FileStore, KvStore};
+///
+/// # let dir = std::env::temp_dir().join(format!("traduora-store-doc-{}", std::process::id()));
+/// let store = FileStore::new(&dir)?;
+/// store.put("state/last-run", b"2022-05-02", None)?;
+///
+/// assert_eq!(store.get("state/last-run")?.unwrap(), b"2022-05-02");
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: PathBuf,
+}
+
+/// Length of the expiry timestamp at the start of every file.
+const EXPIRY_LEN: usize = 8;
+
+impl FileStore {
+    /// Uses the directory to store the entries. It is created if it does not exist.
+    ///
+    /// # Errors
+    /// This method returns an error if the directory cannot be created.
+    pub fn new(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// The directory the entries are stored in.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(encode_key(key))
+    }
+}
+
+impl KvStore for FileStore {
+    fn get(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let path = self.path(key);
+        let mut data = match fs::read(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        if data.len() < EXPIRY_LEN {
+            return Err(io::Error::new(ErrorKind::InvalidData, "truncated entry"));
+        }
+        let mut expiry = [0; EXPIRY_LEN];
+        expiry.copy_from_slice(&data[..EXPIRY_LEN]);
+        let expiry = u64::from_be_bytes(expiry);
+        if expiry != 0 && expiry <= unix_millis(SystemTime::now()) {
+            self.remove(key)?;
+            return Ok(None);
+        }
+        data.drain(..EXPIRY_LEN);
+        Ok(Some(data))
+    }
+
+    fn put(&self, key: &str, value: &[u8], ttl: Option<Duration>) -> io::Result<()> {
+        let expiry = ttl.map_or(0, |ttl| unix_millis(SystemTime::now() + ttl).max(1));
+        let mut data = Vec::with_capacity(EXPIRY_LEN + value.len());
+        data.extend_from_slice(&expiry.to_be_bytes());
+        data.extend_from_slice(value);
+
+        // encoded keys never start with a dot, so temporary files cannot clash with them
+        let temp = self
+            .dir
+            .join(format!(".{}.{}", encode_key(key), std::process::id()));
+        fs::write(&temp, data)?;
+        fs::rename(temp, self.path(key))
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let name = entry?.file_name();
+            let key = match name.to_str().and_then(decode_key) {
+                Some(key) if key.starts_with(prefix) => key,
+                _ => continue,
+            };
+            if self.get(&key)?.is_some() {
+                keys.push(key);
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// Percent-encodes all characters except ASCII letters, digits, `-` and `_`.
+fn encode_key(key: &str) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+fn decode_key(name: &str) -> Option<String> {
+    if name.starts_with('.') {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut rest = name.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{decode_key, encode_key, FileStore, KvStore, MemoryStore};
+
+    fn exercise(store: &dyn KvStore) {
+        store.put("a/1", b"one", None).unwrap();
+        store
+            .put("a/2", b"two", Some(Duration::from_secs(60)))
+            .unwrap();
+        store.put("b/1", b"other", None).unwrap();
+        store.put("a/gone", b"old", Some(Duration::ZERO)).unwrap();
+
+        assert_eq!(store.get("a/1").unwrap().unwrap(), b"one");
+        assert_eq!(store.get("a/gone").unwrap(), None);
+        assert_eq!(store.list("a/").unwrap(), vec!["a/1", "a/2"]);
+
+        store.remove("a/1").unwrap();
+        store.remove("missing").unwrap();
+        assert_eq!(store.get("a/1").unwrap(), None);
+    }
+
+    #[test]
+    fn memory_store() {
+        exercise(&MemoryStore::new());
+    }
+
+    #[test]
+    fn file_store() {
+        let dir = std::env::temp_dir().join(format!("traduora-store-test-{}", std::process::id()));
+        exercise(&FileStore::new(&dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn keys_survive_encoding() {
+        for key in ["http-cache/https://example.com/api?x=ä", ".hidden", "%41"] {
+            let encoded = encode_key(key);
+            assert!(!encoded.starts_with('.'));
+            assert_eq!(decode_key(&encoded).as_deref(), Some(key));
+        }
+    }
+}
@@ -0,0 +1,1216 @@
+//! Synchronization of local translations with a Traduora project.
+//!
+//! A [`TranslationSnapshot`] holds terms and translations, e.g. read from the
+//! translation files of an application. The [`SyncPlanner`] compares it with
+//! the remote project and computes a [`SyncPlan`] of the endpoint calls that
+//! make the remote project match the snapshot. The plan can be printed for
+//! review before it is executed. Translations that were changed both locally
+//! and remotely are decided by a [`ConflictPolicy`] and listed in the plan.
+//! If the snapshot the local changes are based on is known, [`merge3`]
+//! merges both sides and only reports translations that really conflict.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::sync::{SyncPlanner, TranslationSnapshot};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into();
+//!
+//! // pull
+//! let mut local = TranslationSnapshot::fetch(&client, &project)?;
+//!
+//! // edit and diff
+//! local.set("this.is.a.term", "en".into(), "Hello");
+//! let plan = SyncPlanner::new(project).plan(&client, &local)?;
+//! println!("{}", plan);
+//!
+//! // push
+//! plan.execute(&client)?;
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{
+        self,
+        locales::LocaleCode,
+        terms::{CreateTerm, DeleteTerm, EditTerm},
+        translations::{CreateLocale, EditTranslation},
+        ProjectId, TermId, TermKey,
+    },
+    auth::Authenticated,
+    cancel::{Cancellable, CancellationToken},
+    ops::{Journal, JournalOperation, JournaledError, ProjectData},
+    progress::{NoProgress, ProgressSink, Tracker},
+    ApiError, AsyncClient, AsyncCustomQuery, AsyncQuery, Client, CustomQuery, Query,
+};
+
+/// Terms and their translations by locale.
+///
+/// An empty map of translations means that the term exists but is not translated.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TranslationSnapshot {
+    /// Translations by term key and locale.
+    pub terms: BTreeMap<TermKey, BTreeMap<LocaleCode, String>>,
+}
+
+impl TranslationSnapshot {
+    /// Creates an empty snapshot.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a term without translations. Existing translations are kept.
+    pub fn add_term(&mut self, term: impl Into<TermKey>) {
+        self.terms.entry(term.into()).or_default();
+    }
+
+    /// Sets the translation of a term and adds the term if necessary.
+    pub fn set(&mut self, term: impl Into<TermKey>, locale: LocaleCode, value: impl Into<String>) {
+        self.terms
+            .entry(term.into())
+            .or_default()
+            .insert(locale, value.into());
+    }
+
+    /// Returns the translation of a term.
+    #[must_use]
+    pub fn get(&self, term: &str, locale: &LocaleCode) -> Option<&str> {
+        self.terms.get(term)?.get(locale).map(String::as_str)
+    }
+
+    /// All locales that have at least one translation.
+    #[must_use]
+    pub fn locales(&self) -> BTreeSet<&LocaleCode> {
+        self.terms.values().flat_map(BTreeMap::keys).collect()
+    }
+
+    /// Downloads all terms and translations of the project.
+    ///
+    /// Locales without translations are not part of the snapshot.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub fn fetch<C>(client: &C, project: &ProjectId) -> Result<Self, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        ProjectData::fetch(client, project, |_| true).map(|d| Remote::new(d).snapshot)
+    }
+
+    /// Downloads all terms and translations of the project asynchronously.
+    ///
+    /// Locales without translations are not part of the snapshot.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub async fn fetch_async<C>(client: &C, project: &ProjectId) -> Result<Self, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        ProjectData::fetch_async(client, project, |_| true)
+            .await
+            .map(|d| Remote::new(d).snapshot)
+    }
+}
+
+/// The remote project as needed for planning.
+#[derive(Debug, Clone, Default)]
+struct Remote {
+    snapshot: TranslationSnapshot,
+    ids: BTreeMap<TermKey, TermId>,
+    locales: BTreeSet<LocaleCode>,
+    modified: BTreeMap<(TermKey, LocaleCode), DateTime<Utc>>,
+}
+
+impl Remote {
+    fn new(data: ProjectData) -> Self {
+        let mut remote = Self::default();
+        let mut keys = BTreeMap::new();
+        for term in data.terms {
+            remote.snapshot.add_term(term.value.clone());
+            keys.insert(term.id.clone(), TermKey::from(term.value.clone()));
+            remote.ids.insert(term.value.into(), term.id);
+        }
+        for (locale, translations) in data.translations {
+            for translation in translations {
+                match keys.get(&translation.term_id) {
+                    Some(key) if !translation.value.is_empty() => {
+                        remote
+                            .snapshot
+                            .set(key.clone(), locale.clone(), translation.value);
+                        remote
+                            .modified
+                            .insert((key.clone(), locale.clone()), translation.date.modified);
+                    }
+                    _ => {}
+                }
+            }
+            remote.locales.insert(locale);
+        }
+        remote
+    }
+}
+
+/// A single change of the remote project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SyncOperation {
+    /// Adds a locale to the project.
+    CreateLocale {
+        /// The new locale.
+        locale: LocaleCode,
+    },
+    /// Changes the term string of an existing term. Its translations are kept.
+    RenameTerm {
+        /// Unique id of the term.
+        term_id: TermId,
+        /// The current term key.
+        from: TermKey,
+        /// The new term key.
+        to: TermKey,
+    },
+    /// Adds a term to the project.
+    CreateTerm {
+        /// The new term key.
+        term: TermKey,
+    },
+    /// Removes a term and its translations from the project.
+    DeleteTerm {
+        /// Unique id of the term.
+        term_id: TermId,
+        /// The term key.
+        term: TermKey,
+    },
+    /// Sets the translation of a term.
+    SetTranslation {
+        /// The term key.
+        term: TermKey,
+        /// Locale of the translation.
+        locale: LocaleCode,
+        /// The current translation. `None` if the term is not translated.
+        old_value: Option<String>,
+        /// The new translation.
+        new_value: String,
+    },
+}
+
+impl fmt::Display for SyncOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CreateLocale { locale } => write!(f, "+ locale {}", locale),
+            Self::RenameTerm { from, to, .. } => write!(f, "~ term {} -> {}", from, to),
+            Self::CreateTerm { term } => write!(f, "+ term {}", term),
+            Self::DeleteTerm { term, .. } => write!(f, "- term {}", term),
+            Self::SetTranslation {
+                term,
+                locale,
+                old_value: Some(old),
+                new_value,
+            } => write!(f, "~ [{}] {}: {:?} -> {:?}", locale, term, old, new_value),
+            Self::SetTranslation {
+                term,
+                locale,
+                old_value: None,
+                new_value,
+            } => write!(f, "+ [{}] {}: {:?}", locale, term, new_value),
+        }
+    }
+}
+
+/// How [`SyncPlanner`] handles translations that differ locally and remotely.
+///
+/// A conflict is a translation that is not empty in both the snapshot and
+/// the remote project but has different values. Conflicts are decided per
+/// term and locale and reported in [`SyncPlan::conflicts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConflictPolicy {
+    /// Overwrites the remote translation with the local one.
+    PreferLocal,
+    /// Keeps the remote translation.
+    PreferRemote,
+    /// Keeps the remote translation if it was modified after the given time,
+    /// e.g. when the local files were last pulled or edited.
+    PreferNewer(DateTime<Utc>),
+    /// Changes neither translation and reports the conflict as unresolved,
+    /// so [`gates::no_conflicts`](crate::gates::no_conflicts) fails.
+    Fail,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        Self::PreferLocal
+    }
+}
+
+/// The outcome of a [`SyncConflict`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolution {
+    /// The local translation is written to the project.
+    Local,
+    /// The remote translation is kept.
+    Remote,
+    /// The conflict was not resolved because of [`ConflictPolicy::Fail`].
+    Unresolved,
+}
+
+/// A translation that differs between the snapshot and the remote project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct SyncConflict {
+    /// The term key.
+    pub term: TermKey,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The translation in the snapshot.
+    pub local_value: String,
+    /// The translation in the remote project.
+    pub remote_value: String,
+    /// When the remote translation was last changed, if known.
+    pub remote_modified: Option<DateTime<Utc>>,
+    /// How the conflict was decided.
+    pub resolution: ConflictResolution,
+}
+
+impl fmt::Display for SyncConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "! [{}] {}: local {:?}, remote {:?}",
+            self.locale, self.term, self.local_value, self.remote_value
+        )?;
+        match self.resolution {
+            ConflictResolution::Local => write!(f, " (keeping local)"),
+            ConflictResolution::Remote => write!(f, " (keeping remote)"),
+            ConflictResolution::Unresolved => write!(f, " (unresolved)"),
+        }
+    }
+}
+
+/// Computes how to make a remote project match a [`TranslationSnapshot`].
+///
+/// By default, terms that only exist remotely are kept, terms with the
+/// same translations are detected as renamed and conflicting translations
+/// are overwritten with the local ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[must_use]
+pub struct SyncPlanner {
+    project: ProjectId,
+    delete_removed: bool,
+    detect_renames: bool,
+    conflict_policy: ConflictPolicy,
+}
+
+impl SyncPlanner {
+    /// Creates a planner for the project.
+    pub const fn new(project: ProjectId) -> Self {
+        Self {
+            project,
+            delete_removed: false,
+            detect_renames: true,
+            conflict_policy: ConflictPolicy::PreferLocal,
+        }
+    }
+
+    /// Whether terms that are missing from the snapshot are deleted from the project.
+    pub const fn delete_removed(mut self, delete_removed: bool) -> Self {
+        self.delete_removed = delete_removed;
+        self
+    }
+
+    /// Whether a removed and an added term with identical translations are
+    /// treated as a rename. Renaming keeps the labels of the term.
+    pub const fn detect_renames(mut self, detect_renames: bool) -> Self {
+        self.detect_renames = detect_renames;
+        self
+    }
+
+    /// How translations that differ locally and remotely are handled.
+    pub const fn conflict_policy(mut self, conflict_policy: ConflictPolicy) -> Self {
+        self.conflict_policy = conflict_policy;
+        self
+    }
+
+    /// Fetches the remote project and compares it with the snapshot.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub fn plan<C>(
+        &self,
+        client: &C,
+        local: &TranslationSnapshot,
+    ) -> Result<SyncPlan, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let data = ProjectData::fetch(client, &self.project, |_| true)?;
+        Ok(self.diff(local, Remote::new(data)))
+    }
+
+    /// Fetches the remote project asynchronously and compares it with the snapshot.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub async fn plan_async<C>(
+        &self,
+        client: &C,
+        local: &TranslationSnapshot,
+    ) -> Result<SyncPlan, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let data = ProjectData::fetch_async(client, &self.project, |_| true).await?;
+        Ok(self.diff(local, Remote::new(data)))
+    }
+
+    fn diff(&self, local: &TranslationSnapshot, mut remote: Remote) -> SyncPlan {
+        let mut operations: Vec<_> = local
+            .locales()
+            .into_iter()
+            .filter(|l| !remote.locales.contains(*l))
+            .map(|l| SyncOperation::CreateLocale { locale: l.clone() })
+            .collect();
+
+        let mut added: BTreeSet<_> = local
+            .terms
+            .keys()
+            .filter(|t| !remote.ids.contains_key(*t))
+            .collect();
+        let removed: Vec<_> = remote
+            .ids
+            .keys()
+            .filter(|t| !local.terms.contains_key(*t))
+            .cloned()
+            .collect();
+
+        let mut deletions = Vec::new();
+        for term in removed {
+            let translations = &remote.snapshot.terms[&term];
+            let renamed_to = if self.detect_renames && !translations.is_empty() {
+                let mut candidates = added.iter().filter(|a| &local.terms[**a] == translations);
+                match (candidates.next(), candidates.next()) {
+                    (Some(to), None) => Some((*to).clone()),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            let term_id = remote.ids[&term].clone();
+            if let Some(to) = renamed_to {
+                added.remove(&to);
+                let values = remote.snapshot.terms.remove(&term).unwrap_or_default();
+                remote.snapshot.terms.insert(to.clone(), values);
+                operations.push(SyncOperation::RenameTerm {
+                    term_id,
+                    from: term,
+                    to,
+                });
+            } else if self.delete_removed {
+                deletions.push(SyncOperation::DeleteTerm { term_id, term });
+            }
+        }
+
+        operations.extend(
+            added
+                .into_iter()
+                .map(|term| SyncOperation::CreateTerm { term: term.clone() }),
+        );
+        operations.extend(deletions);
+
+        let mut conflicts = Vec::new();
+        for (term, translations) in &local.terms {
+            for (locale, value) in translations.iter().filter(|(_, v)| !v.is_empty()) {
+                let old_value = remote.snapshot.get(term.value(), locale);
+                if old_value == Some(value) {
+                    continue;
+                }
+                if let Some(remote_value) = old_value {
+                    let remote_modified = remote.modified.get(&(term.clone(), locale.clone()));
+                    let resolution = self.resolve(remote_modified);
+                    conflicts.push(SyncConflict {
+                        term: term.clone(),
+                        locale: locale.clone(),
+                        local_value: value.clone(),
+                        remote_value: remote_value.to_owned(),
+                        remote_modified: remote_modified.copied(),
+                        resolution,
+                    });
+                    if resolution != ConflictResolution::Local {
+                        continue;
+                    }
+                }
+                operations.push(SyncOperation::SetTranslation {
+                    term: term.clone(),
+                    locale: locale.clone(),
+                    old_value: old_value.map(ToOwned::to_owned),
+                    new_value: value.clone(),
+                });
+            }
+        }
+
+        SyncPlan {
+            project: self.project.clone(),
+            operations,
+            conflicts,
+            ids: remote.ids,
+        }
+    }
+
+    fn resolve(&self, remote_modified: Option<&DateTime<Utc>>) -> ConflictResolution {
+        match self.conflict_policy {
+            ConflictPolicy::PreferLocal => ConflictResolution::Local,
+            ConflictPolicy::PreferRemote => ConflictResolution::Remote,
+            ConflictPolicy::PreferNewer(local_modified) => match remote_modified {
+                Some(remote_modified) if *remote_modified > local_modified => {
+                    ConflictResolution::Remote
+                }
+                _ => ConflictResolution::Local,
+            },
+            ConflictPolicy::Fail => ConflictResolution::Unresolved,
+        }
+    }
+}
+
+/// The endpoint calls that make the remote project match a snapshot.
+///
+/// Returned by [`SyncPlanner::plan`]. Its [`Display`](fmt::Display)
+/// implementation lists one operation per line followed by the conflicts,
+/// so it can be printed for a dry-run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncPlan {
+    /// The project that is changed.
+    pub project: ProjectId,
+    /// The changes in the order they are executed.
+    pub operations: Vec<SyncOperation>,
+    /// Translations that differ locally and remotely, resolved according
+    /// to the [`ConflictPolicy`] of the planner.
+    pub conflicts: Vec<SyncConflict>,
+    #[serde(skip)]
+    ids: BTreeMap<TermKey, TermId>,
+}
+
+impl SyncPlan {
+    /// Whether the remote project already matches the snapshot.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// The conflicts that were left unresolved because of [`ConflictPolicy::Fail`].
+    pub fn unresolved_conflicts(&self) -> impl Iterator<Item = &SyncConflict> {
+        self.conflicts
+            .iter()
+            .filter(|c| c.resolution == ConflictResolution::Unresolved)
+    }
+
+    /// Sends the operations to the server in order.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub fn execute<C>(&self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.execute_with_progress(client, &NoProgress)
+    }
+
+    /// Sends the operations to the server in order and reports the progress.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub fn execute_with_progress<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        self.execute_cancellable(client, &CancellationToken::new(), progress)
+            .map(|_| ())
+    }
+
+    /// Sends the operations to the server in order until the token is
+    /// cancelled and reports the progress.
+    ///
+    /// Returns the number of applied operations, which are the first ones
+    /// of the plan.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub fn execute_cancellable<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Result<Cancellable<usize>, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let client = Tracker::new(client, progress, self.operations.len());
+        let mut ids = self.ids.clone();
+        let mut applied = 0;
+        for op in self
+            .operations
+            .iter()
+            .take_while(|_| !cancel.is_cancelled())
+        {
+            self.execute_one(&client, op, &mut ids)?;
+            client.item_done();
+            applied += 1;
+        }
+        Ok(Cancellable {
+            result: applied,
+            skipped: self.operations.len() - applied,
+        })
+    }
+
+    /// Sends the operations to the server asynchronously in order.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub async fn execute_async<C>(&self, client: &C) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.execute_with_progress_async(client, &NoProgress).await
+    }
+
+    /// Sends the operations to the server asynchronously in order and reports the progress.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub async fn execute_with_progress_async<C>(
+        &self,
+        client: &C,
+        progress: &dyn ProgressSink,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        self.execute_cancellable_async(client, &CancellationToken::new(), progress)
+            .await
+            .map(|_| ())
+    }
+
+    /// Sends the operations to the server asynchronously in order until the
+    /// token is cancelled and reports the progress.
+    ///
+    /// Returns the number of applied operations, which are the first ones
+    /// of the plan.
+    ///
+    /// # Errors
+    /// This method returns an error if an operation fails.
+    /// Operations before the failing one are already applied.
+    pub async fn execute_cancellable_async<C>(
+        &self,
+        client: &C,
+        cancel: &CancellationToken,
+        progress: &dyn ProgressSink,
+    ) -> Result<Cancellable<usize>, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let client = Tracker::new(client, progress, self.operations.len());
+        let mut ids = self.ids.clone();
+        let mut applied = 0;
+        for op in self
+            .operations
+            .iter()
+            .take_while(|_| !cancel.is_cancelled())
+        {
+            self.execute_one_async(&client, op, &mut ids).await?;
+            client.item_done();
+            applied += 1;
+        }
+        Ok(Cancellable {
+            result: applied,
+            skipped: self.operations.len() - applied,
+        })
+    }
+
+    /// Sends the operations to the server in order and records them in a [`Journal`].
+    ///
+    /// If the process crashes, [`Journal::recover`] reports which operations were applied.
+    ///
+    /// # Errors
+    /// This method returns an error if the journal cannot be written or an operation fails.
+    /// Operations before the failing one are already applied.
+    pub fn execute_journaled<C>(
+        &self,
+        client: &C,
+        journal: impl AsRef<Path>,
+    ) -> Result<(), JournaledError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let mut journal = Journal::create(journal, self.journal_operations())?;
+        let mut ids = self.ids.clone();
+        for (index, op) in self.operations.iter().enumerate() {
+            journal.start(index)?;
+            self.execute_one(client, op, &mut ids)?;
+            journal.complete(index)?;
+        }
+        Ok(journal.finish()?)
+    }
+
+    /// Sends the operations to the server asynchronously in order and records them in a [`Journal`].
+    ///
+    /// If the process crashes or the future is dropped, [`Journal::recover`]
+    /// reports which operations were applied.
+    ///
+    /// # Errors
+    /// This method returns an error if the journal cannot be written or an operation fails.
+    /// Operations before the failing one are already applied.
+    pub async fn execute_journaled_async<C>(
+        &self,
+        client: &C,
+        journal: impl AsRef<Path> + Send,
+    ) -> Result<(), JournaledError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let mut journal = Journal::create(journal, self.journal_operations())?;
+        let mut ids = self.ids.clone();
+        for (index, op) in self.operations.iter().enumerate() {
+            journal.start(index)?;
+            self.execute_one_async(client, op, &mut ids).await?;
+            journal.complete(index)?;
+        }
+        Ok(journal.finish()?)
+    }
+
+    fn journal_operations(&self) -> Vec<JournalOperation> {
+        let unknown = TermId::new("");
+        let project = || self.project.clone();
+        self.operations
+            .iter()
+            .map(|op| {
+                let description = op.to_string();
+                match op {
+                    SyncOperation::CreateLocale { locale } => JournalOperation::new(
+                        &CreateLocale::new(project(), locale.clone()),
+                        description,
+                    ),
+                    SyncOperation::RenameTerm { term_id, to, .. } => JournalOperation::new(
+                        &EditTerm::new(project(), term_id.clone(), to.value()),
+                        description,
+                    ),
+                    SyncOperation::CreateTerm { term } => JournalOperation::new(
+                        &CreateTerm::new(term.clone(), project()),
+                        description,
+                    ),
+                    SyncOperation::DeleteTerm { term_id, .. } => JournalOperation::new(
+                        &DeleteTerm::new(project(), term_id.clone()),
+                        description,
+                    ),
+                    SyncOperation::SetTranslation {
+                        term,
+                        locale,
+                        new_value,
+                        ..
+                    } => {
+                        let term_id = self.ids.get(term).unwrap_or(&unknown).clone();
+                        let endpoint = EditTranslation::new(
+                            project(),
+                            locale.clone(),
+                            term_id,
+                            new_value.as_str(),
+                        );
+                        JournalOperation::new(&endpoint, description)
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn execute_one<C>(
+        &self,
+        client: &C,
+        op: &SyncOperation,
+        ids: &mut BTreeMap<TermKey, TermId>,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let project = self.project.clone();
+        match op {
+            SyncOperation::CreateLocale { locale } => {
+                api::ignore(CreateLocale::new(project, locale.clone())).query_custom(client)
+            }
+            SyncOperation::RenameTerm { term_id, to, .. } => {
+                ids.insert(to.clone(), term_id.clone());
+                api::ignore(EditTerm::new(project, term_id.clone(), to.value()))
+                    .query_custom(client)
+            }
+            SyncOperation::CreateTerm { term } => {
+                let created = CreateTerm::new(term.clone(), project).query(client)?;
+                ids.insert(term.clone(), created.id);
+                Ok(())
+            }
+            SyncOperation::DeleteTerm { term_id, .. } => {
+                api::ignore(DeleteTerm::new(project, term_id.clone())).query_custom(client)
+            }
+            SyncOperation::SetTranslation {
+                term,
+                locale,
+                new_value,
+                ..
+            } => {
+                let endpoint = set_translation(project, ids, term, locale, new_value)?;
+                api::ignore(endpoint).query_custom(client)
+            }
+        }
+    }
+
+    async fn execute_one_async<C>(
+        &self,
+        client: &C,
+        op: &SyncOperation,
+        ids: &mut BTreeMap<TermKey, TermId>,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let project = self.project.clone();
+        match op {
+            SyncOperation::CreateLocale { locale } => {
+                api::ignore(CreateLocale::new(project, locale.clone()))
+                    .query_custom_async(client)
+                    .await
+            }
+            SyncOperation::RenameTerm { term_id, to, .. } => {
+                ids.insert(to.clone(), term_id.clone());
+                api::ignore(EditTerm::new(project, term_id.clone(), to.value()))
+                    .query_custom_async(client)
+                    .await
+            }
+            SyncOperation::CreateTerm { term } => {
+                let created = CreateTerm::new(term.clone(), project)
+                    .query_async(client)
+                    .await?;
+                ids.insert(term.clone(), created.id);
+                Ok(())
+            }
+            SyncOperation::DeleteTerm { term_id, .. } => {
+                api::ignore(DeleteTerm::new(project, term_id.clone()))
+                    .query_custom_async(client)
+                    .await
+            }
+            SyncOperation::SetTranslation {
+                term,
+                locale,
+                new_value,
+                ..
+            } => {
+                let endpoint = set_translation(project, ids, term, locale, new_value)?;
+                api::ignore(endpoint).query_custom_async(client).await
+            }
+        }
+    }
+}
+
+fn set_translation<E>(
+    project: ProjectId,
+    ids: &BTreeMap<TermKey, TermId>,
+    term: &TermKey,
+    locale: &LocaleCode,
+    value: &str,
+) -> Result<EditTranslation, ApiError<E>>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let term_id = ids.get(term).ok_or_else(|| {
+        ApiError::from_traduora(
+            http::StatusCode::NOT_FOUND,
+            serde_json::json!({
+                "message": format!("term {} does not exist in the project", term)
+            }),
+        )
+    })?;
+    Ok(EditTranslation::new(
+        project,
+        locale.clone(),
+        term_id.clone(),
+        value,
+    ))
+}
+
+impl fmt::Display for SyncPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for op in &self.operations {
+            writeln!(f, "{}", op)?;
+        }
+        for conflict in &self.conflicts {
+            writeln!(f, "{}", conflict)?;
+        }
+        Ok(())
+    }
+}
+
+/// A translation that was changed differently on both sides of a [`merge3`].
+///
+/// `None` means that the translation is missing or empty, e.g. because
+/// the term was deleted on that side.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct MergeConflict {
+    /// The term key.
+    pub term: TermKey,
+    /// Locale of the translation.
+    pub locale: LocaleCode,
+    /// The translation in the common base.
+    pub base: Option<String>,
+    /// The translation in the local snapshot.
+    pub local: Option<String>,
+    /// The translation in the remote snapshot.
+    pub remote: Option<String>,
+}
+
+impl fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |v: &Option<String>| {
+            v.as_deref()
+                .map_or_else(|| "-".to_owned(), |v| format!("{:?}", v))
+        };
+        write!(
+            f,
+            "! [{}] {}: base {}, local {}, remote {}",
+            self.locale,
+            self.term,
+            value(&self.base),
+            value(&self.local),
+            value(&self.remote)
+        )
+    }
+}
+
+/// The outcome of a [`merge3`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Merge {
+    /// The remote snapshot with all non-conflicting local changes applied.
+    pub merged: TranslationSnapshot,
+    /// Translations that were changed differently on both sides.
+    /// They are left as in the remote snapshot.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl Merge {
+    /// Whether both sides could be merged without conflicts.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges the changes of `local` and `remote` since their common `base`.
+///
+/// Terms and translations are compared one by one: a change on one side is
+/// taken over, identical changes on both sides are merged and different
+/// changes are reported as [`MergeConflict`]s. Deleting a term on one side
+/// while changing its translations on the other side is a conflict, too.
+/// Empty translations are treated as missing.
+///
+/// The merged snapshot can be pushed with a [`SyncPlanner`]. Because
+/// conflicting translations keep their remote value, only the
+/// non-conflicting local changes are written to the project.
+///
+/// # Examples
+/// ```
+/// use traduora::sync::{merge3, TranslationSnapshot};
+///
+/// let mut base = TranslationSnapshot::new();
+/// base.set("greeting", "de".into(), "Hallo");
+/// base.set("farewell", "de".into(), "Tschüss");
+/// let mut local = base.clone();
+/// local.set("greeting", "de".into(), "Guten Tag");
+/// local.set("farewell", "de".into(), "Auf Wiedersehen");
+/// let mut remote = base.clone();
+/// remote.set("farewell", "de".into(), "Bis bald");
+///
+/// let merge = merge3(&base, &local, &remote);
+/// assert_eq!(merge.merged.get("greeting", &"de".into()), Some("Guten Tag"));
+/// assert_eq!(merge.merged.get("farewell", &"de".into()), Some("Bis bald"));
+/// assert_eq!(merge.conflicts.len(), 1);
+/// ```
+#[must_use]
+pub fn merge3(
+    base: &TranslationSnapshot,
+    local: &TranslationSnapshot,
+    remote: &TranslationSnapshot,
+) -> Merge {
+    let mut merge = Merge::default();
+    let terms: BTreeSet<_> = base
+        .terms
+        .keys()
+        .chain(local.terms.keys())
+        .chain(remote.terms.keys())
+        .collect();
+
+    for term in terms {
+        let (b, l, r) = (
+            base.terms.get(term),
+            local.terms.get(term),
+            remote.terms.get(term),
+        );
+        let locales: BTreeSet<_> = [b, l, r].iter().flatten().flat_map(|t| t.keys()).collect();
+        let mut conflict = |locale: &LocaleCode| {
+            merge.conflicts.push(MergeConflict {
+                term: term.clone(),
+                locale: locale.clone(),
+                base: value(b, locale).cloned(),
+                local: value(l, locale).cloned(),
+                remote: value(r, locale).cloned(),
+            });
+        };
+
+        match (l, r) {
+            (None, None) => {}
+            (Some(_), Some(_)) => {
+                let mut translations = BTreeMap::new();
+                for locale in locales {
+                    let (bv, lv, rv) = (value(b, locale), value(l, locale), value(r, locale));
+                    let merged = if lv == rv || rv == bv {
+                        lv
+                    } else if lv == bv {
+                        rv
+                    } else {
+                        conflict(locale);
+                        rv
+                    };
+                    if let Some(merged) = merged {
+                        translations.insert(locale.clone(), merged.clone());
+                    }
+                }
+                merge.merged.terms.insert(term.clone(), translations);
+            }
+            (Some(side), None) | (None, Some(side)) if b.is_none() => {
+                merge.merged.terms.insert(term.clone(), side.clone());
+            }
+            (Some(side), None) | (None, Some(side)) => {
+                // deleted on one side, conflicts if changed on the other
+                let changed: Vec<_> = locales
+                    .into_iter()
+                    .filter(|locale| value(Some(side), locale) != value(b, locale))
+                    .collect();
+                if !changed.is_empty() {
+                    changed.into_iter().for_each(&mut conflict);
+                    if let Some(r) = r {
+                        merge.merged.terms.insert(term.clone(), r.clone());
+                    }
+                }
+            }
+        }
+    }
+    merge
+}
+
+/// The non-empty translation of a term.
+fn value<'a>(
+    translations: Option<&'a BTreeMap<LocaleCode, String>>,
+    locale: &LocaleCode,
+) -> Option<&'a String> {
+    translations?.get(locale).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{
+        merge3, ConflictPolicy, ConflictResolution, MergeConflict, Remote, SyncOperation,
+        SyncPlanner, TranslationSnapshot,
+    };
+
+    type Translations<'a> = &'a [(&'a str, &'a str)];
+
+    fn remote(terms: &[(&str, &str, Translations<'_>)]) -> Remote {
+        let mut remote = Remote::default();
+        for (id, term, translations) in terms {
+            remote.ids.insert((*term).into(), (*id).into());
+            remote.snapshot.add_term(*term);
+            for (locale, value) in *translations {
+                remote.snapshot.set(*term, (*locale).into(), *value);
+                remote.locales.insert((*locale).into());
+            }
+        }
+        remote
+    }
+
+    #[test]
+    fn changes_are_planned() {
+        let mut local = TranslationSnapshot::new();
+        local.set("hello", "de".into(), "Hallo");
+        local.set("new", "fr".into(), "nouveau");
+        let remote = remote(&[("1", "hello", &[("de", "Hi")]), ("2", "old", &[])]);
+
+        let plan = SyncPlanner::new("p".into())
+            .delete_removed(true)
+            .diff(&local, remote);
+
+        assert_eq!(
+            plan.operations,
+            vec![
+                SyncOperation::CreateLocale {
+                    locale: "fr".into()
+                },
+                SyncOperation::CreateTerm { term: "new".into() },
+                SyncOperation::DeleteTerm {
+                    term_id: "2".into(),
+                    term: "old".into()
+                },
+                SyncOperation::SetTranslation {
+                    term: "hello".into(),
+                    locale: "de".into(),
+                    old_value: Some("Hi".into()),
+                    new_value: "Hallo".into()
+                },
+                SyncOperation::SetTranslation {
+                    term: "new".into(),
+                    locale: "fr".into(),
+                    old_value: None,
+                    new_value: "nouveau".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn renames_are_detected() {
+        let mut local = TranslationSnapshot::new();
+        local.set("greeting", "de".into(), "Hallo");
+        let remote = remote(&[("1", "hello", &[("de", "Hallo")])]);
+
+        let plan = SyncPlanner::new("p".into()).diff(&local, remote);
+
+        assert_eq!(
+            plan.operations,
+            vec![SyncOperation::RenameTerm {
+                term_id: "1".into(),
+                from: "hello".into(),
+                to: "greeting".into()
+            }]
+        );
+        assert_eq!(plan.to_string(), "~ term hello -> greeting\n");
+    }
+
+    #[test]
+    fn removed_terms_are_kept_by_default() {
+        let remote = remote(&[("1", "hello", &[])]);
+
+        let plan = SyncPlanner::new("p".into()).diff(&TranslationSnapshot::new(), remote);
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.ids, BTreeMap::from([("hello".into(), "1".into())]));
+    }
+
+    #[test]
+    fn conflicts_are_resolved_by_policy() {
+        let mut local = TranslationSnapshot::new();
+        local.set("a", "de".into(), "Neu");
+        local.set("b", "de".into(), "Neu");
+        local.set("c", "de".into(), "Neu");
+        let mut remote = remote(&[
+            ("1", "a", &[("de", "Alt")]),
+            ("2", "b", &[("de", "Alt")]),
+            ("3", "c", &[]),
+        ]);
+        let pulled = "2022-06-01T00:00:00Z".parse().unwrap();
+        remote.modified.insert(
+            ("a".into(), "de".into()),
+            "2022-05-01T00:00:00Z".parse().unwrap(),
+        );
+        remote.modified.insert(
+            ("b".into(), "de".into()),
+            "2022-07-01T00:00:00Z".parse().unwrap(),
+        );
+        let plan = |policy| {
+            SyncPlanner::new("p".into())
+                .conflict_policy(policy)
+                .diff(&local, remote.clone())
+        };
+
+        let newer = plan(ConflictPolicy::PreferNewer(pulled));
+        let resolutions: Vec<_> = newer.conflicts.iter().map(|c| c.resolution).collect();
+        assert_eq!(
+            resolutions,
+            [ConflictResolution::Local, ConflictResolution::Remote]
+        );
+        assert_eq!(newer.operations.len(), 2);
+        assert_eq!(plan(ConflictPolicy::PreferLocal).operations.len(), 3);
+        assert_eq!(plan(ConflictPolicy::PreferRemote).operations.len(), 1);
+
+        let failed = plan(ConflictPolicy::Fail);
+        assert_eq!(failed.unresolved_conflicts().count(), 2);
+        assert_eq!(failed.operations.len(), 1);
+        assert!(failed
+            .to_string()
+            .ends_with("! [de] b: local \"Neu\", remote \"Alt\" (unresolved)\n"));
+    }
+
+    #[test]
+    fn merge_takes_one_sided_changes_and_reports_conflicts() {
+        let mut base = TranslationSnapshot::new();
+        base.set("same", "de".into(), "Alt");
+        base.set("deleted", "de".into(), "Weg");
+        base.set("edited", "de".into(), "Alt");
+        base.set("both", "de".into(), "Alt");
+        let mut local = base.clone();
+        local.terms.remove("deleted");
+        local.terms.remove("edited");
+        local.set("same", "de".into(), "Neu");
+        local.set("both", "de".into(), "Lokal");
+        local.set("added", "fr".into(), "nouveau");
+        let mut remote = base.clone();
+        remote.set("same", "de".into(), "Neu");
+        remote.set("edited", "de".into(), "Geändert");
+        remote.set("both", "de".into(), "");
+        remote.set("both", "en".into(), "Remote");
+
+        let merge = merge3(&base, &local, &remote);
+
+        let mut expected = TranslationSnapshot::new();
+        expected.set("same", "de".into(), "Neu");
+        expected.set("edited", "de".into(), "Geändert");
+        expected.set("both", "en".into(), "Remote");
+        expected.set("added", "fr".into(), "nouveau");
+        assert_eq!(merge.merged, expected);
+        assert_eq!(
+            merge.conflicts,
+            [
+                MergeConflict {
+                    term: "both".into(),
+                    locale: "de".into(),
+                    base: Some("Alt".into()),
+                    local: Some("Lokal".into()),
+                    remote: None,
+                },
+                MergeConflict {
+                    term: "edited".into(),
+                    locale: "de".into(),
+                    base: Some("Alt".into()),
+                    local: None,
+                    remote: Some("Geändert".into()),
+                },
+            ]
+        );
+        assert!(merge3(&base, &base, &remote).is_clean());
+    }
+}
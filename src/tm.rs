@@ -0,0 +1,309 @@
+//! Reusing existing translations across projects with a translation memory.
+//!
+//! A [`TranslationMemory`] maps texts of a source locale to the translations
+//! they received in other locales. It is built from the list endpoints of any
+//! number of projects and suggests translations for untranslated terms whose
+//! source text is similar to a known one. Similarity is the Levenshtein
+//! distance normalized to a value between `0.0` and `1.0`, see [`similarity`].
+//!
+//! The memory implements [`Serialize`] and [`Deserialize`], so it can be
+//! exported, e.g. as JSON, and loaded again without fetching the projects.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{sync::TranslationSnapshot, tm::TranslationMemory};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let client = Traduora::with_auth("localhost:8080", login)?;
+//! let mut memory = TranslationMemory::new("en".into());
+//! memory.add_project(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+//!
+//! let mut project = TranslationSnapshot::new();
+//! project.set("greeting", "en".into(), "My first translations");
+//! for (term, suggestions) in memory.suggest_untranslated(&project, &"de_DE".into(), 0.8) {
+//!     println!("{}: {:?}", term, suggestions[0].target);
+//! }
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::{locales::LocaleCode, ProjectId, TermKey},
+    auth::Authenticated,
+    sync::TranslationSnapshot,
+    ApiError, AsyncClient, Client,
+};
+
+/// Similarity of two texts between `0.0` (nothing in common) and `1.0` (equal).
+///
+/// It is the Levenshtein distance of the characters divided by the length
+/// of the longer text, subtracted from 1. Two empty texts are equal.
+///
+/// # Examples
+/// ```
+/// use traduora::tm::similarity;
+///
+/// assert_eq!(similarity("Save", "Save"), 1.0);
+/// assert_eq!(similarity("Save", "Saved"), 0.8);
+/// assert_eq!(similarity("Save", "Load"), 0.0);
+/// ```
+#[must_use]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<_> = a.chars().collect();
+    let b: Vec<_> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut row: Vec<_> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// A known translation of a text similar to the requested one.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Suggestion {
+    /// The known source text.
+    pub source: String,
+    /// The translation of the known source text.
+    pub target: String,
+    /// Similarity of the known and the requested source text, see [`similarity`].
+    pub similarity: f64,
+    /// How often the source text was translated like this.
+    pub occurrences: usize,
+}
+
+/// Known translations of texts of a source locale.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationMemory {
+    source: LocaleCode,
+    /// Occurrences of the translations by source text, locale and translation.
+    entries: BTreeMap<String, BTreeMap<LocaleCode, BTreeMap<String, usize>>>,
+}
+
+impl TranslationMemory {
+    /// Creates an empty memory for texts of the source locale.
+    #[must_use]
+    pub fn new(source: LocaleCode) -> Self {
+        Self {
+            source,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// The locale of the source texts.
+    #[must_use]
+    pub const fn source(&self) -> &LocaleCode {
+        &self.source
+    }
+
+    /// Number of distinct source texts.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the memory knows no translations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Records that `text` was translated into `locale` as `translation`.
+    ///
+    /// Empty texts and translations as well as translations into the
+    /// source locale are ignored.
+    pub fn insert(
+        &mut self,
+        text: impl Into<String>,
+        locale: LocaleCode,
+        translation: impl Into<String>,
+    ) {
+        let (text, translation) = (text.into(), translation.into());
+        if text.is_empty() || translation.is_empty() || locale == self.source {
+            return;
+        }
+        *self
+            .entries
+            .entry(text)
+            .or_default()
+            .entry(locale)
+            .or_default()
+            .entry(translation)
+            .or_default() += 1;
+    }
+
+    /// Records all translations of terms that are translated into the source locale.
+    pub fn add_snapshot(&mut self, snapshot: &TranslationSnapshot) {
+        for translations in snapshot.terms.values() {
+            let text = match translations.get(&self.source) {
+                Some(text) => text,
+                None => continue,
+            };
+            for (locale, translation) in translations {
+                self.insert(text.clone(), locale.clone(), translation.clone());
+            }
+        }
+    }
+
+    /// Downloads all terms and translations of the project and records them.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub fn add_project<C>(
+        &mut self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        let snapshot = TranslationSnapshot::fetch(client, project)?;
+        self.add_snapshot(&snapshot);
+        Ok(())
+    }
+
+    /// Downloads all terms and translations of the project asynchronously and records them.
+    ///
+    /// # Errors
+    /// This method returns an error if fetching the terms or translations fails.
+    pub async fn add_project_async<C>(
+        &mut self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<(), ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        let snapshot = TranslationSnapshot::fetch_async(client, project).await?;
+        self.add_snapshot(&snapshot);
+        Ok(())
+    }
+
+    /// Known translations into `locale` of texts with at least `min_similarity` to `text`.
+    ///
+    /// The suggestions are sorted by similarity, then by how often the
+    /// translation was used, best first.
+    #[must_use]
+    pub fn suggest(&self, text: &str, locale: &LocaleCode, min_similarity: f64) -> Vec<Suggestion> {
+        let length = text.chars().count();
+        let mut suggestions = Vec::new();
+        for (source, locales) in &self.entries {
+            let targets = match locales.get(locale) {
+                Some(targets) => targets,
+                None => continue,
+            };
+            // the distance is at least the difference in length
+            let other = source.chars().count();
+            let longest = length.max(other).max(1) as f64;
+            if 1.0 - (length.max(other) - length.min(other)) as f64 / longest < min_similarity {
+                continue;
+            }
+            let score = similarity(text, source);
+            if score < min_similarity {
+                continue;
+            }
+            suggestions.extend(targets.iter().map(|(target, occurrences)| Suggestion {
+                source: source.clone(),
+                target: target.clone(),
+                similarity: score,
+                occurrences: *occurrences,
+            }));
+        }
+        suggestions.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(Ordering::Equal)
+                .then(b.occurrences.cmp(&a.occurrences))
+                .then_with(|| a.target.cmp(&b.target))
+        });
+        suggestions
+    }
+
+    /// Suggestions for all terms of the project that are translated into the
+    /// source locale but not into `locale`.
+    ///
+    /// Terms without suggestions are left out.
+    #[must_use]
+    pub fn suggest_untranslated(
+        &self,
+        project: &TranslationSnapshot,
+        locale: &LocaleCode,
+        min_similarity: f64,
+    ) -> BTreeMap<TermKey, Vec<Suggestion>> {
+        project
+            .terms
+            .iter()
+            .filter(|(_, t)| t.get(locale).map_or(true, String::is_empty))
+            .filter_map(|(term, translations)| {
+                let text = translations.get(&self.source)?;
+                let suggestions = self.suggest(text, locale, min_similarity);
+                (!suggestions.is_empty()).then(|| (term.clone(), suggestions))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{similarity, TranslationMemory};
+    use crate::sync::TranslationSnapshot;
+
+    #[test]
+    fn similarity_is_normalized() {
+        assert_eq!(similarity("", ""), 1.0);
+        assert_eq!(similarity("abc", ""), 0.0);
+        assert_eq!(similarity("kitten", "sitting"), 1.0 - 3.0 / 7.0);
+        assert_eq!(similarity("Grüße", "Grüsse"), 1.0 - 2.0 / 6.0);
+    }
+
+    #[test]
+    fn untranslated_terms_get_best_suggestions_first() {
+        let mut memory = TranslationMemory::new("en".into());
+        let mut other = TranslationSnapshot::new();
+        other.set("a", "en".into(), "Save file");
+        other.set("a", "de".into(), "Datei speichern");
+        other.set("b", "en".into(), "Save files");
+        other.set("b", "de".into(), "Dateien speichern");
+        other.set("c", "en".into(), "Save files");
+        other.set("c", "de".into(), "Dateien sichern");
+        other.set("d", "en".into(), "Save files");
+        other.set("d", "de".into(), "Dateien sichern");
+        memory.add_snapshot(&other);
+        assert_eq!(memory.len(), 2);
+
+        let mut project = TranslationSnapshot::new();
+        project.set("save", "en".into(), "Save files");
+        project.set("done", "en".into(), "Save file");
+        project.set("done", "de".into(), "Speichern");
+        let suggestions = memory.suggest_untranslated(&project, &"de".into(), 0.8);
+
+        assert_eq!(suggestions.len(), 1);
+        let targets: Vec<_> = suggestions["save"].iter().map(|s| &*s.target).collect();
+        assert_eq!(
+            targets,
+            ["Dateien sichern", "Dateien speichern", "Datei speichern"]
+        );
+        assert_eq!(suggestions["save"][0].occurrences, 2);
+        assert!(memory.suggest("Open", &"de".into(), 0.5).is_empty());
+    }
+}
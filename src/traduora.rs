@@ -1,15 +1,25 @@
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::{self, Debug};
+use std::io::Read;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use bytes::Bytes;
+use http::header::{self, HeaderMap, HeaderName, HeaderValue};
 use http::Response as HttpResponse;
-use log::{debug, error};
 use thiserror::Error;
 use url::Url;
 
-use crate::api;
-use crate::auth::{AuthError, Authenticated, Scope, Unauthenticated};
+use crate::api::{ProjectId, Role};
+use crate::auth::{
+    AuthError, Authenticated, Authenticator, BearerAuth, Scope, StoredToken, Unauthenticated,
+};
+use crate::credentials::{CredentialError, CredentialProvider};
+use crate::logging::{debug, warn};
+use crate::metrics::{MetricsSink, RequestMetrics};
+use crate::ops::{self, Permissions, ServerInfo};
 use crate::{ApiError, AsyncClient, AsyncQuery, Client, Login, Query, RestClient};
 
 /// The error type which is returned by constructor for a Traduora client.
@@ -55,6 +65,30 @@ pub enum TraduoraError {
         /// The type that failed to deserialize.
         typename: &'static str,
     },
+    /// A header set on the builder has an invalid name or value.
+    #[error("invalid header {}", name)]
+    InvalidHeader {
+        /// Name of the header.
+        name: String,
+    },
+    /// The URL of the Traduora instance is not usable.
+    #[error("invalid url {}: {}", url, reason)]
+    InvalidUrl {
+        /// The URL passed to the builder.
+        url: String,
+        /// Why the URL is rejected.
+        reason: &'static str,
+    },
+    /// An option of the builder needs TLS, but the crate was compiled
+    /// without the `native-tls` and the `rustls-tls` feature.
+    #[error(
+        "{} requires TLS, enable the `native-tls` or `rustls-tls` feature",
+        option
+    )]
+    TlsUnavailable {
+        /// The option that needs TLS, e.g. `https`.
+        option: &'static str,
+    },
     /// Error accessing the API.
     #[error("api error: {}", source)]
     Api {
@@ -66,24 +100,57 @@ pub enum TraduoraError {
 
 type TraduoraResult<T> = Result<T, TraduoraError>;
 
-/// A representation of the Traduora API for a single user.
+/// The parts of a client that do not depend on its access token.
 ///
-/// Separate users should use separate instances of this.
-#[derive(Clone)]
-pub struct Traduora<A: Scope> {
+/// Clients share them with their clones and with the clients returned by
+/// e.g. [`Traduora::clone_with_token`].
+struct Shared<T> {
     /// The client to use for API calls.
-    client: reqwest::blocking::Client,
+    client: T,
     /// The base URL to use for API calls.
     rest_url: Url,
+    /// Attaches the authentication information to requests.
+    authenticator: Arc<dyn Authenticator>,
+    /// Observes the outcome of requests.
+    instrumentation: Instrumentation,
+    /// Role of the client within the projects it accesses, if known.
+    roles: HashMap<ProjectId, Role>,
+    /// Responses larger than this many bytes are rejected.
+    max_response_size: Option<usize>,
+}
+
+/// A representation of the Traduora API for a single user.
+///
+/// Separate users should use separate instances of this, which are
+/// created cheaply with [`Traduora::clone_with_token`].
+///
+/// Cloning is cheap as well: the connection pool, the settings and the
+/// access token are reference-counted and shared by all clones.
+pub struct Traduora<A: Scope> {
+    /// Connection pool and settings, shared by all clones.
+    shared: Arc<Shared<Transport>>,
     /// The authentication information to use when communicating with Traduora.
-    token: A,
+    token: Arc<A>,
+}
+
+impl<A: Scope> Clone for Traduora<A> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            token: Arc::clone(&self.token),
+        }
+    }
 }
 
 impl<A: Scope + Debug> Debug for Traduora<A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Traduora")
-            .field("rest_url", &self.rest_url)
+            .field("rest_url", &self.shared.rest_url)
             .field("token", &format!("{:?}", self.token))
+            .field("authenticator", &self.shared.authenticator)
+            .field("instrumentation", &self.shared.instrumentation)
+            .field("roles", &self.shared.roles)
+            .field("max_response_size", &self.shared.max_response_size)
             .finish()
     }
 }
@@ -99,7 +166,7 @@ impl Traduora<Unauthenticated> {
     /// - the provided credentials are invalid.
     /// - the host url fails to parse.
     /// - the underlying [`reqwest::blocking::Client`] cannot be initialized.
-
+    ///
     /// # Examples
     /// ```
     /// # use traduora::TraduoraError;
@@ -170,9 +237,8 @@ impl Traduora<Unauthenticated> {
         let token = login.query(&self)?;
 
         Ok(Traduora {
-            client: self.client,
-            rest_url: self.rest_url,
-            token: token.into(),
+            shared: self.shared,
+            token: Arc::new(token.into()),
         })
     }
 }
@@ -240,6 +306,293 @@ impl Traduora<Authenticated> {
             .authenticate(login)
             .build()
     }
+
+    /// The access token of the client, e.g. to store it for the next run.
+    ///
+    /// See [`StoredToken`].
+    #[must_use]
+    pub fn access_token(&self) -> &StoredToken {
+        self.token.token()
+    }
+
+    /// Requests a new access token, e.g. because the current one expired.
+    ///
+    /// Returns a new client with the new token that shares the connection pool
+    /// and all settings with this one. This client keeps the old token.
+    ///
+    /// Calling this method queries the Traduora API.
+    ///
+    /// # Errors
+    /// This method returns an error if the provided credentials are invalid.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use traduora::TraduoraError;
+    /// use traduora::{Login, Traduora};
+    /// # fn main() -> Result<(), TraduoraError> {
+    /// let login = Login::password("user@traduora.example", "password");
+    /// let mut client = Traduora::with_auth("localhost:8080", login.clone())?;
+    /// if client.access_token().is_expired() {
+    ///     client = client.refresh(&login)?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn refresh(&self, login: &Login) -> TraduoraResult<Self> {
+        self.with_scope(Unauthenticated).authenticate(login)
+    }
+
+    /// Returns a new client that uses `token` instead of the current access token.
+    ///
+    /// The new client shares the connection pool and all settings with this one.
+    /// Calling this method does not query the Traduora API.
+    #[must_use]
+    pub fn with_token<T>(&self, token: T) -> Self
+    where
+        T: Into<StoredToken>,
+    {
+        self.clone_with_token(token)
+    }
+
+    /// Drops the access token, e.g. to log in as another user.
+    ///
+    /// The connection pool and all settings are kept.
+    #[must_use]
+    pub fn into_unauthenticated(self) -> Traduora<Unauthenticated> {
+        Traduora {
+            shared: self.shared,
+            token: Arc::new(Unauthenticated),
+        }
+    }
+
+    /// Determines what the user may do on the project.
+    ///
+    /// See [`ops::probe_permissions`].
+    ///
+    /// # Errors
+    /// This method returns an error if the project could not be queried,
+    /// e.g. because the user is no member of the project.
+    pub fn probe_permissions(
+        &self,
+        project: &ProjectId,
+    ) -> Result<Permissions, ApiError<RestError>> {
+        ops::probe_permissions(self, project)
+    }
+}
+
+impl<A: Scope> Traduora<A> {
+    /// Creates a blocking client that sends its requests with an asynchronous client.
+    ///
+    /// The blocking client shares the connection pool and all settings with
+    /// `client`. Its requests are driven to completion on the given runtime,
+    /// so applications that already run Tokio need only one connection pool.
+    ///
+    /// The blocking client must not be used from within an asynchronous task
+    /// of the runtime because it blocks the thread until the response is received.
+    /// Use it from other threads or within [`tokio::task::spawn_blocking`].
+    ///
+    /// Calling this method does not query the Traduora API.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use traduora::TraduoraError;
+    /// use traduora::{api::users::Me, AsyncTraduora, Login, Query, Traduora};
+    ///
+    /// # async fn main_async() -> Result<(), TraduoraError> {
+    /// let login = Login::password("user@traduora.example", "password");
+    /// let client = AsyncTraduora::with_auth("localhost:8080", login).await?;
+    /// let blocking = Traduora::from_async(client, tokio::runtime::Handle::current());
+    ///
+    /// let me = tokio::task::spawn_blocking(move || Me.query(&blocking)).await.unwrap()?;
+    /// println!("logged in as {}", me.name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn from_async(client: AsyncTraduora<A>, runtime: tokio::runtime::Handle) -> Self {
+        let shared = &client.shared;
+        Self {
+            shared: Arc::new(Shared {
+                client: Transport::Async {
+                    client: shared.client.clone(),
+                    runtime,
+                },
+                rest_url: shared.rest_url.clone(),
+                authenticator: Arc::clone(&shared.authenticator),
+                instrumentation: shared.instrumentation.clone(),
+                roles: shared.roles.clone(),
+                max_response_size: shared.max_response_size,
+            }),
+            token: client.token,
+        }
+    }
+
+    /// Returns a client that is logged in with `token`, e.g. for another user of a server.
+    ///
+    /// The new client shares the connection pool and all settings with this one,
+    /// so creating a client per user is cheap. This client keeps its scope.
+    /// Calling this method does not query the Traduora API.
+    ///
+    /// # Examples
+    /// ```
+    /// use traduora::{api::AccessToken, Traduora};
+    ///
+    /// # fn main() -> Result<(), traduora::TraduoraError> {
+    /// let pool = Traduora::new("localhost:8080")?;
+    /// let alice = pool.clone_with_token(AccessToken::new("eyJhbGc...token-of-alice"));
+    /// let bob = pool.clone_with_token(AccessToken::new("eyJhbGc...token-of-bob"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn clone_with_token<T>(&self, token: T) -> Traduora<Authenticated>
+    where
+        T: Into<StoredToken>,
+    {
+        self.with_scope(token.into().into())
+    }
+
+    /// A client with the same connection pool and settings but another scope.
+    fn with_scope<B: Scope>(&self, token: B) -> Traduora<B> {
+        Traduora {
+            shared: Arc::clone(&self.shared),
+            token: Arc::new(token),
+        }
+    }
+
+    /// Checks that the server is reachable and returns the round-trip time.
+    ///
+    /// See [`ops::ping`].
+    ///
+    /// # Errors
+    /// This method returns an error if the server cannot be reached
+    /// or does not answer with a valid response.
+    pub fn ping(&self) -> Result<Duration, ApiError<RestError>>
+    where
+        Unauthenticated: From<A>,
+    {
+        ops::ping(self)
+    }
+
+    /// Queries what the server tells about itself.
+    ///
+    /// See [`ops::server_info`].
+    ///
+    /// # Errors
+    /// This method returns an error if the server cannot be reached
+    /// or does not answer with a valid response.
+    pub fn server_info(&self) -> Result<ServerInfo, ApiError<RestError>>
+    where
+        Unauthenticated: From<A>,
+    {
+        ops::server_info(self)
+    }
+}
+
+/// How a blocking [`Traduora`] client sends its requests.
+#[derive(Clone, Debug)]
+enum Transport {
+    /// With its own blocking client.
+    Blocking(reqwest::blocking::Client),
+    /// With an asynchronous client whose requests are blocked on.
+    Async {
+        client: reqwest::Client,
+        runtime: tokio::runtime::Handle,
+    },
+}
+
+impl Transport {
+    fn execute(
+        &self,
+        request: http::Request<Bytes>,
+        limit: Option<usize>,
+    ) -> Result<HttpResponse<Bytes>, ApiError<RestError>> {
+        match self {
+            Self::Blocking(client) => {
+                let request = request.try_into().map_err(client_error)?;
+                let mut rsp = client.execute(request).map_err(client_error)?;
+                let http_rsp = response_builder(rsp.status(), rsp.version(), rsp.headers());
+                check_content_length(rsp.content_length(), limit)?;
+                let body = match limit {
+                    Some(limit) => {
+                        let mut body = Vec::new();
+                        (&mut rsp)
+                            .take(limit as u64 + 1)
+                            .read_to_end(&mut body)
+                            .map_err(client_error)?;
+                        check_size(body.len(), limit)?;
+                        body.into()
+                    }
+                    None => rsp.bytes().map_err(client_error)?,
+                };
+                http_rsp.body(body).map_err(client_error)
+            }
+            Self::Async { client, runtime } => runtime.block_on(async {
+                let request = request.try_into().map_err(client_error)?;
+                let rsp = client.execute(request).await.map_err(client_error)?;
+                read_response(rsp, limit).await
+            }),
+        }
+    }
+}
+
+/// Converts the response, reading at most `limit` bytes of the body.
+async fn read_response(
+    mut rsp: reqwest::Response,
+    limit: Option<usize>,
+) -> Result<HttpResponse<Bytes>, ApiError<RestError>> {
+    let http_rsp = response_builder(rsp.status(), rsp.version(), rsp.headers());
+    check_content_length(rsp.content_length(), limit)?;
+    let body = match limit {
+        Some(limit) => {
+            let mut body = Vec::new();
+            while let Some(chunk) = rsp.chunk().await.map_err(client_error)? {
+                check_size(body.len() + chunk.len(), limit)?;
+                body.extend_from_slice(&chunk);
+            }
+            body.into()
+        }
+        None => rsp.bytes().await.map_err(client_error)?,
+    };
+    http_rsp.body(body).map_err(client_error)
+}
+
+/// Rejects a response early if the server announces that it is too large.
+fn check_content_length(
+    length: Option<u64>,
+    limit: Option<usize>,
+) -> Result<(), ApiError<RestError>> {
+    match (length, limit) {
+        (Some(length), Some(limit)) if length > limit as u64 => {
+            Err(ApiError::ResponseTooLarge { limit })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn check_size(size: usize, limit: usize) -> Result<(), ApiError<RestError>> {
+    if size > limit {
+        return Err(ApiError::ResponseTooLarge { limit });
+    }
+    Ok(())
+}
+
+fn client_error<E: Into<RestError>>(error: E) -> ApiError<RestError> {
+    ApiError::client(error.into())
+}
+
+/// A response with the status, version and headers of a `reqwest` response.
+fn response_builder(
+    status: http::StatusCode,
+    version: http::Version,
+    headers: &HeaderMap,
+) -> http::response::Builder {
+    let mut http_rsp = HttpResponse::builder().status(status).version(version);
+    let rsp_headers = http_rsp.headers_mut().unwrap();
+    for (key, value) in headers {
+        rsp_headers.insert(key, value.clone());
+    }
+    http_rsp
 }
 
 #[derive(Debug, Error)]
@@ -260,6 +613,11 @@ pub enum RestError {
         #[from]
         source: http::Error,
     },
+    #[error("failed to read the response: {}", source)]
+    Io {
+        #[from]
+        source: std::io::Error,
+    },
 }
 
 impl<A: Scope> RestClient for Traduora<A> {
@@ -268,92 +626,271 @@ impl<A: Scope> RestClient for Traduora<A> {
 
     fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
         debug!(target: "traduora", "REST api call {}", endpoint);
-        Ok(self.rest_url.join(endpoint)?)
+        Ok(self.shared.rest_url.join(endpoint)?)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.shared.roles.get(project).copied()
     }
 }
 
 impl<A: Scope> Client for Traduora<A> {
     fn rest(
         &self,
-        mut request: http::request::Builder,
-        body: Vec<u8>,
+        request: http::request::Builder,
+        body: Bytes,
     ) -> Result<HttpResponse<Bytes>, ApiError<Self::Error>> {
-        let call = || -> Result<_, RestError> {
-            self.token.set_header(request.headers_mut().unwrap())?;
-            let http_request = request.body(body)?;
-            let request = http_request.try_into()?;
-            let rsp = self.client.execute(request)?;
-
-            let mut http_rsp = HttpResponse::builder()
-                .status(rsp.status())
-                .version(rsp.version());
-            let headers = http_rsp.headers_mut().unwrap();
-            for (key, value) in rsp.headers() {
-                headers.insert(key, value.clone());
-            }
-            Ok(http_rsp.body(rsp.bytes()?)?)
+        let observer = self.shared.instrumentation.observe(&request, &body);
+        #[cfg(feature = "tracing")]
+        let span = observer.span.clone();
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        let call = || {
+            let mut http_request = request.body(body).map_err(client_error)?;
+            self.shared
+                .authenticator
+                .authenticate(&*self.token, &mut http_request)
+                .map_err(client_error)?;
+            self.shared
+                .client
+                .execute(http_request, self.shared.max_response_size)
         };
-        call().map_err(ApiError::client)
+        let result = call();
+        observer.finish(&result);
+        result
+    }
+}
+
+/// Hooks that observe the outcome of every request.
+#[derive(Clone, Debug, Default)]
+struct Instrumentation {
+    /// Requests taking longer than this are logged as warning.
+    slow_call_threshold: Option<Duration>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+}
+
+impl Instrumentation {
+    const fn new() -> Self {
+        Self {
+            slow_call_threshold: None,
+            metrics: None,
+        }
+    }
+
+    fn observe(&self, request: &http::request::Builder, body: &[u8]) -> RequestObserver<'_> {
+        let method = request.method_ref().cloned().unwrap_or_default();
+        let uri = request.uri_ref().cloned().unwrap_or_default();
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            target: "traduora",
+            "request",
+            method = %method,
+            endpoint = uri.path(),
+            status = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        RequestObserver {
+            instrumentation: self,
+            method,
+            uri,
+            request_bytes: body.len(),
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            span,
+        }
+    }
+}
+
+/// Observes a single request from preparing it until the full response is received.
+struct RequestObserver<'i> {
+    instrumentation: &'i Instrumentation,
+    method: http::Method,
+    uri: http::Uri,
+    request_bytes: usize,
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl RequestObserver<'_> {
+    fn finish(self, result: &Result<HttpResponse<Bytes>, ApiError<RestError>>) {
+        let latency = self.start.elapsed();
+        if self
+            .instrumentation
+            .slow_call_threshold
+            .map_or(false, |t| latency > t)
+        {
+            warn!(target: "traduora", "slow call: {} {} took {:?}", self.method, self.uri, latency);
+        }
+
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record(
+                "duration_ms",
+                u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+            );
+            match result {
+                Ok(rsp) => self.span.record("status", rsp.status().as_u16()),
+                Err(e) => self.span.record("error", tracing::field::display(e)),
+            };
+        }
+
+        if let Some(metrics) = &self.instrumentation.metrics {
+            metrics.record(&RequestMetrics {
+                path: self.uri.path().to_owned(),
+                method: self.method,
+                status: result.as_ref().ok().map(HttpResponse::status),
+                latency,
+                request_bytes: self.request_bytes,
+                response_bytes: result.as_ref().map_or(0, |rsp| rsp.body().len()),
+            });
+        }
     }
 }
 
 /// A representation of the asynchronous Traduora API for a single user.
 ///
-/// Separate users should use separate instances of this.
-#[derive(Clone)]
+/// Separate users should use separate instances of this, which are
+/// created cheaply with [`AsyncTraduora::clone_with_token`].
+///
+/// Cloning is cheap as well: the connection pool, the settings and the
+/// access token are reference-counted and shared by all clones.
 pub struct AsyncTraduora<A: Scope> {
-    /// The client to use for API calls.
-    client: reqwest::Client,
-    /// The base URL to use for API calls.
-    rest_url: Url,
+    /// Connection pool and settings, shared by all clones.
+    shared: Arc<Shared<reqwest::Client>>,
     /// The authentication information to use when communicating with Traduora.
-    token: A,
+    token: Arc<A>,
+}
+
+impl<A: Scope> Clone for AsyncTraduora<A> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+            token: Arc::clone(&self.token),
+        }
+    }
 }
 
 impl<A: Scope + Debug> Debug for AsyncTraduora<A> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("AsyncTraduora")
-            .field("rest_url", &self.rest_url)
+            .field("rest_url", &self.shared.rest_url)
             .field("token", &format!("{:?}", self.token))
+            .field("authenticator", &self.shared.authenticator)
+            .field("instrumentation", &self.shared.instrumentation)
+            .field("roles", &self.shared.roles)
+            .field("max_response_size", &self.shared.max_response_size)
             .finish()
     }
 }
 
-#[async_trait]
 impl<A: Scope> RestClient for AsyncTraduora<A> {
     type Error = RestError;
+    type AccessLevel = A;
 
     fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
         debug!(target: "traduora", "REST api call {}", endpoint);
-        Ok(self.rest_url.join(endpoint)?)
+        Ok(self.shared.rest_url.join(endpoint)?)
     }
 
-    type AccessLevel = Authenticated;
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.shared.roles.get(project).copied()
+    }
 }
 
 #[async_trait]
 impl<A: Scope + Send + Sync> AsyncClient for AsyncTraduora<A> {
     async fn rest_async(
         &self,
-        mut request: http::request::Builder,
-        body: Vec<u8>,
+        request: http::request::Builder,
+        body: Bytes,
     ) -> Result<HttpResponse<Bytes>, ApiError<Self::Error>> {
+        let observer = self.shared.instrumentation.observe(&request, &body);
         let call = || async {
-            self.token.set_header(request.headers_mut().unwrap())?;
-            let http_request = request.body(body)?;
-            let request = http_request.try_into()?;
-            let rsp = self.client.execute(request).await?;
-
-            let mut http_rsp = HttpResponse::builder()
-                .status(rsp.status())
-                .version(rsp.version());
-            let headers = http_rsp.headers_mut().unwrap();
-            for (key, value) in rsp.headers() {
-                headers.insert(key, value.clone());
-            }
-            Ok(http_rsp.body(rsp.bytes().await?)?)
+            let mut http_request = request.body(body).map_err(client_error)?;
+            self.shared
+                .authenticator
+                .authenticate(&*self.token, &mut http_request)
+                .map_err(client_error)?;
+            let request = http_request.try_into().map_err(client_error)?;
+            let rsp = self
+                .shared
+                .client
+                .execute(request)
+                .await
+                .map_err(client_error)?;
+            read_response(rsp, self.shared.max_response_size).await
         };
-        call().await.map_err(ApiError::client)
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(call(), observer.span.clone()).await;
+        #[cfg(not(feature = "tracing"))]
+        let result = call().await;
+        observer.finish(&result);
+        result
+    }
+}
+
+impl<A: Scope + Send + Sync> AsyncTraduora<A> {
+    /// Returns a client that is logged in with `token`, e.g. for another user of a server.
+    ///
+    /// The new client shares the connection pool and all settings with this one,
+    /// so creating a client per user is cheap. This client keeps its scope.
+    /// Calling this method does not query the Traduora API.
+    ///
+    /// # Examples
+    /// ```
+    /// use traduora::{api::AccessToken, AsyncTraduora};
+    ///
+    /// # fn main() -> Result<(), traduora::TraduoraError> {
+    /// let pool = AsyncTraduora::new("localhost:8080")?;
+    /// let alice = pool.clone_with_token(AccessToken::new("eyJhbGc...token-of-alice"));
+    /// let bob = pool.clone_with_token(AccessToken::new("eyJhbGc...token-of-bob"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn clone_with_token<T>(&self, token: T) -> AsyncTraduora<Authenticated>
+    where
+        T: Into<StoredToken>,
+    {
+        self.with_scope(token.into().into())
+    }
+
+    /// A client with the same connection pool and settings but another scope.
+    fn with_scope<B: Scope>(&self, token: B) -> AsyncTraduora<B> {
+        AsyncTraduora {
+            shared: Arc::clone(&self.shared),
+            token: Arc::new(token),
+        }
+    }
+
+    /// Checks asynchronously that the server is reachable and returns the round-trip time.
+    ///
+    /// See [`ops::ping`].
+    ///
+    /// # Errors
+    /// This method returns an error if the server cannot be reached
+    /// or does not answer with a valid response.
+    pub async fn ping(&self) -> Result<Duration, ApiError<RestError>>
+    where
+        Unauthenticated: From<A>,
+    {
+        ops::ping_async(self).await
+    }
+
+    /// Queries asynchronously what the server tells about itself.
+    ///
+    /// See [`ops::server_info`].
+    ///
+    /// # Errors
+    /// This method returns an error if the server cannot be reached
+    /// or does not answer with a valid response.
+    pub async fn server_info(&self) -> Result<ServerInfo, ApiError<RestError>>
+    where
+        Unauthenticated: From<A>,
+    {
+        ops::server_info_async(self).await
     }
 }
 
@@ -438,9 +975,8 @@ impl AsyncTraduora<Unauthenticated> {
         let token = login.query_async(&self).await?;
 
         Ok(AsyncTraduora {
-            client: self.client,
-            rest_url: self.rest_url,
-            token: token.into(),
+            shared: self.shared,
+            token: Arc::new(token.into()),
         })
     }
 }
@@ -512,6 +1048,78 @@ impl AsyncTraduora<Authenticated> {
             .build_async()
             .await
     }
+
+    /// The access token of the client, e.g. to store it for the next run.
+    ///
+    /// See [`StoredToken`].
+    #[must_use]
+    pub fn access_token(&self) -> &StoredToken {
+        self.token.token()
+    }
+
+    /// Requests a new access token, e.g. because the current one expired.
+    ///
+    /// Returns a new client with the new token that shares the connection pool
+    /// and all settings with this one. This client keeps the old token.
+    ///
+    /// Calling this method queries the Traduora API.
+    ///
+    /// # Errors
+    /// This method returns an error if the provided credentials are invalid.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # use traduora::TraduoraError;
+    /// use traduora::{Login, AsyncTraduora};
+    /// # async fn main_async() -> Result<(), TraduoraError> {
+    /// let login = Login::password("user@traduora.example", "password");
+    /// let mut client = AsyncTraduora::with_auth("localhost:8080", login.clone()).await?;
+    /// if client.access_token().is_expired() {
+    ///     client = client.refresh(&login).await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn refresh(&self, login: &Login) -> TraduoraResult<Self> {
+        self.with_scope(Unauthenticated).authenticate(login).await
+    }
+
+    /// Returns a new client that uses `token` instead of the current access token.
+    ///
+    /// The new client shares the connection pool and all settings with this one.
+    /// Calling this method does not query the Traduora API.
+    #[must_use]
+    pub fn with_token<T>(&self, token: T) -> Self
+    where
+        T: Into<StoredToken>,
+    {
+        self.clone_with_token(token)
+    }
+
+    /// Drops the access token, e.g. to log in as another user.
+    ///
+    /// The connection pool and all settings are kept.
+    #[must_use]
+    pub fn into_unauthenticated(self) -> AsyncTraduora<Unauthenticated> {
+        AsyncTraduora {
+            shared: self.shared,
+            token: Arc::new(Unauthenticated),
+        }
+    }
+
+    /// Determines asynchronously what the user may do on the project.
+    ///
+    /// See [`ops::probe_permissions`].
+    ///
+    /// # Errors
+    /// This method returns an error if the project could not be queried,
+    /// e.g. because the user is no member of the project.
+    pub async fn probe_permissions(
+        &self,
+        project: &ProjectId,
+    ) -> Result<Permissions, ApiError<RestError>> {
+        ops::probe_permissions_async(self, project).await
+    }
 }
 
 /// Creates a new instance of [`Traduora`] or [`AsyncTraduora`] with custom parameters.
@@ -536,27 +1144,133 @@ impl AsyncTraduora<Authenticated> {
 /// # }
 /// ```
 ///
+/// Instances behind a reverse proxy are reached with the full URL:
+/// ```
+/// use traduora::TraduoraBuilder;
+///
+/// # fn main() -> Result<(), traduora::TraduoraError> {
+/// let client = TraduoraBuilder::new("https://corp.example/tools/traduora").build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
 #[derive(Clone, Debug)]
 #[must_use]
 pub struct Builder<'h, L> {
     host: &'h str,
+    api_root: &'h str,
     protocol: &'static str,
     validate_certs: bool,
+    #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+    tls_backend: TlsBackend,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    instrumentation: Instrumentation,
+    roles: Vec<(ProjectId, Role)>,
+    connection: ConnectionOptions,
+    headers: RequestHeaders,
     login: L,
 }
 
+/// Connection reuse settings forwarded to the underlying [`reqwest`] client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct ConnectionOptions {
+    http2_prior_knowledge: bool,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: usize,
+    tcp_keepalive: Option<Duration>,
+    max_response_size: Option<usize>,
+}
+
+impl ConnectionOptions {
+    /// Same defaults as [`reqwest::ClientBuilder`].
+    const fn new() -> Self {
+        Self {
+            http2_prior_knowledge: false,
+            pool_idle_timeout: Some(Duration::from_secs(90)),
+            pool_max_idle_per_host: usize::MAX,
+            tcp_keepalive: None,
+            max_response_size: None,
+        }
+    }
+}
+
+/// Headers sent with every request.
+#[derive(Clone, Debug)]
+struct RequestHeaders {
+    user_agent: Option<String>,
+    default: Vec<(String, String)>,
+}
+
+impl RequestHeaders {
+    const fn new() -> Self {
+        Self {
+            user_agent: None,
+            default: Vec::new(),
+        }
+    }
+
+    fn to_header_map(&self) -> TraduoraResult<HeaderMap> {
+        let mut map = HeaderMap::new();
+        let user_agent = self
+            .user_agent
+            .as_ref()
+            .map(|agent| (header::USER_AGENT.as_str(), agent));
+        let headers = self.default.iter().map(|(n, v)| (n.as_str(), v));
+        for (name, value) in headers.chain(user_agent) {
+            let invalid = || TraduoraError::InvalidHeader {
+                name: name.to_owned(),
+            };
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| invalid())?;
+            let value = HeaderValue::from_str(value).map_err(|_| invalid())?;
+            map.append(name, value);
+        }
+        Ok(map)
+    }
+}
+
+/// The TLS implementation used to establish HTTPS connections.
+///
+/// Only available if both the `native-tls` and the `rustls-tls` feature
+/// are enabled. Otherwise, the backend of the enabled feature is used.
+#[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TlsBackend {
+    /// The platform's native TLS implementation, e.g. OpenSSL on Linux.
+    NativeTls,
+    /// The pure-Rust TLS implementation `rustls`.
+    Rustls,
+}
+
 impl<'h> Builder<'h, ()> {
     /// Construct a new builder instance.
     ///
+    /// `host` is either a host with an optional port like `localhost:8080` or
+    /// a full URL like `https://corp.example/tools/traduora`. The scheme of a
+    /// full URL takes precedence over [`Builder::use_http`] and its path is
+    /// prepended to the API root.
+    ///
     /// The builder is intialized with the following defaults:
-    /// - uses HTTPS
+    /// - uses HTTPS, which needs the `native-tls` or the `rustls-tls` feature;
+    ///   without them, building fails unless [`Builder::use_http`] is set
+    /// - API root `api/v1`
     /// - validates certificates
+    /// - uses native TLS if the `native-tls` feature is enabled
+    /// - no warnings for slow requests
+    /// - negotiates HTTP/1.1 or HTTP/2 and reuses idle connections for 90 seconds
     /// - unauthenticated access
     pub const fn new(host: &'h str) -> Self {
         Self {
             host,
+            api_root: "api/v1",
             protocol: "https",
             validate_certs: true,
+            #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+            tls_backend: TlsBackend::NativeTls,
+            authenticator: None,
+            instrumentation: Instrumentation::new(),
+            roles: Vec::new(),
+            connection: ConnectionOptions::new(),
+            headers: RequestHeaders::new(),
             login: (),
         }
     }
@@ -566,26 +1280,63 @@ impl<'h> Builder<'h, ()> {
     /// Note that the Traduora API is not queried when calling this
     /// function. It is queried only when calling [`Builder::build`]
     /// or [`Builder::build_async`].
-    pub const fn authenticate(self, login: Login) -> Builder<'h, Login> {
+    pub fn authenticate(self, login: Login) -> Builder<'h, Login> {
         Builder {
             host: self.host,
+            api_root: self.api_root,
             protocol: self.protocol,
             validate_certs: self.validate_certs,
+            #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+            tls_backend: self.tls_backend,
+            authenticator: self.authenticator,
+            instrumentation: self.instrumentation,
+            roles: self.roles,
+            connection: self.connection,
+            headers: self.headers,
             login,
         }
     }
 
-    /// Adds an access token string to the builder.
+    /// Adds login information from a [`CredentialProvider`] to the builder.
+    ///
+    /// The provider is asked immediately, but the Traduora API is queried
+    /// only when calling [`Builder::build`] or [`Builder::build_async`].
+    ///
+    /// # Errors
+    /// This method returns an error if the provider fails or has no credentials.
+    pub fn credentials<P>(self, provider: &P) -> Result<Builder<'h, Login>, CredentialError>
+    where
+        P: CredentialProvider + ?Sized,
+    {
+        let login = provider.login()?.ok_or(CredentialError::NotFound)?;
+        Ok(self.authenticate(login))
+    }
+
+    /// Adds an access token to the builder.
+    ///
+    /// Accepts a token string as [`AccessToken`](crate::api::AccessToken) or a [`StoredToken`]
+    /// that also knows when the token expires.
     ///
     /// Note that the Traduora API won't be queried at all when the
     /// client is built with this method. The token is assumed to be valid
     /// and passed to the client without any modifications.
-    pub const fn with_access_token(self, login: api::AccessToken) -> Builder<'h, api::AccessToken> {
+    pub fn with_access_token<T>(self, token: T) -> Builder<'h, StoredToken>
+    where
+        T: Into<StoredToken>,
+    {
         Builder {
             host: self.host,
+            api_root: self.api_root,
             protocol: self.protocol,
             validate_certs: self.validate_certs,
-            login,
+            #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+            tls_backend: self.tls_backend,
+            authenticator: self.authenticator,
+            instrumentation: self.instrumentation,
+            roles: self.roles,
+            connection: self.connection,
+            headers: self.headers,
+            login: token.into(),
         }
     }
 
@@ -640,7 +1391,7 @@ impl<'h> Builder<'h, Login> {
     }
 }
 
-impl<'h> Builder<'h, api::AccessToken> {
+impl<'h> Builder<'h, StoredToken> {
     /// Builds a synchronous client with authentification information.
     ///
     /// Calling this method does not query the Traduora API. The access
@@ -654,9 +1405,8 @@ impl<'h> Builder<'h, api::AccessToken> {
     pub fn build(&self) -> TraduoraResult<Traduora<Authenticated>> {
         let api = self.build_unauthenticated()?;
         Ok(Traduora {
-            client: api.client,
-            rest_url: api.rest_url,
-            token: self.login.clone().into(),
+            shared: api.shared,
+            token: Arc::new(self.login.clone().into()),
         })
     }
 
@@ -673,9 +1423,8 @@ impl<'h> Builder<'h, api::AccessToken> {
     pub async fn build_async(&self) -> TraduoraResult<AsyncTraduora<Authenticated>> {
         let api = self.build_unauthenticated_async()?;
         Ok(AsyncTraduora {
-            client: api.client,
-            rest_url: api.rest_url,
-            token: self.login.clone().into(),
+            shared: api.shared,
+            token: Arc::new(self.login.clone().into()),
         })
     }
 }
@@ -694,9 +1443,20 @@ impl<'h, L> Builder<'h, L> {
         self
     }
 
+    /// Sets the path of the API relative to the URL of the Traduora instance.
+    ///
+    /// Defaults to `api/v1`. Leading and trailing slashes are ignored.
+    pub const fn api_root(mut self, api_root: &'h str) -> Self {
+        self.api_root = api_root;
+        self
+    }
+
     /// Decides whether the SSL certificates will be validate when
     /// opening the connection.
     ///
+    /// Disabling the validation needs the `native-tls` or the `rustls-tls`
+    /// feature; without them, building fails.
+    ///
     /// # Warning
     /// It is recommended to just use valid (non-self-signed) certificates.
     pub const fn validate_certs(mut self, validate: bool) -> Self {
@@ -704,27 +1464,383 @@ impl<'h, L> Builder<'h, L> {
         self
     }
 
-    fn build_rest_url(&self) -> Result<Url, url::ParseError> {
-        format!("{}://{}/api/v1/", self.protocol, self.host).parse()
+    /// Selects the TLS implementation to use for HTTPS connections.
+    ///
+    /// Only available if both the `native-tls` and the `rustls-tls` feature
+    /// are enabled.
+    #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+    pub const fn tls_backend(mut self, tls_backend: TlsBackend) -> Self {
+        self.tls_backend = tls_backend;
+        self
+    }
+
+    /// Sets the authentication scheme used for every request.
+    ///
+    /// Defaults to [`BearerAuth`] which sends the access token in the
+    /// `Authorization` header.
+    pub fn authenticator<T>(mut self, authenticator: T) -> Self
+    where
+        T: Authenticator + 'static,
+    {
+        self.authenticator = Some(Arc::new(authenticator));
+        self
+    }
+
+    /// Logs a warning with target `traduora` for every request that takes
+    /// longer than the threshold, including the time to download the response.
+    ///
+    /// This helps to spot performance regressions of the Traduora instance.
+    pub const fn slow_call_threshold(mut self, threshold: Duration) -> Self {
+        self.instrumentation.slow_call_threshold = Some(threshold);
+        self
+    }
+
+    /// Reports the outcome of every request to the sink, e.g. to count errors
+    /// or to measure the latency of the Traduora instance.
+    pub fn metrics_sink<T>(mut self, sink: T) -> Self
+    where
+        T: MetricsSink + 'static,
+    {
+        self.instrumentation.metrics = Some(Arc::new(sink));
+        self
+    }
+
+    /// Declares the role of the client within a project,
+    /// e.g. [`Role::Viewer`] for a project it may only read.
+    ///
+    /// Requests for endpoints of the project that need a more privileged role
+    /// then fail with [`ApiError::MissingRole`] without contacting the server.
+    /// Call it once per project; requests for other projects are not checked.
+    pub fn project_role(mut self, project: ProjectId, role: Role) -> Self {
+        self.roles.retain(|(p, _)| *p != project);
+        self.roles.push((project, role));
+        self
+    }
+
+    /// Sets the `User-Agent` header of every request, e.g. to attribute
+    /// requests to an application in the logs of the Traduora instance.
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.headers.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header that is sent with every request, e.g. for rules of an API gateway.
+    ///
+    /// Adding a header with the same name again sends both values.
+    /// The header is validated when building the client.
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.default.push((name.into(), value.into()));
+        self
+    }
+
+    /// Decides whether to only use HTTP/2 without negotiating the protocol first.
+    ///
+    /// # Warning
+    /// Requests fail if the Traduora instance or a proxy in front of it does not support HTTP/2.
+    pub const fn http2_prior_knowledge(mut self, enabled: bool) -> Self {
+        self.connection.http2_prior_knowledge = enabled;
+        self
+    }
+
+    /// Sets how long idle connections are kept open for reuse.
+    /// `None` keeps them open indefinitely.
+    pub const fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.connection.pool_idle_timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept open per host.
+    pub const fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.connection.pool_max_idle_per_host = max;
+        self
+    }
+
+    /// Sets the interval of TCP keepalive probes on open connections.
+    /// `None` disables them.
+    pub const fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.connection.tcp_keepalive = interval;
+        self
+    }
+
+    /// Sets the maximum size of a response body in bytes.
+    ///
+    /// Larger responses are not read into memory but fail with
+    /// [`ApiError::ResponseTooLarge`]. `None`, the default, accepts any size.
+    pub const fn max_response_size(mut self, limit: Option<usize>) -> Self {
+        self.connection.max_response_size = limit;
+        self
+    }
+
+    pub(crate) fn uses_https(&self) -> bool {
+        self.scheme().eq_ignore_ascii_case("https")
+    }
+
+    pub(crate) const fn validates_certs(&self) -> bool {
+        self.validate_certs
+    }
+
+    fn scheme(&self) -> &str {
+        self.host
+            .split_once("://")
+            .map_or(self.protocol, |(scheme, _)| scheme)
+    }
+
+    fn build_rest_url(&self) -> TraduoraResult<Url> {
+        let invalid = |reason| TraduoraError::InvalidUrl {
+            url: self.host.to_owned(),
+            reason,
+        };
+        let mut url: Url = if self.host.contains("://") {
+            self.host.parse()?
+        } else {
+            format!("{}://{}", self.protocol, self.host).parse()?
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(invalid("scheme must be http or https"));
+        }
+        if url.host_str().map_or(true, str::is_empty) {
+            return Err(invalid("host is missing"));
+        }
+        if url.query().is_some() || url.fragment().is_some() {
+            return Err(invalid("query and fragment are not allowed"));
+        }
+
+        let segments: Vec<_> = [url.path(), self.api_root]
+            .iter()
+            .map(|p| p.trim_matches('/'))
+            .filter(|p| !p.is_empty())
+            .collect();
+        let path = if segments.is_empty() {
+            "/".to_owned()
+        } else {
+            format!("/{}/", segments.join("/"))
+        };
+        url.set_path(&path);
+        Ok(url)
+    }
+
+    /// Rejects the options that need TLS if no TLS implementation is compiled in.
+    fn check_tls(&self) -> TraduoraResult<()> {
+        let option = if self.uses_https() {
+            "https"
+        } else if !self.validate_certs {
+            "validate_certs(false)"
+        } else {
+            return Ok(());
+        };
+        if cfg!(any(feature = "native-tls", feature = "rustls-tls")) {
+            Ok(())
+        } else {
+            Err(TraduoraError::TlsUnavailable { option })
+        }
+    }
+
+    fn build_client(&self) -> TraduoraResult<reqwest::blocking::Client> {
+        self.check_tls()?;
+        let builder = reqwest::blocking::Client::builder()
+            .default_headers(self.headers.to_header_map()?)
+            .pool_idle_timeout(self.connection.pool_idle_timeout)
+            .pool_max_idle_per_host(self.connection.pool_max_idle_per_host)
+            .tcp_keepalive(self.connection.tcp_keepalive);
+        let builder = if self.connection.http2_prior_knowledge {
+            builder.http2_prior_knowledge()
+        } else {
+            builder
+        };
+        #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+        let builder = builder.danger_accept_invalid_certs(!self.validate_certs);
+        #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+        let builder = match self.tls_backend {
+            TlsBackend::NativeTls => builder.use_native_tls(),
+            TlsBackend::Rustls => builder.use_rustls_tls(),
+        };
+        Ok(builder.build()?)
+    }
+
+    fn build_async_client(&self) -> TraduoraResult<reqwest::Client> {
+        self.check_tls()?;
+        let builder = reqwest::Client::builder()
+            .default_headers(self.headers.to_header_map()?)
+            .pool_idle_timeout(self.connection.pool_idle_timeout)
+            .pool_max_idle_per_host(self.connection.pool_max_idle_per_host)
+            .tcp_keepalive(self.connection.tcp_keepalive);
+        let builder = if self.connection.http2_prior_knowledge {
+            builder.http2_prior_knowledge()
+        } else {
+            builder
+        };
+        #[cfg(any(feature = "native-tls", feature = "rustls-tls"))]
+        let builder = builder.danger_accept_invalid_certs(!self.validate_certs);
+        #[cfg(all(feature = "native-tls", feature = "rustls-tls"))]
+        let builder = match self.tls_backend {
+            TlsBackend::NativeTls => builder.use_native_tls(),
+            TlsBackend::Rustls => builder.use_rustls_tls(),
+        };
+        Ok(builder.build()?)
+    }
+
+    fn build_authenticator(&self) -> Arc<dyn Authenticator> {
+        self.authenticator
+            .clone()
+            .unwrap_or_else(|| Arc::new(BearerAuth))
     }
 
-    fn build_unauthenticated(&self) -> TraduoraResult<Traduora<Unauthenticated>> {
+    pub(crate) fn build_unauthenticated(&self) -> TraduoraResult<Traduora<Unauthenticated>> {
         Ok(Traduora {
-            client: reqwest::blocking::Client::builder()
-                .danger_accept_invalid_certs(!self.validate_certs)
-                .build()?,
-            rest_url: self.build_rest_url()?,
-            token: Unauthenticated,
+            shared: Arc::new(Shared {
+                client: Transport::Blocking(self.build_client()?),
+                rest_url: self.build_rest_url()?,
+                authenticator: self.build_authenticator(),
+                instrumentation: self.instrumentation.clone(),
+                roles: self.roles.iter().cloned().collect(),
+                max_response_size: self.connection.max_response_size,
+            }),
+            token: Arc::new(Unauthenticated),
         })
     }
 
-    fn build_unauthenticated_async(&self) -> TraduoraResult<AsyncTraduora<Unauthenticated>> {
+    pub(crate) fn build_unauthenticated_async(
+        &self,
+    ) -> TraduoraResult<AsyncTraduora<Unauthenticated>> {
         Ok(AsyncTraduora {
-            client: reqwest::Client::builder()
-                .danger_accept_invalid_certs(!self.validate_certs)
-                .build()?,
-            rest_url: self.build_rest_url()?,
-            token: Unauthenticated,
+            shared: Arc::new(Shared {
+                client: self.build_async_client()?,
+                rest_url: self.build_rest_url()?,
+                authenticator: self.build_authenticator(),
+                instrumentation: self.instrumentation.clone(),
+                roles: self.roles.iter().cloned().collect(),
+                max_response_size: self.connection.max_response_size,
+            }),
+            token: Arc::new(Unauthenticated),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Builder, TraduoraError};
+    use crate::{
+        api::{projects::DeleteProject, terms::Terms, AccessToken, Role},
+        ApiError, Query,
+    };
+    use http::header::USER_AGENT;
+
+    #[test]
+    fn tls_options_need_a_tls_feature() {
+        let tls = cfg!(any(feature = "native-tls", feature = "rustls-tls"));
+        let https = Builder::new("corp.example").check_tls();
+        let insecure = Builder::new("corp.example")
+            .use_http(true)
+            .validate_certs(false)
+            .check_tls();
+
+        assert_eq!(https.is_ok(), tls);
+        assert_eq!(insecure.is_ok(), tls);
+        assert!(Builder::new("corp.example")
+            .use_http(true)
+            .check_tls()
+            .is_ok());
+        if let Err(error) = https {
+            assert_eq!(
+                error.to_string(),
+                "https requires TLS, enable the `native-tls` or `rustls-tls` feature"
+            );
+        }
+    }
+
+    #[test]
+    fn rest_url_accepts_hosts_and_full_urls() {
+        let url = |host| Builder::new(host).build_rest_url().map(|u| u.to_string());
+        assert_eq!(
+            url("localhost:8080").unwrap(),
+            "https://localhost:8080/api/v1/"
+        );
+        assert_eq!(
+            url("http://corp.example/tools/traduora/").unwrap(),
+            "http://corp.example/tools/traduora/api/v1/"
+        );
+        assert_eq!(
+            Builder::new("corp.example/traduora")
+                .use_http(true)
+                .api_root("/custom/")
+                .build_rest_url()
+                .unwrap()
+                .as_str(),
+            "http://corp.example/traduora/custom/"
+        );
+        assert!(!Builder::new("http://corp.example").uses_https());
+
+        assert!(matches!(
+            url("ftp://corp.example"),
+            Err(TraduoraError::InvalidUrl { .. })
+        ));
+        assert!(matches!(
+            url("https://corp.example/?a=b"),
+            Err(TraduoraError::InvalidUrl { .. })
+        ));
+    }
+
+    #[test]
+    fn headers_are_validated() {
+        let builder = Builder::new("localhost")
+            .user_agent("my-app/1.0")
+            .default_header("X-Team", "l10n");
+        let headers = builder.headers.to_header_map().unwrap();
+        assert_eq!(headers[USER_AGENT], "my-app/1.0");
+        assert_eq!(headers["x-team"], "l10n");
+
+        let builder = builder.default_header("bad header", "value");
+        assert!(matches!(
+            builder.build(),
+            Err(TraduoraError::InvalidHeader { name }) if name == "bad header"
+        ));
+    }
+
+    #[test]
+    fn role_is_checked_before_sending() {
+        // nothing listens on the discard port, so only the role check can answer
+        let client = Builder::new("http://127.0.0.1:9")
+            .project_role("project".into(), Role::Viewer)
+            .project_role("other".into(), Role::Admin)
+            .with_access_token(AccessToken::new("token"))
+            .build()
+            .unwrap();
+        let error = DeleteProject("project".into()).query(&client).unwrap_err();
+        assert!(error.is_forbidden());
+        assert!(matches!(
+            error,
+            ApiError::MissingRole {
+                ref project,
+                required: Role::Admin,
+                role: Role::Viewer,
+                ..
+            } if project.value() == "project"
+        ));
+        assert!(!matches!(
+            Terms::new("project".into()).query(&client),
+            Err(ApiError::MissingRole { .. })
+        ));
+        assert!(!matches!(
+            DeleteProject("other".into()).query(&client),
+            Err(ApiError::MissingRole { .. })
+        ));
+        assert!(!matches!(
+            DeleteProject("unknown".into()).query(&client),
+            Err(ApiError::MissingRole { .. })
+        ));
+    }
+
+    #[test]
+    fn clients_of_other_users_share_the_pool() {
+        let pool = Builder::new("localhost:8080").build_async().unwrap();
+        let alice = pool.clone_with_token(AccessToken::new("alice"));
+        let bob = alice.clone_with_token(AccessToken::new("bob"));
+
+        assert!(Arc::ptr_eq(&pool.shared, &bob.shared));
+        assert!(Arc::ptr_eq(&bob.token, &bob.clone().token));
+        assert_eq!(alice.access_token().access_token.value(), "alice");
+        assert_eq!(bob.access_token().access_token.value(), "bob");
+    }
+}
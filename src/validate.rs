@@ -0,0 +1,1085 @@
+//! Checking translations for mistakes that break the application.
+//!
+//! [`PlaceholderValidator`] finds translations whose placeholders differ from
+//! the translation in a reference locale, e.g. a German text that lost the
+//! `{name}` of its English original. [`Glossary`] finds translations that do
+//! not use the prescribed translation of a term or contain forbidden words.
+//! [`LengthPolicy`] finds translations that are too long for the UI.
+//! [`MarkupValidator`] finds translations with broken or unsafe HTML.
+//!
+//! # Examples
+//! ```
+//! use traduora::{sync::TranslationSnapshot, validate::{PlaceholderValidator, ViolationKind}};
+//!
+//! let mut snapshot = TranslationSnapshot::new();
+//! snapshot.set("greeting", "en".into(), "Hello {name}, you have %d messages");
+//! snapshot.set("greeting", "de".into(), "Hallo {nmae}, du hast %d Nachrichten");
+//!
+//! let violations = PlaceholderValidator::new("en".into()).check(&snapshot);
+//! assert_eq!(violations.len(), 2);
+//! assert_eq!(violations[0].kind, ViolationKind::Missing("{name}".to_owned()));
+//! assert_eq!(violations[1].kind, ViolationKind::Unexpected("{nmae}".to_owned()));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    api::{locales::LocaleCode, ProjectId, TermId, TermKey},
+    auth::Authenticated,
+    search::ProjectIndex,
+    sync::TranslationSnapshot,
+    ApiError, AsyncClient, Client,
+};
+
+/// A syntax for placeholders that are replaced by the application at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum PlaceholderStyle {
+    /// ICU message format arguments like `{name}` or `{count, plural, ...}`.
+    ///
+    /// Only the argument name is compared, so `{count, plural, ...}` matches
+    /// `{count}` in another locale.
+    Icu,
+    /// Mustache-style variables like `{{name}}`.
+    Mustache,
+    /// printf-style conversions like `%s`, `%d` or `%1$s`. `%%` is not a placeholder.
+    Printf,
+}
+
+impl PlaceholderStyle {
+    /// All supported styles.
+    pub const ALL: [Self; 3] = [Self::Icu, Self::Mustache, Self::Printf];
+}
+
+/// Compares the placeholders of all translations with those of a reference locale.
+///
+/// Placeholders are compared as sets: repeating a placeholder or changing
+/// their order is allowed. Terms that are not translated in the reference
+/// locale or the checked locale are skipped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaceholderValidator {
+    reference: LocaleCode,
+    styles: BTreeSet<PlaceholderStyle>,
+}
+
+/// Why a translation does not match the reference locale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", content = "placeholder", rename_all = "snake_case")]
+pub enum ViolationKind {
+    /// The placeholder of the reference translation is missing.
+    Missing(String),
+    /// The placeholder does not appear in the reference translation.
+    Unexpected(String),
+}
+
+/// A placeholder mismatch between a translation and the reference locale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Violation {
+    /// Key of the term.
+    pub term: TermKey,
+    /// Locale of the faulty translation.
+    pub locale: LocaleCode,
+    /// What is wrong with the translation.
+    #[serde(flatten)]
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::Missing(placeholder) => write!(
+                f,
+                "{} ({}): placeholder {} is missing",
+                self.term, self.locale, placeholder
+            ),
+            ViolationKind::Unexpected(placeholder) => write!(
+                f,
+                "{} ({}): placeholder {} does not exist in the reference locale",
+                self.term, self.locale, placeholder
+            ),
+        }
+    }
+}
+
+impl PlaceholderValidator {
+    /// Creates a validator comparing with `reference` that recognizes all styles.
+    #[must_use]
+    pub fn new(reference: LocaleCode) -> Self {
+        Self {
+            reference,
+            styles: PlaceholderStyle::ALL.iter().copied().collect(),
+        }
+    }
+
+    /// Only recognizes placeholders of the given styles.
+    ///
+    /// Restrict the styles if a text can contain e.g. a `%` or `{` that is
+    /// not meant as a placeholder.
+    #[must_use]
+    pub fn styles(mut self, styles: &[PlaceholderStyle]) -> Self {
+        self.styles = styles.iter().copied().collect();
+        self
+    }
+
+    /// The placeholders of `text` in normalized form, e.g. `{name}` for `{name, number}`.
+    #[must_use]
+    pub fn placeholders(&self, text: &str) -> BTreeSet<String> {
+        let chars: Vec<_> = text.chars().collect();
+        let mut placeholders = BTreeSet::new();
+        self.collect(&chars, &mut placeholders);
+        placeholders
+    }
+
+    /// Checks all translations of the snapshot.
+    ///
+    /// The violations are sorted by term, locale and placeholder.
+    #[must_use]
+    pub fn check(&self, snapshot: &TranslationSnapshot) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for (term, translations) in &snapshot.terms {
+            let expected = match translations.get(&self.reference) {
+                Some(reference) if !reference.is_empty() => self.placeholders(reference),
+                _ => continue,
+            };
+            for (locale, value) in translations {
+                if *locale == self.reference || value.is_empty() {
+                    continue;
+                }
+                let actual = self.placeholders(value);
+                let violation = |kind| Violation {
+                    term: term.clone(),
+                    locale: locale.clone(),
+                    kind,
+                };
+                violations.extend(
+                    expected
+                        .difference(&actual)
+                        .map(|p| violation(ViolationKind::Missing(p.clone()))),
+                );
+                violations.extend(
+                    actual
+                        .difference(&expected)
+                        .map(|p| violation(ViolationKind::Unexpected(p.clone()))),
+                );
+            }
+        }
+        violations
+    }
+
+    /// Fetches all translations of the project and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub fn validate<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<Violation>, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        TranslationSnapshot::fetch(client, project).map(|snapshot| self.check(&snapshot))
+    }
+
+    /// Fetches all translations of the project asynchronously and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub async fn validate_async<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<Violation>, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        TranslationSnapshot::fetch_async(client, project)
+            .await
+            .map(|snapshot| self.check(&snapshot))
+    }
+
+    fn collect(&self, chars: &[char], placeholders: &mut BTreeSet<String>) {
+        let mut i = 0;
+        while i < chars.len() {
+            i += self.placeholder_at(&chars[i..], placeholders).unwrap_or(1);
+        }
+    }
+
+    /// Collects the placeholder at the start of `chars` and returns the number
+    /// of characters to skip, if there is one.
+    ///
+    /// Sequences like `%%` are skipped without being collected.
+    fn placeholder_at(&self, chars: &[char], placeholders: &mut BTreeSet<String>) -> Option<usize> {
+        let is_name = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '.';
+        let name_len = |start: usize| chars[start..].iter().take_while(|c| is_name(c)).count();
+
+        match chars {
+            ['{', '{', ..] if self.styles.contains(&PlaceholderStyle::Mustache) => {
+                let start = 2 + chars[2..].iter().take_while(|c| **c == ' ').count();
+                let len = name_len(start);
+                let end = start
+                    + len
+                    + chars[start + len..]
+                        .iter()
+                        .take_while(|c| **c == ' ')
+                        .count();
+                if len > 0 && chars[end..].starts_with(&['}', '}']) {
+                    let name: String = chars[start..start + len].iter().collect();
+                    placeholders.insert(format!("{{{{{}}}}}", name));
+                    Some(end + 2)
+                } else {
+                    None
+                }
+            }
+            ['{', ..] if self.styles.contains(&PlaceholderStyle::Icu) => {
+                let len = name_len(1);
+                let name: String = chars[1..=len].iter().collect();
+                match chars.get(1 + len) {
+                    Some('}') if len > 0 => {
+                        placeholders.insert(format!("{{{}}}", name));
+                        Some(len + 2)
+                    }
+                    Some(',') if len > 0 => {
+                        placeholders.insert(format!("{{{}}}", name));
+                        // the branches of `plural` and `select` are messages of their own
+                        let mut depth = 0;
+                        let mut branch_start = 0;
+                        for (i, c) in chars.iter().enumerate().skip(len + 2) {
+                            match (c, depth) {
+                                ('{', 0) => {
+                                    branch_start = i + 1;
+                                    depth = 1;
+                                }
+                                ('{', _) => depth += 1,
+                                ('}', 0) => return Some(i + 1),
+                                ('}', 1) => {
+                                    self.collect(&chars[branch_start..i], placeholders);
+                                    depth = 0;
+                                }
+                                ('}', _) => depth -= 1,
+                                _ => {}
+                            }
+                        }
+                        None
+                    }
+                    _ => None,
+                }
+            }
+            ['%', '%', ..] if self.styles.contains(&PlaceholderStyle::Printf) => Some(2),
+            ['%', rest @ ..] if self.styles.contains(&PlaceholderStyle::Printf) => {
+                let flags = rest
+                    .iter()
+                    .take_while(|c| c.is_ascii_digit() || "$.-+#lh".contains(**c))
+                    .count();
+                rest.get(flags)
+                    .filter(|c| c.is_ascii_alphabetic())
+                    .map(|_| {
+                        placeholders.insert(chars[..flags + 2].iter().collect());
+                        flags + 2
+                    })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why a translation violates a [`Glossary`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum GlossaryViolationKind {
+    /// The source text contains a glossary term, but the translation does
+    /// not contain its required translation.
+    MissingTranslation {
+        /// The glossary term in the source text.
+        glossary_term: String,
+        /// The translation the glossary requires.
+        required: String,
+        /// Character offset of the glossary term in the source text.
+        position: usize,
+    },
+    /// The translation contains a forbidden word.
+    Forbidden {
+        /// The forbidden word as defined in the glossary.
+        word: String,
+        /// Character offset of the word in the translation.
+        position: usize,
+    },
+}
+
+/// A translation that violates a [`Glossary`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct GlossaryViolation {
+    /// Unique id of the term.
+    pub term_id: TermId,
+    /// Key of the term.
+    pub term: TermKey,
+    /// Locale of the faulty translation.
+    pub locale: LocaleCode,
+    /// What is wrong with the translation.
+    #[serde(flatten)]
+    pub kind: GlossaryViolationKind,
+}
+
+impl fmt::Display for GlossaryViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            GlossaryViolationKind::MissingTranslation {
+                glossary_term,
+                required,
+                ..
+            } => write!(
+                f,
+                "{} ({}): {:?} must be translated as {:?}",
+                self.term, self.locale, glossary_term, required
+            ),
+            GlossaryViolationKind::Forbidden { word, position } => write!(
+                f,
+                "{} ({}): forbidden word {:?} at position {}",
+                self.term, self.locale, word, position
+            ),
+        }
+    }
+}
+
+/// Required translations of terms and forbidden words, e.g. to keep brand names consistent.
+///
+/// A glossary term is a word or phrase of the source locale. If the source
+/// text of a translation contains it, the translation must contain the
+/// required translation for its locale. Forbidden words must not appear in
+/// the translations of a locale or of all locales.
+///
+/// Words are only matched as a whole, i.e. not as part of a longer word,
+/// and case-insensitively unless [`Glossary::case_sensitive`] is enabled.
+///
+/// # Examples
+/// ```
+/// # use traduora::{Login, TestClient as Traduora, TraduoraError};
+/// use traduora::validate::Glossary;
+///
+/// # let login = Login::password("tester@mail.example", "letmeinpls");
+/// let client = Traduora::with_auth("localhost:8080", login)?;
+/// let mut glossary = Glossary::new("en".into());
+/// glossary.require("checkout", "de_DE".into(), "Kasse");
+/// glossary.forbid(None, "Traduora Inc.");
+///
+/// let violations = glossary.validate(&client, &"b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())?;
+/// for violation in &violations {
+///     println!("{}: {}", violation.term_id, violation);
+/// }
+/// # Ok::<(), TraduoraError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glossary {
+    source: LocaleCode,
+    required: BTreeMap<String, BTreeMap<LocaleCode, String>>,
+    forbidden: BTreeMap<Option<LocaleCode>, BTreeSet<String>>,
+    case_sensitive: bool,
+}
+
+impl Glossary {
+    /// Creates an empty glossary for terms of the source locale.
+    #[must_use]
+    pub fn new(source: LocaleCode) -> Self {
+        Self {
+            source,
+            required: BTreeMap::new(),
+            forbidden: BTreeMap::new(),
+            case_sensitive: false,
+        }
+    }
+
+    /// Whether words must match with the same case.
+    #[must_use]
+    pub const fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Requires that `term` of the source locale is translated into `locale` as `translation`.
+    pub fn require(
+        &mut self,
+        term: impl Into<String>,
+        locale: LocaleCode,
+        translation: impl Into<String>,
+    ) {
+        self.required
+            .entry(term.into())
+            .or_default()
+            .insert(locale, translation.into());
+    }
+
+    /// Forbids the word in the translations of `locale`, or of all locales if it is `None`.
+    pub fn forbid(&mut self, locale: Option<LocaleCode>, word: impl Into<String>) {
+        self.forbidden
+            .entry(locale)
+            .or_default()
+            .insert(word.into());
+    }
+
+    /// Checks a single translation into `locale`.
+    ///
+    /// `source` is the translation of the same term into the source locale,
+    /// if there is one. Required translations are only checked with it.
+    #[must_use]
+    pub fn check_translation(
+        &self,
+        source: Option<&str>,
+        locale: &LocaleCode,
+        text: &str,
+    ) -> Vec<GlossaryViolationKind> {
+        let text: Vec<_> = text.chars().collect();
+        let mut violations = Vec::new();
+        if let Some(source) = source.filter(|_| *locale != self.source) {
+            let source: Vec<_> = source.chars().collect();
+            for (term, translations) in &self.required {
+                let required = match translations.get(locale) {
+                    Some(required) => required,
+                    None => continue,
+                };
+                let position = match self.find(&source, term).first() {
+                    Some(position) => *position,
+                    None => continue,
+                };
+                if self.find(&text, required).is_empty() {
+                    violations.push(GlossaryViolationKind::MissingTranslation {
+                        glossary_term: term.clone(),
+                        required: required.clone(),
+                        position,
+                    });
+                }
+            }
+        }
+        let words = [None, Some(locale.clone())];
+        for word in words.iter().filter_map(|l| self.forbidden.get(l)).flatten() {
+            violations.extend(self.find(&text, word).into_iter().map(|position| {
+                GlossaryViolationKind::Forbidden {
+                    word: word.clone(),
+                    position,
+                }
+            }));
+        }
+        violations
+    }
+
+    /// Checks all translations of the project.
+    ///
+    /// The violations are sorted in the order of the term list, then by locale.
+    #[must_use]
+    pub fn check(&self, index: &ProjectIndex) -> Vec<GlossaryViolation> {
+        let mut violations = Vec::new();
+        for hit in index.hits() {
+            let source = index
+                .translation(&hit.term.id, &self.source)
+                .map(|t| t.value.as_str())
+                .filter(|t| !t.is_empty());
+            let kinds = self.check_translation(source, hit.locale, &hit.translation.value);
+            violations.extend(kinds.into_iter().map(|kind| GlossaryViolation {
+                term_id: hit.term.id.clone(),
+                term: hit.term.value.clone().into(),
+                locale: hit.locale.clone(),
+                kind,
+            }));
+        }
+        violations
+    }
+
+    /// Fetches all terms and translations of the project and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the terms or translations fails.
+    pub fn validate<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<GlossaryViolation>, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        ProjectIndex::fetch(client, project).map(|index| self.check(&index))
+    }
+
+    /// Fetches all terms and translations of the project asynchronously and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the terms or translations fails.
+    pub async fn validate_async<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<GlossaryViolation>, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        ProjectIndex::fetch_async(client, project)
+            .await
+            .map(|index| self.check(&index))
+    }
+
+    /// Character offsets of the occurrences of `word` as a whole word in `text`.
+    fn find(&self, text: &[char], word: &str) -> Vec<usize> {
+        let word: Vec<_> = word.chars().collect();
+        if word.is_empty() || word.len() > text.len() {
+            return Vec::new();
+        }
+        let same = |a: &char, b: &char| {
+            a == b || (!self.case_sensitive && a.to_lowercase().eq(b.to_lowercase()))
+        };
+        let boundary = |c: Option<&char>| c.map_or(true, |c| !c.is_alphanumeric());
+        (0..=text.len() - word.len())
+            .filter(|&i| {
+                text[i..i + word.len()]
+                    .iter()
+                    .zip(&word)
+                    .all(|(a, b)| same(a, b))
+                    && boundary(i.checked_sub(1).map(|j| &text[j]))
+                    && boundary(text.get(i + word.len()))
+            })
+            .collect()
+    }
+}
+
+/// A translation that is longer than allowed by a [`LengthPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct LengthViolation {
+    /// Key of the term.
+    pub term: TermKey,
+    /// The translation.
+    pub value: String,
+    /// Number of characters of the translation.
+    pub length: usize,
+    /// Maximum number of characters for the term.
+    pub max: usize,
+}
+
+/// Translations that violate a [`LengthPolicy`] by locale.
+///
+/// Returned by [`LengthPolicy::check`]. Locales without violations are left out.
+/// Use [`gates::within_length_limits`](crate::gates::within_length_limits)
+/// to fail a CI pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct LengthReport {
+    /// Translations that are too long by locale, sorted by term.
+    pub locales: BTreeMap<LocaleCode, Vec<LengthViolation>>,
+}
+
+impl LengthReport {
+    /// Whether all translations are within their limits.
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.locales.is_empty()
+    }
+
+    /// All violations with their locale.
+    pub fn violations(&self) -> impl Iterator<Item = (&LocaleCode, &LengthViolation)> {
+        self.locales
+            .iter()
+            .flat_map(|(locale, violations)| violations.iter().map(move |v| (locale, v)))
+    }
+}
+
+/// Maximum lengths of translations by term key pattern, e.g. for buttons
+/// with limited space in the UI.
+///
+/// Patterns are matched with [`TermKey::matches`]. If several patterns
+/// match a term, the smallest maximum applies. Lengths are counted in characters.
+///
+/// # Examples
+/// ```
+/// use traduora::{sync::TranslationSnapshot, validate::LengthPolicy};
+///
+/// let mut snapshot = TranslationSnapshot::new();
+/// snapshot.set("button.save", "en".into(), "Save");
+/// snapshot.set("button.save", "de".into(), "Änderungen speichern und schließen");
+///
+/// let report = LengthPolicy::new().max("button.*", 20).check(&snapshot);
+/// let (locale, violation) = report.violations().next().unwrap();
+/// assert_eq!(locale.value(), "de");
+/// assert_eq!(violation.length, 34);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LengthPolicy {
+    limits: Vec<(String, usize)>,
+}
+
+impl LengthPolicy {
+    /// Creates a policy without limits.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { limits: Vec::new() }
+    }
+
+    /// Limits the translations of terms matching the pattern to `max` characters.
+    #[must_use]
+    pub fn max(mut self, pattern: impl Into<String>, max: usize) -> Self {
+        self.limits.push((pattern.into(), max));
+        self
+    }
+
+    /// The maximum number of characters of the term's translations, if any pattern matches.
+    #[must_use]
+    pub fn limit(&self, term: &TermKey) -> Option<usize> {
+        self.limits
+            .iter()
+            .filter(|(pattern, _)| term.matches(pattern))
+            .map(|(_, max)| *max)
+            .min()
+    }
+
+    /// Checks all translations of the snapshot.
+    #[must_use]
+    pub fn check(&self, snapshot: &TranslationSnapshot) -> LengthReport {
+        let mut report = LengthReport::default();
+        for (term, translations) in &snapshot.terms {
+            let max = match self.limit(term) {
+                Some(max) => max,
+                None => continue,
+            };
+            for (locale, value) in translations {
+                let length = value.chars().count();
+                if length > max {
+                    report
+                        .locales
+                        .entry(locale.clone())
+                        .or_default()
+                        .push(LengthViolation {
+                            term: term.clone(),
+                            value: value.clone(),
+                            length,
+                            max,
+                        });
+                }
+            }
+        }
+        report
+    }
+
+    /// Fetches all translations of the project and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub fn validate<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<LengthReport, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        TranslationSnapshot::fetch(client, project).map(|snapshot| self.check(&snapshot))
+    }
+
+    /// Fetches all translations of the project asynchronously and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub async fn validate_async<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<LengthReport, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        TranslationSnapshot::fetch_async(client, project)
+            .await
+            .map(|snapshot| self.check(&snapshot))
+    }
+}
+
+/// Tags that [`MarkupValidator`] rejects by default because they can execute code or embed content.
+pub const DEFAULT_DISALLOWED_TAGS: [&str; 5] = ["script", "style", "iframe", "object", "embed"];
+
+/// Why the markup of a translation is unsafe or does not match the reference locale.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "kind", content = "markup", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum MarkupViolationKind {
+    /// The tag or entity of the reference translation is missing.
+    Missing(String),
+    /// The tag or entity does not appear in the reference translation.
+    Unexpected(String),
+    /// The tag is not allowed in any translation.
+    Disallowed(String),
+}
+
+/// A translation with broken or unsafe markup.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct MarkupViolation {
+    /// Key of the term.
+    pub term: TermKey,
+    /// Locale of the faulty translation.
+    pub locale: LocaleCode,
+    /// What is wrong with the translation.
+    #[serde(flatten)]
+    pub kind: MarkupViolationKind,
+}
+
+impl fmt::Display for MarkupViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MarkupViolationKind::Missing(markup) => {
+                write!(f, "{} ({}): {} is missing", self.term, self.locale, markup)
+            }
+            MarkupViolationKind::Unexpected(markup) => write!(
+                f,
+                "{} ({}): {} does not exist in the reference locale",
+                self.term, self.locale, markup
+            ),
+            MarkupViolationKind::Disallowed(markup) => write!(
+                f,
+                "{} ({}): {} is not allowed",
+                self.term, self.locale, markup
+            ),
+        }
+    }
+}
+
+/// Compares the HTML tags and entities of all translations with those of a
+/// reference locale and rejects disallowed tags.
+///
+/// Tags are normalized to their lowercase name without attributes, e.g.
+/// `<a href="/">` to `<a>` and `<br/>` to `<br>`, so translators may change
+/// attribute values. Like [`PlaceholderValidator`], tags and entities are
+/// compared as sets. Disallowed tags are reported in all locales, including
+/// the reference locale.
+///
+/// # Examples
+/// ```
+/// use traduora::{sync::TranslationSnapshot, validate::{MarkupValidator, MarkupViolationKind}};
+///
+/// let mut snapshot = TranslationSnapshot::new();
+/// snapshot.set("terms", "en".into(), "Accept the <a href=\"/terms\">terms</a>&nbsp;now");
+/// snapshot.set("terms", "de".into(), "<b>Bedingungen</b> akzeptieren<script>alert(1)</script>");
+///
+/// let violations = MarkupValidator::new("en".into()).check(&snapshot);
+/// let kinds: Vec<_> = violations.iter().map(|v| &v.kind).collect();
+/// assert!(kinds.contains(&&MarkupViolationKind::Missing("<a>".to_owned())));
+/// assert!(kinds.contains(&&MarkupViolationKind::Disallowed("<script>".to_owned())));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkupValidator {
+    reference: LocaleCode,
+    disallowed: BTreeSet<String>,
+}
+
+impl MarkupValidator {
+    /// Creates a validator comparing with `reference` that disallows [`DEFAULT_DISALLOWED_TAGS`].
+    #[must_use]
+    pub fn new(reference: LocaleCode) -> Self {
+        Self {
+            reference,
+            disallowed: DEFAULT_DISALLOWED_TAGS
+                .iter()
+                .map(|t| (*t).to_owned())
+                .collect(),
+        }
+    }
+
+    /// Replaces the disallowed tags by the given tag names, e.g. `["script", "img"]`.
+    #[must_use]
+    pub fn disallow(mut self, tags: &[&str]) -> Self {
+        self.disallowed = tags.iter().map(|t| t.to_ascii_lowercase()).collect();
+        self
+    }
+
+    /// The tags and entities of `text` in normalized form, e.g. `<a>`, `</a>` and `&amp;`.
+    #[must_use]
+    pub fn markup(text: &str) -> BTreeSet<String> {
+        let mut markup = BTreeSet::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(|c| c == '<' || c == '&') {
+            rest = &rest[start..];
+            let (found, len) = if rest.starts_with('<') {
+                tag(rest)
+            } else {
+                entity(rest)
+            };
+            markup.extend(found);
+            rest = &rest[len.max(1)..];
+        }
+        markup
+    }
+
+    /// Checks all translations of the snapshot.
+    ///
+    /// The violations are sorted by term and locale.
+    #[must_use]
+    pub fn check(&self, snapshot: &TranslationSnapshot) -> Vec<MarkupViolation> {
+        let mut violations = Vec::new();
+        for (term, translations) in &snapshot.terms {
+            let expected = translations
+                .get(&self.reference)
+                .filter(|r| !r.is_empty())
+                .map(|r| Self::markup(r));
+            for (locale, value) in translations.iter().filter(|(_, v)| !v.is_empty()) {
+                let actual = Self::markup(value);
+                let violation = |kind| MarkupViolation {
+                    term: term.clone(),
+                    locale: locale.clone(),
+                    kind,
+                };
+                if let Some(expected) = expected.as_ref().filter(|_| *locale != self.reference) {
+                    violations.extend(
+                        expected
+                            .difference(&actual)
+                            .map(|m| violation(MarkupViolationKind::Missing(m.clone()))),
+                    );
+                    violations.extend(
+                        actual
+                            .difference(expected)
+                            .map(|m| violation(MarkupViolationKind::Unexpected(m.clone()))),
+                    );
+                }
+                violations.extend(
+                    actual
+                        .iter()
+                        .filter(|m| self.is_disallowed(m))
+                        .map(|m| violation(MarkupViolationKind::Disallowed(m.clone()))),
+                );
+            }
+        }
+        violations
+    }
+
+    /// Fetches all translations of the project and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub fn validate<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<MarkupViolation>, ApiError<C::Error>>
+    where
+        C: Client<AccessLevel = Authenticated>,
+    {
+        TranslationSnapshot::fetch(client, project).map(|snapshot| self.check(&snapshot))
+    }
+
+    /// Fetches all translations of the project asynchronously and checks them.
+    ///
+    /// # Errors
+    /// This function returns an error if fetching the translations fails.
+    pub async fn validate_async<C>(
+        &self,
+        client: &C,
+        project: &ProjectId,
+    ) -> Result<Vec<MarkupViolation>, ApiError<C::Error>>
+    where
+        C: AsyncClient<AccessLevel = Authenticated> + Sync,
+    {
+        TranslationSnapshot::fetch_async(client, project)
+            .await
+            .map(|snapshot| self.check(&snapshot))
+    }
+
+    fn is_disallowed(&self, markup: &str) -> bool {
+        let name = markup.trim_start_matches(|c| c == '<' || c == '/');
+        let name = name.trim_end_matches('>');
+        markup.starts_with('<') && self.disallowed.contains(name)
+    }
+}
+
+/// The normalized tag at the start of `text` and the number of bytes to skip.
+fn tag(text: &str) -> (Option<String>, usize) {
+    let end = match text.find('>') {
+        Some(end) => end,
+        None => return (None, 1),
+    };
+    let inner = &text[1..end];
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let name_len = inner
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(inner.len());
+    let name = &inner[..name_len];
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && inner[name_len..]
+            .chars()
+            .next()
+            .map_or(true, |c| c.is_whitespace() || c == '/');
+    if !valid {
+        return (None, 1);
+    }
+    let slash = if closing { "/" } else { "" };
+    (
+        Some(format!("<{}{}>", slash, name.to_ascii_lowercase())),
+        end + 1,
+    )
+}
+
+/// The entity at the start of `text` and the number of bytes to skip.
+fn entity(text: &str) -> (Option<String>, usize) {
+    let end = match text.find(';') {
+        Some(end) => end,
+        None => return (None, 1),
+    };
+    let name = &text[1..end];
+    let valid = match name.strip_prefix('#') {
+        Some(code) => match code.strip_prefix(|c| c == 'x' || c == 'X') {
+            Some(hex) => !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !code.is_empty() && code.chars().all(|c| c.is_ascii_digit()),
+        },
+        None => !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()),
+    };
+    if valid {
+        (Some(text[..=end].to_owned()), end + 1)
+    } else {
+        (None, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+
+    use super::{
+        Glossary, GlossaryViolationKind, LengthPolicy, MarkupValidator, MarkupViolationKind,
+        PlaceholderStyle, PlaceholderValidator,
+    };
+    use crate::{
+        api::{terms::Term, translations::Translation, AccessDates},
+        search::ProjectIndex,
+        sync::TranslationSnapshot,
+    };
+
+    #[test]
+    fn placeholders_of_each_style() {
+        let validator = PlaceholderValidator::new("en".into());
+        let text = "{{ user }} has {count, plural, one {# item in {place}} other {many {x}}}: 100%% %1$s%d { x }";
+        let placeholders: Vec<_> = validator.placeholders(text).into_iter().collect();
+        assert_eq!(
+            placeholders,
+            ["%1$s", "%d", "{count}", "{place}", "{x}", "{{user}}"]
+        );
+
+        let icu_only = validator.styles(&[PlaceholderStyle::Icu]);
+        let placeholders: Vec<_> = icu_only.placeholders("100% {{x}} %s").into_iter().collect();
+        assert_eq!(placeholders, ["{x}"]);
+    }
+
+    #[test]
+    fn glossary_violations_have_positions() {
+        let date = AccessDates {
+            created: Utc::now(),
+            modified: Utc::now(),
+        };
+        let mut translations = BTreeMap::new();
+        translations.insert(
+            "en".into(),
+            vec![Translation::new(
+                "1".into(),
+                "Go to Checkout with GitHub",
+                date,
+            )],
+        );
+        translations.insert(
+            "de".into(),
+            vec![Translation::new(
+                "1".into(),
+                "Zur Bezahlung mit Github, github",
+                date,
+            )],
+        );
+        let index = ProjectIndex::new(vec![Term::new("1".into(), "cta", date)], translations);
+
+        let mut glossary = Glossary::new("en".into());
+        glossary.require("checkout", "de".into(), "Kasse");
+        glossary.require("pay", "de".into(), "Zahlen");
+        glossary.forbid(Some("de".into()), "Github");
+        let violations = glossary.check(&index);
+        let kinds: Vec<_> = violations.iter().map(|v| &v.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &GlossaryViolationKind::MissingTranslation {
+                    glossary_term: "checkout".to_owned(),
+                    required: "Kasse".to_owned(),
+                    position: 6,
+                },
+                &GlossaryViolationKind::Forbidden {
+                    word: "Github".to_owned(),
+                    position: 18,
+                },
+                &GlossaryViolationKind::Forbidden {
+                    word: "Github".to_owned(),
+                    position: 26,
+                },
+            ]
+        );
+        assert_eq!(violations[0].term_id, "1".into());
+
+        let strict = glossary.case_sensitive(true);
+        assert_eq!(strict.check(&index).len(), 1);
+    }
+
+    #[test]
+    fn strictest_length_limit_applies() {
+        let policy = LengthPolicy::new()
+            .max("button.**", 10)
+            .max("button.short.*", 3);
+        assert_eq!(policy.limit(&"button.short.ok".into()), Some(3));
+        assert_eq!(policy.limit(&"title".into()), None);
+
+        let mut snapshot = TranslationSnapshot::new();
+        snapshot.set("button.short.ok", "en".into(), "OK");
+        snapshot.set("button.short.ok", "de".into(), "Okay");
+        snapshot.set("button.save", "de".into(), "Speichern");
+        snapshot.set("title", "de".into(), "Ein sehr langer Titel");
+        let report = policy.check(&snapshot);
+
+        assert!(!report.is_ok());
+        let violations: Vec<_> = report
+            .violations()
+            .map(|(l, v)| (l.value(), v.term.value(), v.length, v.max))
+            .collect();
+        assert_eq!(violations, [("de", "button.short.ok", 4, 3)]);
+    }
+
+    #[test]
+    fn markup_is_normalized_and_compared() {
+        let markup: Vec<_> =
+            MarkupValidator::markup("a < b <A HREF='x'>&amp;</a><br/> &#39; &#x1F600; & x; <1>")
+                .into_iter()
+                .collect();
+        assert_eq!(
+            markup,
+            ["&#39;", "&#x1F600;", "&amp;", "</a>", "<a>", "<br>"]
+        );
+
+        let mut snapshot = TranslationSnapshot::new();
+        snapshot.set("t", "en".into(), "<b>Bold</b> <img src=x>");
+        snapshot.set("t", "de".into(), "<b>Fett</b> <i>kursiv<img src=y>");
+        let validator = MarkupValidator::new("en".into()).disallow(&["IMG"]);
+        let kinds: Vec<_> = validator
+            .check(&snapshot)
+            .into_iter()
+            .map(|v| (v.locale.value().to_owned(), v.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (
+                    "de".to_owned(),
+                    MarkupViolationKind::Unexpected("<i>".to_owned())
+                ),
+                (
+                    "de".to_owned(),
+                    MarkupViolationKind::Disallowed("<img>".to_owned())
+                ),
+                (
+                    "en".to_owned(),
+                    MarkupViolationKind::Disallowed("<img>".to_owned())
+                ),
+            ]
+        );
+    }
+}
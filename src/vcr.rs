@@ -0,0 +1,456 @@
+//! Recording real Traduora traffic and replaying it later.
+//!
+//! A [`RecorderClient`] wraps a real client and records every request with its
+//! response into a [`Cassette`]. The cassette can be saved as a JSON file and
+//! served by a [`ReplayClient`], e.g. for deterministic integration tests or
+//! for developing without access to a Traduora instance.
+//!
+//! Headers are not recorded. Passwords, client secrets and access tokens in
+//! request and response bodies are replaced with [`REDACTED`] so that a
+//! cassette can be committed to a repository.
+//!
+//! # Examples
+//! ```
+//! # use traduora::{Login, TestClient as Traduora, TraduoraError};
+//! use traduora::{api::terms::Terms, vcr::{RecorderClient, ReplayClient}, Query};
+//!
+//! # let login = Login::password("tester@mail.example", "letmeinpls");
+//! let recorder = RecorderClient::new(Traduora::with_auth("localhost:8080", login)?);
+//! let project = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+//! let recorded = Terms::new(project.into()).query(&recorder)?;
+//!
+//! // usually saved with `recorder.save(path)` and loaded with `ReplayClient::from_file(path)`
+//! let replay = ReplayClient::new(recorder.cassette());
+//! let replayed = Terms::new(project.into()).query(&replay).unwrap();
+//! assert_eq!(recorded, replayed);
+//! # Ok::<(), TraduoraError>(())
+//! ```
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use http::{request::Builder as RequestBuilder, Method, Response};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use url::Url;
+
+use crate::logging::debug;
+use crate::{
+    api::{ProjectId, Role},
+    auth::Authenticated,
+    ApiError, AsyncClient, Client, RestClient,
+};
+
+/// Replacement for secrets in recorded bodies.
+pub const REDACTED: &str = "<redacted>";
+
+/// Fields of request and response bodies that are replaced with [`REDACTED`].
+const SECRET_FIELDS: [&str; 6] = [
+    "password",
+    "oldPassword",
+    "newPassword",
+    "client_secret",
+    "access_token",
+    "accessToken",
+];
+
+/// Base URL of the endpoints of a [`ReplayClient`].
+const REPLAY_URL: &str = "http://traduora.replay/api/v1/";
+
+/// The error which is returned when a cassette cannot be used.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum VcrError {
+    /// The cassette file could not be read or written.
+    #[error("failed to access cassette {}: {}", path.display(), source)]
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Inner error.
+        source: io::Error,
+    },
+    /// The cassette file has an invalid format.
+    #[error("invalid cassette {}: {}", path.display(), source)]
+    Format {
+        /// Path of the file.
+        path: PathBuf,
+        /// Inner error.
+        source: serde_json::Error,
+    },
+    /// The cassette has no unused interaction for the request.
+    #[error("no recorded interaction for {} {}", method, path)]
+    NoInteraction {
+        /// HTTP method of the request.
+        method: Method,
+        /// Endpoint path of the request.
+        path: String,
+    },
+    /// The request or the recorded response is invalid.
+    #[error("`http` error: {}", source)]
+    Http {
+        /// Inner error.
+        #[from]
+        source: http::Error,
+    },
+}
+
+/// A request and the response it got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// HTTP method of the request.
+    pub method: String,
+    /// Endpoint path relative to the API root, including the query string.
+    pub path: String,
+    /// Body of the request, `null` if it has none.
+    pub request: Value,
+    /// Status code of the response.
+    pub status: u16,
+    /// Body of the response, `null` if it has none.
+    pub response: Value,
+}
+
+/// Recorded interactions in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// The recorded interactions.
+    pub interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Reads a cassette from a JSON file.
+    ///
+    /// # Errors
+    /// This function returns an error if the file cannot be read or has an invalid format.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, VcrError> {
+        let path = path.as_ref();
+        let content = std::fs::read(path).map_err(|source| VcrError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        serde_json::from_slice(&content).map_err(|source| VcrError::Format {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    /// Writes the cassette to a JSON file, replacing it if it exists.
+    ///
+    /// # Errors
+    /// This function returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VcrError> {
+        let path = path.as_ref();
+        let content = serde_json::to_vec_pretty(self).map_err(|source| VcrError::Format {
+            path: path.to_owned(),
+            source,
+        })?;
+        std::fs::write(path, content).map_err(|source| VcrError::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+}
+
+/// Parses a body as JSON with its secrets redacted.
+///
+/// Bodies that are not JSON are kept as string. Empty bodies become `null`.
+fn redacted_body(body: &[u8]) -> Value {
+    fn redact(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if SECRET_FIELDS.contains(&key.as_str()) {
+                        *value = Value::String(REDACTED.to_owned());
+                    } else {
+                        redact(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(redact),
+            _ => {}
+        }
+    }
+
+    if body.is_empty() {
+        return Value::Null;
+    }
+    let mut value = serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()));
+    redact(&mut value);
+    value
+}
+
+/// The method and the path relative to `base` of a request.
+fn method_and_path(request: &RequestBuilder, base: Option<&Url>) -> (Method, String) {
+    let method = request.method_ref().cloned().unwrap_or_default();
+    let uri = request
+        .uri_ref()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    let path = base
+        .and_then(|base| uri.strip_prefix(base.as_str()))
+        .unwrap_or(&uri)
+        .to_owned();
+    (method, path)
+}
+
+/// A client wrapper that records all requests and their responses.
+///
+/// Requests that fail without a response, e.g. because the server is not
+/// reachable, are not recorded.
+#[derive(Debug)]
+pub struct RecorderClient<C> {
+    client: C,
+    cassette: Mutex<Cassette>,
+}
+
+impl<C> RecorderClient<C> {
+    /// Wraps the client.
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            cassette: Mutex::new(Cassette::default()),
+        }
+    }
+
+    /// Returns the wrapped client.
+    pub const fn inner(&self) -> &C {
+        &self.client
+    }
+
+    /// Unwraps the client.
+    pub fn into_inner(self) -> C {
+        self.client
+    }
+
+    /// The interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.lock().clone()
+    }
+
+    /// Writes the interactions recorded so far to a JSON file.
+    ///
+    /// # Errors
+    /// This method returns an error if the file cannot be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), VcrError> {
+        self.cassette().save(path)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Cassette> {
+        self.cassette.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<C: RestClient> RecorderClient<C> {
+    fn record(&self, method: Method, path: String, body: &[u8], response: &Response<Bytes>) {
+        debug!(target: "traduora", "recording {} {}", method, path);
+        self.lock().interactions.push(Interaction {
+            method: method.to_string(),
+            path,
+            request: redacted_body(body),
+            status: response.status().as_u16(),
+            response: redacted_body(response.body()),
+        });
+    }
+
+    fn method_and_path(&self, request: &RequestBuilder) -> (Method, String) {
+        let base = self.client.rest_endpoint("").ok();
+        method_and_path(request, base.as_ref())
+    }
+}
+
+impl<C: RestClient> RestClient for RecorderClient<C> {
+    type Error = C::Error;
+    type AccessLevel = C::AccessLevel;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        self.client.rest_endpoint(endpoint)
+    }
+
+    fn project_role(&self, project: &ProjectId) -> Option<Role> {
+        self.client.project_role(project)
+    }
+}
+
+impl<C: Client> Client for RecorderClient<C> {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (method, path) = self.method_and_path(&request);
+        let response = self.client.rest(request, body.clone())?;
+        self.record(method, path, &body, &response);
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<C: AsyncClient + Sync + Send> AsyncClient for RecorderClient<C> {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        let (method, path) = self.method_and_path(&request);
+        let response = self.client.rest_async(request, body.clone()).await?;
+        self.record(method, path, &body, &response);
+        Ok(response)
+    }
+}
+
+/// A client that answers requests with the interactions of a [`Cassette`].
+///
+/// A request is answered by the first unused interaction with the same method,
+/// path and body. Each interaction is used only once, so repeated requests get
+/// the responses in the order they were recorded.
+///
+/// It has the [`Authenticated`] access level so that every endpoint can be queried.
+#[derive(Debug)]
+pub struct ReplayClient {
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+impl ReplayClient {
+    /// Creates a client serving the interactions of the cassette.
+    #[must_use]
+    pub fn new(cassette: Cassette) -> Self {
+        let interactions = cassette
+            .interactions
+            .into_iter()
+            .map(|i| (i, false))
+            .collect();
+        Self {
+            interactions: Mutex::new(interactions),
+        }
+    }
+
+    /// Creates a client serving the interactions of a cassette file.
+    ///
+    /// # Errors
+    /// This function returns an error if the file cannot be read or has an invalid format.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, VcrError> {
+        Cassette::load(path).map(Self::new)
+    }
+
+    /// The interactions that were not used yet.
+    pub fn unused(&self) -> Vec<Interaction> {
+        self.lock()
+            .iter()
+            .filter(|(_, used)| !used)
+            .map(|(i, _)| i.clone())
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(Interaction, bool)>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn replay(
+        &self,
+        request: &RequestBuilder,
+        body: &[u8],
+    ) -> Result<Response<Bytes>, ApiError<VcrError>> {
+        let base = Url::parse(REPLAY_URL)?;
+        let (method, path) = method_and_path(request, Some(&base));
+        let body = redacted_body(body);
+
+        let mut interactions = self.lock();
+        let found = interactions.iter_mut().find(|(i, used)| {
+            !used && i.method == method.as_str() && i.path == path && i.request == body
+        });
+        let (interaction, used) = match found {
+            Some(found) => found,
+            None => return Err(ApiError::client(VcrError::NoInteraction { method, path })),
+        };
+        *used = true;
+        let response = match &interaction.response {
+            Value::Null => Bytes::new(),
+            Value::String(s) => Bytes::from(s.clone()),
+            value => Bytes::from(value.to_string()),
+        };
+        Response::builder()
+            .status(interaction.status)
+            .body(response)
+            .map_err(|e| ApiError::client(e.into()))
+    }
+}
+
+impl RestClient for ReplayClient {
+    type Error = VcrError;
+    type AccessLevel = Authenticated;
+
+    fn rest_endpoint(&self, endpoint: &str) -> Result<Url, ApiError<Self::Error>> {
+        Ok(Url::parse(REPLAY_URL)?.join(endpoint)?)
+    }
+}
+
+impl Client for ReplayClient {
+    fn rest(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        self.replay(&request, &body)
+    }
+}
+
+#[async_trait]
+impl AsyncClient for ReplayClient {
+    async fn rest_async(
+        &self,
+        request: RequestBuilder,
+        body: Bytes,
+    ) -> Result<Response<Bytes>, ApiError<Self::Error>> {
+        self.replay(&request, &body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cassette, RecorderClient, ReplayClient, VcrError, REDACTED};
+    use crate::{
+        api::auth::{ChangePassword, Token},
+        ApiError, Login, Query, TestClient,
+    };
+
+    #[test]
+    fn secrets_are_redacted_and_replayed() {
+        let login = Login::password("tester@mail.example", "letmeinpls");
+        let recorder = RecorderClient::new(TestClient::with_auth("localhost:8080", login).unwrap());
+        let login = Login::password("tester@mail.example", "letmeinpls");
+        let token = login.query(&recorder).unwrap();
+        ChangePassword::new("letmeinpls", "secret")
+            .query(&recorder)
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("traduora-vcr-{}.json", std::process::id()));
+        recorder.save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(cassette, recorder.cassette());
+        let json = serde_json::to_string(&cassette).unwrap();
+        assert!(!json.contains("letmeinpls"));
+        assert!(!json.contains(token.access_token.value()));
+
+        let replay = ReplayClient::new(cassette);
+        let replayed: Token = Login::password("tester@mail.example", "other");
+        let token = replayed.query(&replay).unwrap();
+        assert_eq!(token.access_token.value(), REDACTED);
+        assert_eq!(replay.unused().len(), 1);
+        let error = Login::password("tester@mail.example", "letmeinpls")
+            .query(&replay)
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            ApiError::Client {
+                source: VcrError::NoInteraction { .. }
+            }
+        ));
+    }
+}
@@ -47,3 +47,22 @@ fn change_password() {
         .build()
         .unwrap();
 }
+
+/// precondition: default user exists.
+#[ignore]
+#[test]
+fn refresh_and_swap_token() {
+    let login = Login::password(MAIL, PASSWORD);
+    let client = TraduoraBuilder::new(HOST)
+        .use_http(USE_HTTP)
+        .validate_certs(VALIDATE_CERTS)
+        .authenticate(login.clone())
+        .build()
+        .unwrap();
+    let refreshed = client.refresh(&login).unwrap();
+    traduora::api::users::Me.query(&refreshed).unwrap();
+
+    let swapped = client.with_token(refreshed.access_token().clone());
+    assert_eq!(swapped.access_token(), refreshed.access_token());
+    Providers.query(&swapped.into_unauthenticated()).unwrap();
+}
@@ -0,0 +1,46 @@
+use super::*;
+use traduora::{api::labels::*, Query};
+
+/// precondition: default user exists and has access to project
+/// b1001dd9-e1c0-4fb0-a60d-eaaec304d332
+#[ignore]
+#[test]
+fn post_label() {
+    let client = build_auth_test_client();
+    let label = CreateLabel::new(
+        "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+        "needs review",
+        "#218380",
+    );
+    let new_label = label.query(&client).unwrap();
+    assert_eq!(new_label.value, "needs review");
+    assert_eq!(new_label.color, "#218380");
+}
+
+/// precondition: default user exists and has access to project
+#[ignore]
+#[test]
+fn get_labels() {
+    let client = build_auth_test_client();
+    let labels = Labels("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+        .query(&client)
+        .unwrap();
+
+    println!("{:#?}", labels);
+    assert!(!labels.is_empty());
+}
+
+/// precondition: label, term and translation exist.
+#[ignore]
+#[test]
+fn post_label_translation() {
+    let client = build_auth_test_client();
+    LabelTranslation::new(
+        "b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into(),
+        "c16d0fc3-73e6-4962-b8d5-f3054b8ff002".into(),
+        "0fa39756-65db-423c-a6d9-534b62fe9ead".into(),
+        "de_DE".into(),
+    )
+    .query(&client)
+    .unwrap();
+}
@@ -24,7 +24,7 @@ fn post_term() {
 #[test]
 fn get_terms() {
     let client = build_auth_test_client();
-    let terms = Terms("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
+    let terms = Terms::new("b1001dd9-e1c0-4fb0-a60d-eaaec304d332".into())
         .query(&client)
         .unwrap();
 
@@ -0,0 +1,7 @@
+//! Checks that the scope of a client is enforced at compile time.
+
+#[test]
+fn authenticated_endpoints_need_authenticated_clients() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
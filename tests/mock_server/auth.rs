@@ -0,0 +1,90 @@
+use super::*;
+use httpmock::Method::GET;
+use traduora::{api::auth::Providers, Query, TraduoraError};
+
+#[test]
+fn login_sends_credentials() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let me = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/v1/users/me")
+            .header("authorization", auth_header());
+        then.status(200).json_body(fixtures::data(fixtures::user(
+            "40379230-ced0-43b8-8b78-37c924f491a7",
+            "Tester",
+            MAIL,
+        )));
+    });
+
+    let user = traduora::api::users::Me.query(&client).unwrap();
+    me.assert();
+    assert_eq!(user.email, MAIL);
+}
+
+#[test]
+fn failed_login_is_reported() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.method(POST).path("/api/v1/auth/token");
+        then.status(401)
+            .json_body(fixtures::error("Unauthorized", "Invalid credentials"));
+    });
+
+    let result = TraduoraBuilder::new(&server.base_url())
+        .authenticate(Login::password(MAIL, "wrong"))
+        .build();
+    match result {
+        Err(TraduoraError::Api { source }) => assert!(source.is_unauthorized()),
+        other => panic!("unexpected result {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn get_providers_without_login() {
+    let server = MockServer::start();
+    let providers = server.mock(|when, then| {
+        when.method(GET).path("/api/v1/auth/providers");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    let client = build_test_client(&server);
+    let result = Providers.query(&client).unwrap();
+    providers.assert();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn blocking_client_reuses_async_client() {
+    let server = MockServer::start();
+    let providers = server.mock(|when, then| {
+        when.method(GET).path("/api/v1/auth/providers");
+        then.status(200).json_body(serde_json::json!([]));
+    });
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let client = TraduoraBuilder::new(&server.base_url())
+        .build_async()
+        .unwrap();
+    let client = Traduora::from_async(client, runtime.handle().clone());
+    let result = Providers.query(&client).unwrap();
+    providers.assert();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn server_info_reads_headers() {
+    let server = MockServer::start();
+    let providers = server.mock(|when, then| {
+        when.method(GET).path("/api/v1/auth/providers");
+        then.status(200)
+            .header("x-powered-by", "Express")
+            .json_body(serde_json::json!([]));
+    });
+
+    let client = build_test_client(&server);
+    let info = client.server_info().unwrap();
+    providers.assert();
+    assert_eq!(info.powered_by.as_deref(), Some("Express"));
+    assert!(info.providers.is_empty());
+}
@@ -0,0 +1,42 @@
+use super::*;
+use httpmock::Method::GET;
+use traduora::{api::auth::Providers, ApiError, AsyncQuery, Query};
+
+#[test]
+fn large_responses_are_rejected() {
+    let server = MockServer::start();
+    let providers = server.mock(|when, then| {
+        when.method(GET).path("/api/v1/auth/providers");
+        then.status(200).body(format!("[{}]", " ".repeat(2000)));
+    });
+
+    let client = TraduoraBuilder::new(&server.base_url())
+        .max_response_size(Some(1000))
+        .build()
+        .unwrap();
+    let error = Providers.query(&client).unwrap_err();
+    assert!(matches!(error, ApiError::ResponseTooLarge { limit: 1000 }));
+
+    let client = TraduoraBuilder::new(&server.base_url())
+        .max_response_size(Some(3000))
+        .build()
+        .unwrap();
+    assert!(Providers.query(&client).unwrap().is_empty());
+    providers.assert_hits(2);
+}
+
+#[tokio::test]
+async fn large_responses_are_rejected_async() {
+    let server = MockServer::start_async().await;
+    server.mock(|when, then| {
+        when.method(GET).path("/api/v1/auth/providers");
+        then.status(200).body(format!("[{}]", " ".repeat(2000)));
+    });
+
+    let client = TraduoraBuilder::new(&server.base_url())
+        .max_response_size(Some(1000))
+        .build_async()
+        .unwrap();
+    let error = Providers.query_async(&client).await.unwrap_err();
+    assert!(matches!(error, ApiError::ResponseTooLarge { limit: 1000 }));
+}
@@ -0,0 +1,22 @@
+use super::*;
+use httpmock::Method::GET;
+use traduora::{api::locales::AllLocales, Query};
+
+#[test]
+fn get_all_locales() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(GET).path("/api/v1/locales");
+        then.status(200)
+            .json_body(fixtures::data(serde_json::json!([
+                fixtures::locale("de_DE", "German", "Germany"),
+                fixtures::locale("en", "English", ""),
+            ])));
+    });
+
+    let locales = AllLocales.query(&client).unwrap();
+    mock.assert();
+    assert_eq!(locales.len(), 2);
+    assert_eq!(locales[0].region, "Germany");
+}
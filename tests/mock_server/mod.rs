@@ -0,0 +1,53 @@
+//! The tests in this module run against a local mock server.
+//!
+//! Unlike the tests in `api`, they do not need a Traduora instance,
+//! so they check paths, methods, bodies and models of the endpoints in CI.
+
+use httpmock::{Method::POST, MockServer};
+use traduora::{
+    auth::{Authenticated, Unauthenticated},
+    fixtures, Login, Traduora, TraduoraBuilder,
+};
+
+pub const MAIL: &str = "test@test.test";
+pub const PASSWORD: &str = "12345678";
+pub const TOKEN: &str = "mock-access-token";
+pub const PROJECT: &str = "b1001dd9-e1c0-4fb0-a60d-eaaec304d332";
+pub const TERM: &str = "0fa39756-65db-423c-a6d9-534b62fe9ead";
+
+pub fn build_test_client(server: &MockServer) -> Traduora<Unauthenticated> {
+    TraduoraBuilder::new(&server.base_url()).build().unwrap()
+}
+
+/// Logs in at the mock server, which accepts [`MAIL`] and [`PASSWORD`].
+///
+/// Mocks created afterwards should expect the header [`auth_header`].
+pub fn build_auth_test_client(server: &MockServer) -> Traduora<Authenticated> {
+    let login = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/v1/auth/token")
+            .json_body(serde_json::json!({
+                "grant_type": "password",
+                "username": MAIL,
+                "password": PASSWORD,
+            }));
+        then.status(200).json_body(fixtures::access_token(TOKEN));
+    });
+    let client = TraduoraBuilder::new(&server.base_url())
+        .authenticate(Login::password(MAIL, PASSWORD))
+        .build()
+        .unwrap();
+    login.assert();
+    client
+}
+
+pub fn auth_header() -> String {
+    format!("Bearer {}", TOKEN)
+}
+
+mod auth;
+mod client;
+mod locales;
+mod projects;
+mod terms;
+mod translations;
@@ -0,0 +1,61 @@
+use super::*;
+use httpmock::Method::{DELETE, GET};
+use traduora::{
+    api::projects::{CreateProject, DeleteProject, Projects},
+    api::Role,
+    Query,
+};
+
+#[test]
+fn get_projects() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path("/api/v1/projects")
+            .header("authorization", auth_header());
+        then.status(200)
+            .json_body(fixtures::data(serde_json::json!([fixtures::project(
+                PROJECT, "App"
+            )])));
+    });
+
+    let projects = Projects.query(&client).unwrap();
+    mock.assert();
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].id.value(), PROJECT);
+    assert_eq!(projects[0].role, Role::Admin);
+}
+
+#[test]
+fn post_project() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/v1/projects")
+            .json_body(serde_json::json!({ "name": "App", "description": "My app" }));
+        then.status(201)
+            .json_body(fixtures::data(fixtures::project(PROJECT, "App")));
+    });
+
+    let project = CreateProject::new("App", "My app").query(&client).unwrap();
+    mock.assert();
+    assert_eq!(project.name, "App");
+}
+
+#[test]
+fn delete_missing_project() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(DELETE)
+            .path(format!("/api/v1/projects/{}", PROJECT));
+        then.status(404)
+            .json_body(fixtures::error("NotFound", "Project not found"));
+    });
+
+    let error = DeleteProject(PROJECT.into()).query(&client).unwrap_err();
+    mock.assert();
+    assert!(error.is_not_found());
+}
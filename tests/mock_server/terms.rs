@@ -0,0 +1,86 @@
+use super::*;
+use httpmock::Method::{GET, PATCH};
+use traduora::{
+    api::terms::{CreateTerm, EditTerm, FindTerm, Terms},
+    CustomQuery, Query,
+};
+
+#[test]
+fn get_terms() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mut term = fixtures::term(TERM, "hello.world");
+    term["labels"] = serde_json::json!([fixtures::label("l", "ui", "#D81159")]);
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/api/v1/projects/{}/terms", PROJECT));
+        then.status(200)
+            .json_body(fixtures::data(serde_json::json!([term])));
+    });
+
+    let terms = Terms::new(PROJECT.into()).query(&client).unwrap();
+    mock.assert();
+    assert_eq!(terms[0].value, "hello.world");
+    assert_eq!(terms[0].labels[0].color, "#D81159");
+}
+
+#[test]
+fn get_term() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/api/v1/projects/{}/terms", PROJECT));
+        then.status(200)
+            .json_body(fixtures::data(serde_json::json!([
+                fixtures::term("other", "other.term"),
+                fixtures::term(TERM, "hello.world"),
+            ])));
+    });
+
+    let term = FindTerm::by_id(PROJECT.into(), TERM.into())
+        .query_custom(&client)
+        .unwrap()
+        .unwrap();
+    mock.assert();
+    assert_eq!(term.id.value(), TERM);
+    assert_eq!(term.value, "hello.world");
+}
+
+#[test]
+fn post_term() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/api/v1/projects/{}/terms", PROJECT))
+            .json_body(serde_json::json!({ "value": "hello.world" }));
+        then.status(201)
+            .json_body(fixtures::data(fixtures::term(TERM, "hello.world")));
+    });
+
+    let term = CreateTerm::new("hello.world", PROJECT.into())
+        .query(&client)
+        .unwrap();
+    mock.assert();
+    assert_eq!(term.id.value(), TERM);
+}
+
+#[test]
+fn patch_term() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(PATCH)
+            .path(format!("/api/v1/projects/{}/terms/{}", PROJECT, TERM))
+            .json_body(serde_json::json!({ "value": "new.term" }));
+        then.status(200)
+            .json_body(fixtures::data(fixtures::term(TERM, "new.term")));
+    });
+
+    let term = EditTerm::new(PROJECT.into(), TERM.into(), "new.term")
+        .query(&client)
+        .unwrap();
+    mock.assert();
+    assert_eq!(term.value, "new.term");
+}
@@ -0,0 +1,44 @@
+use super::*;
+use httpmock::Method::{GET, PATCH};
+use traduora::{
+    api::translations::{EditTranslation, Locales},
+    Query,
+};
+
+#[test]
+fn get_locales() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(GET)
+            .path(format!("/api/v1/projects/{}/translations", PROJECT));
+        then.status(200)
+            .json_body(fixtures::data(serde_json::json!([
+                fixtures::project_locale("91ea2d61-8e14-481f-af19-9ae16bbf95a3", "de_DE")
+            ])));
+    });
+
+    let locales = Locales(PROJECT.into()).query(&client).unwrap();
+    mock.assert();
+    assert_eq!(locales[0].locale.code.value(), "de_DE");
+}
+
+#[test]
+fn patch_translation() {
+    let server = MockServer::start();
+    let client = build_auth_test_client(&server);
+    let mock = server.mock(|when, then| {
+        when.method(PATCH)
+            .path(format!("/api/v1/projects/{}/translations/de_DE", PROJECT))
+            .json_body(serde_json::json!({ "termId": TERM, "value": "Hallo Welt" }));
+        then.status(200)
+            .json_body(fixtures::data(fixtures::translation(TERM, "Hallo Welt")));
+    });
+
+    let translation =
+        EditTranslation::new(PROJECT.into(), "de_DE".into(), TERM.into(), "Hallo Welt")
+            .query(&client)
+            .unwrap();
+    mock.assert();
+    assert_eq!(translation.value, "Hallo Welt");
+}
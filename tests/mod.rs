@@ -1 +1,2 @@
 mod api;
+mod mock_server;
@@ -0,0 +1,10 @@
+use traduora::{api::users::Me, AsyncQuery, AsyncTraduora};
+
+async fn me() {
+    let client = AsyncTraduora::new("localhost:8080").unwrap();
+    let _ = Me.query_async(&client).await;
+}
+
+fn main() {
+    drop(me());
+}
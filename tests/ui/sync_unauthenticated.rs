@@ -0,0 +1,6 @@
+use traduora::{api::users::Me, Query, Traduora};
+
+fn main() {
+    let client = Traduora::new("localhost:8080").unwrap();
+    let _ = Me.query(&client);
+}